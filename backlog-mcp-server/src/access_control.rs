@@ -127,6 +127,11 @@ impl AccessControl {
         self.allowed_projects.is_some()
     }
 
+    /// Project keys from `BACKLOG_PROJECTS`, or `None` when every project is allowed
+    pub fn allowed_projects(&self) -> Option<&[ProjectKey]> {
+        self.allowed_projects.as_deref()
+    }
+
     pub fn project_cache(&self) -> &Arc<ProjectCacheManager> {
         &self.project_cache
     }
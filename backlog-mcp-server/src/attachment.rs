@@ -0,0 +1,68 @@
+//! Attachment upload for write tools
+//!
+//! Files arrive base64-encoded in the tool request, are uploaded through
+//! `POST /api/v2/space/attachment` and the returned IDs are linked by the
+//! follow-up call. Uploads happen only after every other check has passed, and
+//! if the follow-up call still fails the error names the orphaned uploads.
+
+#[cfg(any(feature = "issue_writable", feature = "git_writable"))]
+use crate::error::{Error, Result};
+#[cfg(any(feature = "issue_writable", feature = "git_writable"))]
+use backlog_api_client::{ApiError, AttachmentSource, AttachmentUpload, client::BacklogApiClient};
+#[cfg(any(feature = "issue_writable", feature = "git_writable"))]
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64_STANDARD};
+
+#[derive(Debug, Clone, serde::Deserialize, schemars::JsonSchema)]
+pub struct AttachmentFile {
+    #[schemars(description = "File name including the extension. Example: 'screenshot.png'.")]
+    pub file_name: String,
+    #[schemars(description = "The file content, base64-encoded.")]
+    pub content_base64: String,
+}
+
+#[cfg(any(feature = "issue_writable", feature = "git_writable"))]
+/// Decode and upload the files, checking every size before the first upload
+pub(crate) async fn upload_files(
+    client: &BacklogApiClient,
+    files: Vec<AttachmentFile>,
+) -> Result<AttachmentUpload> {
+    let sources = files
+        .into_iter()
+        .map(|file| {
+            let content = BASE64_STANDARD
+                .decode(file.content_base64.trim())
+                .map_err(|e| {
+                    Error::Parameter(format!(
+                        "Attachment '{}' is not valid base64: {e}",
+                        file.file_name
+                    ))
+                })?;
+            Ok(AttachmentSource::Bytes {
+                file_name: file.file_name,
+                content,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut upload = AttachmentUpload::new();
+    if let Err(e) = upload.upload_all(&client.space(), sources).await {
+        return Err(match e {
+            ApiError::AttachmentTooLarge { .. } => Error::Parameter(e.to_string()),
+            e if upload.uploaded().is_empty() => Error::Api(e),
+            e => Error::AttachmentsNotLinked(upload.failure_report(&e)),
+        });
+    }
+    Ok(upload)
+}
+
+#[cfg(any(feature = "issue_writable", feature = "git_writable"))]
+/// Attach a description of orphaned uploads to the error of a failed follow-up call
+pub(crate) fn report_orphans<T>(upload: &AttachmentUpload, result: Result<T>) -> Result<T> {
+    result.map_err(|e| {
+        if upload.uploaded().is_empty() {
+            e
+        } else {
+            Error::AttachmentsNotLinked(upload.failure_report(&e))
+        }
+    })
+}
@@ -1,2 +1,3 @@
 pub mod bridge;
 pub mod request;
+pub mod response_transformer;
@@ -0,0 +1,18 @@
+use backlog_api_client::DocumentDetail;
+use serde::Serialize;
+
+/// Document details with the ProseMirror body converted to Markdown
+#[derive(Debug, Clone, Serialize)]
+pub struct DocumentDetailResponse {
+    #[serde(flatten)]
+    pub document: DocumentDetail,
+    /// CommonMark rendering of `json`, including tables, mentions and images
+    pub markdown: String,
+}
+
+impl From<DocumentDetail> for DocumentDetailResponse {
+    fn from(document: DocumentDetail) -> Self {
+        let markdown = document.to_markdown();
+        Self { document, markdown }
+    }
+}
@@ -43,6 +43,9 @@ pub enum Error {
 
     #[error("{0}")]
     ProjectNotFound(String),
+
+    #[error("{0}")]
+    AttachmentsNotLinked(String),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -98,6 +101,9 @@ impl From<Error> for McpError {
                         format!("Failed to read file '{path}': {message}"),
                         None,
                     ),
+                    ApiError::AttachmentTooLarge { .. } => {
+                        McpError::invalid_params(api_error.to_string(), None)
+                    }
                     ApiError::RequestBuild(msg) => {
                         McpError::internal_error(format!("Failed to build request: {msg}"), None)
                     }
@@ -108,7 +114,7 @@ impl From<Error> for McpError {
                         format!("Unexpected HTTP status {status}: {body}"),
                         None,
                     ),
-                    ApiError::UnparseableErrorResponse { status, body } => {
+                    ApiError::UnparseableErrorResponse { status, body, .. } => {
                         McpError::invalid_request(
                             format!("HTTP error {status} with unparseable response: {body}"),
                             None,
@@ -140,6 +146,7 @@ impl From<Error> for McpError {
             }
             Error::ProjectAccessDenied { .. } => McpError::invalid_params(err.to_string(), None),
             Error::ProjectNotFound(_) => McpError::invalid_params(err.to_string(), None),
+            Error::AttachmentsNotLinked(_) => McpError::invalid_request(err.to_string(), None),
         }
     }
 }
@@ -0,0 +1,92 @@
+//! Formatting of text written by assistants
+//!
+//! Assistants usually write Markdown, which projects that use Backlog
+//! notation show verbatim. Tools that take a `markdown` flag convert such
+//! text to the project's [`TextFormattingRule`] before it is sent. The
+//! conversion is opt-in because Markdown and Backlog notation overlap: text
+//! already in Backlog notation, such as a `* Heading`, would be mangled.
+
+use crate::access_control::AccessControl;
+use crate::error::Result;
+use backlog_api_client::client::BacklogApiClient;
+use backlog_core::identifier::ProjectId;
+use backlog_core::{TextFormattingRule, notation};
+
+/// Convert Markdown `text` for the project when `markdown` is set
+pub(crate) async fn for_project(
+    access_control: &AccessControl,
+    client: &BacklogApiClient,
+    project_id: &ProjectId,
+    text: String,
+    markdown: Option<bool>,
+) -> Result<String> {
+    if !markdown.unwrap_or(false) {
+        return Ok(text);
+    }
+    let project = access_control
+        .project_cache()
+        .get_by_id(project_id, client)
+        .await?;
+    Ok(notation::convert(
+        &text,
+        TextFormattingRule::Markdown,
+        project.text_formatting_rule,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backlog_core::identifier::Identifier;
+
+    async fn backlog_notation_project(access_control: &AccessControl) -> ProjectId {
+        let project: backlog_domain_models::Project = serde_json::from_value(serde_json::json!({
+            "id": 1, "projectKey": "BLG", "name": "BLG", "chartEnabled": false,
+            "subtaskingEnabled": false, "projectLeaderCanEditProjectLeader": false,
+            "useWiki": true, "useFileSharing": false, "useWikiTreeView": false,
+            "useOriginalImageSizeAtWiki": false, "textFormattingRule": "backlog",
+            "archived": false, "displayOrder": 0, "useDevAttributes": false
+        }))
+        .unwrap();
+        access_control.project_cache().cache_project(project).await;
+        ProjectId::new(1)
+    }
+
+    #[tokio::test]
+    async fn test_backlog_notation_is_sent_unchanged_by_default() {
+        let access_control = AccessControl::new().unwrap();
+        let client = BacklogApiClient::new("http://127.0.0.1:9").unwrap();
+        let project_id = backlog_notation_project(&access_control).await;
+
+        for text in ["* Heading\n- item", "above\n* * *\nbelow"] {
+            let sent = for_project(
+                &access_control,
+                &client,
+                &project_id,
+                text.to_string(),
+                None,
+            )
+            .await
+            .unwrap();
+            assert_eq!(sent, text);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_markdown_is_converted_on_request() {
+        let access_control = AccessControl::new().unwrap();
+        let client = BacklogApiClient::new("http://127.0.0.1:9").unwrap();
+        let project_id = backlog_notation_project(&access_control).await;
+
+        let sent = for_project(
+            &access_control,
+            &client,
+            &project_id,
+            "# Heading\n* item\n* * *".to_string(),
+            Some(true),
+        )
+        .await
+        .unwrap();
+        assert_eq!(sent, "* Heading\n- item\n----");
+    }
+}
@@ -1,15 +1,19 @@
 use crate::access_control::AccessControl;
+#[cfg(feature = "git_writable")]
+use crate::attachment::{report_orphans, upload_files};
 use crate::error::{Error, Result};
 #[cfg(feature = "git_writable")]
 use crate::git::request::AddPullRequestCommentRequest;
 use crate::git::request::{
     DownloadPullRequestAttachmentRequest, GetPullRequestAttachmentListRequest,
-    GetPullRequestCommentListRequest, GetPullRequestDetailsRequest, GetRepositoryDetailsRequest,
-    GetRepositoryListRequest, ListPullRequestsRequest,
+    GetPullRequestCommentListRequest, GetPullRequestDetailsRequest, GetPullRequestInboxRequest,
+    GetRepositoryDetailsRequest, GetRepositoryListRequest, ListPullRequestsRequest,
 };
+use crate::search::bridge::projects_in_scope;
 #[cfg(feature = "git_writable")]
 use backlog_api_client::AddPullRequestCommentParams;
 use backlog_api_client::client::BacklogApiClient;
+use backlog_api_client::pr_inbox::{PrInbox, PrInboxParams, PullRequestState};
 use backlog_api_client::{
     DownloadPullRequestAttachmentParams, DownloadedFile, GetPullRequestAttachmentListParams,
     GetPullRequestCommentListParams, GetPullRequestListParams, GetPullRequestParams,
@@ -169,13 +173,38 @@ pub(crate) async fn get_pull_request_comment_list_tool(
         .await?)
 }
 
+pub(crate) async fn get_pull_request_inbox(
+    client: Arc<Mutex<BacklogApiClient>>,
+    req: GetPullRequestInboxRequest,
+    access_control: &AccessControl,
+) -> Result<PrInbox> {
+    let states = req
+        .statuses
+        .unwrap_or_default()
+        .iter()
+        .map(|status| PullRequestState::from_str(status).map_err(Error::Parameter))
+        .collect::<Result<Vec<_>>>()?;
+
+    let client_guard = client.lock().await;
+    let projects = projects_in_scope(&client_guard, req.project_keys, access_control).await?;
+
+    let mut params = PrInboxParams::new(projects)
+        .repositories(req.repositories.unwrap_or_default())
+        .states(states);
+    if let Some(recent_days) = req.recent_days {
+        params = params.recent_days(recent_days);
+    }
+    Ok(client_guard.pr_inbox(params).await?)
+}
+
 #[cfg(feature = "git_writable")]
 pub(crate) async fn add_pull_request_comment_bridge(
     client: Arc<Mutex<BacklogApiClient>>,
-    req: AddPullRequestCommentRequest,
+    mut req: AddPullRequestCommentRequest,
     access_control: &AccessControl,
 ) -> Result<PullRequestComment> {
     let project_id_or_key = req.project_id_or_key.parse::<ProjectIdOrKey>()?;
+    let attachments = req.attachments.take().unwrap_or_default();
     let params = AddPullRequestCommentParams::try_from(req)?;
 
     let client_guard = client.lock().await;
@@ -184,5 +213,12 @@ pub(crate) async fn add_pull_request_comment_bridge(
         .check_project_access_id_or_key_async(&project_id_or_key, &client_guard)
         .await?;
 
-    Ok(client_guard.git().add_pull_request_comment(params).await?)
+    if attachments.is_empty() {
+        return Ok(client_guard.git().add_pull_request_comment(params).await?);
+    }
+
+    let upload = upload_files(&client_guard, attachments).await?;
+    let params = params.attachment_ids(upload.ids());
+    let result = client_guard.git().add_pull_request_comment(params).await;
+    report_orphans(&upload, result.map_err(Error::from))
 }
@@ -3,8 +3,8 @@ pub mod request;
 
 pub use request::{
     DownloadPullRequestAttachmentRequest, GetPullRequestAttachmentListRequest,
-    GetPullRequestCommentListRequest, GetPullRequestDetailsRequest, GetRepositoryDetailsRequest,
-    GetRepositoryListRequest, ListPullRequestsRequest,
+    GetPullRequestCommentListRequest, GetPullRequestDetailsRequest, GetPullRequestInboxRequest,
+    GetRepositoryDetailsRequest, GetRepositoryListRequest, ListPullRequestsRequest,
 };
 
 #[cfg(feature = "git_writable")]
@@ -129,6 +129,12 @@ pub struct GetPullRequestCommentListRequest {
     #[serde(default)]
     #[schemars(description = "The sort order: 'asc' or 'desc'.")]
     pub order: Option<String>,
+    /// Return the comments as the API returns them instead of threads.
+    #[serde(default)]
+    #[schemars(
+        description = "Return the raw comment objects instead of compact threads. Defaults to false."
+    )]
+    pub raw: Option<bool>,
 }
 
 impl TryFrom<GetPullRequestCommentListRequest> for GetPullRequestCommentListParams {
@@ -168,6 +174,28 @@ impl TryFrom<GetPullRequestCommentListRequest> for GetPullRequestCommentListPara
     }
 }
 
+#[derive(Deserialize, JsonSchema, Debug)]
+pub struct GetPullRequestInboxRequest {
+    #[schemars(
+        description = "Optional project keys to include. Defaults to all allowed projects. Example: [\"MYPROJECTKEY\"]."
+    )]
+    #[serde(default)]
+    pub project_keys: Option<Vec<String>>,
+    #[schemars(description = "Optional repository names to include. Example: [\"my-repo\"].")]
+    #[serde(default)]
+    pub repositories: Option<Vec<String>>,
+    #[schemars(
+        description = "Optional pull request statuses: \"open\", \"closed\" and/or \"merged\". Defaults to open."
+    )]
+    #[serde(default)]
+    pub statuses: Option<Vec<String>>,
+    #[schemars(
+        description = "Days within which other pull requests count as recently updated. Defaults to 7."
+    )]
+    #[serde(default)]
+    pub recent_days: Option<u32>,
+}
+
 #[cfg(feature = "git_writable")]
 #[derive(Deserialize, JsonSchema, Debug)]
 pub struct AddPullRequestCommentRequest {
@@ -191,6 +219,12 @@ pub struct AddPullRequestCommentRequest {
     #[serde(default)]
     #[schemars(description = "Optional list of user IDs to notify about this comment.")]
     pub notified_user_ids: Option<Vec<u32>>,
+    /// Optional files to upload and attach.
+    #[serde(default)]
+    #[schemars(
+        description = "Files to upload and attach (optional). Each file is given as a file name and base64-encoded content; the size limit is 100 MiB per file."
+    )]
+    pub attachments: Option<Vec<crate::attachment::AttachmentFile>>,
 }
 
 #[cfg(feature = "git_writable")]
@@ -1,15 +1,21 @@
 use super::request::{
     AddCommentRequest, DownloadAttachmentRequest, GetAttachmentListRequest,
     GetIssueCommentsRequest, GetIssueDetailsRequest, GetIssueSharedFilesRequest,
-    GetIssuesByMilestoneNameRequest, GetVersionMilestoneListRequest, UpdateIssueRequest,
+    GetIssueSubtreeRequest, GetIssuesByMilestoneNameRequest, GetVersionMilestoneListRequest,
+    UpdateIssueRequest,
 };
 #[cfg(feature = "issue_writable")]
 use super::request::{AddIssueRequest, UpdateCommentRequest};
 use crate::access_control::AccessControl;
+#[cfg(feature = "issue_writable")]
+use crate::attachment::{report_orphans, upload_files};
 use crate::error::{Error as McpError, Result};
+#[cfg(feature = "issue_writable")]
+use crate::formatting;
 use crate::util::{MatchResult, find_by_name_from_array};
 #[cfg(feature = "issue_writable")]
 use backlog_api_client::backlog_issue::AddIssueParamsBuilder;
+use backlog_api_client::backlog_issue::IssueTreeNode;
 use backlog_api_client::client::BacklogApiClient;
 use backlog_api_client::{
     AddCommentParams, Attachment, AttachmentId, Comment, DownloadedFile, GetCommentListParams,
@@ -41,6 +47,27 @@ pub(crate) async fn get_issue_details(
     Ok(issue)
 }
 
+pub(crate) async fn get_issue_subtree(
+    client: Arc<Mutex<BacklogApiClient>>,
+    req: GetIssueSubtreeRequest,
+    access_control: &AccessControl,
+) -> Result<IssueTreeNode> {
+    let client_guard = client.lock().await;
+    let parsed_issue_key = IssueKey::from_str(req.issue_key.trim())?;
+    let root = client_guard
+        .issue()
+        .get_issue(backlog_issue::GetIssueParams::new(parsed_issue_key))
+        .await?;
+
+    // Child issues always belong to the project of their parent, so checking
+    // the root before fetching them covers the whole subtree
+    access_control
+        .check_project_access_by_id_async(&root.project_id, &client_guard)
+        .await?;
+
+    Ok(client_guard.issue().get_issue_descendants(root).await?)
+}
+
 pub(crate) async fn get_version_milestone_list(
     client: Arc<Mutex<BacklogApiClient>>,
     req: GetVersionMilestoneListRequest,
@@ -237,10 +264,11 @@ pub(crate) async fn download_issue_attachment_file(
 #[cfg(feature = "issue_writable")]
 pub(crate) async fn add_comment_impl(
     client: Arc<Mutex<BacklogApiClient>>,
-    req: AddCommentRequest,
+    mut req: AddCommentRequest,
     access_control: &AccessControl,
 ) -> Result<Comment> {
-    let add_comment_params = AddCommentParams::try_from(req.clone())?;
+    let attachments = req.attachments.take().unwrap_or_default();
+    let mut add_comment_params = AddCommentParams::try_from(req.clone())?;
 
     let client_guard = client.lock().await;
 
@@ -254,8 +282,27 @@ pub(crate) async fn add_comment_impl(
         .check_project_access_by_id_async(&issue.project_id, &client_guard)
         .await?;
 
-    let comment = client_guard.issue().add_comment(add_comment_params).await?;
-    Ok(comment)
+    add_comment_params.content = formatting::for_project(
+        access_control,
+        &client_guard,
+        &issue.project_id,
+        add_comment_params.content,
+        req.markdown,
+    )
+    .await?;
+
+    if attachments.is_empty() {
+        let comment = client_guard.issue().add_comment(add_comment_params).await?;
+        return Ok(comment);
+    }
+
+    let upload = upload_files(&client_guard, attachments).await?;
+    add_comment_params
+        .attachment_id
+        .get_or_insert_with(Vec::new)
+        .extend(upload.ids());
+    let result = client_guard.issue().add_comment(add_comment_params).await;
+    report_orphans(&upload, result.map_err(McpError::from))
 }
 
 #[cfg(feature = "issue_writable")]
@@ -358,6 +405,14 @@ pub(crate) async fn add_issue_impl(
         .priority_id(priority_id);
 
     if let Some(description) = req.description {
+        let description = formatting::for_project(
+            access_control,
+            &client_guard,
+            &project_id,
+            description,
+            req.markdown,
+        )
+        .await?;
         builder.description(description);
     }
 
@@ -373,8 +428,19 @@ pub(crate) async fn add_issue_impl(
         builder.custom_fields(custom_fields);
     }
 
-    let params = builder.build()?;
+    let mut params = builder.build()?;
 
-    let issue = client_guard.issue().add_issue(params).await?;
-    Ok(issue)
+    let attachments = req.attachments.unwrap_or_default();
+    if attachments.is_empty() {
+        let issue = client_guard.issue().add_issue(params).await?;
+        return Ok(issue);
+    }
+
+    let upload = upload_files(&client_guard, attachments).await?;
+    params
+        .attachment_id
+        .get_or_insert_with(Vec::new)
+        .extend(upload.ids());
+    let result = client_guard.issue().add_issue(params).await;
+    report_orphans(&upload, result.map_err(McpError::from))
 }
@@ -13,6 +13,14 @@ pub(crate) struct GetIssueDetailsRequest {
     pub issue_key: String,
 }
 
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub(crate) struct GetIssueSubtreeRequest {
+    #[schemars(
+        description = "The issue key whose child issues to retrieve, e.g., 'PROJECT-123'. The returned tree starts at this issue."
+    )]
+    pub issue_key: String,
+}
+
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
 pub(crate) struct GetVersionMilestoneListRequest {
     #[schemars(
@@ -145,6 +153,16 @@ pub(crate) struct AddCommentRequest {
     pub notified_user_ids: Option<Vec<u32>>,
     #[schemars(description = "Attachment IDs to include with this comment (optional).")]
     pub attachment_ids: Option<Vec<u32>>,
+    #[serde(default)]
+    #[schemars(
+        description = "Files to upload and attach (optional). Each file is given as a file name and base64-encoded content; the size limit is 100 MiB per file."
+    )]
+    pub attachments: Option<Vec<crate::attachment::AttachmentFile>>,
+    #[serde(default)]
+    #[schemars(
+        description = "Treat the text as Markdown and convert it to Backlog notation when the project uses Backlog notation (optional, default false). Leave unset for text that is already in the project's notation."
+    )]
+    pub markdown: Option<bool>,
 }
 
 impl TryFrom<AddCommentRequest> for AddCommentParams {
@@ -217,4 +235,14 @@ pub(crate) struct AddIssueRequest {
         description = "Optional custom fields as a JSON object mapping field names to values. Use get_custom_field_list to see available fields and their expected formats."
     )]
     pub custom_fields: Option<std::collections::HashMap<String, serde_json::Value>>,
+    #[serde(default)]
+    #[schemars(
+        description = "Files to upload and attach (optional). Each file is given as a file name and base64-encoded content; the size limit is 100 MiB per file."
+    )]
+    pub attachments: Option<Vec<crate::attachment::AttachmentFile>>,
+    #[serde(default)]
+    #[schemars(
+        description = "Treat the text as Markdown and convert it to Backlog notation when the project uses Backlog notation (optional, default false). Leave unset for text that is already in the project's notation."
+    )]
+    pub markdown: Option<bool>,
 }
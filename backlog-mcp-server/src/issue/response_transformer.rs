@@ -1,6 +1,8 @@
 use backlog_core::identifier::Identifier;
 use backlog_core::{IssueKey, Star, User};
-use backlog_issue::models::{Attachment, ExternalFileLink, Issue, SharedFile};
+use backlog_issue::models::{
+    Attachment, ExternalFileLink, Issue, IssueRollup, IssueTreeNode, SharedFile,
+};
 use backlog_project::{Category, IssueType, Milestone, Priority, Resolution, Status};
 use serde::Serialize;
 use serde_json::Value;
@@ -109,3 +111,42 @@ impl From<Issue> for IssueResponse {
 #[cfg(test)]
 #[path = "response_transformer_test.rs"]
 mod tests;
+
+/// Compact node of an issue tree with totals over its descendants
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueTreeResponse {
+    pub issue_key: IssueKey,
+    pub summary: String,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_hours: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual_hours: Option<f64>,
+    /// Totals over this issue and all of its descendants
+    pub rollup: IssueRollup,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<IssueTreeResponse>,
+}
+
+impl From<IssueTreeNode> for IssueTreeResponse {
+    fn from(node: IssueTreeNode) -> Self {
+        let rollup = node.rollup();
+        let issue = node.issue;
+        IssueTreeResponse {
+            issue_key: issue.issue_key,
+            summary: issue.summary,
+            status: issue.status.name,
+            assignee: issue.assignee.map(|user| user.name),
+            due_date: issue.due_date,
+            estimated_hours: issue.estimated_hours,
+            actual_hours: issue.actual_hours,
+            rollup,
+            children: node.children.into_iter().map(Self::from).collect(),
+        }
+    }
+}
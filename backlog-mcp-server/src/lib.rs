@@ -1,13 +1,16 @@
 #![allow(unused_imports, dead_code)]
 
 pub mod access_control;
+pub mod attachment;
 pub mod document;
 mod error;
 pub mod file;
+mod formatting;
 pub mod git;
 pub mod issue;
 pub mod project;
 pub(crate) mod project_cache;
+pub mod search;
 mod server;
 pub mod user;
 mod util;
@@ -1,12 +1,15 @@
 use crate::access_control::AccessControl;
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::project::request::{
-    GetCustomFieldListRequest, GetPrioritiesRequest, GetProjectIssueTypesRequest,
-    GetProjectStatusListRequest,
+    GetCustomFieldListRequest, GetPrioritiesRequest, GetProjectDigestRequest,
+    GetProjectIssueTypesRequest, GetProjectStatusListRequest,
 };
+use crate::search::bridge::projects_in_scope;
 use backlog_api_client::IssueType;
 use backlog_api_client::ProjectIdOrKey; // From backlog-core, re-exported by backlog-api-client
 use backlog_api_client::client::BacklogApiClient;
+use backlog_api_client::digest::{Digest, DigestParams, parse_since};
+use backlog_core::identifier::UserId;
 use backlog_project::Priority;
 use backlog_project::Status; // Specific model from backlog-project
 use std::sync::Arc;
@@ -217,3 +220,41 @@ fn generate_custom_field_info(
         }
     }
 }
+
+/// Whether the digest is wanted as JSON rather than Markdown
+pub(crate) fn digest_as_json(format: Option<&str>) -> Result<bool> {
+    match format.map(str::to_ascii_lowercase).as_deref() {
+        None | Some("markdown") => Ok(false),
+        Some("json") => Ok(true),
+        Some(other) => Err(Error::Parameter(format!(
+            "Unknown format '{other}' (expected markdown or json)"
+        ))),
+    }
+}
+
+/// Helper function to implement the project_digest_get tool.
+pub(crate) async fn get_project_digest(
+    client: Arc<Mutex<BacklogApiClient>>,
+    req: GetProjectDigestRequest,
+    access_control: &AccessControl,
+) -> Result<Digest> {
+    let since = parse_since(req.since.as_deref().unwrap_or("24h"), chrono::Utc::now())
+        .map_err(Error::Parameter)?;
+
+    let client_guard = client.lock().await;
+    let mut params = DigestParams::new(since);
+    // Without keys or a restriction the whole space is covered in one listing
+    if req
+        .project_keys
+        .as_ref()
+        .is_some_and(|keys| !keys.is_empty())
+        || access_control.allowed_projects().is_some()
+    {
+        params = params
+            .projects(projects_in_scope(&client_guard, req.project_keys, access_control).await?);
+    }
+    if let Some(user_id) = req.user_id {
+        params = params.user(UserId::new(user_id));
+    }
+    Ok(client_guard.digest(params).await?)
+}
@@ -29,3 +29,21 @@ pub struct GetCustomFieldListRequest {
     /// Ensure there are no leading or trailing spaces.
     pub project_id_or_key: String,
 }
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetProjectDigestRequest {
+    /// Start of the period: "90m", "24h", "7d", "2w", a date like "2024-05-01"
+    /// or an RFC 3339 time. Defaults to "24h".
+    #[serde(default)]
+    pub since: Option<String>,
+    /// Project keys to cover. Defaults to the allowed projects, or the whole
+    /// space when no project restriction is configured.
+    #[serde(default)]
+    pub project_keys: Option<Vec<String>>,
+    /// Only activities by this numeric user ID.
+    #[serde(default)]
+    pub user_id: Option<u32>,
+    /// "markdown" (default) for text ready to post to chat, or "json".
+    #[serde(default)]
+    pub format: Option<String>,
+}
@@ -0,0 +1,70 @@
+use crate::access_control::AccessControl;
+use crate::error::{Error as McpError, Result};
+use crate::search::request::SearchRequest;
+use backlog_api_client::backlog_project::{GetProjectListParams, Project};
+use backlog_api_client::client::BacklogApiClient;
+use backlog_api_client::search::{SearchKind, SearchParams, SearchResults};
+use backlog_core::ProjectKey;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+const DEFAULT_LIMIT: u8 = 20;
+
+/// The requested projects, or every allowed project when none are given
+pub(crate) async fn projects_in_scope(
+    client: &BacklogApiClient,
+    project_keys: Option<Vec<String>>,
+    access_control: &AccessControl,
+) -> Result<Vec<Project>> {
+    let keys: Vec<ProjectKey> = match (project_keys, access_control.allowed_projects()) {
+        (Some(keys), _) if !keys.is_empty() => keys
+            .iter()
+            .map(|key| ProjectKey::from_str(key.trim()))
+            .collect::<std::result::Result<_, _>>()?,
+        (_, Some(allowed)) => allowed.to_vec(),
+        (_, None) => {
+            let params = GetProjectListParams {
+                archived: Some(false),
+                all: false,
+            };
+            return Ok(client.project().get_project_list(params).await?);
+        }
+    };
+
+    let mut projects = Vec::with_capacity(keys.len());
+    for key in &keys {
+        access_control
+            .check_project_access_by_key_async(key)
+            .await?;
+        let project = access_control
+            .project_cache()
+            .get_by_key(key, client)
+            .await?;
+        projects.push(Project::clone(&project));
+    }
+    Ok(projects)
+}
+
+pub(crate) async fn search(
+    client: Arc<Mutex<BacklogApiClient>>,
+    req: SearchRequest,
+    access_control: &AccessControl,
+) -> Result<SearchResults> {
+    let client_guard = client.lock().await;
+    let kinds = req
+        .kinds
+        .unwrap_or_default()
+        .iter()
+        .map(|kind| SearchKind::from_str(kind).map_err(McpError::Parameter))
+        .collect::<Result<Vec<_>>>()?;
+    let limit = req.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, 100);
+    let projects = projects_in_scope(&client_guard, req.project_keys, access_control).await?;
+
+    let params = SearchParams::new(req.keyword, projects)
+        .kinds(kinds)
+        .count(limit);
+    let mut results = client_guard.search(params).await?;
+    results.hits.truncate(limit.into());
+    Ok(results)
+}
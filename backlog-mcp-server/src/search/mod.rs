@@ -0,0 +1,2 @@
+pub(crate) mod bridge;
+pub(crate) mod request;
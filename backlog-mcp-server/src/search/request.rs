@@ -0,0 +1,22 @@
+use rmcp::schemars;
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub(crate) struct SearchRequest {
+    #[schemars(description = "Words to search for. Example: \"login timeout\".")]
+    pub keyword: String,
+    #[schemars(
+        description = "Optional kinds to search: \"issue\", \"wiki\", \"document\" and/or \"pull_request\". Defaults to all kinds."
+    )]
+    #[serde(default)]
+    pub kinds: Option<Vec<String>>,
+    #[schemars(
+        description = "Optional project keys to search in. Defaults to all allowed projects. Example: [\"MYPROJECTKEY\"]."
+    )]
+    #[serde(default)]
+    pub project_keys: Option<Vec<String>>,
+    #[schemars(
+        description = "Maximum number of results (1-100). Defaults to 20. Pull requests are only matched among the latest `limit` pull requests of each repository."
+    )]
+    #[serde(default)]
+    pub limit: Option<u8>,
+}
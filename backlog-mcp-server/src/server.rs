@@ -1,12 +1,13 @@
 #![allow(unused_imports, dead_code)]
 
+use crate::document::response_transformer::DocumentDetailResponse;
 use crate::file_utils::{FileFormat, SerializableFile};
 #[cfg(feature = "issue_writable")]
 use crate::issue::request::{AddIssueRequest, UpdateCommentRequest};
 use crate::issue::request::{
     GetIssueCommentsRequest, GetIssueSharedFilesRequest, UpdateIssueRequest,
 };
-use crate::issue::response_transformer::IssueResponse;
+use crate::issue::response_transformer::{IssueResponse, IssueTreeResponse};
 use crate::{
     document::{
         self,
@@ -23,30 +24,32 @@ use crate::{
         request::{
             DownloadPullRequestAttachmentRequest, GetPullRequestAttachmentListRequest,
             GetPullRequestCommentListRequest, GetPullRequestDetailsRequest,
-            GetRepositoryDetailsRequest, GetRepositoryListRequest, ListPullRequestsRequest,
+            GetPullRequestInboxRequest, GetRepositoryDetailsRequest, GetRepositoryListRequest,
+            ListPullRequestsRequest,
         },
     },
     issue::{
         self,
         request::{
             AddCommentRequest, DownloadAttachmentRequest, GetAttachmentListRequest,
-            GetIssueDetailsRequest, GetIssuesByMilestoneNameRequest,
+            GetIssueDetailsRequest, GetIssueSubtreeRequest, GetIssuesByMilestoneNameRequest,
             GetVersionMilestoneListRequest,
         },
     },
     project::{
         self,
         request::{
-            GetCustomFieldListRequest, GetPrioritiesRequest, GetProjectIssueTypesRequest,
-            GetProjectStatusListRequest,
+            GetCustomFieldListRequest, GetPrioritiesRequest, GetProjectDigestRequest,
+            GetProjectIssueTypesRequest, GetProjectStatusListRequest,
         },
     },
+    search::{self, request::SearchRequest},
     user::{self, request::GetUserListRequest},
     wiki::{
         self,
         request::{
             DownloadWikiAttachmentRequest, GetWikiAttachmentListRequest, GetWikiDetailRequest,
-            GetWikiListRequest,
+            GetWikiHistoryDiffRequest, GetWikiListRequest,
         },
     },
 };
@@ -61,6 +64,7 @@ use crate::access_control::AccessControl;
 #[cfg(feature = "git_writable")]
 use crate::git::request::AddPullRequestCommentRequest;
 use backlog_api_client::client::BacklogApiClient;
+use backlog_api_client::comment_thread;
 use rmcp::handler::server::router::tool;
 use rmcp::{
     ErrorData as McpError,
@@ -160,6 +164,19 @@ impl Server {
         Ok(CallToolResult::success(vec![Content::json(pull_requests)?]))
     }
 
+    #[tool(
+        description = "Get the pull requests needing the current user's attention across repositories, grouped into assignedToMe, createdByMe and recentlyUpdated. Optional: project_keys (defaults to all allowed projects), repositories, statuses (\"open\", \"closed\", \"merged\"; defaults to open), recent_days (defaults to 7). Each entry has comment count, age in days, related issue status and unread notification count."
+    )]
+    async fn git_pr_inbox_get(&self, request: Parameters<GetPullRequestInboxRequest>) -> McpResult {
+        let inbox = git::bridge::get_pull_request_inbox(
+            self.client.clone(),
+            request.0,
+            &self.access_control,
+        )
+        .await?;
+        Ok(CallToolResult::success(vec![Content::json(inbox)?]))
+    }
+
     #[tool(
         description = "Get details for a specific pull request. Requires project_id_or_key, repository_id_or_name, and number (PR number) parameters."
     )]
@@ -187,7 +204,19 @@ impl Server {
     }
 
     #[tool(
-        description = "Get details for a specific Backlog document. Returns document title, content as both Markdown ('plain') and ProseMirror JSON ('json'), and metadata. Requires project_id_or_key and document_id."
+        description = "Get the child issues of a Backlog issue as a tree, recursively. Each node has the issue key, summary, status, assignee, hours and a rollup of issue count, closed count and estimated/actual hours over its subtree. Requires issue_key."
+    )]
+    async fn issue_subtree_get(&self, request: Parameters<GetIssueSubtreeRequest>) -> McpResult {
+        let subtree =
+            issue::bridge::get_issue_subtree(self.client.clone(), request.0, &self.access_control)
+                .await?;
+        Ok(CallToolResult::success(vec![Content::json(
+            IssueTreeResponse::from(subtree),
+        )?]))
+    }
+
+    #[tool(
+        description = "Get details for a specific Backlog document. Returns document title, content as plain text ('plain'), ProseMirror JSON ('json') and Markdown converted from the ProseMirror JSON ('markdown'), and metadata. Requires document_id."
     )]
     async fn document_details_get(
         &self,
@@ -199,8 +228,11 @@ impl Server {
             &self.access_control,
         )
         .await?;
+        let document_response = DocumentDetailResponse::from(document);
 
-        Ok(CallToolResult::success(vec![Content::json(document)?]))
+        Ok(CallToolResult::success(vec![Content::json(
+            document_response,
+        )?]))
     }
 
     #[tool(
@@ -295,7 +327,7 @@ impl Server {
 
     #[cfg(feature = "issue_writable")]
     #[tool(
-        description = "Add a comment to a Backlog issue. Requires issue_id_or_key and content. Optional: notified_user_ids array for mentioning users."
+        description = "Add a comment to a Backlog issue. Requires issue_id_or_key and content. Optional: notified_user_ids array for mentioning users, attachment_ids of already uploaded files, and attachments (file_name + base64 content) to upload and attach. Set markdown to convert Markdown text to Backlog notation when the project uses it; text is sent unchanged otherwise."
     )]
     async fn issue_comment_add(&self, request: Parameters<AddCommentRequest>) -> McpResult {
         let comment =
@@ -320,7 +352,7 @@ impl Server {
 
     #[cfg(feature = "issue_writable")]
     #[tool(
-        description = "Create a new issue in a Backlog project. Requires project_id, issue_type_id, and summary. Optional: description, assignee_id, priority_id, due_date, custom fields, etc."
+        description = "Create a new issue in a Backlog project. Requires project_id, issue_type_id, and summary. Optional: description, assignee_id, priority_id, due_date, custom fields, attachments (file_name + base64 content) to upload and attach, etc. Set markdown to convert Markdown text to Backlog notation when the project uses it; text is sent unchanged otherwise."
     )]
     async fn issue_add(&self, request: Parameters<AddIssueRequest>) -> McpResult {
         let issue =
@@ -476,6 +508,35 @@ impl Server {
         Ok(CallToolResult::success(vec![Content::json(custom_fields)?]))
     }
 
+    #[tool(
+        description = "Summarize what happened in a period as a digest: issues created, closed and reassigned, status transitions, comments per issue, wiki pages edited, pull requests opened and merged, and activity per user. Optional: since (\"24h\" by default; also \"90m\", \"7d\", \"2w\" or a date), project_keys (defaults to all allowed projects, or the whole space without restriction), user_id, format (\"markdown\" ready to post to chat, or \"json\")."
+    )]
+    async fn project_digest_get(&self, request: Parameters<GetProjectDigestRequest>) -> McpResult {
+        let as_json = project::bridge::digest_as_json(request.0.format.as_deref())?;
+        let digest = project::bridge::get_project_digest(
+            self.client.clone(),
+            request.0,
+            &self.access_control,
+        )
+        .await?;
+        if as_json {
+            Ok(CallToolResult::success(vec![Content::json(digest)?]))
+        } else {
+            Ok(CallToolResult::success(vec![Content::text(
+                digest.to_markdown(),
+            )]))
+        }
+    }
+
+    #[tool(
+        description = "Search issues, wiki pages, documents and pull requests at once. Requires keyword. Optional: kinds (\"issue\", \"wiki\", \"document\", \"pull_request\"), project_keys (defaults to all allowed projects), limit. Pull requests have no server-side search, so only the latest `limit` pull requests of each repository are matched; older ones are not found. Returns hits with kind, project, key, title, snippet, updated and URL, best match first, plus any searches that failed."
+    )]
+    async fn search_results_get(&self, request: Parameters<SearchRequest>) -> McpResult {
+        let results =
+            search::bridge::search(self.client.clone(), request.0, &self.access_control).await?;
+        Ok(CallToolResult::success(vec![Content::json(results)?]))
+    }
+
     #[tool(
         description = "Get a list of shared files in a project directory. Requires project_id_or_key and path. Optional: order, offset, count. Returns file/folder information."
     )]
@@ -559,19 +620,24 @@ impl Server {
     }
 
     #[tool(
-        description = "Get a list of comments for a specific pull request. Requires project_id_or_key, repository_id_or_name, and number (PR number). Optional: min_id, max_id, count, order."
+        description = "Get the comments of a specific pull request as compact threads: the general conversation first, then one thread per referenced file and line. Each comment has author, dates, content, state changes (e.g. \"status: Open → Merged\"), stars, mentions and any quoted diff hunk. Requires project_id_or_key, repository_id_or_name, and number (PR number). Optional: min_id, max_id, count, order, raw (return the full comment objects instead)."
     )]
     async fn git_pr_comment_list_get(
         &self,
         request: Parameters<GetPullRequestCommentListRequest>,
     ) -> McpResult {
+        let raw = request.0.raw.unwrap_or(false);
         let comments = git::bridge::get_pull_request_comment_list_tool(
             self.client.clone(),
             request.0,
             &self.access_control,
         )
         .await?;
-        Ok(CallToolResult::success(vec![Content::json(comments)?]))
+        if raw {
+            return Ok(CallToolResult::success(vec![Content::json(comments)?]));
+        }
+        let threads = comment_thread::build_threads(&comments);
+        Ok(CallToolResult::success(vec![Content::json(threads)?]))
     }
 
     #[tool(
@@ -631,9 +697,22 @@ impl Server {
         Ok(CallToolResult::success(vec![response_data.try_into()?]))
     }
 
+    #[tool(
+        description = "Get a unified diff between two versions of a wiki page. Requires wiki_id and from_version. Optional: to_version (defaults to the current content). Returns line counts and the diff text."
+    )]
+    async fn wiki_history_diff_get(
+        &self,
+        request: Parameters<GetWikiHistoryDiffRequest>,
+    ) -> McpResult {
+        let client = self.client.lock().await;
+        let diff =
+            wiki::bridge::get_wiki_history_diff(&client, request.0, &self.access_control).await?;
+        Ok(CallToolResult::success(vec![Content::json(diff)?]))
+    }
+
     #[cfg(feature = "wiki_writable")]
     #[tool(
-        description = "Update a wiki page. Requires wiki_id. Optional: name (page title), content (markdown), mail_notify (boolean for notifications)."
+        description = "Update a wiki page. Requires wiki_id. Optional: name (page title), content (Markdown), mail_notify (boolean for notifications), markdown. Set markdown to convert Markdown text to Backlog notation when the project uses it; text is sent unchanged otherwise."
     )]
     async fn wiki_update(&self, request: Parameters<UpdateWikiRequest>) -> McpResult {
         let client = self.client.lock().await;
@@ -644,7 +723,7 @@ impl Server {
 
     #[cfg(feature = "git_writable")]
     #[tool(
-        description = "Add a comment to a pull request. Requires project_id_or_key, repository_id_or_name, number (PR number), and content. Optional: notified_user_ids array."
+        description = "Add a comment to a pull request. Requires project_id_or_key, repository_id_or_name, number (PR number), and content. Optional: notified_user_ids array and attachments (file_name + base64 content) to upload and attach."
     )]
     async fn git_pr_comment_add(
         &self,
@@ -2,7 +2,7 @@ use crate::access_control::AccessControl;
 use crate::error::{Error as McpError, Result};
 use crate::wiki::request::{
     DownloadWikiAttachmentRequest, GetWikiAttachmentListRequest, GetWikiDetailRequest,
-    GetWikiListRequest,
+    GetWikiHistoryDiffRequest, GetWikiListRequest,
 };
 
 #[cfg(feature = "wiki_writable")]
@@ -11,7 +11,8 @@ use backlog_api_client::{
     DownloadedFile, GetWikiListParams, ProjectIdOrKey, client::BacklogApiClient,
 };
 use backlog_wiki::{
-    DownloadWikiAttachmentParams, GetWikiAttachmentListParams, GetWikiDetailParams,
+    DownloadWikiAttachmentParams, GetWikiAttachmentListParams, GetWikiDetailParams, WikiHistory,
+    WikiVersionDiff,
 };
 
 use backlog_core::{
@@ -131,6 +132,51 @@ pub(crate) async fn download_wiki_attachment(
     Ok(downloaded_file)
 }
 
+pub(crate) async fn get_wiki_history_diff(
+    client: &BacklogApiClient,
+    request: GetWikiHistoryDiffRequest,
+    access_control: &AccessControl,
+) -> Result<serde_json::Value> {
+    let wiki_api = client.wiki();
+    let wiki_id = WikiId::new(request.wiki_id);
+
+    // First get wiki details to check project access
+    let wiki_detail = wiki_api
+        .get_wiki_detail(GetWikiDetailParams::new(wiki_id))
+        .await?;
+
+    access_control
+        .check_project_access_by_id_async(&wiki_detail.project_id, client)
+        .await?;
+
+    let from = find_wiki_version(client, wiki_id, request.from_version).await?;
+    let diff = match request.to_version {
+        Some(to_version) => {
+            let to = find_wiki_version(client, wiki_id, to_version).await?;
+            WikiVersionDiff::between(&from, &to)
+        }
+        None => WikiVersionDiff::against_current(&from, &wiki_detail),
+    };
+
+    Ok(serde_json::to_value(diff)?)
+}
+
+async fn find_wiki_version(
+    client: &BacklogApiClient,
+    wiki_id: WikiId,
+    version: u32,
+) -> Result<WikiHistory> {
+    client
+        .wiki()
+        .get_wiki_version(wiki_id, version)
+        .await?
+        .ok_or_else(|| {
+            McpError::Parameter(format!(
+                "Version {version} not found in history of wiki {wiki_id}."
+            ))
+        })
+}
+
 #[cfg(feature = "wiki_writable")]
 pub(crate) async fn update_wiki(
     client: &BacklogApiClient,
@@ -157,6 +203,14 @@ pub(crate) async fn update_wiki(
     }
 
     if let Some(content) = request.content {
+        let content = crate::formatting::for_project(
+            access_control,
+            client,
+            &wiki_detail_before.project_id,
+            content,
+            request.markdown,
+        )
+        .await?;
         params = params.content(content);
     }
 
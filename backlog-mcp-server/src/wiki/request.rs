@@ -41,6 +41,19 @@ pub(crate) struct DownloadWikiAttachmentRequest {
     pub format: Option<String>,
 }
 
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+pub(crate) struct GetWikiHistoryDiffRequest {
+    #[schemars(description = "Wiki page ID to compare versions of. Must be a positive integer.")]
+    pub wiki_id: u32,
+    #[schemars(description = "Older version number to compare from.")]
+    pub from_version: u32,
+    #[schemars(
+        description = "Optional newer version number to compare to. If omitted, the current page content is used."
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_version: Option<u32>,
+}
+
 #[cfg(feature = "wiki_writable")]
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
 pub(crate) struct UpdateWikiRequest {
@@ -55,4 +68,9 @@ pub(crate) struct UpdateWikiRequest {
     #[schemars(description = "Optional whether to send email notification of update.")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mail_notify: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        description = "Treat the text as Markdown and convert it to Backlog notation when the project uses Backlog notation (optional, default false). Leave unset for text that is already in the project's notation."
+    )]
+    pub markdown: Option<bool>,
 }
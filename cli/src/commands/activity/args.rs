@@ -1,3 +1,5 @@
+#[cfg(feature = "events")]
+use clap::Args;
 use clap::Parser;
 
 #[cfg(feature = "project")]
@@ -43,4 +45,43 @@ pub enum ActivityCommands {
         #[clap(long)]
         order: Option<String>,
     },
+    /// Print activities of the space as they happen, by polling
+    #[cfg(feature = "events")]
+    Watch(ActivityWatchArgs),
+}
+
+#[cfg(feature = "events")]
+#[derive(Args, Debug)]
+pub struct ActivityWatchArgs {
+    /// Only activities of these projects (IDs or keys, comma-separated)
+    #[arg(short, long, value_delimiter = ',')]
+    pub project: Vec<String>,
+
+    /// Only activities of these types (comma-separated IDs)
+    #[arg(long, value_delimiter = ',')]
+    pub type_ids: Vec<u32>,
+
+    /// Only activities by these users (comma-separated numeric IDs)
+    #[arg(long, value_delimiter = ',')]
+    pub user_ids: Vec<u32>,
+
+    /// Seconds between polls while activities keep coming
+    #[arg(long, default_value_t = 5)]
+    pub interval: u64,
+
+    /// Seconds the interval grows to while nothing happens
+    #[arg(long, default_value_t = 60)]
+    pub max_interval: u64,
+
+    /// File remembering the last activity seen [default: in the cache directory]
+    #[arg(long)]
+    pub cursor: Option<std::path::PathBuf>,
+
+    /// Run this shell command for every activity, with its JSON on stdin
+    #[arg(long)]
+    pub exec: Option<String>,
+
+    /// Print each activity as one line of JSON
+    #[arg(long)]
+    pub json: bool,
 }
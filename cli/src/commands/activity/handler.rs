@@ -25,6 +25,10 @@ pub async fn execute(client: &BacklogApiClient, activity_args: ActivityArgs) ->
         } => {
             subcommands::recent::space_recent(client, type_ids, count, order).await?;
         }
+        #[cfg(feature = "events")]
+        ActivityCommands::Watch(args) => {
+            subcommands::watch::watch(client, args).await?;
+        }
     }
     Ok(())
 }
@@ -1,2 +1,4 @@
 #[cfg(feature = "project")]
 pub(crate) mod recent;
+#[cfg(feature = "events")]
+pub(crate) mod watch;
@@ -0,0 +1,148 @@
+//! Tail the space's activities without webhooks
+
+use crate::commands::activity::args::ActivityWatchArgs;
+use crate::commands::common::{CliResult, cache_dir, shell_command, truncate_text};
+use anyhow::Context;
+use backlog_api_client::ProjectIdOrKey;
+use backlog_api_client::client::BacklogApiClient;
+use backlog_api_client::events::EventParams;
+use backlog_core::ActiveTypeId;
+use backlog_core::activity::{Activity, Content};
+use backlog_core::identifier::{ActivityTypeId, Identifier, UserId};
+use futures_util::StreamExt;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+/// Default cursor file, one per space
+fn default_cursor_path(client: &BacklogApiClient) -> CliResult<PathBuf> {
+    let host = client.base_url().host_str().unwrap_or("default");
+    Ok(cache_dir()?.join(format!("activity-cursor-{host}")))
+}
+
+/// One line per activity: time, project, type, user and subject
+fn format_activity(activity: &Activity) -> String {
+    let type_name = serde_json::from_value::<ActiveTypeId>(activity.type_id.into()).map_or_else(
+        |_| format!("Type {}", activity.type_id),
+        |name| format!("{name:?}"),
+    );
+    let project = activity.project_key().unwrap_or("-");
+    let subject = match &activity.content {
+        Content::Standard {
+            key_id, summary, ..
+        } => {
+            let key = key_id.map(|key_id| format!("{project}-{key_id} "));
+            let summary = summary.as_deref().unwrap_or_default();
+            truncate_text(&format!("{}{summary}", key.unwrap_or_default()), 80)
+        }
+        _ => String::new(),
+    };
+    format!(
+        "{}  {project:<10} {type_name:<26} {:<16} {subject}",
+        activity
+            .created
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M:%S"),
+        activity.created_user.name,
+    )
+    .trim_end()
+    .to_string()
+}
+
+/// Run `command` through the shell with the activity JSON on stdin
+async fn run_hook(command: &str, activity: &Activity, json: &str) -> CliResult<()> {
+    let mut child = shell_command(command)
+        .env("BLG_ACTIVITY_ID", activity.id.value().to_string())
+        .env("BLG_ACTIVITY_TYPE", activity.type_id.to_string())
+        .stdin(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run '{command}'"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A hook that ignores its input closes the pipe early; that is fine
+        let _ = stdin.write_all(json.as_bytes()).await;
+    }
+    let status = child.wait().await?;
+    if !status.success() {
+        anyhow::bail!("'{command}' exited with {status}");
+    }
+    Ok(())
+}
+
+/// Print activities as they happen until interrupted
+pub(crate) async fn watch(client: &BacklogApiClient, args: ActivityWatchArgs) -> CliResult<()> {
+    let projects = args
+        .project
+        .iter()
+        .map(|project| {
+            project
+                .parse::<ProjectIdOrKey>()
+                .with_context(|| format!("Failed to parse project '{project}'"))
+        })
+        .collect::<CliResult<Vec<_>>>()?;
+    let cursor = match args.cursor {
+        Some(path) => path,
+        None => default_cursor_path(client)?,
+    };
+    let params = EventParams::default()
+        .projects(projects)
+        .activity_type_ids(args.type_ids.into_iter().map(ActivityTypeId::new).collect())
+        .user_ids(args.user_ids.into_iter().map(UserId::new).collect())
+        .interval(
+            Duration::from_secs(args.interval.max(1)),
+            Duration::from_secs(args.max_interval),
+        )
+        .cursor_file(&cursor);
+
+    eprintln!(
+        "Watching activities (cursor: {}, Ctrl+C to stop)",
+        cursor.display()
+    );
+    let mut events = std::pin::pin!(client.events(params));
+    while let Some(event) = events.next().await {
+        let activity = match event {
+            Ok(activity) => activity,
+            Err(e) => {
+                eprintln!("Warning: polling failed, retrying: {e}");
+                continue;
+            }
+        };
+        let json = serde_json::to_string(&activity)?;
+        if args.json {
+            println!("{json}");
+        } else {
+            println!("{}", format_activity(&activity));
+        }
+        if let Some(command) = &args.exec
+            && let Err(e) = run_hook(command, &activity, &json).await
+        {
+            eprintln!("Warning: hook for activity {}: {e:#}", activity.id.value());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_activity() {
+        let activity: Activity = serde_json::from_value(serde_json::json!({
+            "id": 5, "project": {"id": 1, "projectKey": "BLG", "name": "Backlog"},
+            "type": 3,
+            "content": {"id": 10, "keyId": 42, "summary": "Login fails"},
+            "notifications": [],
+            "createdUser": {"id": 1, "userId": "john", "name": "John", "roleType": 1, "mailAddress": "john@example.com"},
+            "created": "2024-01-01T00:00:00Z"
+        }))
+        .unwrap();
+        let line = format_activity(&activity);
+        assert!(
+            line.ends_with(
+                "BLG        IssueCommented             John             BLG-42 Login fails"
+            ),
+            "{line}"
+        );
+    }
+}
@@ -2,7 +2,12 @@
 //!
 //! This module provides reusable functions for:
 //! - Date parsing and conversion
-//! - Display helpers (truncate text, format bytes)
+//! - Display helpers (truncate text, format bytes, download progress)
+//! - Opening URLs in a browser
+//! - Locating the local cache directory
+//! - Reading CSV input for bulk operations
+//! - Converting Markdown input to a project's formatting rule
+//! - Resolving project arguments
 //! - File operations (download files)
 //! - Error handling
 
@@ -58,6 +63,221 @@ pub fn format_bytes(bytes: u64) -> String {
     }
 }
 
+/// Progress callback for streaming downloads, drawing a single line on stderr
+///
+/// The line is redrawn at most once per percent (or per MiB when the size is
+/// unknown) and finished with a newline once the download completes.
+pub fn download_progress() -> impl FnMut(backlog_api_client::DownloadProgress) {
+    use std::io::Write;
+
+    let mut last_step = None;
+    move |progress| {
+        let step = match progress.total {
+            Some(total) if total > 0 => progress.downloaded * 100 / total,
+            _ => progress.downloaded / (1024 * 1024),
+        };
+        let done = progress.total == Some(progress.downloaded);
+        if last_step == Some(step) && !done {
+            return;
+        }
+        last_step = Some(step);
+
+        let line = match progress.total {
+            Some(total) => format!(
+                "{} / {} ({step}%)",
+                format_bytes(progress.downloaded),
+                format_bytes(total)
+            ),
+            None => format_bytes(progress.downloaded),
+        };
+        let mut stderr = std::io::stderr();
+        let _ = write!(stderr, "\r   {line}\x1b[K");
+        if done {
+            let _ = writeln!(stderr);
+        }
+        let _ = stderr.flush();
+    }
+}
+
+/// Convert Markdown text to the formatting rule of the project it is written to
+///
+/// Projects using Backlog notation would otherwise show Markdown verbatim.
+#[cfg(feature = "project")]
+pub async fn markdown_for_project(
+    client: &backlog_api_client::client::BacklogApiClient,
+    project: impl Into<backlog_api_client::ProjectIdOrKey>,
+    text: &str,
+) -> CliResult<String> {
+    use backlog_api_client::backlog_project::GetProjectDetailParams;
+    use backlog_core::{TextFormattingRule, notation};
+
+    let project = client
+        .project()
+        .get_project(GetProjectDetailParams::new(project.into()))
+        .await?;
+    Ok(notation::convert(
+        text,
+        TextFormattingRule::Markdown,
+        project.text_formatting_rule,
+    ))
+}
+
+/// The given projects by ID or key, or all active projects when none are given
+#[cfg(feature = "project")]
+pub async fn resolve_projects(
+    client: &backlog_api_client::client::BacklogApiClient,
+    projects: &[String],
+) -> CliResult<Vec<backlog_api_client::backlog_project::Project>> {
+    use anyhow::Context;
+    use backlog_api_client::ProjectIdOrKey;
+    use backlog_api_client::backlog_project::{GetProjectDetailParams, GetProjectListParams};
+
+    if projects.is_empty() {
+        let params = GetProjectListParams {
+            archived: Some(false),
+            all: false,
+        };
+        return Ok(client.project().get_project_list(params).await?);
+    }
+
+    let mut resolved = Vec::with_capacity(projects.len());
+    for project in projects {
+        let project_id_or_key = project
+            .parse::<ProjectIdOrKey>()
+            .with_context(|| format!("Failed to parse project '{project}'"))?;
+        let project = client
+            .project()
+            .get_project(GetProjectDetailParams::new(project_id_or_key))
+            .await
+            .with_context(|| format!("Failed to get project '{project}'"))?;
+        resolved.push(project);
+    }
+    Ok(resolved)
+}
+
+/// Directory for locally cached data, such as the completion index
+///
+/// `$XDG_CACHE_HOME/blg`, falling back to `~/.cache/blg`.
+pub fn cache_dir() -> CliResult<std::path::PathBuf> {
+    let base = match std::env::var_os("XDG_CACHE_HOME").filter(|v| !v.is_empty()) {
+        Some(dir) => std::path::PathBuf::from(dir),
+        None => std::env::var_os("HOME")
+            .or_else(|| std::env::var_os("USERPROFILE"))
+            .map(|home| std::path::PathBuf::from(home).join(".cache"))
+            .ok_or_else(|| anyhow::anyhow!("Cannot determine the cache directory"))?,
+    };
+    Ok(base.join("blg"))
+}
+
+/// Open a URL in the default browser
+///
+/// Uses `$BROWSER` when set, otherwise the platform opener.
+pub fn open_in_browser(url: &str) -> CliResult<()> {
+    use std::process::Command;
+
+    let mut command = match std::env::var("BROWSER") {
+        Ok(browser) if !browser.is_empty() => Command::new(browser),
+        _ if cfg!(target_os = "macos") => Command::new("open"),
+        _ if cfg!(target_os = "windows") => {
+            let mut command = Command::new("cmd");
+            command.args(["/C", "start", ""]);
+            command
+        }
+        _ => Command::new("xdg-open"),
+    };
+
+    let status = command
+        .arg(url)
+        .status()
+        .map_err(|e| anyhow::anyhow!("Failed to open browser: {e}"))?;
+    if !status.success() {
+        anyhow::bail!("Browser exited with {status}");
+    }
+    Ok(())
+}
+
+/// Command running `command` through the platform shell
+///
+/// `sh -c` everywhere but Windows, where `cmd /C` is used.
+pub fn shell_command(command: &str) -> tokio::process::Command {
+    let mut shell = if cfg!(target_os = "windows") {
+        let mut shell = tokio::process::Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = tokio::process::Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    shell.arg(command);
+    shell
+}
+
+/// Parse CSV text with a header row into records keyed by column name
+///
+/// Supports quoted fields with `""` escapes and embedded commas or newlines.
+/// Column names are trimmed and blank lines are skipped.
+pub fn parse_csv(text: &str) -> CliResult<Vec<std::collections::HashMap<String, String>>> {
+    let mut rows: Vec<(usize, Vec<String>)> = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut row_line = 1;
+    let mut chars = text.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if in_quotes => in_quotes = false,
+            '"' if field.is_empty() => in_quotes = true,
+            '\n' if in_quotes => {
+                line += 1;
+                field.push(c);
+            }
+            ',' if !in_quotes => row.push(std::mem::take(&mut field)),
+            '\r' if !in_quotes && chars.peek() == Some(&'\n') => {}
+            '\n' if !in_quotes => {
+                row.push(std::mem::take(&mut field));
+                rows.push((row_line, std::mem::take(&mut row)));
+                line += 1;
+                row_line = line;
+            }
+            c => field.push(c),
+        }
+    }
+    if in_quotes {
+        anyhow::bail!("Unterminated quoted field starting on line {row_line}");
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push((row_line, row));
+    }
+
+    let mut rows = rows
+        .into_iter()
+        .filter(|(_, r)| !(r.len() == 1 && r[0].trim().is_empty()));
+    let Some((_, header)) = rows.next() else {
+        anyhow::bail!("CSV input is empty");
+    };
+    let header: Vec<String> = header.iter().map(|h| h.trim().to_string()).collect();
+
+    rows.map(|(line, values)| {
+        if values.len() != header.len() {
+            anyhow::bail!(
+                "Line {line}: expected {} columns, found {}",
+                header.len(),
+                values.len()
+            );
+        }
+        Ok(header.iter().cloned().zip(values).collect())
+    })
+    .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -88,4 +308,23 @@ mod tests {
         assert_eq!(start.format("%H:%M:%S").to_string(), "00:00:00");
         assert_eq!(end.format("%H:%M:%S").to_string(), "23:59:59");
     }
+
+    #[test]
+    fn test_parse_csv() {
+        let text = "userId, name ,mailAddress\r\nalice,\"Smith, Alice\",alice@example.com\n\nbob,\"Bob \"\"B\"\"\nJones\",bob@example.com\n";
+        let records = parse_csv(text).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0]["userId"], "alice");
+        assert_eq!(records[0]["name"], "Smith, Alice");
+        assert_eq!(records[1]["name"], "Bob \"B\"\nJones");
+        assert_eq!(records[1]["mailAddress"], "bob@example.com");
+    }
+
+    #[test]
+    fn test_parse_csv_errors() {
+        assert!(parse_csv("").is_err());
+        assert!(parse_csv("a,b\n\"unterminated,x\n").is_err());
+        let err = parse_csv("a,b\n1,2\n3\n").unwrap_err();
+        assert_eq!(err.to_string(), "Line 3: expected 2 columns, found 1");
+    }
 }
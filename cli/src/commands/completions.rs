@@ -0,0 +1,665 @@
+//! Shell completions
+//!
+//! `blg completions <shell>` prints a completion script generated from the
+//! clap definitions, extended with a hook that asks
+//! `blg completions <shell> --values -- <words>` for values such as project
+//! keys or issue keys. Those values come from an index cached on disk, which
+//! is refreshed in a background process when it gets stale.
+
+use crate::commands::common::{CliResult, cache_dir};
+use backlog_api_client::client::BacklogApiClient;
+use backlog_core::identifier::Identifier;
+use chrono::{DateTime, Duration, Utc};
+use clap::{Args, Command, ValueEnum};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Age after which the cached index is refreshed in the background
+const INDEX_TTL_MINUTES: i64 = 60;
+/// Minimum time between two background refreshes
+const REFRESH_BACKOFF_MINUTES: i64 = 5;
+/// Number of recently viewed issues to cache
+const RECENT_ISSUE_COUNT: u32 = 100;
+
+#[derive(Args)]
+pub struct CompletionsArgs {
+    /// Shell to generate the completion script for
+    #[arg(value_enum)]
+    shell: Option<Shell>,
+    /// Rebuild the cached index of projects, repositories, wikis and issues
+    #[arg(long, conflicts_with = "values")]
+    refresh: bool,
+    /// Print candidate values for the word after WORDS (used by the scripts)
+    #[arg(long, hide = true, requires = "shell")]
+    values: bool,
+    /// Words typed so far, excluding the program name and the current word
+    #[arg(last = true, hide = true)]
+    words: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// Kind of value an argument accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ValueKind {
+    Project,
+    Repository,
+    Wiki,
+    Status,
+    StatusName,
+    IssueType,
+    IssueTypeName,
+    Issue,
+}
+
+impl ValueKind {
+    /// Map an argument ID (the field name, or the `name` given to clap) to a kind
+    fn from_arg_id(id: &str) -> Option<Self> {
+        match id.to_lowercase().as_str() {
+            "project" | "project_id" | "project_id_or_key" | "project_key" => Some(Self::Project),
+            "repo" | "repo_id" | "repo_id_or_name" | "repository" => Some(Self::Repository),
+            "wiki_id" => Some(Self::Wiki),
+            "status_id" | "substitute_status_id" => Some(Self::Status),
+            "status" => Some(Self::StatusName),
+            "issue_type_id" | "substitute_issue_type_id" => Some(Self::IssueType),
+            "issue_type" => Some(Self::IssueTypeName),
+            "issue" | "issue_id_or_key" | "issue_key" => Some(Self::Issue),
+            _ => None,
+        }
+    }
+}
+
+/// A completion value with a short description
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Candidate {
+    pub value: String,
+    pub description: String,
+}
+
+impl Candidate {
+    fn new(value: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            value: value.into(),
+            description: description.into(),
+        }
+    }
+}
+
+/// Values offered for dynamic completion, cached per space
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct CompletionIndex {
+    pub base_url: String,
+    pub updated: DateTime<Utc>,
+    #[serde(default)]
+    pub projects: Vec<Candidate>,
+    #[serde(default)]
+    pub repositories: Vec<Candidate>,
+    #[serde(default)]
+    pub wikis: Vec<Candidate>,
+    #[serde(default)]
+    pub statuses: Vec<Candidate>,
+    /// Distinct status names, described by the projects using them
+    #[serde(default)]
+    pub status_names: Vec<Candidate>,
+    #[serde(default)]
+    pub issue_types: Vec<Candidate>,
+    /// Distinct issue type names, described by the projects using them
+    #[serde(default)]
+    pub issue_type_names: Vec<Candidate>,
+    #[serde(default)]
+    pub issues: Vec<Candidate>,
+}
+
+impl CompletionIndex {
+    fn candidates(&self, kind: ValueKind) -> &[Candidate] {
+        match kind {
+            ValueKind::Project => &self.projects,
+            ValueKind::Repository => &self.repositories,
+            ValueKind::Wiki => &self.wikis,
+            ValueKind::Status => &self.statuses,
+            ValueKind::StatusName => &self.status_names,
+            ValueKind::IssueType => &self.issue_types,
+            ValueKind::IssueTypeName => &self.issue_type_names,
+            ValueKind::Issue => &self.issues,
+        }
+    }
+
+    fn is_stale(&self, now: DateTime<Utc>) -> bool {
+        now - self.updated > Duration::minutes(INDEX_TTL_MINUTES)
+    }
+}
+
+/// Execute the completions command
+///
+/// Runs before the API client is created so that printing a script does not
+/// require credentials.
+pub async fn execute(args: &CompletionsArgs, mut command: Command) -> CliResult<()> {
+    if args.refresh {
+        let client = crate::client_from_env()?;
+        let index = build_index(&client).await?;
+        save_index(&client, &index)?;
+        println!(
+            "✅ Cached {} projects, {} repositories, {} wikis, {} issues",
+            index.projects.len(),
+            index.repositories.len(),
+            index.wikis.len(),
+            index.issues.len()
+        );
+        return Ok(());
+    }
+
+    let Some(shell) = args.shell else {
+        anyhow::bail!("Specify a shell (bash, zsh or fish) or --refresh");
+    };
+
+    if args.values {
+        // Completion must never fail loudly; print nothing on errors.
+        if let Some(kind) = value_kind_for(&command, &args.words)
+            && let Ok(client) = crate::client_from_env()
+            && let Some(index) = load_index_and_refresh(&client)
+        {
+            for candidate in index.candidates(kind) {
+                println!("{}", format_candidate(shell, candidate));
+            }
+        }
+        return Ok(());
+    }
+
+    let name = command.get_name().to_string();
+    let generator = match shell {
+        Shell::Bash => clap_complete::Shell::Bash,
+        Shell::Zsh => clap_complete::Shell::Zsh,
+        Shell::Fish => clap_complete::Shell::Fish,
+    };
+    let mut script = Vec::new();
+    clap_complete::generate(generator, &mut command, &name, &mut script);
+    print!("{}", String::from_utf8(script)?);
+    print!("{}", dynamic_hook(shell, &name));
+
+    Ok(())
+}
+
+/// Find the kind of value expected after `words`, by walking the clap tree
+fn value_kind_for(root: &Command, words: &[String]) -> Option<ValueKind> {
+    let mut command = root;
+    let mut positional = 0;
+    let mut pending_option: Option<&clap::Arg> = None;
+
+    for word in words {
+        if pending_option.take().is_some() {
+            continue;
+        }
+        if let Some(long) = word.strip_prefix("--") {
+            if long.is_empty() || long.contains('=') {
+                continue;
+            }
+            pending_option = command
+                .get_arguments()
+                .find(|a| {
+                    a.get_long() == Some(long)
+                        || a.get_all_aliases().is_some_and(|al| al.contains(&long))
+                })
+                .filter(|a| a.get_action().takes_values());
+        } else if let Some(short) = word.strip_prefix('-').filter(|s| !s.is_empty()) {
+            let short = short.chars().last()?;
+            pending_option = command
+                .get_arguments()
+                .find(|a| a.get_short() == Some(short))
+                .filter(|a| a.get_action().takes_values());
+        } else if let Some(subcommand) = command.find_subcommand(word) {
+            command = subcommand;
+            positional = 0;
+        } else {
+            positional += 1;
+        }
+    }
+
+    let arg = match pending_option {
+        Some(arg) => arg,
+        None => command.get_positionals().nth(positional)?,
+    };
+    ValueKind::from_arg_id(arg.get_id().as_str())
+}
+
+fn format_candidate(shell: Shell, candidate: &Candidate) -> String {
+    match shell {
+        Shell::Bash => candidate.value.clone(),
+        Shell::Zsh => format!(
+            "{}:{}",
+            candidate.value.replace(':', "\\:"),
+            candidate.description
+        ),
+        Shell::Fish => format!("{}\t{}", candidate.value, candidate.description),
+    }
+}
+
+/// Shell code that consults `--values` before falling back to the clap script
+fn dynamic_hook(shell: Shell, name: &str) -> String {
+    match shell {
+        Shell::Bash => format!(
+            r#"
+_{name}_with_values() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}"
+    local values
+    values=$({name} completions bash --values -- "${{COMP_WORDS[@]:1:COMP_CWORD-1}}" 2>/dev/null)
+    if [[ -n "$values" ]]; then
+        COMPREPLY=($(compgen -W "$values" -- "$cur"))
+        return 0
+    fi
+    _{name} "$@"
+}}
+complete -F _{name}_with_values -o bashdefault -o default {name}
+"#
+        ),
+        Shell::Zsh => format!(
+            r#"
+_{name}_with_values() {{
+    local -a values
+    values=("${{(@f)$({name} completions zsh --values -- "${{(@)words[2,CURRENT-1]}}" 2>/dev/null)}}")
+    if [[ -n "${{values[1]}}" ]]; then
+        _describe 'value' values
+        return
+    fi
+    _{name} "$@"
+}}
+compdef _{name}_with_values {name}
+"#
+        ),
+        Shell::Fish => format!(
+            r#"
+complete -c {name} -a '({name} completions fish --values -- (commandline -opc)[2..-1] 2>/dev/null)'
+"#
+        ),
+    }
+}
+
+/// Index file of the client's space
+fn index_path(client: &BacklogApiClient) -> CliResult<PathBuf> {
+    let base_url = client.base_url();
+    let host = base_url.host_str().unwrap_or("default");
+    let name = match base_url.port() {
+        Some(port) => format!("completion-index-{host}_{port}.json"),
+        None => format!("completion-index-{host}.json"),
+    };
+    Ok(cache_dir()?.join(name))
+}
+
+fn save_index(client: &BacklogApiClient, index: &CompletionIndex) -> CliResult<()> {
+    let path = index_path(client)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    // Write to a temporary file first so a reader never sees a partial index
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, serde_json::to_vec(index)?)?;
+    std::fs::rename(tmp, path)?;
+    Ok(())
+}
+
+/// Load the cached index, starting a background refresh if it is missing or stale
+fn load_index_and_refresh(client: &BacklogApiClient) -> Option<CompletionIndex> {
+    let path = index_path(client).ok()?;
+    let index = std::fs::read(&path)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<CompletionIndex>(&bytes).ok());
+
+    if index.as_ref().is_none_or(|i| i.is_stale(Utc::now())) {
+        spawn_refresh(&path);
+    }
+    index
+}
+
+/// Run `blg completions --refresh` detached, at most once per backoff period
+fn spawn_refresh(index_path: &std::path::Path) {
+    let marker = index_path.with_extension("refreshing");
+    let recently_started = std::fs::metadata(&marker)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|elapsed| elapsed.as_secs() < (REFRESH_BACKOFF_MINUTES * 60).unsigned_abs());
+    if recently_started {
+        return;
+    }
+    if let Some(parent) = marker.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let _ = std::fs::write(&marker, b"");
+
+    if let Ok(exe) = std::env::current_exe() {
+        let _ = std::process::Command::new(exe)
+            .args(["completions", "--refresh"])
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn();
+    }
+}
+
+/// Add `name` to the candidates, or add `project` to the description of an existing one
+fn add_name(candidates: &mut Vec<Candidate>, name: &str, project: &str) {
+    match candidates.iter_mut().find(|c| c.value == name) {
+        Some(candidate) => {
+            candidate.description.push_str(", ");
+            candidate.description.push_str(project);
+        }
+        None => candidates.push(Candidate::new(name, project)),
+    }
+}
+
+/// Fetch every value offered for completion from the space
+///
+/// Per-project lookups that fail (e.g. Git disabled for a project) are skipped.
+pub(crate) async fn build_index(client: &BacklogApiClient) -> CliResult<CompletionIndex> {
+    use backlog_project::{GetIssueTypeListParams, GetProjectListParams, GetStatusListParams};
+
+    let projects = client
+        .project()
+        .get_project_list(GetProjectListParams::default())
+        .await?;
+
+    let mut index = CompletionIndex {
+        base_url: client.base_url().to_string(),
+        updated: Utc::now(),
+        projects: projects
+            .iter()
+            .map(|p| Candidate::new(p.project_key.to_string(), p.name.clone()))
+            .collect(),
+        repositories: Vec::new(),
+        wikis: Vec::new(),
+        statuses: Vec::new(),
+        status_names: Vec::new(),
+        issue_types: Vec::new(),
+        issue_type_names: Vec::new(),
+        issues: Vec::new(),
+    };
+
+    for project in &projects {
+        let key = project.project_key.to_string();
+
+        if let Ok(statuses) = client
+            .project()
+            .get_status_list(GetStatusListParams::new(project.id))
+            .await
+        {
+            index.statuses.extend(
+                statuses.iter().map(|s| {
+                    Candidate::new(s.id.value().to_string(), format!("{key}: {}", s.name))
+                }),
+            );
+            for status in &statuses {
+                add_name(&mut index.status_names, &status.name, &key);
+            }
+        }
+
+        if let Ok(issue_types) = client
+            .project()
+            .get_issue_type_list(GetIssueTypeListParams::new(project.id))
+            .await
+        {
+            index.issue_types.extend(
+                issue_types.iter().map(|t| {
+                    Candidate::new(t.id.value().to_string(), format!("{key}: {}", t.name))
+                }),
+            );
+            for issue_type in &issue_types {
+                add_name(&mut index.issue_type_names, &issue_type.name, &key);
+            }
+        }
+
+        #[cfg(feature = "git")]
+        if let Ok(repositories) = client
+            .git()
+            .get_repository_list(backlog_api_client::GetRepositoryListParams::new(project.id))
+            .await
+        {
+            index.repositories.extend(repositories.iter().map(|r| {
+                Candidate::new(
+                    r.name.clone(),
+                    format!("{key}: {}", r.description.as_deref().unwrap_or_default()),
+                )
+            }));
+        }
+
+        #[cfg(feature = "wiki")]
+        if project.use_wiki
+            && let Ok(wikis) = client
+                .wiki()
+                .get_wiki_list(
+                    backlog_api_client::GetWikiListParams::new().project_id_or_key(project.id),
+                )
+                .await
+        {
+            index.wikis.extend(
+                wikis.iter().map(|w| {
+                    Candidate::new(w.id.value().to_string(), format!("{key}: {}", w.name))
+                }),
+            );
+        }
+    }
+
+    #[cfg(feature = "issue")]
+    {
+        let params = backlog_issue::GetRecentlyViewedIssuesParamsBuilder::default()
+            .count(RECENT_ISSUE_COUNT)
+            .build()?;
+        if let Ok(issues) = client.issue().get_recently_viewed_issues(params).await {
+            index.issues = issues
+                .iter()
+                .map(|i| Candidate::new(i.issue_key.to_string(), i.summary.clone()))
+                .collect();
+        }
+    }
+
+    Ok(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{Parser, Subcommand};
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[derive(Parser)]
+    struct TestCli {
+        #[clap(subcommand)]
+        command: TestCommands,
+    }
+
+    #[derive(Subcommand)]
+    enum TestCommands {
+        Issue {
+            #[clap(subcommand)]
+            command: TestIssueCommands,
+        },
+        Wiki {
+            #[arg(long)]
+            wiki_id: u32,
+        },
+    }
+
+    #[derive(Subcommand)]
+    enum TestIssueCommands {
+        View {
+            #[clap(name = "ISSUE_ID_OR_KEY")]
+            issue_id_or_key: String,
+        },
+        Branch {
+            #[arg(long)]
+            status: Option<String>,
+        },
+        List {
+            #[arg(short, long)]
+            project_id: Option<String>,
+            #[arg(long)]
+            status_id: Option<u32>,
+            #[arg(long)]
+            json: bool,
+        },
+    }
+
+    fn kind(words: &[&str]) -> Option<ValueKind> {
+        use clap::CommandFactory;
+        let words: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+        value_kind_for(&TestCli::command(), &words)
+    }
+
+    #[test]
+    fn test_value_kind_for_positional() {
+        assert_eq!(kind(&["issue", "view"]), Some(ValueKind::Issue));
+        assert_eq!(kind(&["issue", "view", "PROJ-1"]), None);
+    }
+
+    #[test]
+    fn test_value_kind_for_options() {
+        assert_eq!(
+            kind(&["issue", "list", "--project-id"]),
+            Some(ValueKind::Project)
+        );
+        assert_eq!(kind(&["issue", "list", "-p"]), Some(ValueKind::Project));
+        assert_eq!(
+            kind(&["issue", "list", "--json", "--status-id"]),
+            Some(ValueKind::Status)
+        );
+        assert_eq!(kind(&["issue", "list", "-p", "PROJ"]), None);
+        assert_eq!(kind(&["wiki", "--wiki-id"]), Some(ValueKind::Wiki));
+        assert_eq!(
+            kind(&["issue", "branch", "--status"]),
+            Some(ValueKind::StatusName)
+        );
+    }
+
+    #[test]
+    fn test_format_candidate() {
+        let candidate = Candidate::new("a:b", "desc");
+        assert_eq!(format_candidate(Shell::Bash, &candidate), "a:b");
+        assert_eq!(format_candidate(Shell::Zsh, &candidate), "a\\:b:desc");
+        assert_eq!(format_candidate(Shell::Fish, &candidate), "a:b\tdesc");
+    }
+
+    #[test]
+    fn test_add_name_merges_projects() {
+        let mut names = Vec::new();
+        add_name(&mut names, "Open", "PROJ");
+        add_name(&mut names, "処理中", "PROJ");
+        add_name(&mut names, "Open", "APP");
+        assert_eq!(
+            names,
+            vec![
+                Candidate::new("Open", "PROJ, APP"),
+                Candidate::new("処理中", "PROJ")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_index_staleness() {
+        let now = Utc::now();
+        let mut index = CompletionIndex {
+            base_url: String::new(),
+            updated: now,
+            projects: Vec::new(),
+            repositories: Vec::new(),
+            wikis: Vec::new(),
+            statuses: Vec::new(),
+            status_names: Vec::new(),
+            issue_types: Vec::new(),
+            issue_type_names: Vec::new(),
+            issues: Vec::new(),
+        };
+        assert!(!index.is_stale(now));
+        index.updated = now - Duration::minutes(INDEX_TTL_MINUTES + 1);
+        assert!(index.is_stale(now));
+    }
+
+    #[tokio::test]
+    async fn test_build_index() {
+        let server = MockServer::start().await;
+        let user = serde_json::json!({
+            "id": 1, "userId": "john", "name": "John", "roleType": 1,
+            "mailAddress": "john@example.com"
+        });
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/projects"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                    "id": 1, "projectKey": "PROJ", "name": "Project",
+                    "chartEnabled": false, "subtaskingEnabled": false,
+                    "projectLeaderCanEditProjectLeader": false, "useWiki": true,
+                    "useFileSharing": false, "useWikiTreeView": false,
+                    "useOriginalImageSizeAtWiki": false, "textFormattingRule": "markdown",
+                    "archived": false, "displayOrder": 0, "useDevAttributes": false
+                }])),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/projects/1/statuses"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"id": 1, "projectId": 1, "name": "Open", "color": "#ed8077", "displayOrder": 1000}
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/projects/1/issueTypes"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+                {"id": 7, "projectId": 1, "name": "Bug", "color": "#990000", "displayOrder": 0}
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/projects/1/git/repositories"))
+            .respond_with(ResponseTemplate::new(403))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/wikis"))
+            .and(query_param("projectIdOrKey", "1"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                    "id": 42, "projectId": 1, "name": "Home", "tags": [],
+                    "createdUser": user, "created": "2024-01-01T00:00:00Z",
+                    "updatedUser": user, "updated": "2024-01-01T00:00:00Z"
+                }])),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/users/myself/recentlyViewedIssues"))
+            .and(query_param("count", RECENT_ISSUE_COUNT.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                "id": 10, "projectId": 1, "issueKey": "PROJ-3", "keyId": 3,
+                "issueType": {"id": 7, "projectId": 1, "name": "Bug", "color": "#990000", "displayOrder": 0},
+                "summary": "Crash on start", "description": "",
+                "status": {"id": 1, "projectId": 1, "name": "Open", "color": "#ed8077", "displayOrder": 1000},
+                "category": [], "versions": [], "milestone": [],
+                "createdUser": user, "created": "2024-01-01T00:00:00Z",
+                "updated": "2024-01-01T00:00:00Z"
+            }])))
+            .mount(&server)
+            .await;
+
+        let client = BacklogApiClient::new(&server.uri())
+            .unwrap()
+            .with_api_key("test");
+        let index = build_index(&client).await.unwrap();
+
+        assert_eq!(index.projects, vec![Candidate::new("PROJ", "Project")]);
+        assert_eq!(index.statuses, vec![Candidate::new("1", "PROJ: Open")]);
+        assert_eq!(index.status_names, vec![Candidate::new("Open", "PROJ")]);
+        assert_eq!(index.issue_types, vec![Candidate::new("7", "PROJ: Bug")]);
+        assert_eq!(index.issue_type_names, vec![Candidate::new("Bug", "PROJ")]);
+        assert!(index.repositories.is_empty());
+        assert_eq!(index.wikis, vec![Candidate::new("42", "PROJ: Home")]);
+        assert_eq!(
+            index.issues,
+            vec![Candidate::new("PROJ-3", "Crash on start")]
+        );
+    }
+}
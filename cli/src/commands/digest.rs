@@ -0,0 +1,53 @@
+//! Digest of recent activity for posting to chat
+
+use crate::commands::common::{CliResult, resolve_projects};
+use backlog_api_client::client::BacklogApiClient;
+use backlog_api_client::digest::{DigestParams, parse_since};
+use backlog_core::identifier::UserId;
+use chrono::Utc;
+use clap::Parser;
+
+#[derive(Parser)]
+pub struct DigestArgs {
+    /// Start of the period: 90m, 24h, 7d, 2w, a date (2024-05-01) or an RFC 3339 time
+    #[clap(long, default_value = "24h")]
+    since: String,
+    /// End of the period in the same forms as --since [default: now]
+    #[clap(long)]
+    until: Option<String>,
+    /// Only these projects (repeatable; default: the whole space)
+    #[clap(long = "project")]
+    projects: Vec<String>,
+    /// Only activities by this user ID
+    #[clap(long)]
+    user: Option<u32>,
+    /// Output as JSON instead of Markdown
+    #[clap(long)]
+    json: bool,
+}
+
+pub async fn execute(client: &BacklogApiClient, args: DigestArgs) -> CliResult<()> {
+    let now = Utc::now();
+    let since = parse_since(&args.since, now).map_err(anyhow::Error::msg)?;
+    let mut params = DigestParams::new(since);
+    if let Some(until) = &args.until {
+        params = params.until(parse_since(until, now).map_err(anyhow::Error::msg)?);
+    }
+    if !args.projects.is_empty() {
+        params = params.projects(resolve_projects(client, &args.projects).await?);
+    }
+    if let Some(user) = args.user {
+        params = params.user(UserId::new(user));
+    }
+    let digest = client.digest(params).await?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&digest)?);
+        return Ok(());
+    }
+    for failure in &digest.failures {
+        eprintln!("Warning: {}: {}", failure.source, failure.message);
+    }
+    print!("{}", digest.to_markdown());
+    Ok(())
+}
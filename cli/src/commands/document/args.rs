@@ -36,25 +36,44 @@ pub enum DocumentCommands {
     },
     /// Get document details
     Get {
-        /// Document ID (32-character hex string)
+        /// Document ID (32-character hex string) or path such as PROJ:/Guides/Onboarding
         #[clap(name = "DOCUMENT_ID")]
         document_id: String,
         /// Output in JSON format
         #[clap(long)]
         json: bool,
+        /// Output format (text, md, json)
+        #[clap(short, long, value_enum, conflicts_with = "json")]
+        format: Option<DocumentFormat>,
     },
-    /// Get document tree structure
+    /// Show the Active and Trash document trees of a project
     Tree {
-        /// Project ID or Key (required)
+        /// Project ID or Key
+        #[clap(
+            name = "PROJECT",
+            required_unless_present = "project_id",
+            conflicts_with = "project_id"
+        )]
+        project: Option<String>,
+        /// Project ID or Key (same as PROJECT)
         #[clap(short, long)]
-        project_id: String,
+        project_id: Option<String>,
         /// Output in JSON format
         #[clap(long)]
         json: bool,
     },
+    /// Export the document tree of a project to Markdown files
+    Export {
+        /// Project ID or Key
+        #[clap(name = "PROJECT")]
+        project: String,
+        /// Output directory
+        #[clap(name = "DIR")]
+        dir: std::path::PathBuf,
+    },
     /// Download attachment from a document
     Download {
-        /// Document ID
+        /// Document ID or path such as PROJ:/Guides/Onboarding
         #[clap(name = "DOCUMENT_ID")]
         document_id: String,
         /// Attachment ID
@@ -100,3 +119,15 @@ pub enum DocumentCommands {
         json: bool,
     },
 }
+
+#[cfg(feature = "document")]
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum DocumentFormat {
+    /// Summary with plain text content
+    Text,
+    /// Document body converted to Markdown
+    #[value(name = "md", alias = "markdown")]
+    Markdown,
+    /// Raw API response
+    Json,
+}
@@ -3,7 +3,7 @@
 //! Dispatches document subcommands to their respective implementations.
 
 use crate::commands::common::CliResult;
-use crate::commands::document::args::{DocumentArgs, DocumentCommands};
+use crate::commands::document::args::{DocumentArgs, DocumentCommands, DocumentFormat};
 use backlog_api_client::client::BacklogApiClient;
 
 use super::subcommands;
@@ -34,11 +34,30 @@ pub async fn execute(client: &BacklogApiClient, args: DocumentArgs) -> CliResult
             )
             .await?
         }
-        DocumentCommands::Get { document_id, json } => {
-            subcommands::list::get(client, document_id, json).await?
+        DocumentCommands::Get {
+            document_id,
+            json,
+            format,
+        } => {
+            let format = match format {
+                Some(format) => format,
+                None if json => DocumentFormat::Json,
+                None => DocumentFormat::Text,
+            };
+            subcommands::list::get(client, document_id, format).await?
+        }
+        DocumentCommands::Tree {
+            project,
+            project_id,
+            json,
+        } => {
+            let Some(project) = project.or(project_id) else {
+                anyhow::bail!("A project is required");
+            };
+            subcommands::tree::tree(client, project, json).await?
         }
-        DocumentCommands::Tree { project_id, json } => {
-            subcommands::list::tree(client, project_id, json).await?
+        DocumentCommands::Export { project, dir } => {
+            subcommands::tree::export(client, project, dir).await?
         }
         DocumentCommands::Download {
             document_id,
@@ -1,4 +1,6 @@
+use super::tree::resolve_document_id;
 use crate::commands::common::CliResult;
+use crate::commands::document::args::DocumentFormat;
 use anyhow::Context;
 use backlog_api_client::client::BacklogApiClient;
 use backlog_core::ProjectIdOrKey;
@@ -7,7 +9,7 @@ use backlog_core::identifier::{DocumentAttachmentId, DocumentId, Identifier, Pro
 use backlog_document::{AddDocumentParams, DeleteDocumentParams};
 use backlog_document::{
     DocumentOrder, DocumentSortKey, DownloadAttachmentParams, GetDocumentParams,
-    GetDocumentTreeParamsBuilder, ListDocumentsParamsBuilder,
+    ListDocumentsParamsBuilder,
 };
 use std::str::FromStr;
 
@@ -131,18 +133,20 @@ pub(crate) async fn list(client: &BacklogApiClient, options: ListOptions) -> Cli
 pub(crate) async fn get(
     client: &BacklogApiClient,
     document_id: String,
-    json: bool,
+    format: DocumentFormat,
 ) -> CliResult<()> {
-    if !json {
+    if format == DocumentFormat::Text {
         println!("Getting document: {document_id}");
     }
 
-    let doc_id = DocumentId::from_str(&document_id)?;
+    let doc_id = resolve_document_id(client, &document_id).await?;
     let params = GetDocumentParams::new(doc_id);
     let doc = client.document().get_document(params).await?;
 
-    if json {
+    if format == DocumentFormat::Json {
         println!("{}", serde_json::to_string_pretty(&doc)?);
+    } else if format == DocumentFormat::Markdown {
+        print!("{}", doc.to_markdown());
     } else {
         let emoji = doc.emoji.as_deref().unwrap_or("📄");
         println!("\n{} {}", emoji, doc.title);
@@ -178,49 +182,6 @@ pub(crate) async fn get(
     Ok(())
 }
 
-/// Get document tree structure
-pub(crate) async fn tree(
-    client: &BacklogApiClient,
-    project_id: String,
-    json: bool,
-) -> CliResult<()> {
-    if !json {
-        println!("Getting document tree for project: {project_id}");
-    }
-
-    let project_id_or_key: ProjectIdOrKey = project_id
-        .parse()
-        .with_context(|| format!("Invalid project: '{project_id}'"))?;
-    let params = GetDocumentTreeParamsBuilder::default()
-        .project_id_or_key(project_id_or_key)
-        .build()?;
-    let tree = client.document().get_document_tree(params).await?;
-
-    if json {
-        println!("{}", serde_json::to_string_pretty(&tree)?);
-    } else {
-        println!("\nDocument Tree (Active):");
-        print_tree_node(&tree.active_tree.children, 0);
-        if !tree.trash_tree.children.is_empty() {
-            println!("\nDocument Tree (Trash):");
-            print_tree_node(&tree.trash_tree.children, 0);
-        }
-    }
-
-    Ok(())
-}
-
-fn print_tree_node(nodes: &[backlog_document::DocumentTreeNode], depth: usize) {
-    let indent = "  ".repeat(depth);
-    for node in nodes {
-        let emoji = node.emoji.as_deref().unwrap_or("📄");
-        println!("{}{} {} (ID: {})", indent, emoji, node.name, node.id);
-        if !node.children.is_empty() {
-            print_tree_node(&node.children, depth + 1);
-        }
-    }
-}
-
 /// Download attachment from a document
 pub(crate) async fn download(
     client: &BacklogApiClient,
@@ -233,7 +194,7 @@ pub(crate) async fn download(
         attachment_id, document_id
     );
 
-    let doc_id = DocumentId::from_str(&document_id)?;
+    let doc_id = resolve_document_id(client, &document_id).await?;
     let att_id = DocumentAttachmentId::new(attachment_id);
     let params = DownloadAttachmentParams::new(doc_id, att_id);
 
@@ -0,0 +1,377 @@
+use crate::commands::common::CliResult;
+use anyhow::Context;
+use backlog_api_client::client::BacklogApiClient;
+use backlog_core::ProjectIdOrKey;
+use backlog_core::identifier::{DocumentId, Identifier};
+use backlog_document::prosemirror::{self, Node};
+use backlog_document::{
+    DocumentDetail, DocumentTreeNode, DocumentTreeRootNode, DownloadAttachmentParams,
+    GetDocumentParams, GetDocumentTreeParamsBuilder, GetDocumentTreeResponse,
+};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+async fn fetch_tree(
+    client: &BacklogApiClient,
+    project: &str,
+) -> CliResult<GetDocumentTreeResponse> {
+    let project_id_or_key: ProjectIdOrKey = project
+        .parse()
+        .with_context(|| format!("Invalid project: '{project}'"))?;
+    let params = GetDocumentTreeParamsBuilder::default()
+        .project_id_or_key(project_id_or_key)
+        .build()?;
+    Ok(client.document().get_document_tree(params).await?)
+}
+
+/// Resolve a document ID, or a path like `PROJ:/Guides/Onboarding`, to a document ID
+pub(crate) async fn resolve_document_id(
+    client: &BacklogApiClient,
+    document: &str,
+) -> CliResult<DocumentId> {
+    let Some((project, path)) = document.split_once(':') else {
+        return Ok(DocumentId::from_str(document)?);
+    };
+
+    let tree = fetch_tree(client, project).await?;
+    match tree.active_tree.find_by_path(path).as_slice() {
+        [] => anyhow::bail!("Document not found: '{path}' in project {project}"),
+        [node] => Ok(node.id.clone()),
+        nodes => {
+            let ids: Vec<String> = nodes.iter().map(|node| node.id.to_string()).collect();
+            anyhow::bail!(
+                "Ambiguous document path: '{path}' in project {project} matches {} documents ({}); use a document ID instead",
+                nodes.len(),
+                ids.join(", ")
+            )
+        }
+    }
+}
+
+/// Show the document tree of a project
+pub(crate) async fn tree(client: &BacklogApiClient, project: String, json: bool) -> CliResult<()> {
+    let tree = fetch_tree(client, &project).await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&tree)?);
+        return Ok(());
+    }
+
+    print_root(&tree.active_tree, "📚");
+    if !tree.trash_tree.children.is_empty() {
+        println!();
+        print_root(&tree.trash_tree, "🗑️");
+    }
+
+    Ok(())
+}
+
+fn print_root(root: &DocumentTreeRootNode, emoji: &str) {
+    println!(
+        "{emoji} {} ({} documents)",
+        root.id,
+        count_nodes(&root.children)
+    );
+    print_nodes(&root.children, "");
+}
+
+fn print_nodes(nodes: &[DocumentTreeNode], prefix: &str) {
+    for (i, node) in nodes.iter().enumerate() {
+        let last = i + 1 == nodes.len();
+        let (branch, indent) = if last {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        let emoji = node.emoji.as_deref().unwrap_or("📄");
+        println!("{prefix}{branch}{emoji} {} ({})", node.name, node.id);
+        print_nodes(&node.children, &format!("{prefix}{indent}"));
+    }
+}
+
+fn count_nodes(nodes: &[DocumentTreeNode]) -> usize {
+    nodes.iter().map(|n| 1 + count_nodes(&n.children)).sum()
+}
+
+/// Export the active document tree of a project to Markdown files
+///
+/// Each document is written to `<title>.md`, its attachments to
+/// `<title>.attachments/` and its children to the `<title>/` directory.
+pub(crate) async fn export(
+    client: &BacklogApiClient,
+    project: String,
+    dir: PathBuf,
+) -> CliResult<()> {
+    let tree = fetch_tree(client, &project).await?;
+
+    let mut entries = Vec::new();
+    plan_export(&tree.active_tree.children, &dir, &mut entries);
+    println!(
+        "Exporting {} documents from project {project} to {}",
+        entries.len(),
+        dir.display()
+    );
+
+    let mut failed = 0;
+    for (path, node) in &entries {
+        match export_document(client, &node.id, path).await {
+            Ok(attachments) => {
+                println!("  ✅ {}", path.display());
+                if attachments > 0 {
+                    println!("     {attachments} attachment(s)");
+                }
+            }
+            Err(e) => {
+                eprintln!("  ❌ {}: {e}", path.display());
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        anyhow::bail!("Failed to export {failed} of {} documents", entries.len());
+    }
+    println!("✅ Exported {} documents", entries.len());
+
+    Ok(())
+}
+
+/// Assign a unique file path (without extension) to every document in the tree
+fn plan_export<'a>(
+    nodes: &'a [DocumentTreeNode],
+    dir: &Path,
+    entries: &mut Vec<(PathBuf, &'a DocumentTreeNode)>,
+) {
+    let mut used = HashSet::new();
+    for node in nodes {
+        let mut name = sanitize_file_name(&node.name);
+        if name.is_empty() {
+            name = node.id.to_string();
+        }
+        if !used.insert(name.to_lowercase()) {
+            let id_short: String = node.id.as_str().chars().take(8).collect();
+            name = format!("{name} ({id_short})");
+            used.insert(name.to_lowercase());
+        }
+
+        let path = dir.join(&name);
+        entries.push((path.clone(), node));
+        plan_export(&node.children, &path, entries);
+    }
+}
+
+async fn export_document(
+    client: &BacklogApiClient,
+    document_id: &DocumentId,
+    path: &Path,
+) -> CliResult<usize> {
+    let doc = client
+        .document()
+        .get_document(GetDocumentParams::new(document_id.clone()))
+        .await?;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    // Attachment ID -> path of the downloaded file relative to the Markdown file
+    let mut local_files = HashMap::new();
+    if !doc.attachments.is_empty() {
+        let attachment_dir = with_suffix(path, ".attachments");
+        std::fs::create_dir_all(&attachment_dir)?;
+        let dir_name = attachment_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut used = HashSet::new();
+        for attachment in &doc.attachments {
+            let params = DownloadAttachmentParams::new(doc.id.clone(), attachment.id);
+            let file_name = attachment_file_name(&attachment.name, attachment.id, &mut used);
+            client
+                .download_to_path(params, attachment_dir.join(&file_name), |_| {})
+                .await?;
+            local_files.insert(attachment.id.value(), format!("{dir_name}/{file_name}"));
+        }
+    }
+
+    std::fs::write(
+        with_suffix(path, ".md"),
+        render_markdown(&doc, &local_files)?,
+    )?;
+
+    Ok(doc.attachments.len())
+}
+
+/// A file name for an attachment that no other attachment of the document uses
+///
+/// Attachments with the same name get their ID as a prefix.
+fn attachment_file_name(
+    name: &str,
+    id: impl std::fmt::Display,
+    used: &mut HashSet<String>,
+) -> String {
+    let mut file_name = sanitize_file_name(name);
+    if file_name.is_empty() {
+        file_name = id.to_string();
+    }
+    if !used.insert(file_name.to_lowercase()) {
+        file_name = format!("{id}_{file_name}");
+        used.insert(file_name.to_lowercase());
+    }
+    file_name
+}
+
+/// Markdown body with a front matter block identifying the source document
+///
+/// Images that embed one of the downloaded attachments point at the local
+/// file, so the exported Markdown renders offline.
+fn render_markdown(doc: &DocumentDetail, local_files: &HashMap<u32, String>) -> CliResult<String> {
+    let body = match doc.content() {
+        Ok(mut node) => {
+            localize_images(&mut node, local_files);
+            prosemirror::to_markdown(&node)
+        }
+        Err(_) => doc.plain.clone(),
+    };
+    Ok(format!(
+        "---\nid: {}\ntitle: {}\nupdated: {}\n---\n\n{}",
+        doc.id,
+        serde_json::to_string(&doc.title)?,
+        doc.updated.to_rfc3339(),
+        body
+    ))
+}
+
+fn localize_images(node: &mut Node, local_files: &HashMap<u32, String>) {
+    if let Node::Image { attrs } = node
+        && let Some(local) = attachment_id_in_src(&attrs.src).and_then(|id| local_files.get(&id))
+    {
+        attrs.src = local.clone();
+    }
+    for child in node.children_mut() {
+        localize_images(child, local_files);
+    }
+}
+
+/// The attachment ID of an image source such as
+/// `/api/v2/documents/<document>/attachments/<id>`
+fn attachment_id_in_src(src: &str) -> Option<u32> {
+    let (_, rest) = src.rsplit_once("/attachments/")?;
+    rest.split(['/', '?', '#']).next()?.parse().ok()
+}
+
+// `Path::with_extension` would cut titles such as "v1.2 notes" at the dot
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+/// Replace characters that are not allowed in file names on common platforms
+fn sanitize_file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect::<String>()
+        .trim()
+        .trim_matches('.')
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: &str, name: &str, children: Vec<DocumentTreeNode>) -> DocumentTreeNode {
+        DocumentTreeNode {
+            id: DocumentId::unsafe_new(id.to_string()),
+            name: name.to_string(),
+            emoji: None,
+            emoji_type: None,
+            children,
+        }
+    }
+
+    #[test]
+    fn test_plan_export() {
+        let nodes = vec![
+            node(
+                "0195faa11fcb7aaab4c4005a7ada4b6f",
+                "Guides",
+                vec![node("0195faa2", "Setup: macOS", vec![])],
+            ),
+            node("0195faa3ffffffff", "guides", vec![]),
+            node("0195faa4", "..", vec![]),
+        ];
+        let mut entries = Vec::new();
+        plan_export(&nodes, Path::new("out"), &mut entries);
+
+        let paths: Vec<PathBuf> = entries.iter().map(|(path, _)| path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                Path::new("out").join("Guides"),
+                Path::new("out").join("Guides").join("Setup_ macOS"),
+                Path::new("out").join("guides (0195faa3)"),
+                Path::new("out").join("0195faa4"),
+            ]
+        );
+        assert_eq!(entries[2].1.id.as_str(), "0195faa3ffffffff");
+    }
+
+    #[test]
+    fn test_sanitize_file_name() {
+        assert_eq!(sanitize_file_name("Q&A: a/b?"), "Q&A_ a_b_");
+        assert_eq!(sanitize_file_name(" line\nbreak "), "line_break");
+        assert_eq!(sanitize_file_name("...hidden."), "hidden");
+        assert_eq!(sanitize_file_name("設計 v1.2"), "設計 v1.2");
+    }
+
+    #[test]
+    fn test_attachment_file_name_deduplicates() {
+        let mut used = HashSet::new();
+        assert_eq!(attachment_file_name("image.png", 1, &mut used), "image.png");
+        assert_eq!(
+            attachment_file_name("Image.PNG", 2, &mut used),
+            "2_Image.PNG"
+        );
+        assert_eq!(attachment_file_name("?", 3, &mut used), "_");
+        assert_eq!(attachment_file_name("", 4, &mut used), "4");
+    }
+
+    #[test]
+    fn test_attachment_id_in_src() {
+        assert_eq!(
+            attachment_id_in_src("/api/v2/documents/0192/attachments/22"),
+            Some(22)
+        );
+        assert_eq!(
+            attachment_id_in_src(
+                "https://example.backlog.com/api/v2/documents/0192/attachments/22?apiKey=x"
+            ),
+            Some(22)
+        );
+        assert_eq!(
+            attachment_id_in_src("https://example.com/diagram.png"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_localize_images() {
+        let mut doc = prosemirror::from_markdown(
+            "![arch](/api/v2/documents/0192/attachments/22 \"Arch\")\n\n> ![other](/api/v2/documents/0192/attachments/23)\n\n![web](https://example.com/a.png)\n",
+        );
+        let local_files = HashMap::from([(22, "Design.attachments/arch diagram.png".to_string())]);
+        localize_images(&mut doc, &local_files);
+
+        assert_eq!(
+            prosemirror::to_markdown(&doc),
+            "![arch](<Design.attachments/arch diagram.png> \"Arch\")\n\n> ![other](/api/v2/documents/0192/attachments/23)\n\n![web](https://example.com/a.png)\n"
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use clap::Parser;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+pub struct FileArgs {
+    #[clap(subcommand)]
+    pub command: FileCommands,
+}
+
+#[derive(Parser)]
+pub enum FileCommands {
+    /// List shared files in a directory
+    Ls {
+        /// Project ID or key
+        project: String,
+        /// Remote directory (default: the root directory)
+        #[clap(default_value = "/")]
+        path: String,
+        /// List subdirectories recursively
+        #[clap(short = 'R', long)]
+        recursive: bool,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
+    },
+    /// Download a shared file by its path
+    Get {
+        /// Project ID or key
+        project: String,
+        /// Remote file path (e.g., "/docs/spec.pdf")
+        path: String,
+        /// Output file path (default: the file name in the current directory)
+        #[clap(short, long, value_name = "FILE_PATH")]
+        output: Option<PathBuf>,
+    },
+    /// Mirror a remote directory into a local directory
+    ///
+    /// Only new or changed files are downloaded. What was downloaded is
+    /// recorded in `.blg-sync.json` inside the local directory.
+    Sync {
+        /// Project ID or key
+        project: String,
+        /// Remote directory to mirror (e.g., "/design")
+        remote_dir: String,
+        /// Local directory to write to
+        local_dir: PathBuf,
+        /// Number of files downloaded at the same time (1-16)
+        #[clap(short = 'j', long, default_value = "4", value_parser = clap::value_parser!(u8).range(1..=16))]
+        concurrency: u8,
+        /// Delete local files whose remote file was removed
+        #[clap(long)]
+        prune: bool,
+        /// Show what would be downloaded or deleted without doing it
+        #[clap(long)]
+        dry_run: bool,
+    },
+}
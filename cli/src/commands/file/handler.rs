@@ -0,0 +1,35 @@
+use super::args::{FileArgs, FileCommands};
+use super::subcommands;
+use crate::commands::common::CliResult;
+use backlog_api_client::client::BacklogApiClient;
+
+pub async fn execute(client: &BacklogApiClient, args: FileArgs) -> CliResult<()> {
+    match args.command {
+        FileCommands::Ls {
+            project,
+            path,
+            recursive,
+            json,
+        } => subcommands::list::ls(client, &project, &path, recursive, json).await,
+        FileCommands::Get {
+            project,
+            path,
+            output,
+        } => subcommands::list::get(client, &project, &path, output).await,
+        FileCommands::Sync {
+            project,
+            remote_dir,
+            local_dir,
+            concurrency,
+            prune,
+            dry_run,
+        } => {
+            let options = subcommands::sync::SyncOptions {
+                concurrency: concurrency.into(),
+                prune,
+                dry_run,
+            };
+            subcommands::sync::sync(client, &project, &remote_dir, &local_dir, options).await
+        }
+    }
+}
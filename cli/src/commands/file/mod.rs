@@ -0,0 +1,6 @@
+mod args;
+mod handler;
+mod subcommands;
+
+pub use args::FileArgs;
+pub use handler::execute;
@@ -0,0 +1,99 @@
+//! Listing shared files and downloading a single file by path
+
+use super::remote::{full_path, list_dir, normalize_dir, walk};
+use crate::commands::common::{CliResult, download_progress, format_bytes};
+use anyhow::Context;
+use backlog_api_client::client::BacklogApiClient;
+use backlog_api_client::{FileContent, GetFileParams, ProjectIdOrKey};
+use std::path::PathBuf;
+
+/// List a directory, optionally recursively
+///
+/// Corresponds to `GET /api/v2/projects/:projectIdOrKey/files/metadata/:path`
+pub async fn ls(
+    client: &BacklogApiClient,
+    project: &str,
+    path: &str,
+    recursive: bool,
+    json: bool,
+) -> CliResult<()> {
+    let project_id_or_key = project
+        .parse::<ProjectIdOrKey>()
+        .with_context(|| format!("Failed to parse project '{project}'"))?;
+    let files = walk(client, &project_id_or_key, path, recursive).await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&files)?);
+        return Ok(());
+    }
+    if files.is_empty() {
+        println!("No shared files in {}", normalize_dir(path));
+        return Ok(());
+    }
+
+    for file in &files {
+        let size = match file.content {
+            FileContent::File { size } => format_bytes(size),
+            FileContent::Directory => "-".to_string(),
+        };
+        let updated = file.updated.unwrap_or(file.created);
+        // Recursive listings show full paths so entries stay unambiguous
+        let name = if recursive {
+            full_path(file)
+        } else {
+            match file.content {
+                FileContent::Directory => format!("{}/", file.name),
+                FileContent::File { .. } => file.name.clone(),
+            }
+        };
+        println!(
+            "{:>10}  {}  {}",
+            size,
+            updated.format("%Y-%m-%d %H:%M"),
+            name
+        );
+    }
+    Ok(())
+}
+
+/// Download a shared file by its path
+///
+/// Corresponds to `GET /api/v2/projects/:projectIdOrKey/files/:sharedFileId`
+pub async fn get(
+    client: &BacklogApiClient,
+    project: &str,
+    path: &str,
+    output: Option<PathBuf>,
+) -> CliResult<()> {
+    let project_id_or_key = project
+        .parse::<ProjectIdOrKey>()
+        .with_context(|| format!("Failed to parse project '{project}'"))?;
+
+    let trimmed = path.trim().trim_end_matches('/');
+    let (dir, name) = trimmed.rsplit_once('/').unwrap_or(("", trimmed));
+    if name.is_empty() {
+        anyhow::bail!("'{path}' is not a file path");
+    }
+
+    let file = list_dir(client, &project_id_or_key, dir)
+        .await?
+        .into_iter()
+        .find(|file| file.name == name)
+        .with_context(|| format!("Shared file '{path}' not found"))?;
+    if matches!(file.content, FileContent::Directory) {
+        anyhow::bail!("'{path}' is a directory; use `blg file sync` to download directories");
+    }
+
+    let output = output.unwrap_or_else(|| PathBuf::from(&file.name));
+    println!("Downloading {} to {}", full_path(&file), output.display());
+    let params = GetFileParams::new(project_id_or_key, file.id);
+    let download = client
+        .download_to_path(params, &output, download_progress())
+        .await?;
+    println!(
+        "✅ Downloaded {} ({})",
+        output.display(),
+        format_bytes(download.size)
+    );
+    Ok(())
+}
@@ -0,0 +1,9 @@
+//! Subcommand modules for shared file operations
+//!
+//! - `remote`: Walking the remote directory tree
+//! - `list`: Listing and downloading single files
+//! - `sync`: Mirroring a directory with a local manifest
+
+pub mod list;
+pub(crate) mod remote;
+pub mod sync;
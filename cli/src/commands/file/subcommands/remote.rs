@@ -0,0 +1,157 @@
+//! Walking the shared file tree of a project
+//!
+//! Shared file directories are addressed as `/dir/sub/` (the `dir` field of
+//! a `SharedFile`); the metadata endpoint takes the same path without the
+//! surrounding slashes.
+
+use crate::commands::common::CliResult;
+use backlog_api_client::client::BacklogApiClient;
+use backlog_api_client::{
+    FileContent, GetSharedFilesListParamsBuilder, ProjectIdOrKey, SharedFile,
+};
+
+/// Maximum page size of `GET /api/v2/projects/:projectIdOrKey/files/metadata/:path`
+const PAGE_SIZE: u32 = 100;
+
+/// Normalize a user-supplied directory to the `/dir/sub/` form
+pub(crate) fn normalize_dir(dir: &str) -> String {
+    let trimmed = dir.trim().trim_matches('/');
+    if trimmed.is_empty() {
+        "/".to_string()
+    } else {
+        format!("/{trimmed}/")
+    }
+}
+
+/// Full remote path of a file or directory, e.g. `/docs/spec.pdf`
+pub(crate) fn full_path(file: &SharedFile) -> String {
+    match file.content {
+        FileContent::Directory => format!("{}{}/", normalize_dir(&file.dir), file.name),
+        FileContent::File { .. } => format!("{}{}", normalize_dir(&file.dir), file.name),
+    }
+}
+
+/// List one directory, following pagination
+pub(crate) async fn list_dir(
+    client: &BacklogApiClient,
+    project: &ProjectIdOrKey,
+    dir: &str,
+) -> CliResult<Vec<SharedFile>> {
+    let path = normalize_dir(dir).trim_matches('/').to_string();
+    let mut files = Vec::new();
+    let mut offset = 0;
+    loop {
+        let params = GetSharedFilesListParamsBuilder::default()
+            .project_id_or_key(project.clone())
+            .path(path.clone())
+            .count(PAGE_SIZE)
+            .offset(offset)
+            .build()?;
+        let page = client.file().get_shared_files_list(params).await?;
+        let page_len = page.len() as u32;
+        files.extend(page);
+        if page_len < PAGE_SIZE {
+            return Ok(files);
+        }
+        offset += page_len;
+    }
+}
+
+/// List a directory and, when `recursive`, every directory below it
+///
+/// Entries are returned in the order they are found: each directory's
+/// entries, then those of its subdirectories.
+pub(crate) async fn walk(
+    client: &BacklogApiClient,
+    project: &ProjectIdOrKey,
+    root: &str,
+    recursive: bool,
+) -> CliResult<Vec<SharedFile>> {
+    let mut entries = Vec::new();
+    let mut pending = vec![normalize_dir(root)];
+    while let Some(dir) = pending.pop() {
+        let files = list_dir(client, project, &dir).await?;
+        if recursive {
+            // Reverse so subdirectories are visited in listing order
+            for file in files.iter().rev() {
+                if matches!(file.content, FileContent::Directory) {
+                    pending.push(full_path(file));
+                }
+            }
+        }
+        entries.extend(files);
+    }
+    Ok(entries)
+}
+
+/// Path of a remote file relative to `root`, as `/`-separated components
+///
+/// Returns `None` for files outside `root` or with names that are unsafe to
+/// use as local paths.
+pub(crate) fn relative_path(root: &str, file: &SharedFile) -> Option<String> {
+    let root = normalize_dir(root);
+    let dir = normalize_dir(&file.dir);
+    let sub_dir = dir.strip_prefix(&root)?;
+    let relative = format!("{sub_dir}{}", file.name);
+    let safe = relative
+        .split('/')
+        .all(|part| !part.is_empty() && part != "." && part != ".." && !part.contains('\\'));
+    safe.then_some(relative)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use backlog_core::User;
+    use backlog_core::identifier::{ProjectId, SharedFileId};
+    use chrono::{TimeZone, Utc};
+
+    pub(crate) fn shared_file(id: u32, dir: &str, name: &str, size: Option<u64>) -> SharedFile {
+        let user: User = serde_json::from_value(serde_json::json!({
+            "id": 1, "userId": "admin", "name": "admin", "roleType": 1,
+            "lang": null, "mailAddress": "admin@example.com", "lastLoginTime": null
+        }))
+        .unwrap();
+        SharedFile {
+            id: SharedFileId::new(id),
+            project_id: ProjectId::new(1),
+            dir: dir.to_string(),
+            name: name.to_string(),
+            created_user: user,
+            created: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+            updated_user: None,
+            updated: None,
+            content: match size {
+                Some(size) => FileContent::File { size },
+                None => FileContent::Directory,
+            },
+        }
+    }
+
+    #[test]
+    fn test_normalize_dir() {
+        assert_eq!(normalize_dir(""), "/");
+        assert_eq!(normalize_dir("/"), "/");
+        assert_eq!(normalize_dir("design"), "/design/");
+        assert_eq!(normalize_dir("/design/icons/"), "/design/icons/");
+    }
+
+    #[test]
+    fn test_relative_path() {
+        let file = shared_file(1, "/design/icons/", "logo.svg", Some(10));
+        assert_eq!(
+            relative_path("/design", &file).as_deref(),
+            Some("icons/logo.svg")
+        );
+        assert_eq!(
+            relative_path("/", &file).as_deref(),
+            Some("design/icons/logo.svg")
+        );
+        assert_eq!(relative_path("/docs", &file), None);
+        // Directory names sharing a prefix are not nested
+        assert_eq!(relative_path("/des", &file), None);
+
+        let unsafe_file = shared_file(2, "/design/", "..", Some(10));
+        assert_eq!(relative_path("/design", &unsafe_file), None);
+    }
+}
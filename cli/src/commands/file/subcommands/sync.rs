@@ -0,0 +1,462 @@
+//! Mirroring a shared file directory into a local directory
+//!
+//! Every downloaded file is recorded in `.blg-sync.json` in the local
+//! directory with its shared file ID, size and update time. A later sync
+//! downloads a file again only when one of those changed or the local copy is
+//! missing, and `--prune` deletes only files the manifest knows about. The
+//! manifest is saved after every downloaded file, so an interrupted sync
+//! keeps what it finished.
+
+use super::remote::{relative_path, walk};
+use crate::commands::common::{CliResult, format_bytes};
+use anyhow::Context;
+use backlog_api_client::client::BacklogApiClient;
+use backlog_api_client::{
+    FileContent, GetFileParams, ProjectIdOrKey, SharedFile, discard_partial_download,
+};
+use backlog_core::identifier::Identifier;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+const MANIFEST_FILE: &str = ".blg-sync.json";
+
+pub struct SyncOptions {
+    pub concurrency: usize,
+    pub prune: bool,
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct Manifest {
+    project: String,
+    remote_dir: String,
+    files: BTreeMap<String, ManifestEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ManifestEntry {
+    id: u32,
+    size: u64,
+    updated: DateTime<Utc>,
+}
+
+impl ManifestEntry {
+    fn from_file(file: &SharedFile, size: u64) -> Self {
+        Self {
+            id: file.id.value(),
+            size,
+            updated: file.updated.unwrap_or(file.created),
+        }
+    }
+}
+
+impl Manifest {
+    fn load(local_dir: &Path) -> CliResult<Option<Self>> {
+        let path = local_dir.join(MANIFEST_FILE);
+        match std::fs::read(&path) {
+            Ok(bytes) => {
+                Ok(Some(serde_json::from_slice(&bytes).with_context(|| {
+                    format!("Failed to parse {}", path.display())
+                })?))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    fn save(&self, local_dir: &Path) -> CliResult<()> {
+        // Write to a temporary file first so an interrupted sync never leaves
+        // a truncated manifest
+        let path = local_dir.join(MANIFEST_FILE);
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec_pretty(self)?)?;
+        std::fs::rename(tmp, path)?;
+        Ok(())
+    }
+}
+
+/// What a sync has to do
+#[derive(Debug, Default)]
+pub(crate) struct SyncPlan {
+    /// Files to download, keyed by their relative path
+    downloads: Vec<(String, SharedFile)>,
+    /// Downloads whose file changed since it was last synced, so partial
+    /// data left by an interrupted download must not be resumed
+    changed: HashSet<String>,
+    /// Number of files that are up to date
+    unchanged: usize,
+    /// Manifest entries whose remote file no longer exists
+    removed: Vec<String>,
+}
+
+/// Compare the remote files with the manifest
+///
+/// `exists` tells whether the local copy of a relative path is present.
+pub(crate) fn plan(
+    remote: Vec<(String, SharedFile)>,
+    manifest: &Manifest,
+    exists: impl Fn(&str) -> bool,
+) -> SyncPlan {
+    let mut plan = SyncPlan::default();
+    let mut seen = HashSet::new();
+    for (relative, file) in remote {
+        let FileContent::File { size } = file.content else {
+            continue;
+        };
+        seen.insert(relative.clone());
+        let current = ManifestEntry::from_file(&file, size);
+        match manifest.files.get(&relative) {
+            Some(synced) if *synced == current && exists(&relative) => plan.unchanged += 1,
+            Some(synced) if *synced != current => {
+                plan.changed.insert(relative.clone());
+                plan.downloads.push((relative, file));
+            }
+            _ => plan.downloads.push((relative, file)),
+        }
+    }
+    plan.removed = manifest
+        .files
+        .keys()
+        .filter(|relative| !seen.contains(*relative))
+        .cloned()
+        .collect();
+    plan
+}
+
+fn local_path(local_dir: &Path, relative: &str) -> PathBuf {
+    relative
+        .split('/')
+        .fold(local_dir.to_path_buf(), |path, part| path.join(part))
+}
+
+/// Mirror `remote_dir` of a project into `local_dir`
+///
+/// Corresponds to `GET /api/v2/projects/:projectIdOrKey/files/metadata/:path`
+/// and `GET /api/v2/projects/:projectIdOrKey/files/:sharedFileId`
+pub async fn sync(
+    client: &BacklogApiClient,
+    project: &str,
+    remote_dir: &str,
+    local_dir: &Path,
+    options: SyncOptions,
+) -> CliResult<()> {
+    let project_id_or_key = project
+        .parse::<ProjectIdOrKey>()
+        .with_context(|| format!("Failed to parse project '{project}'"))?;
+    let remote_dir = super::remote::normalize_dir(remote_dir);
+
+    let mut manifest = match Manifest::load(local_dir)? {
+        Some(manifest) if manifest.project != project || manifest.remote_dir != remote_dir => {
+            anyhow::bail!(
+                "{} mirrors {}:{}, not {project}:{remote_dir}. Use another local directory.",
+                local_dir.display(),
+                manifest.project,
+                manifest.remote_dir
+            );
+        }
+        Some(manifest) => manifest,
+        None => Manifest {
+            project: project.to_string(),
+            remote_dir: remote_dir.clone(),
+            files: BTreeMap::new(),
+        },
+    };
+
+    println!("Listing {project}:{remote_dir}...");
+    let mut remote = Vec::new();
+    for file in walk(client, &project_id_or_key, &remote_dir, true).await? {
+        match relative_path(&remote_dir, &file) {
+            Some(relative) => remote.push((relative, file)),
+            None => println!("⚠️  Skipping '{}{}': unsafe file name", file.dir, file.name),
+        }
+    }
+
+    let plan = plan(remote, &manifest, |relative| {
+        local_path(local_dir, relative).is_file()
+    });
+    println!(
+        "{} to download, {} up to date, {} removed remotely",
+        plan.downloads.len(),
+        plan.unchanged,
+        plan.removed.len()
+    );
+
+    if options.dry_run {
+        for (relative, file) in &plan.downloads {
+            if let FileContent::File { size } = file.content {
+                println!("⬇️  {relative} ({})", format_bytes(size));
+            }
+        }
+        if options.prune {
+            for relative in &plan.removed {
+                println!("🗑️  {relative}");
+            }
+        }
+        println!("Dry run: nothing was changed");
+        return Ok(());
+    }
+
+    std::fs::create_dir_all(local_dir)
+        .with_context(|| format!("Failed to create {}", local_dir.display()))?;
+
+    let semaphore = Arc::new(Semaphore::new(options.concurrency));
+    let mut tasks = JoinSet::new();
+    for (relative, file) in plan.downloads {
+        let client = client.clone();
+        let semaphore = Arc::clone(&semaphore);
+        let project_id_or_key = project_id_or_key.clone();
+        let target = local_path(local_dir, &relative);
+        let changed = plan.changed.contains(&relative);
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let result = async {
+                if let Some(parent) = target.parent() {
+                    tokio::fs::create_dir_all(parent).await?;
+                }
+                if changed {
+                    discard_partial_download(&target).await?;
+                }
+                let params = GetFileParams::new(project_id_or_key, file.id);
+                let download = client.download_to_path(params, &target, |_| {}).await?;
+                CliResult::Ok(download.size)
+            }
+            .await;
+            (relative, file, result)
+        });
+    }
+
+    let mut failures = 0;
+    while let Some(joined) = tasks.join_next().await {
+        let (relative, file, result) = joined?;
+        match result {
+            Ok(size) => {
+                println!("⬇️  {relative} ({})", format_bytes(size));
+                manifest
+                    .files
+                    .insert(relative, ManifestEntry::from_file(&file, size));
+                manifest.save(local_dir)?;
+            }
+            Err(e) => {
+                failures += 1;
+                eprintln!("❌ {relative}: {e}");
+            }
+        }
+    }
+
+    if options.prune {
+        for relative in plan.removed {
+            let path = local_path(local_dir, &relative);
+            match std::fs::remove_file(&path) {
+                Ok(()) => println!("🗑️  {relative}"),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    failures += 1;
+                    eprintln!("❌ Failed to delete {}: {e}", path.display());
+                    continue;
+                }
+            }
+            manifest.files.remove(&relative);
+        }
+    }
+
+    manifest.save(local_dir)?;
+    if failures > 0 {
+        anyhow::bail!("{failures} file(s) failed; run the sync again to retry them");
+    }
+    println!("✅ {} is in sync", local_dir.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::remote::tests::shared_file;
+    use super::*;
+
+    fn manifest_with(entries: &[(&str, &SharedFile)]) -> Manifest {
+        Manifest {
+            project: "PROJ".to_string(),
+            remote_dir: "/".to_string(),
+            files: entries
+                .iter()
+                .map(|(relative, file)| {
+                    let FileContent::File { size } = file.content else {
+                        unreachable!()
+                    };
+                    (relative.to_string(), ManifestEntry::from_file(file, size))
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_plan_downloads_new_and_changed_files() {
+        let unchanged = shared_file(1, "/", "a.txt", Some(10));
+        let changed_before = shared_file(2, "/", "b.txt", Some(10));
+        let changed_after = shared_file(2, "/", "b.txt", Some(20));
+        let new = shared_file(3, "/", "c.txt", Some(5));
+        let gone = shared_file(4, "/", "d.txt", Some(5));
+        let manifest = manifest_with(&[
+            ("a.txt", &unchanged),
+            ("b.txt", &changed_before),
+            ("d.txt", &gone),
+        ]);
+
+        let remote = vec![
+            ("a.txt".to_string(), unchanged),
+            ("b.txt".to_string(), changed_after),
+            ("c.txt".to_string(), new),
+            ("sub".to_string(), shared_file(5, "/", "sub", None)),
+        ];
+        let plan = plan(remote, &manifest, |_| true);
+
+        let downloads: Vec<&str> = plan.downloads.iter().map(|(r, _)| r.as_str()).collect();
+        assert_eq!(downloads, vec!["b.txt", "c.txt"]);
+        assert_eq!(plan.changed, HashSet::from(["b.txt".to_string()]));
+        assert_eq!(plan.unchanged, 1);
+        assert_eq!(plan.removed, vec!["d.txt".to_string()]);
+    }
+
+    #[test]
+    fn test_plan_downloads_missing_local_copy() {
+        let file = shared_file(1, "/", "a.txt", Some(10));
+        let manifest = manifest_with(&[("a.txt", &file)]);
+        let plan = plan(vec![("a.txt".to_string(), file)], &manifest, |_| false);
+        assert_eq!(plan.downloads.len(), 1);
+        assert!(plan.changed.is_empty());
+        assert_eq!(plan.unchanged, 0);
+    }
+
+    #[tokio::test]
+    async fn test_sync_downloads_once_and_prunes() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let client = BacklogApiClient::new(&server.uri()).unwrap();
+        let dir = tempfile::TempDir::new().unwrap();
+        let options = || SyncOptions {
+            concurrency: 2,
+            prune: true,
+            dry_run: false,
+        };
+
+        let listing = |files: &[&SharedFile]| {
+            ResponseTemplate::new(200).set_body_json(serde_json::to_value(files).unwrap())
+        };
+        let spec = shared_file(1, "/design/", "spec.txt", Some(4));
+        let icons = shared_file(2, "/design/", "icons", None);
+        let logo = shared_file(3, "/design/icons/", "logo.svg", Some(5));
+
+        let root_mock = Mock::given(method("GET"))
+            .and(path("/api/v2/projects/PROJ/files/metadata/design"))
+            .respond_with(listing(&[&spec, &icons]))
+            .mount_as_scoped(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/projects/PROJ/files/metadata/design/icons"))
+            .respond_with(listing(&[&logo]))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/projects/PROJ/files/1"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"spec".to_vec()))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/projects/PROJ/files/3"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"<svg>".to_vec()))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        sync(&client, "PROJ", "/design", dir.path(), options())
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read(dir.path().join("spec.txt")).unwrap(), b"spec");
+        assert_eq!(
+            std::fs::read(dir.path().join("icons").join("logo.svg")).unwrap(),
+            b"<svg>"
+        );
+
+        // Unchanged files are not downloaded again; removed ones are pruned
+        drop(root_mock);
+        Mock::given(method("GET"))
+            .and(path("/api/v2/projects/PROJ/files/metadata/design"))
+            .respond_with(listing(&[&icons]))
+            .mount(&server)
+            .await;
+        sync(&client, "PROJ", "design/", dir.path(), options())
+            .await
+            .unwrap();
+        assert!(!dir.path().join("spec.txt").exists());
+        assert!(dir.path().join("icons").join("logo.svg").exists());
+
+        let manifest = Manifest::load(dir.path()).unwrap().unwrap();
+        assert_eq!(
+            manifest.files.keys().collect::<Vec<_>>(),
+            vec!["icons/logo.svg"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sync_does_not_resume_partial_data_of_changed_file() {
+        use wiremock::matchers::{header_exists, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let client = BacklogApiClient::new(&server.uri()).unwrap();
+        let dir = tempfile::TempDir::new().unwrap();
+        let before = shared_file(1, "/", "spec.txt", Some(4));
+        let after = shared_file(1, "/", "spec.txt", Some(6));
+        manifest_with(&[("spec.txt", &before)])
+            .save(dir.path())
+            .unwrap();
+        std::fs::write(dir.path().join("spec.txt.part"), b"spe").unwrap();
+        std::fs::write(dir.path().join("spec.txt.part.validator"), "\"v1\"").unwrap();
+
+        Mock::given(method("GET"))
+            .and(path("/api/v2/projects/PROJ/files/metadata/"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::to_value([&after]).unwrap()),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/projects/PROJ/files/1"))
+            .and(header_exists("Range"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v2/projects/PROJ/files/1"))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(b"spec v2".to_vec()))
+            .mount(&server)
+            .await;
+
+        let options = SyncOptions {
+            concurrency: 1,
+            prune: false,
+            dry_run: false,
+        };
+        sync(&client, "PROJ", "/", dir.path(), options)
+            .await
+            .unwrap();
+        assert_eq!(
+            std::fs::read(dir.path().join("spec.txt")).unwrap(),
+            b"spec v2"
+        );
+    }
+
+    #[test]
+    fn test_local_path() {
+        let path = local_path(Path::new("mirror"), "icons/logo.svg");
+        assert_eq!(path, Path::new("mirror").join("icons").join("logo.svg"));
+    }
+}
@@ -13,18 +13,18 @@ pub struct RepoArgs {
 pub enum RepoCommands {
     /// List repositories in a project
     List {
-        /// Project ID or Key
+        /// Project ID or Key (default: from the git checkout)
         #[clap(short, long)]
-        project_id: String,
+        project_id: Option<String>,
     },
     /// Show details of a specific repository
     Show {
-        /// Project ID or Key
+        /// Project ID or Key (default: from the git checkout)
         #[clap(short, long)]
-        project_id: String,
-        /// Repository ID or Name
+        project_id: Option<String>,
+        /// Repository ID or Name (default: from the git checkout)
         #[clap(short, long)]
-        repo_id: String,
+        repo_id: Option<String>,
     },
 }
 
@@ -40,25 +40,28 @@ pub struct PrArgs {
 pub enum PrCommands {
     /// List pull requests in a repository
     List {
-        /// Project ID or Key
+        /// Project ID or Key (default: from the git checkout)
         #[clap(short, long)]
-        project_id: String,
-        /// Repository ID or Name
+        project_id: Option<String>,
+        /// Repository ID or Name (default: from the git checkout)
         #[clap(short, long)]
-        repo_id: String,
+        repo_id: Option<String>,
     },
     /// Show details of a specific pull request
     Show {
-        /// Project ID or Key
+        /// Project ID or Key (default: from the git checkout)
         #[clap(short, long)]
-        project_id: String,
-        /// Repository ID or Name
+        project_id: Option<String>,
+        /// Repository ID or Name (default: from the git checkout)
         #[clap(short, long)]
-        repo_id: String,
+        repo_id: Option<String>,
         /// Pull Request number
         #[clap(short = 'n', long)]
         pr_number: u64,
     },
+    /// Pull requests assigned to you, created by you or recently updated, across repositories
+    #[cfg(feature = "pr-inbox")]
+    Inbox(PrInboxArgs),
     /// Download a pull request attachment
     #[command(about = "Download a pull request attachment")]
     DownloadAttachment(DownloadPrAttachmentArgs),
@@ -69,12 +72,12 @@ pub enum PrCommands {
     /// Update a pull request
     #[cfg(feature = "git_writable")]
     Update {
-        /// Project ID or Key
+        /// Project ID or Key (default: from the git checkout)
         #[clap(short, long)]
-        project_id: String,
-        /// Repository ID or Name
+        project_id: Option<String>,
+        /// Repository ID or Name (default: from the git checkout)
         #[clap(short, long)]
-        repo_id: String,
+        repo_id: Option<String>,
         /// Pull Request number
         #[clap(long)]
         pr_number: u64,
@@ -100,12 +103,12 @@ pub enum PrCommands {
     /// Update a pull request comment
     #[cfg(feature = "git_writable")]
     CommentUpdate {
-        /// Project ID or Key
+        /// Project ID or Key (default: from the git checkout)
         #[clap(short, long)]
-        project_id: String,
-        /// Repository ID or Name
+        project_id: Option<String>,
+        /// Repository ID or Name (default: from the git checkout)
         #[clap(short, long)]
-        repo_id: String,
+        repo_id: Option<String>,
         /// Pull Request number
         #[clap(long)]
         pr_number: u64,
@@ -116,26 +119,41 @@ pub enum PrCommands {
         #[clap(short, long)]
         content: String,
     },
+    /// Show the comments of a pull request as threads, with state changes, stars and code context
+    Comments {
+        /// Project ID or Key (default: from the git checkout)
+        #[clap(short, long)]
+        project_id: Option<String>,
+        /// Repository ID or Name (default: from the git checkout)
+        #[clap(short, long)]
+        repo_id: Option<String>,
+        /// Pull Request number
+        #[clap(short = 'n', long)]
+        pr_number: u64,
+        /// Output the threads as JSON
+        #[clap(long)]
+        json: bool,
+    },
     /// Get the number of comments on a pull request
     CommentCount {
-        /// Project ID or Key
+        /// Project ID or Key (default: from the git checkout)
         #[clap(short, long)]
-        project_id: String,
-        /// Repository ID or Name
+        project_id: Option<String>,
+        /// Repository ID or Name (default: from the git checkout)
         #[clap(short, long)]
-        repo_id: String,
+        repo_id: Option<String>,
         /// Pull Request number
         #[clap(long)]
         pr_number: u64,
     },
     /// Get the number of pull requests in a repository
     Count {
-        /// Project ID or Key
+        /// Project ID or Key (default: from the git checkout)
         #[clap(short, long)]
-        project_id: String,
-        /// Repository ID or Name
+        project_id: Option<String>,
+        /// Repository ID or Name (default: from the git checkout)
         #[clap(short, long)]
-        repo_id: String,
+        repo_id: Option<String>,
         /// Filter by status IDs (comma-separated, e.g., "1,2,3")
         #[clap(long)]
         status_ids: Option<String>,
@@ -158,27 +176,30 @@ pub enum PrCommands {
     /// Create a new pull request
     #[cfg(feature = "git_writable")]
     Create {
-        /// Project ID or Key
-        #[clap(short, long)]
-        project_id: String,
-        /// Repository ID or Name
-        #[clap(short, long)]
-        repo_id: String,
-        /// Pull request title
+        /// Project ID or Key (default: from the git checkout)
         #[clap(short, long)]
-        summary: String,
-        /// Pull request description
+        project_id: Option<String>,
+        /// Repository ID or Name (default: from the git checkout)
         #[clap(short, long)]
-        description: String,
-        /// Target merge branch
+        repo_id: Option<String>,
+        /// Pull request title (default with --from-branch: the issue summary)
+        #[clap(short, long, required_unless_present = "from_branch")]
+        summary: Option<String>,
+        /// Pull request description (default with --from-branch: the issue key)
+        #[clap(short, long, required_unless_present = "from_branch")]
+        description: Option<String>,
+        /// Target merge branch (default: the remote's default branch)
         #[clap(short, long)]
-        base: String,
-        /// Source branch to be merged
+        base: Option<String>,
+        /// Source branch to be merged (default: the current branch)
         #[clap(short = 'B', long)]
-        branch: String,
+        branch: Option<String>,
         /// Related issue ID
-        #[clap(long)]
+        #[clap(long, conflicts_with = "from_branch")]
         issue_id: Option<u32>,
+        /// Relate the issue whose key appears in the source branch name (e.g. feature/PROJ-12-login)
+        #[clap(long)]
+        from_branch: bool,
         /// Assignee user ID
         #[clap(long)]
         assignee_id: Option<u32>,
@@ -188,18 +209,49 @@ pub enum PrCommands {
         /// Attachment IDs (comma-separated, e.g., "789,101112")
         #[clap(long)]
         attachment_ids: Option<String>,
+        /// Local file to upload and attach (can be specified multiple times)
+        #[clap(long = "attach", value_name = "PATH")]
+        attach: Vec<PathBuf>,
     },
 }
 
+#[cfg(feature = "pr-inbox")]
+#[derive(Args, Debug)]
+pub struct PrInboxArgs {
+    /// Only these projects (repeatable; default: all your active projects)
+    #[clap(short, long = "project")]
+    pub projects: Vec<String>,
+    /// Only these repositories, by name (repeatable)
+    #[clap(short, long = "repo")]
+    pub repos: Vec<String>,
+    /// Only pull requests with these statuses (repeatable)
+    #[clap(short, long = "status", value_enum, default_values_t = [PrStatusArg::Open])]
+    pub statuses: Vec<PrStatusArg>,
+    /// Days within which other pull requests count as recently updated
+    #[clap(long, default_value_t = 7)]
+    pub recent_days: u32,
+    /// Output as JSON
+    #[clap(long)]
+    pub json: bool,
+}
+
+#[cfg(feature = "pr-inbox")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum PrStatusArg {
+    Open,
+    Closed,
+    Merged,
+}
+
 #[cfg(feature = "git")]
 #[derive(Args, Debug)]
 pub struct DownloadPrAttachmentArgs {
-    /// Project ID or Key
+    /// Project ID or Key (default: from the git checkout)
     #[clap(short = 'p', long)]
-    pub project_id: String,
-    /// Repository ID or Name
+    pub project_id: Option<String>,
+    /// Repository ID or Name (default: from the git checkout)
     #[clap(short = 'r', long)]
-    pub repo_id: String,
+    pub repo_id: Option<String>,
     /// Pull Request number
     #[clap(short = 'n', long)]
     pub pr_number: u64,
@@ -214,12 +266,12 @@ pub struct DownloadPrAttachmentArgs {
 #[cfg(feature = "git_writable")]
 #[derive(Args, Debug)]
 pub struct DeletePrAttachmentArgs {
-    /// Project ID or Key
+    /// Project ID or Key (default: from the git checkout)
     #[clap(short = 'p', long)]
-    pub project_id: String,
-    /// Repository ID or Name
+    pub project_id: Option<String>,
+    /// Repository ID or Name (default: from the git checkout)
     #[clap(short = 'r', long)]
-    pub repo_id: String,
+    pub repo_id: Option<String>,
     /// Pull Request number
     #[clap(short = 'n', long)]
     pub pr_number: u64,
@@ -227,3 +279,31 @@ pub struct DeletePrAttachmentArgs {
     #[clap(short = 'a', long)]
     pub attachment_id: u32,
 }
+
+#[cfg(feature = "git")]
+#[derive(Parser)]
+pub struct GitArgs {
+    #[clap(subcommand)]
+    pub command: GitCommands,
+}
+
+#[cfg(feature = "git")]
+#[derive(Parser)]
+pub enum GitCommands {
+    /// Manage git hooks of the current checkout
+    Hook {
+        #[clap(subcommand)]
+        command: HookCommands,
+    },
+}
+
+#[cfg(feature = "git")]
+#[derive(Parser)]
+pub enum HookCommands {
+    /// Install a prepare-commit-msg hook prefixing commit messages with the branch's issue key
+    Install {
+        /// Replace an existing hook that was not installed by blg
+        #[clap(long)]
+        force: bool,
+    },
+}
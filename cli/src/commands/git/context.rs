@@ -0,0 +1,436 @@
+//! Project, repository and branches of the local git checkout
+//!
+//! Lets `blg pr` and `blg repo` run inside a clone of a Backlog repository
+//! without `--project-id` and `--repo-id`. Only files under `.git` are read;
+//! the `git` executable is not required.
+
+use crate::commands::common::CliResult;
+use backlog_api_client::client::BacklogApiClient;
+use backlog_api_client::{GetRepositoryListParams, ProjectIdOrKey, Repository};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Domains of Backlog spaces
+const BACKLOG_DOMAINS: [&str; 3] = [".backlog.com", ".backlog.jp", ".backlogtool.com"];
+
+/// A Backlog repository named by a git remote URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct BacklogRemote {
+    /// Web host of the space, e.g. `example.backlog.com`
+    pub host: String,
+    pub project_key: String,
+    pub repo_name: String,
+}
+
+impl BacklogRemote {
+    /// Parse HTTPS (`https://example.backlog.com/git/PROJ/repo.git`) and SSH
+    /// (`example@example.git.backlog.jp:/PROJ/repo.git`) remote URLs
+    pub(crate) fn parse(url: &str) -> Option<Self> {
+        let url = url.trim();
+        let (host, path) = if let Some(rest) = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+        {
+            let (authority, path) = rest.split_once('/')?;
+            (host_of(authority), path.strip_prefix("git/")?)
+        } else if let Some(rest) = url.strip_prefix("ssh://") {
+            let (authority, path) = rest.split_once('/')?;
+            (host_of(authority).replacen(".git.", ".", 1), path)
+        } else {
+            let (authority, path) = url.split_once(':')?;
+            (host_of(authority).replacen(".git.", ".", 1), path)
+        };
+
+        let host = host.to_ascii_lowercase();
+        if !BACKLOG_DOMAINS.iter().any(|domain| host.ends_with(domain)) {
+            return None;
+        }
+        let path = path.trim_matches('/');
+        let path = path.strip_suffix(".git").unwrap_or(path);
+        let (project_key, repo_name) = path.split_once('/')?;
+        if project_key.is_empty() || repo_name.is_empty() || repo_name.contains('/') {
+            return None;
+        }
+        Some(Self {
+            host,
+            project_key: project_key.to_string(),
+            repo_name: repo_name.to_string(),
+        })
+    }
+
+    /// Whether the remote belongs to the space with the web host `host`
+    pub(crate) fn is_in_space(&self, host: &str) -> bool {
+        host.eq_ignore_ascii_case(&self.host)
+    }
+
+    /// Whether one of the repository's clone URLs points to this remote
+    pub(crate) fn matches(&self, repository: &Repository) -> bool {
+        [&repository.http_url, &repository.ssh_url]
+            .into_iter()
+            .flatten()
+            .filter_map(|url| Self::parse(url))
+            .any(|other| {
+                other.host == self.host
+                    && other.project_key.eq_ignore_ascii_case(&self.project_key)
+                    && other.repo_name == self.repo_name
+            })
+    }
+}
+
+/// Host of a URL authority, without user info and port
+fn host_of(authority: &str) -> String {
+    let host = authority.rsplit('@').next().unwrap_or(authority);
+    host.split(':').next().unwrap_or(host).to_string()
+}
+
+/// The `.git` directory of a working tree
+#[derive(Debug, Clone)]
+pub(crate) struct Checkout {
+    /// Holds `HEAD`; differs from `common_dir` in linked worktrees
+    git_dir: PathBuf,
+    /// Holds `config` and `refs`
+    common_dir: PathBuf,
+}
+
+impl Checkout {
+    /// Find the checkout containing `start`, looking in parent directories
+    pub(crate) fn discover(start: &Path) -> Option<Self> {
+        start.ancestors().find_map(|dir| {
+            let dot_git = dir.join(".git");
+            let git_dir = if dot_git.is_dir() {
+                dot_git
+            } else {
+                // Worktrees and submodules have a `.git` file pointing elsewhere
+                let content = fs::read_to_string(&dot_git).ok()?;
+                dir.join(content.trim().strip_prefix("gitdir:")?.trim())
+            };
+            let common_dir = fs::read_to_string(git_dir.join("commondir"))
+                .map(|common| git_dir.join(common.trim()))
+                .unwrap_or_else(|_| git_dir.clone());
+            Some(Self {
+                git_dir,
+                common_dir,
+            })
+        })
+    }
+
+    /// The checkout containing the current directory
+    pub(crate) fn current() -> Option<Self> {
+        Self::discover(&std::env::current_dir().ok()?)
+    }
+
+    /// Remote names and URLs from `.git/config`, in file order
+    pub(crate) fn remotes(&self) -> Vec<(String, String)> {
+        let Ok(config) = fs::read_to_string(self.common_dir.join("config")) else {
+            return Vec::new();
+        };
+        let mut remotes = Vec::new();
+        let mut section: Option<String> = None;
+        for line in config.lines().map(str::trim) {
+            if line.starts_with('[') {
+                section = line
+                    .strip_prefix("[remote \"")
+                    .and_then(|rest| rest.strip_suffix("\"]"))
+                    .map(str::to_string);
+            } else if let Some(name) = &section
+                && let Some((key, value)) = line.split_once('=')
+                && key.trim() == "url"
+            {
+                remotes.push((name.clone(), value.trim().to_string()));
+            }
+        }
+        remotes
+    }
+
+    /// The remote pointing to Backlog, preferring `origin`
+    pub(crate) fn backlog_remote(&self) -> Option<(String, BacklogRemote)> {
+        let mut remotes: Vec<(String, BacklogRemote)> = self
+            .remotes()
+            .into_iter()
+            .filter_map(|(name, url)| BacklogRemote::parse(&url).map(|remote| (name, remote)))
+            .collect();
+        let origin = remotes.iter().position(|(name, _)| name == "origin");
+        match origin {
+            Some(index) => Some(remotes.swap_remove(index)),
+            None => remotes.into_iter().next(),
+        }
+    }
+
+    /// Directory holding the hooks shared by all worktrees
+    pub(crate) fn hooks_dir(&self) -> PathBuf {
+        self.common_dir.join("hooks")
+    }
+
+    /// The checked out branch, or `None` for a detached HEAD
+    pub(crate) fn current_branch(&self) -> Option<String> {
+        let head = fs::read_to_string(self.git_dir.join("HEAD")).ok()?;
+        head.trim()
+            .strip_prefix("ref: refs/heads/")
+            .map(str::to_string)
+    }
+
+    /// The default branch of a remote as last fetched
+    ///
+    /// Uses `refs/remotes/<remote>/HEAD`, falling back to `main` or `master`
+    /// when the remote has one of them.
+    pub(crate) fn default_branch(&self, remote: &str) -> Option<String> {
+        let prefix = format!("refs/remotes/{remote}/");
+        if let Ok(head) = fs::read_to_string(self.common_dir.join(&prefix).join("HEAD"))
+            && let Some(branch) = head
+                .trim()
+                .strip_prefix("ref: ")
+                .and_then(|target| target.strip_prefix(&prefix))
+        {
+            return Some(branch.to_string());
+        }
+
+        let packed = fs::read_to_string(self.common_dir.join("packed-refs")).unwrap_or_default();
+        ["main", "master"]
+            .into_iter()
+            .find(|branch| {
+                let name = format!("{prefix}{branch}");
+                self.common_dir.join(&name).is_file()
+                    || packed
+                        .lines()
+                        .any(|line| line.split_whitespace().nth(1) == Some(name.as_str()))
+            })
+            .map(str::to_string)
+    }
+}
+
+/// The Backlog remote of the current checkout
+///
+/// Fails when the remote belongs to another space than `BACKLOG_BASE_URL`,
+/// as its project and repository would be looked up in the wrong space.
+fn checkout_remote(client: &BacklogApiClient) -> CliResult<Option<BacklogRemote>> {
+    let Some((name, remote)) = Checkout::current().and_then(|checkout| checkout.backlog_remote())
+    else {
+        return Ok(None);
+    };
+    let base_url = client.base_url();
+    if !base_url
+        .host_str()
+        .is_some_and(|host| remote.is_in_space(host))
+    {
+        anyhow::bail!(
+            "The remote '{name}' of this checkout belongs to {}, but BACKLOG_BASE_URL is {}; pass --project-id and --repo-id explicitly",
+            remote.host,
+            base_url
+        );
+    }
+    Ok(Some(remote))
+}
+
+/// The project, defaulting to the one of the current checkout
+pub(crate) fn resolve_project(
+    client: &BacklogApiClient,
+    project: Option<String>,
+) -> CliResult<String> {
+    if let Some(project) = project {
+        return Ok(project);
+    }
+    checkout_remote(client)?
+        .map(|remote| remote.project_key)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Not inside a clone of a Backlog git repository; pass --project-id explicitly"
+            )
+        })
+}
+
+/// The project and repository, defaulting to those of the current checkout
+///
+/// The repository is looked up by comparing the remote URL with the clone
+/// URLs of the project's repositories.
+pub(crate) async fn resolve_repo(
+    client: &BacklogApiClient,
+    project: Option<String>,
+    repo: Option<String>,
+) -> CliResult<(String, String)> {
+    if let (Some(project), Some(repo)) = (&project, &repo) {
+        return Ok((project.clone(), repo.clone()));
+    }
+    let Some(remote) = checkout_remote(client)? else {
+        anyhow::bail!(
+            "Not inside a clone of a Backlog git repository; pass --project-id and --repo-id explicitly"
+        );
+    };
+
+    let project = project.unwrap_or_else(|| remote.project_key.clone());
+    if let Some(repo) = repo {
+        return Ok((project, repo));
+    }
+    if !project.eq_ignore_ascii_case(&remote.project_key) {
+        anyhow::bail!(
+            "This checkout belongs to project {}; pass --repo-id for project {project}",
+            remote.project_key
+        );
+    }
+
+    let project_id_or_key = project.parse::<ProjectIdOrKey>()?;
+    let repositories = client
+        .git()
+        .get_repository_list(GetRepositoryListParams::new(project_id_or_key))
+        .await?;
+    let repository = repositories
+        .iter()
+        .find(|repository| remote.matches(repository))
+        .or_else(|| {
+            repositories
+                .iter()
+                .find(|repository| repository.name == remote.repo_name)
+        })
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "No repository in {project} matches the remote {}/{}",
+                remote.project_key,
+                remote.repo_name
+            )
+        })?;
+    eprintln!("Using {project}/{} from the git checkout", repository.name);
+    Ok((project, repository.name.clone()))
+}
+
+/// Base and source branches of a new pull request
+///
+/// Defaults to the remote's default branch and the current branch.
+pub(crate) fn resolve_branches(
+    base: Option<String>,
+    branch: Option<String>,
+) -> CliResult<(String, String)> {
+    if let (Some(base), Some(branch)) = (&base, &branch) {
+        return Ok((base.clone(), branch.clone()));
+    }
+    let checkout = Checkout::current().ok_or_else(|| {
+        anyhow::anyhow!("Not inside a git checkout; pass --base and --branch explicitly")
+    })?;
+
+    let branch = match branch {
+        Some(branch) => branch,
+        None => checkout
+            .current_branch()
+            .ok_or_else(|| anyhow::anyhow!("HEAD is detached; pass --branch explicitly"))?,
+    };
+    let base = match base {
+        Some(base) => base,
+        None => {
+            let remote = checkout
+                .backlog_remote()
+                .map_or_else(|| "origin".to_string(), |(name, _)| name);
+            checkout.default_branch(&remote).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Unknown default branch of {remote}; run `git remote set-head {remote} --auto` or pass --base"
+                )
+            })?
+        }
+    };
+    if base == branch {
+        anyhow::bail!("Base and source branch are both '{base}'");
+    }
+    Ok((base, branch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remote(host: &str, project: &str, repo: &str) -> Option<BacklogRemote> {
+        Some(BacklogRemote {
+            host: host.to_string(),
+            project_key: project.to_string(),
+            repo_name: repo.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_parse_backlog_remote_urls() {
+        assert_eq!(
+            BacklogRemote::parse("https://example.backlog.com/git/PROJ/app.git"),
+            remote("example.backlog.com", "PROJ", "app")
+        );
+        assert_eq!(
+            BacklogRemote::parse("https://john@example.backlog.jp/git/PROJ/app"),
+            remote("example.backlog.jp", "PROJ", "app")
+        );
+        assert_eq!(
+            BacklogRemote::parse("example@example.git.backlog.jp:/PROJ/app.git"),
+            remote("example.backlog.jp", "PROJ", "app")
+        );
+        assert_eq!(
+            BacklogRemote::parse("ssh://example@example.git.backlog.com/PROJ/app.git"),
+            remote("example.backlog.com", "PROJ", "app")
+        );
+        assert_eq!(BacklogRemote::parse("git@github.com:owner/app.git"), None);
+        assert_eq!(
+            BacklogRemote::parse("https://example.backlog.com/view/PROJ-1"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_remote_is_in_space() {
+        let remote = BacklogRemote::parse("example@example.git.backlog.jp:/PROJ/app.git").unwrap();
+        assert!(remote.is_in_space("Example.backlog.jp"));
+        assert!(!remote.is_in_space("other.backlog.jp"));
+        assert!(!remote.is_in_space("example.backlog.com"));
+    }
+
+    #[test]
+    fn test_remote_matches_repository_clone_urls() {
+        let repository: Repository = serde_json::from_value(serde_json::json!({
+            "id": 1, "projectId": 1, "name": "app",
+            "httpUrl": "https://example.backlog.com/git/PROJ/app.git",
+            "sshUrl": "example@example.git.backlog.com:/PROJ/app.git"
+        }))
+        .unwrap();
+        let ssh = BacklogRemote::parse("example@example.git.backlog.com:/PROJ/app.git").unwrap();
+        assert!(ssh.matches(&repository));
+        let other = BacklogRemote::parse("https://example.backlog.com/git/PROJ/web.git").unwrap();
+        assert!(!other.matches(&repository));
+    }
+
+    #[test]
+    fn test_checkout_reads_remotes_and_branches() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = dir.path().join(".git");
+        fs::create_dir_all(git.join("refs/remotes/origin")).unwrap();
+        fs::write(
+            git.join("config"),
+            "[core]\n\tbare = false\n[remote \"github\"]\n\turl = git@github.com:o/app.git\n\
+             [remote \"origin\"]\n\turl = https://example.backlog.com/git/PROJ/app.git\n\
+             \tfetch = +refs/heads/*:refs/remotes/origin/*\n",
+        )
+        .unwrap();
+        fs::write(git.join("HEAD"), "ref: refs/heads/feature/PROJ-12\n").unwrap();
+        fs::write(
+            git.join("refs/remotes/origin/HEAD"),
+            "ref: refs/remotes/origin/develop\n",
+        )
+        .unwrap();
+        let nested = dir.path().join("src/bin");
+        fs::create_dir_all(&nested).unwrap();
+
+        let checkout = Checkout::discover(&nested).unwrap();
+        assert_eq!(checkout.remotes().len(), 2);
+        let (name, remote) = checkout.backlog_remote().unwrap();
+        assert_eq!(name, "origin");
+        assert_eq!(remote.host, "example.backlog.com");
+        assert_eq!(
+            checkout.current_branch().as_deref(),
+            Some("feature/PROJ-12")
+        );
+        assert_eq!(
+            checkout.default_branch("origin").as_deref(),
+            Some("develop")
+        );
+
+        fs::remove_file(git.join("refs/remotes/origin/HEAD")).unwrap();
+        assert_eq!(checkout.default_branch("origin"), None);
+        fs::write(
+            git.join("packed-refs"),
+            "# pack-refs with: peeled\n0123abcd refs/remotes/origin/master\n",
+        )
+        .unwrap();
+        assert_eq!(checkout.default_branch("origin").as_deref(), Some("master"));
+    }
+}
@@ -1,19 +1,26 @@
 use crate::commands::common::CliResult;
-use crate::commands::git::args::{PrArgs, PrCommands, RepoArgs, RepoCommands};
+use crate::commands::git::args::{
+    GitArgs, GitCommands, HookCommands, PrArgs, PrCommands, RepoArgs, RepoCommands,
+};
 use backlog_api_client::client::BacklogApiClient;
+#[cfg(feature = "git_writable")]
+use backlog_core::identifier::Identifier;
 
+use super::context;
 use super::subcommands;
 
 /// Execute repository-related commands.
 pub async fn execute_repo(client: &BacklogApiClient, args: RepoArgs) -> CliResult<()> {
     match args.command {
         RepoCommands::List { project_id } => {
+            let project_id = context::resolve_project(client, project_id)?;
             subcommands::repo::list(client, project_id).await?;
         }
         RepoCommands::Show {
             project_id,
             repo_id,
         } => {
+            let (project_id, repo_id) = context::resolve_repo(client, project_id, repo_id).await?;
             subcommands::repo::show(client, project_id, repo_id).await?;
         }
     }
@@ -27,23 +34,42 @@ pub async fn execute_pr(client: &BacklogApiClient, args: PrArgs) -> CliResult<()
             project_id,
             repo_id,
         } => {
+            let (project_id, repo_id) = context::resolve_repo(client, project_id, repo_id).await?;
             subcommands::pr::list(client, project_id, repo_id).await?;
         }
+        #[cfg(feature = "pr-inbox")]
+        PrCommands::Inbox(inbox_args) => subcommands::pr_inbox::inbox(client, inbox_args).await?,
         PrCommands::Show {
             project_id,
             repo_id,
             pr_number,
         } => {
+            let (project_id, repo_id) = context::resolve_repo(client, project_id, repo_id).await?;
             subcommands::pr::show(client, project_id, repo_id, pr_number).await?;
         }
-        PrCommands::DownloadAttachment(dl_args) => {
-            subcommands::pr_attachments::download_attachment(client, dl_args).await?;
+        PrCommands::DownloadAttachment(mut dl_args) => {
+            let (project_id, repo_id) =
+                context::resolve_repo(client, dl_args.project_id.take(), dl_args.repo_id.take())
+                    .await?;
+            subcommands::pr_attachments::download_attachment(client, project_id, repo_id, dl_args)
+                .await?;
+        }
+        PrCommands::Comments {
+            project_id,
+            repo_id,
+            pr_number,
+            json,
+        } => {
+            let (project_id, repo_id) = context::resolve_repo(client, project_id, repo_id).await?;
+            subcommands::pr_comments::comments(client, project_id, repo_id, pr_number, json)
+                .await?;
         }
         PrCommands::CommentCount {
             project_id,
             repo_id,
             pr_number,
         } => {
+            let (project_id, repo_id) = context::resolve_repo(client, project_id, repo_id).await?;
             subcommands::pr_comments::comment_count(client, project_id, repo_id, pr_number).await?;
         }
         PrCommands::Count {
@@ -56,6 +82,7 @@ pub async fn execute_pr(client: &BacklogApiClient, args: PrArgs) -> CliResult<()
             offset: _,
             count: _,
         } => {
+            let (project_id, repo_id) = context::resolve_repo(client, project_id, repo_id).await?;
             subcommands::pr::count(
                 client,
                 project_id,
@@ -68,8 +95,12 @@ pub async fn execute_pr(client: &BacklogApiClient, args: PrArgs) -> CliResult<()
             .await?;
         }
         #[cfg(feature = "git_writable")]
-        PrCommands::DeleteAttachment(del_args) => {
-            subcommands::pr_attachments::delete_attachment(client, del_args).await?;
+        PrCommands::DeleteAttachment(mut del_args) => {
+            let (project_id, repo_id) =
+                context::resolve_repo(client, del_args.project_id.take(), del_args.repo_id.take())
+                    .await?;
+            subcommands::pr_attachments::delete_attachment(client, project_id, repo_id, del_args)
+                .await?;
         }
         #[cfg(feature = "git_writable")]
         PrCommands::Update {
@@ -83,6 +114,7 @@ pub async fn execute_pr(client: &BacklogApiClient, args: PrArgs) -> CliResult<()
             notify_user_ids,
             comment,
         } => {
+            let (project_id, repo_id) = context::resolve_repo(client, project_id, repo_id).await?;
             subcommands::pr::update(
                 client,
                 project_id,
@@ -105,6 +137,7 @@ pub async fn execute_pr(client: &BacklogApiClient, args: PrArgs) -> CliResult<()
             comment_id,
             content,
         } => {
+            let (project_id, repo_id) = context::resolve_repo(client, project_id, repo_id).await?;
             subcommands::pr_comments::comment_update(
                 client, project_id, repo_id, pr_number, comment_id, content,
             )
@@ -119,10 +152,30 @@ pub async fn execute_pr(client: &BacklogApiClient, args: PrArgs) -> CliResult<()
             base,
             branch,
             issue_id,
+            from_branch,
             assignee_id,
             notify_user_ids,
             attachment_ids,
+            attach,
         } => {
+            let (project_id, repo_id) = context::resolve_repo(client, project_id, repo_id).await?;
+            let (base, branch) = context::resolve_branches(base, branch)?;
+            let (summary, description, issue_id) = if from_branch {
+                let issue = subcommands::pr::issue_for_branch(client, &branch).await?;
+                println!("Linking issue {}: {}", issue.issue_key, issue.summary);
+                (
+                    summary.unwrap_or_else(|| issue.summary.clone()),
+                    description.unwrap_or_else(|| issue.issue_key.to_string()),
+                    Some(issue.id.value()),
+                )
+            } else {
+                // clap requires both unless --from-branch is given
+                (
+                    summary.unwrap_or_default(),
+                    description.unwrap_or_default(),
+                    issue_id,
+                )
+            };
             subcommands::pr::create(
                 client,
                 project_id,
@@ -135,6 +188,7 @@ pub async fn execute_pr(client: &BacklogApiClient, args: PrArgs) -> CliResult<()
                 assignee_id,
                 notify_user_ids,
                 attachment_ids,
+                &attach,
             )
             .await?;
         }
@@ -147,3 +201,12 @@ pub async fn execute_pr(client: &BacklogApiClient, args: PrArgs) -> CliResult<()
     }
     Ok(())
 }
+
+/// Execute commands acting on the local checkout only.
+pub fn execute_git(args: &GitArgs) -> CliResult<()> {
+    match &args.command {
+        GitCommands::Hook {
+            command: HookCommands::Install { force },
+        } => subcommands::hook::install(*force),
+    }
+}
@@ -1,6 +1,7 @@
 pub mod args;
+pub(crate) mod context;
 mod handler;
 mod subcommands;
 
-pub use args::{PrArgs, RepoArgs};
-pub use handler::{execute_pr, execute_repo};
+pub use args::{GitArgs, PrArgs, RepoArgs};
+pub use handler::{execute_git, execute_pr, execute_repo};
@@ -0,0 +1,132 @@
+//! Git hooks linking commits to issues
+
+use crate::commands::common::CliResult;
+use crate::commands::git::context::Checkout;
+use anyhow::Context;
+use std::fs;
+use std::path::Path;
+
+/// Line identifying hooks written by `blg git hook install`
+const MARKER: &str = "# Installed by blg git hook install";
+
+/// Prefix the commit message with the issue key found in the branch name,
+/// unless the message already mentions it
+const PREPARE_COMMIT_MSG: &str = r#"#!/bin/sh
+# Installed by blg git hook install
+# Prefixes commit messages with the issue key in the branch name.
+
+case "$2" in
+    merge|squash|commit) exit 0 ;;
+esac
+
+branch=$(git symbolic-ref --quiet --short HEAD) || exit 0
+key=$(printf '%s\n' "$branch" | grep -oE '(^|[^_A-Za-z0-9])[A-Z][_A-Z0-9]{0,24}-[1-9][0-9]*($|[^A-Za-z0-9])' | head -n 1 | sed -E 's/^[^A-Z]//; s/[^0-9]$//')
+[ -n "$key" ] || exit 0
+
+if grep -qE "(^|[^_A-Za-z0-9])$key([^A-Za-z0-9]|$)" "$1"; then
+    exit 0
+fi
+
+tmp="$1.blg"
+{ printf '%s ' "$key"; cat "$1"; } > "$tmp" && mv "$tmp" "$1"
+"#;
+
+/// Write the hook, refusing to replace a foreign one unless `force` is set
+fn install_hook(hooks_dir: &Path, force: bool) -> CliResult<std::path::PathBuf> {
+    let path = hooks_dir.join("prepare-commit-msg");
+    if let Ok(existing) = fs::read_to_string(&path)
+        && !existing.contains(MARKER)
+        && !force
+    {
+        anyhow::bail!(
+            "{} already exists and was not installed by blg; pass --force to replace it",
+            path.display()
+        );
+    }
+
+    fs::create_dir_all(hooks_dir)
+        .with_context(|| format!("Failed to create {}", hooks_dir.display()))?;
+    fs::write(&path, PREPARE_COMMIT_MSG)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+    }
+    Ok(path)
+}
+
+/// Install the prepare-commit-msg hook into the current checkout
+pub(crate) fn install(force: bool) -> CliResult<()> {
+    let checkout =
+        Checkout::current().ok_or_else(|| anyhow::anyhow!("Not inside a git checkout"))?;
+    let path = install_hook(&checkout.hooks_dir(), force)?;
+    println!("✅ Installed {}", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_install_hook_keeps_foreign_hooks() {
+        let dir = tempfile::tempdir().unwrap();
+        let hooks = dir.path().join("hooks");
+
+        let path = install_hook(&hooks, false).unwrap();
+        assert!(fs::read_to_string(&path).unwrap().contains(MARKER));
+        // Reinstalling over our own hook is fine
+        install_hook(&hooks, false).unwrap();
+
+        fs::write(&path, "#!/bin/sh\nexit 0\n").unwrap();
+        assert!(install_hook(&hooks, false).is_err());
+        install_hook(&hooks, true).unwrap();
+        assert!(fs::read_to_string(&path).unwrap().contains(MARKER));
+    }
+
+    /// Commit message after running the hook on a checkout of `branch`
+    #[cfg(unix)]
+    fn run_hook(branch: &str, message: &str) -> String {
+        use std::process::Command;
+
+        let dir = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(dir.path())
+                .args(args)
+                .status()
+                .unwrap();
+            assert!(status.success());
+        };
+        git(&["init", "-q"]);
+        git(&["symbolic-ref", "HEAD", &format!("refs/heads/{branch}")]);
+        let hook = install_hook(&dir.path().join(".git").join("hooks"), false).unwrap();
+        let message_path = dir.path().join("COMMIT_EDITMSG");
+        fs::write(&message_path, message).unwrap();
+
+        let status = Command::new(&hook)
+            .arg(&message_path)
+            .current_dir(dir.path())
+            .status()
+            .unwrap();
+        assert!(status.success());
+        fs::read_to_string(&message_path).unwrap()
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hook_finds_keys_like_issue_key_find_in() {
+        assert_eq!(
+            run_hook("feature/BLG-12-login", "Fix login"),
+            "BLG-12 Fix login"
+        );
+        assert_eq!(run_hook("BLG-12_login", "Fix BLG-12"), "Fix BLG-12");
+        // Mentions glued to a longer word are not the key
+        assert_eq!(run_hook("BLG-12", "Fix BLG-123"), "BLG-12 Fix BLG-123");
+        assert_eq!(run_hook("BLG-12", "Fix BLG-12a"), "BLG-12 Fix BLG-12a");
+        assert_eq!(run_hook("utf-8/UTF-8bit", "Fix"), "Fix");
+        assert_eq!(run_hook(&format!("{}-1", "A".repeat(26)), "Fix"), "Fix");
+    }
+}
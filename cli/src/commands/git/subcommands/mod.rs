@@ -1,4 +1,7 @@
+pub(crate) mod hook;
 pub(crate) mod pr;
 pub(crate) mod pr_attachments;
 pub(crate) mod pr_comments;
+#[cfg(feature = "pr-inbox")]
+pub(crate) mod pr_inbox;
 pub(crate) mod repo;
@@ -106,6 +106,22 @@ pub(crate) async fn count(
     Ok(())
 }
 
+/// The issue whose key appears in a branch name
+#[cfg(feature = "git_writable")]
+pub(crate) async fn issue_for_branch(
+    client: &BacklogApiClient,
+    branch: &str,
+) -> CliResult<backlog_issue::models::Issue> {
+    let key = backlog_core::IssueKey::find_in(branch)
+        .ok_or_else(|| anyhow::anyhow!("No issue key found in branch name '{branch}'"))?;
+    let issue = client
+        .issue()
+        .get_issue(backlog_issue::GetIssueParams::new(key.clone()))
+        .await
+        .with_context(|| format!("Failed to get issue {key}"))?;
+    Ok(issue)
+}
+
 #[cfg(feature = "git_writable")]
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn create(
@@ -120,7 +136,10 @@ pub(crate) async fn create(
     assignee_id: Option<u32>,
     notify_user_ids: Option<String>,
     attachment_ids: Option<String>,
+    attach: &[std::path::PathBuf],
 ) -> CliResult<()> {
+    use crate::commands::issue::{link_uploaded, upload_files};
+
     println!("Creating pull request in repo {repo_id} (project {project_id})");
 
     let parsed_project_id: ProjectIdOrKey = project_id
@@ -168,7 +187,15 @@ pub(crate) async fn create(
         params = params.attachment_ids(attachment_ids);
     }
 
-    let pull_request = client.git().add_pull_request(params).await?;
+    let upload = upload_files(client, attach).await?;
+    if !upload.uploaded().is_empty() {
+        params
+            .attachment_ids
+            .get_or_insert_with(Vec::new)
+            .extend(upload.ids());
+    }
+
+    let pull_request = link_uploaded(&upload, client.git().add_pull_request(params).await)?;
     println!("✅ Pull request created successfully");
     println!("ID: {}", pull_request.id.value());
     println!("Number: {}", pull_request.number.value());
@@ -1,4 +1,4 @@
-use crate::commands::common::CliResult;
+use crate::commands::common::{CliResult, download_progress, format_bytes};
 use crate::commands::git::args::DownloadPrAttachmentArgs;
 use anyhow::Context;
 use backlog_api_client::{
@@ -6,32 +6,31 @@ use backlog_api_client::{
     client::BacklogApiClient,
 };
 use backlog_core::identifier::Identifier;
-use tokio::fs;
 
 #[cfg(feature = "git_writable")]
 use crate::commands::git::args::DeletePrAttachmentArgs;
 
 pub(crate) async fn download_attachment(
     client: &BacklogApiClient,
+    project_id: String,
+    repo_id: String,
     dl_args: DownloadPrAttachmentArgs,
 ) -> CliResult<()> {
     println!(
         "Downloading attachment {} for PR #{} in repo {} (project {}) to {}",
         dl_args.attachment_id,
         dl_args.pr_number,
-        dl_args.repo_id,
-        dl_args.project_id,
+        repo_id,
+        project_id,
         dl_args.output.display()
     );
 
-    let parsed_project_id: ProjectIdOrKey = dl_args
-        .project_id
+    let parsed_project_id: ProjectIdOrKey = project_id
         .parse()
-        .with_context(|| format!("Failed to parse project_id '{}'", dl_args.project_id))?;
-    let parsed_repo_id: RepositoryIdOrName = dl_args
-        .repo_id
+        .with_context(|| format!("Failed to parse project_id '{project_id}'"))?;
+    let parsed_repo_id: RepositoryIdOrName = repo_id
         .parse()
-        .with_context(|| format!("Failed to parse repo_id '{}'", dl_args.repo_id))?;
+        .with_context(|| format!("Failed to parse repo_id '{repo_id}'"))?;
     let parsed_attachment_id = PullRequestAttachmentId::new(dl_args.attachment_id);
 
     let parsed_pr_number = PullRequestNumber::from(dl_args.pr_number);
@@ -42,20 +41,22 @@ pub(crate) async fn download_attachment(
         parsed_pr_number,
         parsed_attachment_id,
     );
-    match client.git().download_pull_request_attachment(params).await {
-        Ok(downloaded_file) => {
-            if let Err(e) = fs::write(&dl_args.output, &downloaded_file.bytes).await {
-                eprintln!(
-                    "Error writing attachment to {}: {}",
-                    dl_args.output.display(),
-                    e
-                );
-            } else {
+    match client
+        .download_to_path(params, &dl_args.output, download_progress())
+        .await
+    {
+        Ok(download) => {
+            if download.resumed_from > 0 {
                 println!(
-                    "Attachment downloaded successfully to: {}",
-                    dl_args.output.display()
+                    "Resumed from {} of a previous download",
+                    format_bytes(download.resumed_from)
                 );
             }
+            println!(
+                "Attachment downloaded successfully to: {} ({})",
+                dl_args.output.display(),
+                format_bytes(download.size)
+            );
         }
         Err(e) => {
             eprintln!("Error downloading PR attachment: {e}");
@@ -67,21 +68,21 @@ pub(crate) async fn download_attachment(
 #[cfg(feature = "git_writable")]
 pub(crate) async fn delete_attachment(
     client: &BacklogApiClient,
+    project_id: String,
+    repo_id: String,
     del_args: DeletePrAttachmentArgs,
 ) -> CliResult<()> {
     println!(
         "Deleting attachment {} from PR #{} in repo {} (project {})",
-        del_args.attachment_id, del_args.pr_number, del_args.repo_id, del_args.project_id
+        del_args.attachment_id, del_args.pr_number, repo_id, project_id
     );
 
-    let parsed_project_id: ProjectIdOrKey = del_args
-        .project_id
+    let parsed_project_id: ProjectIdOrKey = project_id
         .parse()
-        .with_context(|| format!("Failed to parse project_id '{}'", del_args.project_id))?;
-    let parsed_repo_id: RepositoryIdOrName = del_args
-        .repo_id
+        .with_context(|| format!("Failed to parse project_id '{project_id}'"))?;
+    let parsed_repo_id: RepositoryIdOrName = repo_id
         .parse()
-        .with_context(|| format!("Failed to parse repo_id '{}'", del_args.repo_id))?;
+        .with_context(|| format!("Failed to parse repo_id '{repo_id}'"))?;
     let parsed_attachment_id = PullRequestAttachmentId::new(del_args.attachment_id);
     let parsed_pr_number = PullRequestNumber::from(del_args.pr_number);
 
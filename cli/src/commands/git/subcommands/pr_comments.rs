@@ -1,10 +1,142 @@
 use crate::commands::common::CliResult;
 use anyhow::Context;
 use backlog_api_client::{
-    ProjectIdOrKey, PullRequestCommentId, PullRequestNumber, RepositoryIdOrName,
-    client::BacklogApiClient,
+    CompactPrComment, GetPullRequestCommentListParams, PrCommentOrder, PrCommentThread,
+    ProjectIdOrKey, PullRequestComment, PullRequestCommentId, PullRequestNumber,
+    RepositoryIdOrName, client::BacklogApiClient, comment_thread,
 };
 use backlog_core::identifier::Identifier;
+use std::fmt::Write as _;
+use std::io::IsTerminal;
+
+/// Comments fetched per request
+const COMMENT_PAGE_SIZE: u8 = 100;
+
+/// Every comment of a pull request, oldest first
+async fn fetch_all_comments(
+    client: &BacklogApiClient,
+    project_id: &ProjectIdOrKey,
+    repo_id: &RepositoryIdOrName,
+    pr_number: PullRequestNumber,
+) -> CliResult<Vec<PullRequestComment>> {
+    let mut comments: Vec<PullRequestComment> = Vec::new();
+    loop {
+        let mut params =
+            GetPullRequestCommentListParams::new(project_id.clone(), repo_id.clone(), pr_number)
+                .order(PrCommentOrder::Asc)
+                .count(COMMENT_PAGE_SIZE);
+        if let Some(last) = comments.last() {
+            params = params.min_id(last.id.value() + 1);
+        }
+        let page = client.git().get_pull_request_comment_list(params).await?;
+        let done = page.len() < usize::from(COMMENT_PAGE_SIZE);
+        comments.extend(page);
+        if done {
+            return Ok(comments);
+        }
+    }
+}
+
+/// Wrap `@name` mentions in bold
+fn highlight_mentions(text: &str, mentions: &[String]) -> String {
+    let mut text = text.to_string();
+    for name in mentions {
+        text = text.replace(&format!("@{name}"), &format!("\x1b[1m@{name}\x1b[0m"));
+    }
+    text
+}
+
+fn render_comment(out: &mut String, comment: &CompactPrComment, highlight: bool) {
+    let _ = write!(
+        out,
+        "#{} {} · {}",
+        comment.id,
+        comment.author,
+        comment.created.format("%Y-%m-%d %H:%M")
+    );
+    if comment.edited.is_some() {
+        out.push_str(" (edited)");
+    }
+    if !comment.starred_by.is_empty() {
+        let _ = write!(out, "  ★ {}", comment.starred_by.join(", "));
+    }
+    out.push('\n');
+    for change in &comment.changes {
+        let _ = writeln!(out, "  → {change}");
+    }
+    if let Some(content) = &comment.content {
+        let content = if highlight {
+            highlight_mentions(content, &comment.mentions)
+        } else {
+            content.clone()
+        };
+        for line in content.lines() {
+            let _ = writeln!(out, "  {line}");
+        }
+    }
+}
+
+/// Render threads as indented text; mentions are bolded when `highlight` is set
+pub(crate) fn render_threads(threads: &[PrCommentThread], highlight: bool) -> String {
+    let mut out = String::new();
+    for (index, thread) in threads.iter().enumerate() {
+        if index > 0 {
+            out.push('\n');
+        }
+        match &thread.location {
+            Some(location) => {
+                let _ = writeln!(out, "── {}:{} ──", location.path, location.line);
+                // Show the code once, from the first comment quoting it
+                if let Some(hunk) = thread.comments.iter().find_map(|c| c.hunk.as_deref()) {
+                    for line in hunk.lines() {
+                        let _ = writeln!(out, "│ {line}");
+                    }
+                }
+            }
+            None => out.push_str("── Conversation ──\n"),
+        }
+        for comment in &thread.comments {
+            render_comment(&mut out, comment, highlight);
+        }
+    }
+    out
+}
+
+/// Show the comments of a pull request as threads
+pub(crate) async fn comments(
+    client: &BacklogApiClient,
+    project_id: String,
+    repo_id: String,
+    pr_number: u64,
+    json: bool,
+) -> CliResult<()> {
+    let parsed_project_id: ProjectIdOrKey = project_id
+        .parse()
+        .with_context(|| format!("Failed to parse project_id '{project_id}'"))?;
+    let parsed_repo_id: RepositoryIdOrName = repo_id
+        .parse()
+        .with_context(|| format!("Failed to parse repo_id '{repo_id}'"))?;
+    let comments = fetch_all_comments(
+        client,
+        &parsed_project_id,
+        &parsed_repo_id,
+        PullRequestNumber::from(pr_number),
+    )
+    .await?;
+    let threads = comment_thread::build_threads(&comments);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&threads)?);
+    } else if threads.is_empty() {
+        println!("No comments on {repo_id}#{pr_number}");
+    } else {
+        print!(
+            "{}",
+            render_threads(&threads, std::io::stdout().is_terminal())
+        );
+    }
+    Ok(())
+}
 
 pub(crate) async fn comment_count(
     client: &BacklogApiClient,
@@ -76,3 +208,49 @@ pub(crate) async fn comment_update(
     println!("Updated: {}", comment.updated);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_threads() {
+        let comments: Vec<PullRequestComment> = serde_json::from_value(serde_json::json!([
+            {
+                "id": 1, "content": "", "changeLog": [{"field": "status", "originalValue": "Open", "newValue": "Merged"}],
+                "createdUser": {"id": 1, "userId": "alice", "name": "Alice", "roleType": 1, "mailAddress": "alice@example.com"},
+                "created": "2024-01-01T10:00:00Z", "updated": "2024-01-01T10:00:00Z",
+                "stars": [], "notifications": []
+            },
+            {
+                "id": 2, "content": "src/lib.rs:3 @bob why?\n@@ -3 +3 @@\n-a\n+b",
+                "changeLog": [],
+                "createdUser": {"id": 1, "userId": "alice", "name": "Alice", "roleType": 1, "mailAddress": "alice@example.com"},
+                "created": "2024-01-01T11:00:00Z", "updated": "2024-01-01T12:00:00Z",
+                "stars": [{
+                    "id": 9, "comment": null, "url": "", "title": "",
+                    "presenter": {"id": 2, "userId": "bob", "name": "Bob", "roleType": 1, "mailAddress": "bob@example.com"},
+                    "created": "2024-01-01T12:00:00Z"
+                }],
+                "notifications": []
+            }
+        ]))
+        .unwrap();
+        let threads = comment_thread::build_threads(&comments);
+
+        assert_eq!(
+            render_threads(&threads, false),
+            "── Conversation ──\n\
+             #1 Alice · 2024-01-01 10:00\n\
+             \x20 → status: Open → Merged\n\
+             \n\
+             ── src/lib.rs:3 ──\n\
+             │ @@ -3 +3 @@\n\
+             │ -a\n\
+             │ +b\n\
+             #2 Alice · 2024-01-01 11:00 (edited)  ★ Bob\n\
+             \x20 src/lib.rs:3 @bob why?\n"
+        );
+        assert!(render_threads(&threads, true).contains("\x1b[1m@bob\x1b[0m"));
+    }
+}
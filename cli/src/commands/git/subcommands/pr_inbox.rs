@@ -0,0 +1,91 @@
+//! Pull request review dashboard across repositories
+
+use crate::commands::common::{CliResult, resolve_projects, truncate_text};
+use crate::commands::git::args::{PrInboxArgs, PrStatusArg};
+use backlog_api_client::client::BacklogApiClient;
+use backlog_api_client::pr_inbox::{InboxPullRequest, PrInboxParams, PullRequestState};
+use prettytable::{Table, row};
+
+impl From<PrStatusArg> for PullRequestState {
+    fn from(status: PrStatusArg) -> Self {
+        match status {
+            PrStatusArg::Open => PullRequestState::Open,
+            PrStatusArg::Closed => PullRequestState::Closed,
+            PrStatusArg::Merged => PullRequestState::Merged,
+        }
+    }
+}
+
+/// `3d` for days, `-` when unknown
+fn format_age(age_days: Option<i64>) -> String {
+    age_days.map_or_else(|| "-".to_string(), |days| format!("{days}d"))
+}
+
+fn print_group(title: &str, pull_requests: &[InboxPullRequest]) {
+    println!("{title} ({})", pull_requests.len());
+    if pull_requests.is_empty() {
+        println!("  (none)\n");
+        return;
+    }
+
+    let mut table = Table::new();
+    table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    table.set_titles(row![
+        "", "PR", "Title", "Author", "Assignee", "Age", "Comments", "Issue"
+    ]);
+    for pr in pull_requests {
+        let unread = if pr.unread_notifications > 0 {
+            "●"
+        } else {
+            ""
+        };
+        let issue = pr.issue.as_ref().map_or_else(String::new, |issue| {
+            format!("{} ({})", issue.key, issue.status)
+        });
+        table.add_row(row![
+            unread,
+            format!("{}/{}#{}", pr.project, pr.repository, pr.number),
+            truncate_text(&pr.summary, 40),
+            pr.author.as_deref().unwrap_or("-"),
+            pr.assignee.as_deref().unwrap_or("-"),
+            format_age(pr.age_days),
+            pr.comment_count
+                .map_or_else(|| "?".to_string(), |count| count.to_string()),
+            issue
+        ]);
+    }
+    table.printstd();
+    println!();
+}
+
+/// Show pull requests assigned to or created by the current user and recently updated ones
+pub(crate) async fn inbox(client: &BacklogApiClient, args: PrInboxArgs) -> CliResult<()> {
+    let projects = resolve_projects(client, &args.projects).await?;
+    let params = PrInboxParams::new(projects)
+        .repositories(args.repos)
+        .states(
+            args.statuses
+                .into_iter()
+                .map(PullRequestState::from)
+                .collect(),
+        )
+        .recent_days(args.recent_days);
+    let inbox = client.pr_inbox(params).await?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&inbox)?);
+        return Ok(());
+    }
+
+    for failure in &inbox.failures {
+        eprintln!("Warning: {}: {}", failure.source, failure.message);
+    }
+    print_group("Assigned to me", &inbox.assigned_to_me);
+    print_group("Created by me", &inbox.created_by_me);
+    print_group(
+        &format!("Recently updated (last {} days)", args.recent_days),
+        &inbox.recently_updated,
+    );
+    println!("● unread notifications");
+    Ok(())
+}
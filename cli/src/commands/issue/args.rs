@@ -20,6 +20,17 @@ pub enum IssueCommands {
         #[clap(name = "ISSUE_ID_OR_KEY")]
         issue_id_or_key: String,
     },
+    /// Show an issue with its comments, change logs, files, child issues and pull requests
+    View {
+        /// Issue ID or Key (e.g., "PROJECT-123" or "12345")
+        #[clap(name = "ISSUE_ID_OR_KEY")]
+        issue_id_or_key: String,
+        /// Open the issue in the browser instead
+        #[clap(long)]
+        web: bool,
+    },
+    /// Show parent/child issue trees with status and hour roll-ups
+    Tree(IssueTreeArgs),
     /// Download an issue attachment
     #[command(about = "Download an issue attachment")]
     DownloadAttachment(DownloadAttachmentArgs),
@@ -51,6 +62,9 @@ pub enum IssueCommands {
     #[cfg(feature = "issue_writable")]
     #[command(about = "Delete an issue")]
     Delete(DeleteIssueArgs),
+    /// Create a local git branch for an issue and mark the issue as in progress
+    #[cfg(feature = "issue_writable")]
+    Branch(IssueBranchArgs),
     /// Count comments for an issue
     #[command(about = "Count comments for an issue")]
     CountComment(CountCommentArgs),
@@ -123,6 +137,41 @@ pub enum IssueCommands {
     },
 }
 
+#[derive(Args, Debug)]
+#[command(group(
+    clap::ArgGroup::new("scope")
+        .required(true)
+        .args(["issue_id_or_key", "milestone", "project_id"])
+        .multiple(true)
+))]
+pub struct IssueTreeArgs {
+    /// Issue ID or Key whose subtree to show (e.g., "PROJECT-123" or "12345")
+    #[arg(conflicts_with_all = ["milestone", "project_id"])]
+    pub issue_id_or_key: Option<String>,
+
+    /// Show every issue in a milestone
+    #[arg(long, value_name = "MILESTONE_ID")]
+    pub milestone: Option<u32>,
+
+    /// Show the trees of all parent issues in a project (or limit --milestone to it)
+    #[arg(long)]
+    pub project_id: Option<u32>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = TreeFormat::Text)]
+    pub format: TreeFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum TreeFormat {
+    /// Indented tree with roll-ups
+    Text,
+    /// Graphviz DOT
+    Dot,
+    /// Mermaid flowchart
+    Mermaid,
+}
+
 #[derive(Args, Debug)]
 pub struct DownloadAttachmentArgs {
     /// The ID or key of the issue (e.g., "PROJECT-123" or "12345")
@@ -152,6 +201,34 @@ pub struct AddCommentArgs {
     /// Attachment IDs to include (comma-separated, e.g., "789,101112")
     #[arg(short, long)]
     pub attachments: Option<String>,
+
+    /// Local file to upload and attach (can be specified multiple times)
+    #[arg(long = "attach", value_name = "PATH")]
+    pub attach: Vec<PathBuf>,
+    /// Treat the text as Markdown and convert it to Backlog notation if the project uses it
+    #[arg(long)]
+    pub markdown: bool,
+}
+
+#[cfg(feature = "issue_writable")]
+#[derive(Args, Debug)]
+pub struct IssueBranchArgs {
+    /// Issue key (e.g., "PROJECT-123")
+    pub issue_key: String,
+    /// Branch name template with {key}, {project}, {type} and {slug} placeholders
+    /// [default: $BLG_BRANCH_TEMPLATE or "{key}-{slug}"]
+    #[arg(long)]
+    pub template: Option<String>,
+    /// Status to move the issue to, by name or ID
+    /// [default: $BLG_IN_PROGRESS_STATUS or "In Progress" (処理中)]
+    #[arg(long, conflicts_with = "no_status")]
+    pub status: Option<String>,
+    /// Leave the issue status unchanged
+    #[arg(long)]
+    pub no_status: bool,
+    /// Print the branch name without creating it or updating the issue
+    #[arg(long)]
+    pub dry_run: bool,
 }
 
 #[cfg(feature = "issue_writable")]
@@ -253,6 +330,13 @@ pub struct CreateIssueArgs {
         conflicts_with = "custom_fields"
     )]
     pub custom_fields_json: Option<std::path::PathBuf>,
+
+    /// Local file to upload and attach (can be specified multiple times)
+    #[arg(long = "attach", value_name = "PATH")]
+    pub attach: Vec<PathBuf>,
+    /// Treat the text as Markdown and convert it to Backlog notation if the project uses it
+    #[arg(long)]
+    pub markdown: bool,
 }
 
 #[derive(Args, Debug)]
@@ -307,6 +391,10 @@ pub struct UpdateIssueArgs {
         conflicts_with = "custom_fields"
     )]
     pub custom_fields_json: Option<std::path::PathBuf>,
+
+    /// Treat the description and comment as Markdown and convert them to Backlog notation if the project uses it
+    #[arg(long)]
+    pub markdown: bool,
 }
 
 #[derive(Args, Debug)]
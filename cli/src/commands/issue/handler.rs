@@ -16,6 +16,11 @@ pub async fn execute(client: &BacklogApiClient, args: IssueArgs) -> CliResult<()
         IssueCommands::Show { issue_id_or_key } => {
             subcommands::list::show(client, &issue_id_or_key).await?
         }
+        IssueCommands::View {
+            issue_id_or_key,
+            web,
+        } => subcommands::view::view(client, &issue_id_or_key, web).await?,
+        IssueCommands::Tree(tree_args) => subcommands::tree::tree(client, tree_args).await?,
         IssueCommands::RecentlyViewed {
             order,
             count,
@@ -35,6 +40,10 @@ pub async fn execute(client: &BacklogApiClient, args: IssueArgs) -> CliResult<()
         IssueCommands::Delete(delete_args) => {
             subcommands::crud::delete(client, delete_args.issue_key).await?
         }
+        #[cfg(feature = "issue_writable")]
+        IssueCommands::Branch(branch_args) => {
+            subcommands::branch::branch(client, branch_args).await?
+        }
 
         // Comments (from subcommands::comments)
         #[cfg(feature = "issue_writable")]
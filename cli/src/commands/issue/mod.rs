@@ -11,3 +11,7 @@ pub use handler::execute;
 
 // Re-export args for use in main.rs
 pub use args::IssueArgs;
+
+// Re-export the upload flow for other commands that attach files
+#[cfg(any(feature = "issue_writable", feature = "git_writable"))]
+pub(crate) use subcommands::attachments::{link_uploaded, upload_files};
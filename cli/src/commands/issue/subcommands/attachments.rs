@@ -3,14 +3,14 @@
 //! This module provides handlers for managing issue attachments:
 //! - Downloading attachments
 //! - Deleting attachments
+//! - Uploading local files for a follow-up call that links them
 
-use crate::commands::common::CliResult;
+use crate::commands::common::{CliResult, download_progress, format_bytes};
 use anyhow::Context;
 use backlog_api_client::client::BacklogApiClient;
 use backlog_api_client::{AttachmentId, IssueIdOrKey};
 use backlog_core::IssueKey;
 use backlog_issue::{DeleteAttachmentParams, GetAttachmentFileParams};
-use tokio::fs;
 
 /// Download an issue attachment
 ///
@@ -34,20 +34,22 @@ pub async fn download_attachment(
     let parsed_attachment_id = AttachmentId::new(args.attachment_id);
 
     let params = GetAttachmentFileParams::new(parsed_issue_id_or_key, parsed_attachment_id);
-    match client.issue().get_attachment_file(params).await {
-        Ok(downloaded_file) => {
-            if let Err(e) = fs::write(&args.output, &downloaded_file.bytes).await {
-                eprintln!(
-                    "Error writing attachment to {}: {}",
-                    args.output.display(),
-                    e
-                );
-            } else {
+    match client
+        .download_to_path(params, &args.output, download_progress())
+        .await
+    {
+        Ok(download) => {
+            if download.resumed_from > 0 {
                 println!(
-                    "Attachment downloaded successfully to: {}",
-                    args.output.display()
+                    "Resumed from {} of a previous download",
+                    format_bytes(download.resumed_from)
                 );
             }
+            println!(
+                "Attachment downloaded successfully to: {} ({})",
+                args.output.display(),
+                format_bytes(download.size)
+            );
         }
         Err(e) => {
             eprintln!("Error downloading attachment: {e}");
@@ -78,3 +80,46 @@ pub async fn delete_attachment(
     println!("Originally Created: {}", attachment.created);
     Ok(())
 }
+
+/// Upload local files as space attachments before linking them to an issue, comment or pull request
+///
+/// Sizes are checked for every file before the first upload.
+/// Corresponds to `POST /api/v2/space/attachment`
+#[cfg(any(feature = "issue_writable", feature = "git_writable"))]
+pub(crate) async fn upload_files(
+    client: &BacklogApiClient,
+    paths: &[std::path::PathBuf],
+) -> CliResult<backlog_api_client::AttachmentUpload> {
+    use backlog_api_client::{AttachmentSource, AttachmentUpload};
+
+    let mut upload = AttachmentUpload::new();
+    if paths.is_empty() {
+        return Ok(upload);
+    }
+
+    println!("📤 Uploading {} file(s)...", paths.len());
+    let sources = paths.iter().cloned().map(AttachmentSource::Path).collect();
+    if let Err(e) = upload.upload_all(&client.space(), sources).await {
+        anyhow::bail!("{}", upload.failure_report(&e));
+    }
+    for attachment in upload.uploaded() {
+        println!(
+            "   {} ({}) → attachment ID {}",
+            attachment.name,
+            crate::commands::common::format_bytes(attachment.size),
+            attachment.id
+        );
+    }
+    Ok(upload)
+}
+
+/// Turn the result of the call linking the uploads into a CLI result
+///
+/// When the call fails the error lists the uploads left unlinked.
+#[cfg(any(feature = "issue_writable", feature = "git_writable"))]
+pub(crate) fn link_uploaded<T>(
+    upload: &backlog_api_client::AttachmentUpload,
+    result: Result<T, backlog_api_client::ApiError>,
+) -> CliResult<T> {
+    result.map_err(|e| anyhow::anyhow!("{}", upload.failure_report(&e)))
+}
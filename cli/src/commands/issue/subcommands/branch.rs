@@ -0,0 +1,255 @@
+//! Start work on an issue in a local git branch
+//!
+//! The branch name is rendered from a template and always contains the issue
+//! key, so `blg pr create --from-branch` and the `blg git hook install` hook
+//! can find the issue again.
+
+use crate::commands::common::CliResult;
+use crate::commands::issue::args::IssueBranchArgs;
+use anyhow::Context;
+use backlog_api_client::backlog_project::{GetStatusListParams, Status};
+use backlog_api_client::client::BacklogApiClient;
+use backlog_core::IssueKey;
+use backlog_core::identifier::Identifier;
+use backlog_issue::models::Issue;
+use backlog_issue::{GetIssueParams, UpdateIssueParamsBuilder};
+use std::process::Command;
+
+/// Template used when neither `--template` nor `BLG_BRANCH_TEMPLATE` is set
+const DEFAULT_TEMPLATE: &str = "{key}-{slug}";
+/// Names of the built-in in-progress status, in English and Japanese spaces,
+/// used when neither `--status` nor `BLG_IN_PROGRESS_STATUS` is set
+const DEFAULT_STATUSES: [&str; 2] = ["In Progress", "処理中"];
+/// Maximum length of the slug derived from the summary
+const MAX_SLUG_LENGTH: usize = 40;
+
+/// Lowercase ASCII words of `text` joined by `-`, cut at a word boundary
+///
+/// Non-ASCII text such as Japanese summaries yields an empty slug.
+pub(crate) fn slugify(text: &str, max_length: usize) -> String {
+    let mut slug = String::new();
+    for word in text
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        let extra = usize::from(!slug.is_empty()) + word.len();
+        if slug.len() + extra > max_length {
+            if slug.is_empty() {
+                slug.push_str(&word[..max_length].to_ascii_lowercase());
+            }
+            break;
+        }
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+        slug.push_str(&word.to_ascii_lowercase());
+    }
+    slug
+}
+
+/// Render a branch name, dropping separators left dangling by empty placeholders
+pub(crate) fn render_branch_name(template: &str, issue: &Issue) -> String {
+    let key = issue.issue_key.to_string();
+    let project = key
+        .rsplit_once('-')
+        .map_or(key.as_str(), |(project, _)| project);
+    let issue_type = slugify(&issue.issue_type.name, MAX_SLUG_LENGTH);
+    let rendered = template
+        .replace("{key}", &key)
+        .replace("{project}", project)
+        .replace("{type}", &issue_type)
+        .replace("{slug}", &slugify(&issue.summary, MAX_SLUG_LENGTH));
+
+    let mut name = String::with_capacity(rendered.len());
+    for c in rendered.chars() {
+        let c = if c.is_whitespace() { '-' } else { c };
+        let previous = name.chars().last();
+        if c == '-' && matches!(previous, None | Some('-' | '/')) {
+            continue;
+        }
+        if c == '/' && previous == Some('-') {
+            name.pop();
+        }
+        name.push(c);
+    }
+    name.trim_matches(['-', '/']).to_string()
+}
+
+/// Create the branch in the current checkout and switch to it
+fn checkout_new_branch(name: &str) -> CliResult<()> {
+    let status = Command::new("git")
+        .args(["checkout", "-b", name])
+        .status()
+        .context("Failed to run git")?;
+    if !status.success() {
+        anyhow::bail!("git checkout -b {name} failed");
+    }
+    Ok(())
+}
+
+/// The status named (or numbered) `wanted`, or the built-in in-progress
+/// status when nothing is wanted
+pub(crate) fn find_status(statuses: Vec<Status>, wanted: Option<&str>) -> CliResult<Status> {
+    let matches = |status: &Status, name: &str| {
+        status.name.eq_ignore_ascii_case(name) || status.id.value().to_string() == name
+    };
+    let found = match wanted {
+        Some(wanted) => statuses.iter().position(|status| matches(status, wanted)),
+        None => statuses
+            .iter()
+            .position(|status| DEFAULT_STATUSES.iter().any(|name| matches(status, name))),
+    };
+    match found {
+        Some(index) => Ok(statuses.into_iter().nth(index).expect("index was found")),
+        None => {
+            let names: Vec<&str> = statuses.iter().map(|status| status.name.as_str()).collect();
+            anyhow::bail!(
+                "No status '{}' in the project; available: {}",
+                wanted.unwrap_or(DEFAULT_STATUSES[0]),
+                names.join(", ")
+            )
+        }
+    }
+}
+
+/// Move the issue to `status`
+async fn update_status(client: &BacklogApiClient, issue: &Issue, status: &Status) -> CliResult<()> {
+    if status.id == issue.status.id {
+        println!("{} is already {}", issue.issue_key, status.name);
+        return Ok(());
+    }
+
+    let params = UpdateIssueParamsBuilder::default()
+        .issue_id_or_key(issue.issue_key.clone())
+        .status_id(status.id.value().to_string())
+        .build()?;
+    let updated = client.issue().update_issue(params).await?;
+    println!("{} is now {}", updated.issue_key, updated.status.name);
+    Ok(())
+}
+
+/// Create a branch named after an issue and mark the issue as in progress
+pub async fn branch(client: &BacklogApiClient, args: IssueBranchArgs) -> CliResult<()> {
+    let issue_key: IssueKey = args
+        .issue_key
+        .parse()
+        .with_context(|| format!("Failed to parse issue key '{}'", args.issue_key))?;
+    let issue = client
+        .issue()
+        .get_issue(GetIssueParams::new(issue_key.clone()))
+        .await
+        .with_context(|| format!("Failed to get issue {issue_key}"))?;
+
+    let template = args
+        .template
+        .or_else(|| std::env::var("BLG_BRANCH_TEMPLATE").ok())
+        .unwrap_or_else(|| DEFAULT_TEMPLATE.to_string());
+    let name = render_branch_name(&template, &issue);
+    if IssueKey::find_in(&name).as_ref() != Some(&issue.issue_key) {
+        anyhow::bail!(
+            "Branch name '{name}' must contain the issue key; add {{key}} to the template"
+        );
+    }
+    if args.dry_run {
+        println!("{name}");
+        return Ok(());
+    }
+
+    // Resolved up front so a missing status does not leave a branch behind
+    let status = if args.no_status {
+        None
+    } else {
+        let wanted = args
+            .status
+            .or_else(|| std::env::var("BLG_IN_PROGRESS_STATUS").ok());
+        let statuses = client
+            .project()
+            .get_status_list(GetStatusListParams::new(issue.project_id))
+            .await?;
+        Some(find_status(statuses, wanted.as_deref())?)
+    };
+
+    checkout_new_branch(&name)?;
+    match status {
+        Some(status) => update_status(client, &issue, &status).await,
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(summary: &str) -> Issue {
+        serde_json::from_value(serde_json::json!({
+            "id": 1, "projectId": 1, "issueKey": "PROJ-12", "keyId": 12,
+            "summary": summary, "description": "",
+            "issueType": {"id": 1, "projectId": 1, "name": "Bug", "color": "#990000", "displayOrder": 0},
+            "status": {"id": 1, "projectId": 1, "name": "Open", "color": "#ed8077", "displayOrder": 0},
+            "category": [], "versions": [], "milestone": [],
+            "createdUser": {"id": 1, "userId": "john", "name": "John", "roleType": 1, "mailAddress": "john@example.com"},
+            "created": "2024-01-01T00:00:00Z", "updated": "2024-01-01T00:00:00Z"
+        }))
+        .unwrap()
+    }
+
+    fn status(id: u32, name: &str) -> Status {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "projectId": 1, "name": name, "color": "#ed8077", "displayOrder": id
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_find_status() {
+        let english = vec![status(1, "Open"), status(2, "In Progress")];
+        let japanese = vec![status(1, "未対応"), status(2, "処理中")];
+        assert_eq!(
+            find_status(english.clone(), None).unwrap().name,
+            "In Progress"
+        );
+        assert_eq!(find_status(japanese, None).unwrap().name, "処理中");
+        assert_eq!(
+            find_status(english.clone(), Some("open")).unwrap().name,
+            "Open"
+        );
+        assert_eq!(
+            find_status(english.clone(), Some("2")).unwrap().name,
+            "In Progress"
+        );
+        let err = find_status(english, Some("Review")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "No status 'Review' in the project; available: Open, In Progress"
+        );
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(
+            slugify("Fix login: redirect loop!", 40),
+            "fix-login-redirect-loop"
+        );
+        assert_eq!(slugify("one two three", 7), "one-two");
+        assert_eq!(slugify("ログインできない", 40), "");
+        assert_eq!(slugify("Supercalifragilistic", 5), "super");
+    }
+
+    #[test]
+    fn test_render_branch_name() {
+        let fix = issue("Fix login redirect");
+        assert_eq!(
+            render_branch_name(DEFAULT_TEMPLATE, &fix),
+            "PROJ-12-fix-login-redirect"
+        );
+        assert_eq!(
+            render_branch_name("{type}/{project}/{key} {slug}", &fix),
+            "bug/PROJ/PROJ-12-fix-login-redirect"
+        );
+        // Summaries without ASCII words leave just the key
+        assert_eq!(
+            render_branch_name("feature/{key}-{slug}", &issue("ログイン")),
+            "feature/PROJ-12"
+        );
+    }
+}
@@ -6,6 +6,8 @@
 //! - Managing comment notifications
 
 use crate::commands::common::CliResult;
+#[cfg(feature = "issue_writable")]
+use crate::commands::common::markdown_for_project;
 use anyhow::Context;
 use backlog_api_client::client::BacklogApiClient;
 use backlog_api_client::{
@@ -36,9 +38,21 @@ pub async fn add_comment(
         .parse()
         .with_context(|| format!("Failed to parse issue_id_or_key '{}'", args.issue_id_or_key))?;
 
+    let content = if args.markdown {
+        let issue = client
+            .issue()
+            .get_issue(backlog_issue::GetIssueParams::new(
+                parsed_issue_id_or_key.clone(),
+            ))
+            .await?;
+        markdown_for_project(client, issue.project_id, &args.content).await?
+    } else {
+        args.content.clone()
+    };
+
     let mut builder = AddCommentParamsBuilder::default();
     builder.issue_id_or_key(parsed_issue_id_or_key);
-    builder.content(&args.content);
+    builder.content(content);
 
     // Parse notify_users if provided
     if let Some(notify_str) = &args.notify_users {
@@ -68,9 +82,18 @@ pub async fn add_comment(
         builder.attachment_id(attachment_ids);
     }
 
-    let params = builder.build()?;
+    let mut params = builder.build()?;
+
+    let upload = super::attachments::upload_files(client, &args.attach).await?;
+    if !upload.uploaded().is_empty() {
+        params
+            .attachment_id
+            .get_or_insert_with(Vec::new)
+            .extend(upload.ids());
+    }
 
-    let comment = client.issue().add_comment(params).await?;
+    let comment =
+        super::attachments::link_uploaded(&upload, client.issue().add_comment(params).await)?;
     println!("Comment added successfully!");
     println!("Comment ID: {}", comment.id);
     println!("Created by: {}", comment.created_user.name);
@@ -4,6 +4,8 @@
 //! All functions require the `issue_writable` feature flag.
 
 use crate::commands::common::CliResult;
+#[cfg(feature = "issue_writable")]
+use crate::commands::common::markdown_for_project;
 use backlog_api_client::ProjectIdOrKey;
 use backlog_api_client::client::BacklogApiClient;
 use backlog_core::IssueKey;
@@ -40,7 +42,11 @@ pub async fn create(
         .priority_id(PriorityId::new(args.priority_id));
 
     if let Some(description) = &args.description {
-        builder.description(description);
+        if args.markdown {
+            builder.description(markdown_for_project(client, project_id, description).await?);
+        } else {
+            builder.description(description);
+        }
     }
 
     if let Some(assignee_id) = args.assignee_id {
@@ -98,9 +104,17 @@ pub async fn create(
         builder.custom_fields(fields);
     }
 
-    let params = builder.build()?;
+    let mut params = builder.build()?;
+
+    let upload = super::attachments::upload_files(client, &args.attach).await?;
+    if !upload.uploaded().is_empty() {
+        params
+            .attachment_id
+            .get_or_insert_with(Vec::new)
+            .extend(upload.ids());
+    }
 
-    let issue = client.issue().add_issue(params).await?;
+    let issue = super::attachments::link_uploaded(&upload, client.issue().add_issue(params).await)?;
     println!("Issue created successfully!");
     println!("Issue Key: {}", issue.issue_key);
     println!("Issue ID: {}", issue.id);
@@ -123,15 +137,27 @@ pub async fn update(
         .issue_id_or_key
         .parse::<backlog_api_client::IssueIdOrKey>()?;
 
+    // Markdown is converted for the project the issue belongs to
+    let convert = async |text: &str| -> CliResult<String> {
+        if !args.markdown {
+            return Ok(text.to_string());
+        }
+        let issue = client
+            .issue()
+            .get_issue(backlog_issue::GetIssueParams::new(issue_id_or_key.clone()))
+            .await?;
+        markdown_for_project(client, issue.project_id, text).await
+    };
+
     let mut builder = UpdateIssueParamsBuilder::default();
-    builder.issue_id_or_key(issue_id_or_key);
+    builder.issue_id_or_key(issue_id_or_key.clone());
 
     if let Some(summary) = &args.summary {
         builder.summary(summary);
     }
 
     if let Some(description) = &args.description {
-        builder.description(description);
+        builder.description(convert(description).await?);
     }
 
     if let Some(issue_type_id) = args.issue_type_id {
@@ -155,7 +181,7 @@ pub async fn update(
     }
 
     if let Some(comment) = &args.comment {
-        builder.comment(comment);
+        builder.comment(convert(comment).await?);
     }
 
     // Handle custom fields
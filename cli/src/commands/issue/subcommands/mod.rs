@@ -7,10 +7,17 @@
 //! - `attachments`: Attachment management operations
 //! - `shared_files`: Shared file linking operations
 //! - `participants`: Participant listing operations
+//! - `view`: Full thread view combining the above
+//! - `tree`: Parent/child trees with roll-ups and DOT/Mermaid export
+//! - `branch`: Local git branches named after issues (require issue_writable)
 
 pub mod attachments;
+#[cfg(feature = "issue_writable")]
+pub mod branch;
 pub mod comments;
 pub mod crud;
 pub mod list;
 pub mod participants;
 pub mod shared_files;
+pub mod tree;
+pub mod view;
@@ -0,0 +1,239 @@
+//! Parent/child issue trees
+//!
+//! Renders the hierarchy below an issue, or of a whole milestone or project,
+//! with status and hour roll-ups, as text or as Graphviz DOT / Mermaid.
+
+use crate::commands::common::CliResult;
+use crate::commands::issue::args::{IssueTreeArgs, TreeFormat};
+use anyhow::Context;
+use backlog_api_client::client::BacklogApiClient;
+use backlog_api_client::{GetIssueListParamsBuilder, IssueIdOrKey};
+use backlog_core::identifier::{Identifier, MilestoneId, ProjectId};
+use backlog_issue::models::{IssueRollup, IssueTreeNode, ParentChildCondition};
+use std::fmt::Write;
+
+/// Show the parent/child tree of an issue, milestone or project
+pub async fn tree(client: &BacklogApiClient, args: IssueTreeArgs) -> CliResult<()> {
+    let trees = if let Some(issue_id_or_key) = &args.issue_id_or_key {
+        let parsed: IssueIdOrKey = issue_id_or_key
+            .parse()
+            .with_context(|| format!("Failed to parse issue_id_or_key '{issue_id_or_key}'"))?;
+        vec![client.issue().get_issue_subtree(parsed).await?]
+    } else {
+        let mut builder = GetIssueListParamsBuilder::default();
+        builder.sort("created").order("asc");
+        if let Some(milestone_id) = args.milestone {
+            builder.milestone_id(vec![MilestoneId::new(milestone_id)]);
+        }
+        if let Some(project_id) = args.project_id {
+            builder.project_id(vec![ProjectId::new(project_id)]);
+            if args.milestone.is_none() {
+                // A whole project is mostly standalone issues; start from parents
+                builder.parent_child_condition(ParentChildCondition::ParentIssue);
+            }
+        }
+        client.issue().get_issue_tree(builder.build()?).await?
+    };
+
+    match args.format {
+        TreeFormat::Text => print_text(&trees),
+        TreeFormat::Dot => print!("{}", to_dot(&trees)),
+        TreeFormat::Mermaid => print!("{}", to_mermaid(&trees)),
+    }
+
+    Ok(())
+}
+
+fn print_text(trees: &[IssueTreeNode]) {
+    if trees.is_empty() {
+        println!("No issues found");
+        return;
+    }
+
+    for tree in trees {
+        println!("{}", describe(tree));
+        print_children(&tree.children, "");
+    }
+
+    if trees.len() > 1 {
+        let total = trees.iter().map(IssueTreeNode::rollup).fold(
+            IssueRollup::default(),
+            |mut total, rollup| {
+                total.issues += rollup.issues;
+                total.closed += rollup.closed;
+                total.estimated_hours += rollup.estimated_hours;
+                total.actual_hours += rollup.actual_hours;
+                total
+            },
+        );
+        println!("\nTotal: {}", format_rollup(&total));
+    }
+}
+
+fn print_children(nodes: &[IssueTreeNode], prefix: &str) {
+    for (i, node) in nodes.iter().enumerate() {
+        let last = i + 1 == nodes.len();
+        let (branch, indent) = if last {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        println!("{prefix}{branch}{}", describe(node));
+        print_children(&node.children, &format!("{prefix}{indent}"));
+    }
+}
+
+/// One line per issue; issues with children show their subtree totals
+fn describe(node: &IssueTreeNode) -> String {
+    let issue = &node.issue;
+    let mut line = format!(
+        "{} {} [{}]",
+        issue.issue_key, issue.summary, issue.status.name
+    );
+    if node.children.is_empty() {
+        if let Some(hours) = issue.estimated_hours {
+            let _ = write!(line, " est {hours:.1}h");
+        }
+        if let Some(hours) = issue.actual_hours {
+            let _ = write!(line, " act {hours:.1}h");
+        }
+    } else {
+        let _ = write!(line, " — {}", format_rollup(&node.rollup()));
+    }
+    line
+}
+
+fn format_rollup(rollup: &IssueRollup) -> String {
+    format!(
+        "{}/{} closed ({:.0}%), est {:.1}h, act {:.1}h",
+        rollup.closed,
+        rollup.issues,
+        rollup.progress() * 100.0,
+        rollup.estimated_hours,
+        rollup.actual_hours
+    )
+}
+
+fn node_label(node: &IssueTreeNode) -> (String, String) {
+    let issue = &node.issue;
+    let title = format!("{}: {}", issue.issue_key, issue.summary);
+    let status = if node.children.is_empty() {
+        issue.status.name.clone()
+    } else {
+        let rollup = node.rollup();
+        format!(
+            "{} · {}/{} closed · est {:.1}h",
+            issue.status.name, rollup.closed, rollup.issues, rollup.estimated_hours
+        )
+    };
+    (title, status)
+}
+
+/// Render the trees as a Graphviz DOT digraph
+fn to_dot(trees: &[IssueTreeNode]) -> String {
+    fn escape(text: &str) -> String {
+        text.replace('\\', "\\\\").replace('"', "\\\"")
+    }
+
+    fn walk(node: &IssueTreeNode, out: &mut String) {
+        let id = node.issue.id.value();
+        let (title, status) = node_label(node);
+        let _ = writeln!(
+            out,
+            "  i{id} [label=\"{}\\n{}\"];",
+            escape(&title),
+            escape(&status)
+        );
+        for child in &node.children {
+            let _ = writeln!(out, "  i{id} -> i{};", child.issue.id.value());
+            walk(child, out);
+        }
+    }
+
+    let mut out = String::from("digraph issues {\n  rankdir=LR;\n  node [shape=box];\n");
+    for tree in trees {
+        walk(tree, &mut out);
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Render the trees as a Mermaid flowchart
+fn to_mermaid(trees: &[IssueTreeNode]) -> String {
+    fn escape(text: &str) -> String {
+        text.replace('"', "#quot;")
+            .replace('<', "#lt;")
+            .replace('>', "#gt;")
+    }
+
+    fn walk(node: &IssueTreeNode, out: &mut String) {
+        let id = node.issue.id.value();
+        let (title, status) = node_label(node);
+        let _ = writeln!(
+            out,
+            "  i{id}[\"{}<br/>{}\"]",
+            escape(&title),
+            escape(&status)
+        );
+        for child in &node.children {
+            let _ = writeln!(out, "  i{id} --> i{}", child.issue.id.value());
+            walk(child, out);
+        }
+    }
+
+    let mut out = String::from("flowchart TD\n");
+    for tree in trees {
+        walk(tree, &mut out);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backlog_issue::models::Issue;
+    use serde_json::json;
+
+    fn issue(id: u32, parent: Option<u32>, summary: &str) -> Issue {
+        serde_json::from_value(json!({
+            "id": id, "projectId": 1, "issueKey": format!("BLG-{id}"), "keyId": id,
+            "summary": summary, "description": "",
+            "issueType": {"id": 1, "projectId": 1, "name": "Task", "color": "#7ea800", "displayOrder": 0},
+            "status": {"id": 1, "projectId": 1, "name": "Open", "color": "#ed8077", "displayOrder": 1000},
+            "category": [], "versions": [], "milestone": [],
+            "estimatedHours": 2.0, "parentIssueId": parent,
+            "createdUser": {"id": 1, "userId": "john", "name": "John", "roleType": 1, "mailAddress": "john@example.com"},
+            "created": "2024-03-14T06:35:39Z",
+            "updated": "2024-04-13T06:35:39Z"
+        }))
+        .unwrap()
+    }
+
+    fn sample() -> Vec<IssueTreeNode> {
+        IssueTreeNode::build(vec![
+            issue(1, None, "Epic \"A\""),
+            issue(2, Some(1), "Child <one>"),
+        ])
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let dot = to_dot(&sample());
+        assert!(dot.starts_with("digraph issues {"));
+        assert!(
+            dot.contains(
+                "  i1 [label=\"BLG-1: Epic \\\"A\\\"\\nOpen · 0/2 closed · est 4.0h\"];\n"
+            )
+        );
+        assert!(dot.contains("  i1 -> i2;\n"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_to_mermaid() {
+        let mermaid = to_mermaid(&sample());
+        assert!(mermaid.starts_with("flowchart TD\n"));
+        assert!(mermaid.contains("  i2[\"BLG-2: Child #lt;one#gt;<br/>Open\"]\n"));
+        assert!(mermaid.contains("  i1 --> i2\n"));
+    }
+}
@@ -0,0 +1,228 @@
+//! Full thread view of an issue
+//!
+//! Combines the issue body, every comment with its change logs, attachments,
+//! shared files, child issues and related pull requests into one output.
+
+use crate::commands::common::{CliResult, format_bytes, open_in_browser};
+use anyhow::Context;
+use backlog_api_client::client::BacklogApiClient;
+use backlog_api_client::{GetIssueListParamsBuilder, IssueIdOrKey};
+use backlog_core::identifier::{Identifier, IssueId};
+use backlog_issue::GetIssueParams;
+use backlog_issue::models::{Comment, Issue};
+
+/// Maximum number of child issues the API returns per request
+const CHILD_PAGE_SIZE: u32 = 100;
+
+/// Show an issue with its comments, change logs, files, children and pull requests
+pub async fn view(client: &BacklogApiClient, issue_id_or_key: &str, web: bool) -> CliResult<()> {
+    let parsed_issue_id_or_key: IssueIdOrKey = issue_id_or_key
+        .parse()
+        .with_context(|| format!("Failed to parse issue_id_or_key '{issue_id_or_key}'"))?;
+
+    if web {
+        let issue_key = match parsed_issue_id_or_key {
+            IssueIdOrKey::Key(key) => key,
+            IssueIdOrKey::Id(_) => {
+                client
+                    .issue()
+                    .get_issue(GetIssueParams::new(parsed_issue_id_or_key))
+                    .await?
+                    .issue_key
+            }
+        };
+        let url = client.base_url().join(&format!("view/{issue_key}"))?;
+        println!("Opening {url}");
+        return open_in_browser(url.as_str());
+    }
+
+    let issue = client
+        .issue()
+        .get_issue(GetIssueParams::new(parsed_issue_id_or_key))
+        .await?;
+    let comments = client.issue().get_all_comments(issue.id).await?;
+    let children = fetch_children(client, issue.id).await?;
+
+    print_header(client, &issue);
+
+    println!("\n── Description ──");
+    if issue.description.trim().is_empty() {
+        println!("(no description)");
+    } else {
+        println!("{}", issue.description.trim_end());
+    }
+
+    if !issue.attachments.is_empty() {
+        println!("\n── Attachments ({}) ──", issue.attachments.len());
+        for attachment in &issue.attachments {
+            println!(
+                "  [{}] {} ({})",
+                attachment.id.value(),
+                attachment.name,
+                format_bytes(attachment.size)
+            );
+        }
+    }
+
+    if !issue.shared_files.is_empty() {
+        println!("\n── Shared files ({}) ──", issue.shared_files.len());
+        for file in &issue.shared_files {
+            println!(
+                "  [{}] {}/{}",
+                file.id.value(),
+                file.dir.trim_end_matches('/'),
+                file.name
+            );
+        }
+    }
+
+    if !children.is_empty() {
+        println!("\n── Child issues ({}) ──", children.len());
+        for child in &children {
+            let assignee = child
+                .assignee
+                .as_ref()
+                .map(|u| format!(" @{}", u.name))
+                .unwrap_or_default();
+            println!(
+                "  {} [{}] {}{assignee}",
+                child.issue_key, child.status.name, child.summary
+            );
+        }
+    }
+
+    #[cfg(feature = "git")]
+    print_pull_requests(client, &issue).await?;
+
+    println!("\n── Comments ({}) ──", comments.len());
+    if comments.is_empty() {
+        println!("(no comments)");
+    }
+    for comment in &comments {
+        print_comment(comment);
+    }
+
+    Ok(())
+}
+
+fn print_header(client: &BacklogApiClient, issue: &Issue) {
+    println!("{} {}", issue.issue_key, issue.summary);
+
+    let mut fields = vec![
+        format!("Status: {}", issue.status.name),
+        format!("Type: {}", issue.issue_type.name),
+    ];
+    if let Some(priority) = &issue.priority {
+        fields.push(format!("Priority: {}", priority.name));
+    }
+    fields.push(format!(
+        "Assignee: {}",
+        issue
+            .assignee
+            .as_ref()
+            .map(|u| u.name.as_str())
+            .unwrap_or("(unassigned)")
+    ));
+    println!("{}", fields.join(" | "));
+
+    if !issue.milestone.is_empty() {
+        let names: Vec<&str> = issue.milestone.iter().map(|m| m.name.as_str()).collect();
+        println!("Milestone: {}", names.join(", "));
+    }
+    if let Some(due_date) = &issue.due_date {
+        println!("Due: {due_date}");
+    }
+    if let Some(parent_issue_id) = issue.parent_issue_id {
+        println!("Parent issue ID: {parent_issue_id}");
+    }
+    println!("Created: {} by {}", issue.created, issue.created_user.name);
+    match &issue.updated_user {
+        Some(user) => println!("Updated: {} by {}", issue.updated, user.name),
+        None => println!("Updated: {}", issue.updated),
+    }
+    if let Ok(url) = client.base_url().join(&format!("view/{}", issue.issue_key)) {
+        println!("URL: {url}");
+    }
+}
+
+fn print_comment(comment: &Comment) {
+    let stars = if comment.stars.is_empty() {
+        String::new()
+    } else {
+        format!(" ⭐{}", comment.stars.len())
+    };
+    println!(
+        "\n#{} {} · {}{stars}",
+        comment.id.value(),
+        comment.created_user.name,
+        comment.created.format("%Y-%m-%d %H:%M")
+    );
+    for change in &comment.change_log {
+        println!("  • {change}");
+    }
+    if let Some(content) = comment.content.as_deref().filter(|c| !c.trim().is_empty()) {
+        for line in content.trim_end().lines() {
+            println!("  {line}");
+        }
+    }
+}
+
+/// Fetch every issue whose parent is `issue_id`
+async fn fetch_children(client: &BacklogApiClient, issue_id: IssueId) -> CliResult<Vec<Issue>> {
+    let mut children = Vec::new();
+    loop {
+        let params = GetIssueListParamsBuilder::default()
+            .parent_issue_id(vec![issue_id])
+            .count(CHILD_PAGE_SIZE)
+            .offset(children.len() as u32)
+            .build()?;
+        let page = client.issue().get_issue_list(params).await?;
+        let page_len = page.len();
+        children.extend(page);
+        if page_len < CHILD_PAGE_SIZE as usize {
+            return Ok(children);
+        }
+    }
+}
+
+/// Print pull requests in the issue's project whose related issue is this one
+#[cfg(feature = "git")]
+async fn print_pull_requests(client: &BacklogApiClient, issue: &Issue) -> CliResult<()> {
+    use backlog_api_client::{GetPullRequestListParams, GetRepositoryListParams};
+
+    let repositories = client
+        .git()
+        .get_repository_list(GetRepositoryListParams::new(issue.project_id))
+        .await?;
+
+    let mut lines = Vec::new();
+    for repository in &repositories {
+        let params = GetPullRequestListParams::new(issue.project_id, repository.id)
+            .issue_ids(vec![issue.id])
+            .count(100);
+        let pull_requests = client.git().get_pull_request_list(params).await?;
+        for pr in pull_requests
+            .iter()
+            .filter(|pr| pr.related_issue.as_ref().is_some_and(|i| i.id == issue.id))
+        {
+            lines.push(format!(
+                "  {}#{} [{}] {} ({} → {})",
+                repository.name,
+                pr.number.value(),
+                pr.status.name,
+                pr.summary,
+                pr.branch,
+                pr.base
+            ));
+        }
+    }
+
+    if !lines.is_empty() {
+        println!("\n── Pull requests ({}) ──", lines.len());
+        for line in lines {
+            println!("{line}");
+        }
+    }
+
+    Ok(())
+}
@@ -3,12 +3,24 @@ pub mod common;
 #[cfg(feature = "project")]
 pub mod activity;
 
+#[cfg(feature = "project")]
+pub mod completions;
+
+#[cfg(feature = "digest")]
+pub mod digest;
+
 #[cfg(feature = "document")]
 pub mod document;
 
+#[cfg(feature = "file")]
+pub mod file;
+
 #[cfg(feature = "issue")]
 pub mod issue;
 
+#[cfg(feature = "notifications")]
+pub mod notifications;
+
 #[cfg(feature = "project")]
 pub mod project;
 
@@ -16,6 +28,10 @@ pub mod project;
 pub mod git;
 #[cfg(feature = "rate-limit")]
 pub mod rate_limit;
+#[cfg(feature = "report")]
+pub mod report;
+#[cfg(feature = "search")]
+pub mod search;
 #[cfg(feature = "space")]
 pub mod space;
 #[cfg(feature = "star")]
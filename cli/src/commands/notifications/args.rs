@@ -0,0 +1,88 @@
+use clap::{Parser, Subcommand};
+
+#[cfg(feature = "user_writable")]
+use clap::{ArgGroup, Args, ValueEnum};
+
+#[derive(Parser)]
+pub struct NotificationsArgs {
+    #[clap(subcommand)]
+    pub command: Option<NotificationsCommands>,
+
+    /// Include notifications already read
+    #[clap(long)]
+    pub all: bool,
+
+    /// Number of latest notifications fetched
+    #[clap(long, default_value_t = 100, value_parser = clap::value_parser!(u8).range(1..=100))]
+    pub count: u8,
+
+    /// Keep running and print new notifications as they arrive
+    ///
+    /// Without an earlier listing, the notifications already there are only remembered.
+    #[clap(long)]
+    pub watch: bool,
+
+    /// Seconds between polls with --watch
+    #[clap(long, default_value_t = 30)]
+    pub interval: u64,
+
+    /// Shell command run for each new notification with --watch, e.g. a desktop notifier
+    /// [default: $BLG_NOTIFY_COMMAND]
+    #[clap(long)]
+    pub notify_command: Option<String>,
+
+    /// Output as JSON
+    #[clap(long)]
+    pub json: bool,
+}
+
+#[derive(Subcommand)]
+pub enum NotificationsCommands {
+    /// Open a notification in the browser by its inbox number
+    Open {
+        /// Number shown in the last `blg notifications` listing
+        index: usize,
+    },
+    /// Mark notifications as read by inbox number or filter
+    #[cfg(feature = "user_writable")]
+    Read(ReadArgs),
+    /// Mark all notifications as read
+    #[cfg(feature = "user_writable")]
+    ReadAll,
+}
+
+#[cfg(feature = "user_writable")]
+#[derive(Args)]
+#[clap(group(
+    ArgGroup::new("selection")
+        .required(true)
+        .multiple(true)
+        .args(["indexes", "reason", "project", "issue"])
+))]
+pub struct ReadArgs {
+    /// Numbers shown in the last `blg notifications` listing
+    pub indexes: Vec<usize>,
+
+    /// All unread notifications with this reason
+    #[clap(long, value_enum)]
+    pub reason: Option<ReasonArg>,
+
+    /// All unread notifications of this project key
+    #[clap(long)]
+    pub project: Option<String>,
+
+    /// All unread notifications about this issue key
+    #[clap(long)]
+    pub issue: Option<String>,
+}
+
+#[cfg(feature = "user_writable")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReasonArg {
+    Mentioned,
+    Assigned,
+    Commented,
+    Created,
+    Updated,
+    Other,
+}
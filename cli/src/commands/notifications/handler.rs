@@ -0,0 +1,16 @@
+use super::args::{NotificationsArgs, NotificationsCommands};
+use super::subcommands::{inbox, watch};
+use crate::commands::common::CliResult;
+use backlog_api_client::client::BacklogApiClient;
+
+pub async fn execute(client: &BacklogApiClient, mut args: NotificationsArgs) -> CliResult<()> {
+    match args.command.take() {
+        None if args.watch => watch::watch(client, &args).await,
+        None => inbox::list(client, &args).await,
+        Some(NotificationsCommands::Open { index }) => inbox::open(client, index).await,
+        #[cfg(feature = "user_writable")]
+        Some(NotificationsCommands::Read(read_args)) => inbox::read(client, read_args).await,
+        #[cfg(feature = "user_writable")]
+        Some(NotificationsCommands::ReadAll) => inbox::read_all(client).await,
+    }
+}
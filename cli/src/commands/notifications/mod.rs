@@ -0,0 +1,7 @@
+pub mod args;
+mod handler;
+mod state;
+mod subcommands;
+
+pub use args::NotificationsArgs;
+pub use handler::execute;
@@ -0,0 +1,110 @@
+//! Locally kept inbox state
+//!
+//! Remembers which notifications were already shown, so new ones can be
+//! highlighted, and the order of the last listing, so its numbers can be
+//! used by `open` and `read`.
+
+use crate::commands::common::{CliResult, cache_dir};
+use anyhow::Context;
+use backlog_api_client::client::BacklogApiClient;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// Number of notification IDs remembered as seen
+const SEEN_CAPACITY: usize = 2000;
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct InboxState {
+    /// Notification IDs already shown
+    seen: BTreeSet<u32>,
+    /// Notification IDs of the last listing, in the order they were numbered
+    listing: Vec<u32>,
+}
+
+impl InboxState {
+    /// State file of the client's space
+    pub(crate) fn path(client: &BacklogApiClient) -> CliResult<PathBuf> {
+        let host = client.base_url().host_str().unwrap_or("default");
+        Ok(cache_dir()?.join(format!("notifications-{host}.json")))
+    }
+
+    /// Load the state, starting empty if there is none yet
+    pub(crate) fn load(path: &Path) -> CliResult<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => serde_json::from_str(&text)
+                .with_context(|| format!("Failed to parse {}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    pub(crate) fn save(&self, path: &Path) -> CliResult<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    pub(crate) fn is_seen(&self, id: u32) -> bool {
+        self.seen.contains(&id)
+    }
+
+    /// Whether nothing was shown yet, as before the first listing or watch
+    pub(crate) fn is_fresh(&self) -> bool {
+        self.seen.is_empty()
+    }
+
+    /// Remember `ids` as seen, forgetting the oldest beyond the capacity
+    pub(crate) fn mark_seen(&mut self, ids: impl IntoIterator<Item = u32>) {
+        self.seen.extend(ids);
+        while self.seen.len() > SEEN_CAPACITY {
+            self.seen.pop_first();
+        }
+    }
+
+    pub(crate) fn set_listing(&mut self, ids: Vec<u32>) {
+        self.listing = ids;
+    }
+
+    /// Notification ID listed under the 1-based `index`
+    pub(crate) fn listed(&self, index: usize) -> CliResult<u32> {
+        index
+            .checked_sub(1)
+            .and_then(|position| self.listing.get(position))
+            .copied()
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No notification #{index} in the last listing ({} entries); run `blg notifications` first",
+                    self.listing.len()
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inbox_state_round_trip() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("blg").join("notifications.json");
+        assert_eq!(InboxState::load(&path).unwrap(), InboxState::default());
+
+        let mut state = InboxState::default();
+        assert!(state.is_fresh());
+        state.mark_seen(0..SEEN_CAPACITY as u32 + 5);
+        state.set_listing(vec![42, 7]);
+        state.save(&path).unwrap();
+
+        let state = InboxState::load(&path).unwrap();
+        assert!(!state.is_fresh());
+        assert!(!state.is_seen(4));
+        assert!(state.is_seen(5));
+        assert_eq!(state.listed(2).unwrap(), 7);
+        assert!(state.listed(0).is_err());
+        assert!(state.listed(3).is_err());
+    }
+}
@@ -0,0 +1,573 @@
+//! Unread notifications grouped by what they are about
+
+use crate::commands::common::{CliResult, open_in_browser, truncate_text};
+use crate::commands::notifications::args::NotificationsArgs;
+use crate::commands::notifications::state::InboxState;
+use backlog_api_client::client::BacklogApiClient;
+use backlog_api_client::{GetRepositoryParams, RepositoryIdOrName};
+use backlog_core::User;
+use backlog_core::activity::NotificationReason;
+use backlog_core::identifier::{Identifier, RepositoryId};
+use backlog_user::{GetNotificationsParams, GetOwnUserParams, Notification, NotificationOrder};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+#[cfg(feature = "user_writable")]
+use crate::commands::notifications::args::{ReadArgs, ReasonArg};
+
+/// Characters of a comment shown next to its notification
+const EXCERPT_LENGTH: usize = 60;
+
+/// Why a notification was sent, coarser than [`NotificationReason`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum Reason {
+    Mentioned,
+    Assigned,
+    Commented,
+    Created,
+    Updated,
+    Other,
+}
+
+impl Reason {
+    /// `Mentioned` when the comment names the user, otherwise the notification's own reason
+    pub(crate) fn of(notification: &Notification, me: &User) -> Self {
+        if comment_text(notification).is_some_and(|text| mentions(text, me)) {
+            return Reason::Mentioned;
+        }
+        match notification.reason {
+            NotificationReason::AssignedToIssue | NotificationReason::AssignedToPullRequest => {
+                Reason::Assigned
+            }
+            NotificationReason::IssueCommented
+            | NotificationReason::CommentAddedOnPullRequest
+            | NotificationReason::DocumentCommented
+            | NotificationReason::DocumentCommentReplied => Reason::Commented,
+            NotificationReason::IssueCreated
+            | NotificationReason::IssueMultiCreated
+            | NotificationReason::PullRequestAdded => Reason::Created,
+            NotificationReason::IssueUpdated | NotificationReason::PullRequestUpdated => {
+                Reason::Updated
+            }
+            _ => Reason::Other,
+        }
+    }
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reason::Mentioned => write!(f, "mentioned"),
+            Reason::Assigned => write!(f, "assigned"),
+            Reason::Commented => write!(f, "commented"),
+            Reason::Created => write!(f, "created"),
+            Reason::Updated => write!(f, "updated"),
+            Reason::Other => write!(f, "other"),
+        }
+    }
+}
+
+#[cfg(feature = "user_writable")]
+impl From<ReasonArg> for Reason {
+    fn from(reason: ReasonArg) -> Self {
+        match reason {
+            ReasonArg::Mentioned => Reason::Mentioned,
+            ReasonArg::Assigned => Reason::Assigned,
+            ReasonArg::Commented => Reason::Commented,
+            ReasonArg::Created => Reason::Created,
+            ReasonArg::Updated => Reason::Updated,
+            ReasonArg::Other => Reason::Other,
+        }
+    }
+}
+
+fn comment_text(notification: &Notification) -> Option<&str> {
+    notification
+        .comment
+        .as_ref()
+        .and_then(|comment| comment.content.as_deref())
+        .or_else(|| {
+            notification
+                .pull_request_comment
+                .as_ref()
+                .and_then(|comment| comment.get("content"))
+                .and_then(|content| content.as_str())
+        })
+        .filter(|text| !text.trim().is_empty())
+}
+
+/// Whether `text` contains `@name` or `@userId` of the user
+fn mentions(text: &str, me: &User) -> bool {
+    text.contains(&format!("@{}", me.name))
+        || me
+            .user_id
+            .as_deref()
+            .is_some_and(|user_id| text.contains(&format!("@{user_id}")))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum SubjectKind {
+    Issue,
+    PullRequest,
+    Project,
+}
+
+/// The issue, pull request or project a notification is about
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Subject {
+    pub kind: SubjectKind,
+    pub project: String,
+    /// `BLG-12`, `app#3` or the project key
+    pub key: String,
+    pub title: String,
+    pub url: String,
+}
+
+impl Subject {
+    pub(crate) fn of(
+        notification: &Notification,
+        base_url: &str,
+        repositories: &HashMap<u64, String>,
+    ) -> Self {
+        let base_url = base_url.trim_end_matches('/');
+        let project = notification.project.project_key.to_string();
+        if let Some(issue) = &notification.issue {
+            return Subject {
+                kind: SubjectKind::Issue,
+                project,
+                key: issue.issue_key.to_string(),
+                title: issue.summary.clone(),
+                url: format!("{base_url}/view/{}", issue.issue_key),
+            };
+        }
+        if let Some(pull_request) = &notification.pull_request {
+            let number = pull_request["number"].as_u64().unwrap_or_default();
+            let repository = pull_request["repositoryId"]
+                .as_u64()
+                .map(|id| {
+                    repositories
+                        .get(&id)
+                        .cloned()
+                        .unwrap_or_else(|| id.to_string())
+                })
+                .unwrap_or_default();
+            return Subject {
+                kind: SubjectKind::PullRequest,
+                url: format!("{base_url}/git/{project}/{repository}/pullRequests/{number}"),
+                project,
+                key: format!("{repository}#{number}"),
+                title: pull_request["summary"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+            };
+        }
+        Subject {
+            kind: SubjectKind::Project,
+            url: format!("{base_url}/projects/{project}"),
+            key: project.clone(),
+            title: notification.project.name.clone(),
+            project,
+        }
+    }
+}
+
+/// One notification as listed in the inbox
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct InboxEntry {
+    /// 1-based number used by `open` and `read`
+    pub index: usize,
+    pub id: u32,
+    pub reason: Reason,
+    pub read: bool,
+    /// Not shown by an earlier listing or watch
+    pub new: bool,
+    pub sender: String,
+    pub excerpt: Option<String>,
+    pub created: DateTime<Utc>,
+}
+
+impl InboxEntry {
+    pub(crate) fn of(notification: &Notification, me: &User, state: &InboxState) -> Self {
+        let id = notification.id.value();
+        InboxEntry {
+            index: 0,
+            id,
+            reason: Reason::of(notification, me),
+            read: notification.already_read,
+            new: !state.is_seen(id),
+            sender: notification.sender.name.clone(),
+            excerpt: comment_text(notification).map(|text| {
+                truncate_text(
+                    &text.split_whitespace().collect::<Vec<_>>().join(" "),
+                    EXCERPT_LENGTH,
+                )
+            }),
+            created: notification.created,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct InboxGroup {
+    pub subject: Subject,
+    pub entries: Vec<InboxEntry>,
+}
+
+/// Group notifications (newest first) by subject and number them in display order
+pub(crate) fn build_inbox(
+    notifications: &[Notification],
+    me: &User,
+    state: &InboxState,
+    base_url: &str,
+    repositories: &HashMap<u64, String>,
+) -> Vec<InboxGroup> {
+    let mut groups: Vec<InboxGroup> = Vec::new();
+    for notification in notifications {
+        let subject = Subject::of(notification, base_url, repositories);
+        let entry = InboxEntry::of(notification, me, state);
+        match groups.iter_mut().find(|group| group.subject == subject) {
+            Some(group) => group.entries.push(entry),
+            None => groups.push(InboxGroup {
+                subject,
+                entries: vec![entry],
+            }),
+        }
+    }
+    let entries = groups.iter_mut().flat_map(|group| group.entries.iter_mut());
+    for (index, entry) in entries.enumerate() {
+        entry.index = index + 1;
+    }
+    groups
+}
+
+/// Latest notifications, newest first
+pub(crate) async fn fetch_notifications(
+    client: &BacklogApiClient,
+    count: u8,
+    max_id: Option<u64>,
+) -> CliResult<Vec<Notification>> {
+    let mut params = GetNotificationsParams::new()
+        .with_count(count)
+        .with_order(NotificationOrder::Desc);
+    if let Some(max_id) = max_id {
+        params = params.with_max_id(max_id);
+    }
+    Ok(client.user().get_notifications(params).await?)
+}
+
+pub(crate) async fn myself(client: &BacklogApiClient) -> CliResult<User> {
+    Ok(client.user().get_own_user(GetOwnUserParams::new()).await?)
+}
+
+/// Names of the repositories pull request notifications point to, by ID
+///
+/// Repositories that cannot be read are left out and shown by ID.
+pub(crate) async fn repository_names(
+    client: &BacklogApiClient,
+    notifications: &[Notification],
+) -> HashMap<u64, String> {
+    let wanted: HashSet<(String, u64)> = notifications
+        .iter()
+        .filter_map(|notification| {
+            let id = notification.pull_request.as_ref()?["repositoryId"].as_u64()?;
+            Some((notification.project.project_key.to_string(), id))
+        })
+        .collect();
+
+    let mut names = HashMap::new();
+    for (project, id) in wanted {
+        let Ok(repository_id) = u32::try_from(id) else {
+            continue;
+        };
+        let params = GetRepositoryParams::new(
+            project
+                .parse::<backlog_api_client::ProjectIdOrKey>()
+                .expect("project keys from the API are valid"),
+            RepositoryIdOrName::from(RepositoryId::new(repository_id)),
+        );
+        if let Ok(repository) = client.git().get_repository(params).await {
+            names.insert(id, repository.name);
+        }
+    }
+    names
+}
+
+fn print_inbox(groups: &[InboxGroup]) {
+    for group in groups {
+        let subject = &group.subject;
+        println!("{} {}", subject.key, truncate_text(&subject.title, 60));
+        for entry in &group.entries {
+            let reason = if entry.read {
+                format!("{} (read)", entry.reason)
+            } else {
+                entry.reason.to_string()
+            };
+            let line = format!(
+                "{:>4} {} {:<17} {:<16} {}  {}",
+                entry.index,
+                if entry.new { "●" } else { " " },
+                reason,
+                truncate_text(&entry.sender, 16),
+                entry
+                    .created
+                    .with_timezone(&chrono::Local)
+                    .format("%m-%d %H:%M"),
+                entry.excerpt.as_deref().unwrap_or_default(),
+            );
+            println!("{}", line.trim_end());
+        }
+        println!();
+    }
+}
+
+/// List notifications grouped by subject and remember them as seen
+pub(crate) async fn list(client: &BacklogApiClient, args: &NotificationsArgs) -> CliResult<()> {
+    let path = InboxState::path(client)?;
+    let mut state = InboxState::load(&path)?;
+    let me = myself(client).await?;
+    let mut notifications = fetch_notifications(client, args.count, None).await?;
+    notifications.retain(|notification| args.all || !notification.already_read);
+    let repositories = repository_names(client, &notifications).await;
+    let groups = build_inbox(
+        &notifications,
+        &me,
+        &state,
+        client.base_url().as_str(),
+        &repositories,
+    );
+
+    let ids: Vec<u32> = groups
+        .iter()
+        .flat_map(|group| group.entries.iter().map(|entry| entry.id))
+        .collect();
+    state.mark_seen(ids.iter().copied());
+    state.set_listing(ids);
+    state.save(&path)?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&groups)?);
+        return Ok(());
+    }
+    if groups.is_empty() {
+        println!("No {}notifications", if args.all { "" } else { "unread " });
+        return Ok(());
+    }
+    print_inbox(&groups);
+    println!(
+        "● new since the last listing · open with `blg notifications open <N>`, mark read with `blg notifications read <N>`"
+    );
+    Ok(())
+}
+
+/// Open the notification numbered `index` in the last listing
+pub(crate) async fn open(client: &BacklogApiClient, index: usize) -> CliResult<()> {
+    let state = InboxState::load(&InboxState::path(client)?)?;
+    let id = state.listed(index)?;
+    let notifications = fetch_notifications(client, 100, None).await?;
+    let notification = notifications
+        .iter()
+        .find(|notification| notification.id.value() == id)
+        .ok_or_else(|| {
+            anyhow::anyhow!("Notification {id} is no longer among the latest 100 notifications")
+        })?;
+    let repositories = repository_names(client, std::slice::from_ref(notification)).await;
+    let subject = Subject::of(notification, client.base_url().as_str(), &repositories);
+    println!("Opening {}", subject.url);
+    open_in_browser(&subject.url)?;
+
+    #[cfg(feature = "user_writable")]
+    if !notification.already_read {
+        client.user().mark_notification_as_read(id).await?;
+    }
+    Ok(())
+}
+
+/// Mark the selected notifications as read
+#[cfg(feature = "user_writable")]
+pub(crate) async fn read(client: &BacklogApiClient, args: ReadArgs) -> CliResult<()> {
+    let state = InboxState::load(&InboxState::path(client)?)?;
+    let mut ids = args
+        .indexes
+        .iter()
+        .map(|&index| state.listed(index))
+        .collect::<CliResult<Vec<_>>>()?;
+
+    if args.reason.is_some() || args.project.is_some() || args.issue.is_some() {
+        let me = myself(client).await?;
+        let reason = args.reason.map(Reason::from);
+        let notifications = fetch_notifications(client, 100, None).await?;
+        ids.extend(
+            notifications
+                .iter()
+                .filter(|notification| !notification.already_read)
+                .filter(|notification| {
+                    reason.is_none_or(|reason| Reason::of(notification, &me) == reason)
+                })
+                .filter(|notification| {
+                    args.project.as_deref().is_none_or(|project| {
+                        notification
+                            .project
+                            .project_key
+                            .to_string()
+                            .eq_ignore_ascii_case(project)
+                    })
+                })
+                .filter(|notification| {
+                    args.issue.as_deref().is_none_or(|key| {
+                        notification.issue.as_ref().is_some_and(|issue| {
+                            issue.issue_key.to_string().eq_ignore_ascii_case(key)
+                        })
+                    })
+                })
+                .map(|notification| notification.id.value()),
+        );
+    }
+    ids.sort_unstable();
+    ids.dedup();
+
+    for &id in &ids {
+        client.user().mark_notification_as_read(id).await?;
+    }
+    println!("Marked {} notification(s) as read", ids.len());
+    Ok(())
+}
+
+/// Mark every unread notification as read and reset the unread count
+#[cfg(feature = "user_writable")]
+pub(crate) async fn read_all(client: &BacklogApiClient) -> CliResult<()> {
+    let mut marked = 0;
+    let mut max_id = None;
+    loop {
+        let page = fetch_notifications(client, 100, max_id).await?;
+        let unread: Vec<u32> = page
+            .iter()
+            .filter(|notification| !notification.already_read)
+            .map(|notification| notification.id.value())
+            .collect();
+        for &id in &unread {
+            client.user().mark_notification_as_read(id).await?;
+        }
+        marked += unread.len();
+        // A page without unread notifications does not mean older ones are read
+        if page.len() < 100 {
+            break;
+        }
+        max_id = page
+            .last()
+            .map(|notification| u64::from(notification.id.value()));
+    }
+    client.user().reset_unread_notification_count().await?;
+    println!("Marked {marked} notification(s) as read");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification(
+        id: u32,
+        reason: u8,
+        issue: Option<&str>,
+        comment: Option<&str>,
+    ) -> Notification {
+        let user = |id: u32, name: &str| {
+            serde_json::json!({
+                "id": id, "userId": name.to_lowercase(), "name": name,
+                "roleType": 2, "mailAddress": format!("{}@example.com", name.to_lowercase())
+            })
+        };
+        serde_json::from_value(serde_json::json!({
+            "id": id, "alreadyRead": false, "reason": reason, "resourceAlreadyRead": false,
+            "project": {
+                "id": 1, "projectKey": "BLG", "name": "Backlog", "chartEnabled": true,
+                "subtaskingEnabled": true, "projectLeaderCanEditProjectLeader": false,
+                "useWiki": true, "useFileSharing": true, "useWikiTreeView": false,
+                "useOriginalImageSizeAtWiki": false, "textFormattingRule": "markdown",
+                "archived": false, "displayOrder": 0, "useDevAttributes": true
+            },
+            "issue": issue.map(|key| serde_json::json!({
+                "id": 10, "projectId": 1, "issueKey": key, "keyId": 12,
+                "summary": "Login fails", "description": "",
+                "issueType": {"id": 1, "projectId": 1, "name": "Bug", "color": "#990000", "displayOrder": 0},
+                "status": {"id": 1, "projectId": 1, "name": "Open", "color": "#ed8077", "displayOrder": 0},
+                "category": [], "versions": [], "milestone": [],
+                "createdUser": user(2, "Mary"),
+                "created": "2024-01-01T00:00:00Z", "updated": "2024-01-01T00:00:00Z"
+            })),
+            "comment": comment.map(|content| serde_json::json!({
+                "id": 100 + id, "content": content, "changeLog": [],
+                "createdUser": user(2, "Mary"), "created": "2024-01-02T00:00:00Z",
+                "updated": "2024-01-02T00:00:00Z", "stars": [], "notifications": []
+            })),
+            "pullRequest": if issue.is_none() {
+                serde_json::json!({"id": 7, "repositoryId": 5, "number": 3, "summary": "Fix login"})
+            } else {
+                serde_json::Value::Null
+            },
+            "sender": user(2, "Mary"),
+            "created": "2024-01-02T00:00:00Z"
+        }))
+        .unwrap()
+    }
+
+    fn me() -> User {
+        serde_json::from_value(serde_json::json!({
+            "id": 1, "userId": "john", "name": "John", "roleType": 1, "mailAddress": "john@example.com"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_build_inbox() {
+        let notifications = vec![
+            notification(5, 2, Some("BLG-12"), Some("@John could you check?")),
+            notification(4, 11, None, None),
+            notification(3, 1, Some("BLG-12"), None),
+        ];
+        let mut state = InboxState::default();
+        state.mark_seen([3]);
+        let repositories = HashMap::from([(5, "app".to_string())]);
+
+        let groups = build_inbox(
+            &notifications,
+            &me(),
+            &state,
+            "https://example.backlog.com/",
+            &repositories,
+        );
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].subject.key, "BLG-12");
+        assert_eq!(
+            groups[0].subject.url,
+            "https://example.backlog.com/view/BLG-12"
+        );
+        let summary: Vec<_> = groups
+            .iter()
+            .flat_map(|group| &group.entries)
+            .map(|entry| (entry.index, entry.id, entry.reason, entry.new))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (1, 5, Reason::Mentioned, true),
+                (2, 3, Reason::Assigned, false),
+                (3, 4, Reason::Commented, true),
+            ]
+        );
+        assert_eq!(groups[1].subject.key, "app#3");
+        assert_eq!(
+            groups[1].subject.url,
+            "https://example.backlog.com/git/BLG/app/pullRequests/3"
+        );
+    }
+}
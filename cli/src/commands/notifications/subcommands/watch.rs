@@ -0,0 +1,126 @@
+//! Print notifications as they arrive
+
+use super::inbox::{InboxEntry, Subject, fetch_notifications, myself, repository_names};
+use crate::commands::common::{CliResult, shell_command};
+use crate::commands::notifications::args::NotificationsArgs;
+use crate::commands::notifications::state::InboxState;
+use backlog_api_client::client::BacklogApiClient;
+use backlog_core::identifier::Identifier;
+use serde::Serialize;
+use std::time::Duration;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WatchEvent<'a> {
+    subject: &'a Subject,
+    #[serde(flatten)]
+    entry: &'a InboxEntry,
+}
+
+/// Run the notify command with the notification in its environment
+async fn notify(command: &str, subject: &Subject, entry: &InboxEntry) -> CliResult<()> {
+    let title = format!("{} {} {}", entry.sender, entry.reason, subject.key);
+    let body = entry.excerpt.as_deref().unwrap_or(&subject.title);
+    let status = shell_command(command)
+        .env("BLG_NOTIFICATION_TITLE", &title)
+        .env("BLG_NOTIFICATION_BODY", body)
+        .env("BLG_NOTIFICATION_URL", &subject.url)
+        .env("BLG_NOTIFICATION_ID", entry.id.to_string())
+        .status()
+        .await?;
+    if !status.success() {
+        anyhow::bail!("'{command}' exited with {status}");
+    }
+    Ok(())
+}
+
+/// Poll for notifications not seen before and print them until interrupted
+pub(crate) async fn watch(client: &BacklogApiClient, args: &NotificationsArgs) -> CliResult<()> {
+    let notify_command = args
+        .notify_command
+        .clone()
+        .or_else(|| std::env::var("BLG_NOTIFY_COMMAND").ok())
+        .filter(|command| !command.is_empty());
+    let path = InboxState::path(client)?;
+    let me = myself(client).await?;
+    let interval = Duration::from_secs(args.interval.max(1));
+    eprintln!(
+        "Watching notifications every {}s (Ctrl+C to stop)",
+        interval.as_secs()
+    );
+
+    let mut first_round = true;
+    loop {
+        let notifications = match fetch_notifications(client, args.count, None).await {
+            Ok(notifications) => notifications,
+            Err(e) => {
+                eprintln!("Warning: polling failed, retrying: {e:#}");
+                tokio::time::sleep(interval).await;
+                continue;
+            }
+        };
+        // Reloaded every round so listings made meanwhile are kept
+        let mut state = InboxState::load(&path)?;
+        // Without any state every notification would count as new; remember
+        // the existing ones instead of announcing them all
+        if std::mem::take(&mut first_round) && state.is_fresh() {
+            state.mark_seen(
+                notifications
+                    .iter()
+                    .map(|notification| notification.id.value()),
+            );
+            state.save(&path)?;
+            eprintln!(
+                "Remembered {} existing notification(s); showing new ones from now on",
+                notifications.len()
+            );
+            tokio::time::sleep(interval).await;
+            continue;
+        }
+        let mut fresh: Vec<_> = notifications
+            .into_iter()
+            .filter(|notification| !state.is_seen(notification.id.value()))
+            .filter(|notification| args.all || !notification.already_read)
+            .collect();
+        fresh.reverse();
+
+        let repositories = repository_names(client, &fresh).await;
+        for notification in &fresh {
+            let subject = Subject::of(notification, client.base_url().as_str(), &repositories);
+            let entry = InboxEntry::of(notification, &me, &state);
+            if args.json {
+                let event = WatchEvent {
+                    subject: &subject,
+                    entry: &entry,
+                };
+                println!("{}", serde_json::to_string(&event)?);
+            } else {
+                println!(
+                    "{}  {:<10} {:<16} {} {}{}",
+                    entry
+                        .created
+                        .with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M:%S"),
+                    entry.reason,
+                    entry.sender,
+                    subject.key,
+                    subject.title,
+                    entry
+                        .excerpt
+                        .as_deref()
+                        .map_or_else(String::new, |excerpt| format!(" — {excerpt}")),
+                );
+            }
+            if let Some(command) = &notify_command
+                && let Err(e) = notify(command, &subject, &entry).await
+            {
+                eprintln!("Warning: notify command failed: {e:#}");
+            }
+        }
+        if !fresh.is_empty() {
+            state.mark_seen(fresh.iter().map(|notification| notification.id.value()));
+            state.save(&path)?;
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
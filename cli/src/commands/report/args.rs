@@ -0,0 +1,140 @@
+use clap::Parser;
+
+#[derive(Parser)]
+pub struct ReportArgs {
+    #[clap(subcommand)]
+    pub command: ReportCommands,
+}
+
+#[derive(Parser)]
+pub enum ReportCommands {
+    /// Remaining work per day of a milestone, rebuilt from status change logs
+    Burndown {
+        /// Milestone ID, or its name together with --project
+        #[clap(long)]
+        milestone: String,
+        /// Project ID or key (required to look up a milestone by name)
+        #[clap(long)]
+        project: Option<String>,
+        /// What to measure
+        #[clap(long, value_enum, default_value_t = Metric::Hours)]
+        metric: Metric,
+        /// Output format
+        #[clap(long, value_enum, default_value_t = ReportFormat::Table)]
+        format: ReportFormat,
+        /// Number of issue histories fetched at the same time
+        #[clap(short = 'j', long, default_value_t = 4, value_parser = clap::value_parser!(u8).range(1..=16))]
+        concurrency: u8,
+    },
+    /// Committed and completed work of the last finished milestones, with
+    /// completion rebuilt from status change logs
+    Velocity {
+        /// Project ID or key
+        #[clap(long)]
+        project: String,
+        /// Number of finished milestones to include
+        #[clap(long, default_value_t = 5)]
+        last: usize,
+        /// What to measure
+        #[clap(long, value_enum, default_value_t = Metric::Hours)]
+        metric: Metric,
+        /// Output format
+        #[clap(long, value_enum, default_value_t = ReportFormat::Table)]
+        format: ReportFormat,
+        /// Number of issue histories fetched at the same time
+        #[clap(short = 'j', long, default_value_t = 4, value_parser = clap::value_parser!(u8).range(1..=16))]
+        concurrency: u8,
+    },
+    /// Hours logged per user and day, rebuilt from actual hours change logs
+    Timesheet {
+        /// User ID to include (repeatable, defaults to yourself)
+        #[clap(long = "user")]
+        users: Vec<u32>,
+        /// First day of the period (YYYY-MM-DD)
+        #[clap(long)]
+        from: chrono::NaiveDate,
+        /// Last day of the period (YYYY-MM-DD)
+        #[clap(long)]
+        to: chrono::NaiveDate,
+        /// Sum the hours per group instead of listing every entry
+        #[clap(long, value_enum, default_value_t = TimesheetGroup::Entry)]
+        group_by: TimesheetGroup,
+        /// Output format
+        #[clap(long, value_enum, default_value_t = TimesheetFormat::Table)]
+        format: TimesheetFormat,
+        /// Number of issue histories fetched at the same time
+        #[clap(short = 'j', long, default_value_t = 4, value_parser = clap::value_parser!(u8).range(1..=16))]
+        concurrency: u8,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Metric {
+    /// Estimated hours
+    Hours,
+    /// Number of issues
+    Count,
+}
+
+impl Metric {
+    pub fn unit(self) -> &'static str {
+        match self {
+            Metric::Hours => "hours",
+            Metric::Count => "issues",
+        }
+    }
+
+    pub fn format(self, value: f64) -> String {
+        match self {
+            Metric::Hours => format!("{value:.1}"),
+            Metric::Count => format!("{value:.0}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+    /// Table for the terminal
+    Table,
+    /// Comma-separated values
+    Csv,
+    /// Bar chart drawn with text
+    Ascii,
+    /// SVG chart
+    Svg,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum TimesheetGroup {
+    /// One row per day, user and issue
+    Entry,
+    /// Total per user
+    User,
+    /// Total per project
+    Project,
+    /// Total per issue type
+    IssueType,
+    /// Total per combination of categories
+    Category,
+}
+
+impl TimesheetGroup {
+    pub fn title(self) -> &'static str {
+        match self {
+            TimesheetGroup::Entry | TimesheetGroup::User => "User",
+            TimesheetGroup::Project => "Project",
+            TimesheetGroup::IssueType => "Issue Type",
+            TimesheetGroup::Category => "Category",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum TimesheetFormat {
+    /// Table for the terminal
+    Table,
+    /// Comma-separated values
+    Csv,
+    /// JSON array
+    Json,
+}
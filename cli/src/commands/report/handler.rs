@@ -0,0 +1,55 @@
+use super::args::{ReportArgs, ReportCommands};
+use super::subcommands;
+use crate::commands::common::CliResult;
+use backlog_api_client::client::BacklogApiClient;
+
+pub async fn execute(client: &BacklogApiClient, args: ReportArgs) -> CliResult<()> {
+    match args.command {
+        ReportCommands::Burndown {
+            milestone,
+            project,
+            metric,
+            format,
+            concurrency,
+        } => {
+            subcommands::burndown::run(
+                client,
+                &milestone,
+                project.as_deref(),
+                metric,
+                format,
+                concurrency.into(),
+            )
+            .await
+        }
+        ReportCommands::Velocity {
+            project,
+            last,
+            metric,
+            format,
+            concurrency,
+        } => {
+            subcommands::velocity::run(client, &project, last, metric, format, concurrency.into())
+                .await
+        }
+        ReportCommands::Timesheet {
+            users,
+            from,
+            to,
+            group_by,
+            format,
+            concurrency,
+        } => {
+            subcommands::timesheet::run(
+                client,
+                users,
+                from,
+                to,
+                group_by,
+                format,
+                concurrency.into(),
+            )
+            .await
+        }
+    }
+}
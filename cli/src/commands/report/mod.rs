@@ -0,0 +1,6 @@
+mod args;
+mod handler;
+mod subcommands;
+
+pub use args::ReportArgs;
+pub use handler::execute;
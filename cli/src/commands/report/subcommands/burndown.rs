@@ -0,0 +1,240 @@
+//! Remaining work per day of a milestone
+
+use super::data::{self, WorkItem};
+use super::render::{self, Series};
+use crate::commands::common::CliResult;
+use crate::commands::report::args::{Metric, ReportFormat};
+use backlog_api_client::client::BacklogApiClient;
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use prettytable::{Table, row};
+
+/// Remaining and ideal work at the end of one day
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct BurndownPoint {
+    pub date: NaiveDate,
+    /// `None` for days that have not ended yet
+    pub remaining: Option<f64>,
+    pub ideal: f64,
+}
+
+/// Compute the burndown from `start` to `due`
+///
+/// An issue counts towards a day once it was created and stops counting
+/// while it is closed. The ideal line falls linearly from the work at the
+/// end of the first day to zero on the due date.
+pub(crate) fn burndown(
+    items: &[WorkItem],
+    start: NaiveDate,
+    due: NaiveDate,
+    today: NaiveDate,
+    end_of_day: impl Fn(NaiveDate) -> DateTime<Utc>,
+) -> Vec<BurndownPoint> {
+    let days: Vec<NaiveDate> = start.iter_days().take_while(|day| *day <= due).collect();
+    let remaining: Vec<Option<f64>> = days
+        .iter()
+        .map(|day| {
+            if *day > today {
+                return None;
+            }
+            let end = end_of_day(*day);
+            Some(
+                items
+                    .iter()
+                    .filter(|item| item.created.is_none_or(|created| created <= end))
+                    .filter(|item| !item.history.is_closed_at(end))
+                    .map(|item| item.weight)
+                    .sum(),
+            )
+        })
+        .collect();
+
+    let initial = remaining.first().copied().flatten().unwrap_or_default();
+    let last = days.len().saturating_sub(1).max(1) as f64;
+    days.into_iter()
+        .zip(remaining)
+        .enumerate()
+        .map(|(i, (date, remaining))| BurndownPoint {
+            date,
+            remaining,
+            ideal: initial * (1.0 - i as f64 / last).max(0.0),
+        })
+        .collect()
+}
+
+/// Show the burndown of a milestone
+pub async fn run(
+    client: &BacklogApiClient,
+    milestone: &str,
+    project: Option<&str>,
+    metric: Metric,
+    format: ReportFormat,
+    concurrency: usize,
+) -> CliResult<()> {
+    let milestone = data::resolve_milestone(client, milestone, project).await?;
+    let issues = data::milestone_issues(client, &milestone).await?;
+    let closed_name = data::closed_status_name(client, milestone.project_id).await?;
+    let today = Local::now().date_naive();
+
+    let start = data::milestone_date(milestone.start_date)
+        .or_else(|| {
+            issues
+                .iter()
+                .filter_map(data::created_at)
+                .min()
+                .map(|created| created.with_timezone(&Local).date_naive())
+        })
+        .unwrap_or(today);
+    let due = data::milestone_date(milestone.release_due_date).unwrap_or(today);
+    if due < start {
+        anyhow::bail!(
+            "Milestone '{}' ends ({due}) before it starts ({start})",
+            milestone.name
+        );
+    }
+
+    let items: Vec<WorkItem> = data::fetch_comments(client, issues, concurrency)
+        .await?
+        .into_iter()
+        .map(|(issue, comments)| WorkItem::new(&issue, &comments, &closed_name, metric))
+        .collect();
+    let points = burndown(&items, start, due, today, data::end_of_local_day);
+
+    let unit = metric.unit();
+    match format {
+        ReportFormat::Table => {
+            println!(
+                "Burndown of {} ({start} – {due}), {} issues",
+                milestone.name,
+                items.len()
+            );
+            let mut table = Table::new();
+            table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+            table.set_titles(row!["Date", format!("Remaining ({unit})"), "Ideal"]);
+            for point in &points {
+                table.add_row(row![
+                    point.date,
+                    point
+                        .remaining
+                        .map_or_else(String::new, |value| metric.format(value)),
+                    metric.format(point.ideal)
+                ]);
+            }
+            table.printstd();
+        }
+        ReportFormat::Csv => {
+            println!("{}", render::csv_row(&["date", "remaining", "ideal"]));
+            for point in &points {
+                println!(
+                    "{}",
+                    render::csv_row(&[
+                        point.date.to_string(),
+                        point.remaining.map_or_else(String::new, |v| v.to_string()),
+                        point.ideal.to_string(),
+                    ])
+                );
+            }
+        }
+        ReportFormat::Ascii => {
+            println!("Burndown of {} ({unit})", milestone.name);
+            let max = points
+                .iter()
+                .map(|p| p.remaining.unwrap_or_default().max(p.ideal))
+                .fold(0.0, f64::max);
+            for point in &points {
+                match point.remaining {
+                    Some(remaining) => println!(
+                        "{} {} {} (ideal {})",
+                        point.date,
+                        render::ascii_bar(remaining, max),
+                        metric.format(remaining),
+                        metric.format(point.ideal)
+                    ),
+                    None => println!("{} (ideal {})", point.date, metric.format(point.ideal)),
+                }
+            }
+        }
+        ReportFormat::Svg => {
+            let labels: Vec<String> = points
+                .iter()
+                .map(|p| p.date.format("%m-%d").to_string())
+                .collect();
+            print!(
+                "{}",
+                render::svg_line_chart(
+                    &format!("Burndown of {} ({unit})", milestone.name),
+                    &labels,
+                    &[
+                        Series {
+                            name: "Remaining",
+                            color: "#e30000",
+                            dashed: false,
+                            values: points.iter().map(|p| p.remaining).collect(),
+                        },
+                        Series {
+                            name: "Ideal",
+                            color: "#888888",
+                            dashed: true,
+                            values: points.iter().map(|p| Some(p.ideal)).collect(),
+                        },
+                    ],
+                )
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::report::subcommands::data::tests::{issue, status_comment};
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    fn end_of_utc_day(day: NaiveDate) -> DateTime<Utc> {
+        day.and_hms_opt(23, 59, 59).unwrap().and_utc()
+    }
+
+    fn item(
+        id: u32,
+        estimated: f64,
+        created: &str,
+        comments: &[backlog_issue::models::Comment],
+    ) -> WorkItem {
+        let issue = issue(id, 1, Some(estimated), created);
+        WorkItem::new(&issue, comments, "Closed", Metric::Hours)
+    }
+
+    #[test]
+    fn test_burndown_tracks_closing_and_added_scope() {
+        let items = vec![
+            item(
+                1,
+                4.0,
+                "2024-01-01T00:00:00Z",
+                &[status_comment(1, "2024-01-02T12:00:00Z", "Open", "Closed")],
+            ),
+            item(2, 6.0, "2024-01-01T00:00:00Z", &[]),
+            item(3, 2.0, "2024-01-03T09:00:00Z", &[]),
+        ];
+
+        let points = burndown(
+            &items,
+            date("2024-01-01"),
+            date("2024-01-05"),
+            date("2024-01-03"),
+            end_of_utc_day,
+        );
+
+        let remaining: Vec<Option<f64>> = points.iter().map(|p| p.remaining).collect();
+        assert_eq!(
+            remaining,
+            vec![Some(10.0), Some(6.0), Some(8.0), None, None]
+        );
+        let ideal: Vec<f64> = points.iter().map(|p| p.ideal).collect();
+        assert_eq!(ideal, vec![10.0, 7.5, 5.0, 2.5, 0.0]);
+    }
+}
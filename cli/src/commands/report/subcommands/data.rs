@@ -0,0 +1,307 @@
+//! Fetching issues and reconstructing their status over time
+
+use crate::commands::common::CliResult;
+use crate::commands::report::args::Metric;
+use anyhow::Context;
+use backlog_api_client::backlog_project::{GetMilestoneListParams, GetStatusListParams};
+use backlog_api_client::client::BacklogApiClient;
+use backlog_api_client::{GetIssueListParamsBuilder, Milestone, ProjectIdOrKey};
+use backlog_core::identifier::{Identifier, MilestoneId, ProjectId};
+use backlog_issue::models::{CLOSED_STATUS_ID, Comment, Issue};
+use chrono::{DateTime, Local, NaiveDate, TimeZone, Utc};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// Find a milestone by ID, or by name within a project
+///
+/// Without a project the milestone is taken from its issues, since the API
+/// only lists milestones per project.
+pub(crate) async fn resolve_milestone(
+    client: &BacklogApiClient,
+    milestone: &str,
+    project: Option<&str>,
+) -> CliResult<Milestone> {
+    let id = milestone.parse::<u32>().ok();
+
+    if let Some(project) = project {
+        let project_id_or_key = project
+            .parse::<ProjectIdOrKey>()
+            .with_context(|| format!("Failed to parse project '{project}'"))?;
+        let milestones = client
+            .project()
+            .get_version_milestone_list(GetMilestoneListParams::new(project_id_or_key))
+            .await?;
+        return milestones
+            .into_iter()
+            .find(|m| Some(m.id.value()) == id || m.name == milestone)
+            .ok_or_else(|| anyhow::anyhow!("Milestone '{milestone}' not found in {project}"));
+    }
+
+    let Some(id) = id else {
+        anyhow::bail!("Pass --project to look up milestone '{milestone}' by name");
+    };
+    let params = GetIssueListParamsBuilder::default()
+        .milestone_id(vec![MilestoneId::new(id)])
+        .count(1u32)
+        .build()?;
+    client
+        .issue()
+        .get_issue_list(params)
+        .await?
+        .into_iter()
+        .flat_map(|issue| issue.milestone)
+        .find(|m| m.id.value() == id)
+        .ok_or_else(|| anyhow::anyhow!("Milestone {id} not found or has no issues"))
+}
+
+/// Every issue assigned to a milestone
+pub(crate) async fn milestone_issues(
+    client: &BacklogApiClient,
+    milestone: &Milestone,
+) -> CliResult<Vec<Issue>> {
+    let params = GetIssueListParamsBuilder::default()
+        .project_id(vec![milestone.project_id])
+        .milestone_id(vec![milestone.id])
+        .build()?;
+    Ok(client.issue().get_all_issues(params).await?)
+}
+
+/// Name of the built-in "Closed" status, which change logs refer to by name
+pub(crate) async fn closed_status_name(
+    client: &BacklogApiClient,
+    project_id: ProjectId,
+) -> CliResult<String> {
+    let statuses = client
+        .project()
+        .get_status_list(GetStatusListParams::new(project_id))
+        .await?;
+    statuses
+        .into_iter()
+        .find(|status| status.id.value() == CLOSED_STATUS_ID)
+        .map(|status| status.name)
+        .ok_or_else(|| anyhow::anyhow!("Project {project_id} has no Closed status"))
+}
+
+/// Fetch the comments of each issue, a few issues at a time
+pub(crate) async fn fetch_comments(
+    client: &BacklogApiClient,
+    issues: Vec<Issue>,
+    concurrency: usize,
+) -> CliResult<Vec<(Issue, Vec<Comment>)>> {
+    let semaphore = Arc::new(Semaphore::new(concurrency));
+    let mut tasks = JoinSet::new();
+    for (index, issue) in issues.into_iter().enumerate() {
+        let client = client.clone();
+        let semaphore = Arc::clone(&semaphore);
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let comments = client.issue().get_all_comments(issue.id).await;
+            (index, issue, comments)
+        });
+    }
+
+    let mut histories = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        let (index, issue, comments) = joined?;
+        let comments = comments.with_context(|| format!("Failed to fetch {}", issue.issue_key))?;
+        histories.push((index, issue, comments));
+    }
+    histories.sort_by_key(|(index, _, _)| *index);
+    Ok(histories
+        .into_iter()
+        .map(|(_, issue, comments)| (issue, comments))
+        .collect())
+}
+
+/// An issue reduced to what the burndown and velocity reports need
+pub(crate) struct WorkItem {
+    pub weight: f64,
+    pub created: Option<DateTime<Utc>>,
+    pub history: StatusHistory,
+}
+
+impl WorkItem {
+    pub(crate) fn new(
+        issue: &Issue,
+        comments: &[Comment],
+        closed_name: &str,
+        metric: Metric,
+    ) -> Self {
+        WorkItem {
+            weight: weight(issue, metric),
+            created: created_at(issue),
+            history: StatusHistory::new(issue, comments, closed_name),
+        }
+    }
+}
+
+/// Whether an issue was closed at any point in time
+///
+/// Rebuilt from the `status` change logs of its comments, which name the
+/// statuses as they were called at the time. Besides the current name of the
+/// Closed status, the name an issue that is closed now was last closed under
+/// counts as closed. Other former names of a renamed Closed status cannot be
+/// recovered from the API, so changes to them read as not closed.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct StatusHistory {
+    initially_closed: bool,
+    changes: Vec<(DateTime<Utc>, bool)>,
+}
+
+impl StatusHistory {
+    pub(crate) fn new(issue: &Issue, comments: &[Comment], closed_name: &str) -> Self {
+        let mut changes: Vec<(DateTime<Utc>, Option<&str>, Option<&str>)> = comments
+            .iter()
+            .flat_map(|comment| {
+                comment
+                    .change_log
+                    .iter()
+                    .filter(|entry| entry.field == "status")
+                    .map(|entry| {
+                        (
+                            comment.created,
+                            entry.original_value.as_deref(),
+                            entry.new_value.as_deref(),
+                        )
+                    })
+            })
+            .collect();
+        changes.sort_by_key(|(at, _, _)| *at);
+
+        let closed_now = issue.status.id.value() == CLOSED_STATUS_ID;
+        let closed_under = changes
+            .last()
+            .filter(|_| closed_now)
+            .and_then(|(_, _, new)| *new);
+        let is_closed = |name: Option<&str>| {
+            name.is_some_and(|name| name == closed_name || Some(name) == closed_under)
+        };
+        let initially_closed = match changes.first() {
+            Some((_, original, _)) => is_closed(*original),
+            None => closed_now,
+        };
+        StatusHistory {
+            initially_closed,
+            changes: changes
+                .into_iter()
+                .map(|(at, _, new)| (at, is_closed(new)))
+                .collect(),
+        }
+    }
+
+    pub(crate) fn is_closed_at(&self, at: DateTime<Utc>) -> bool {
+        self.changes
+            .iter()
+            .take_while(|(changed, _)| *changed <= at)
+            .last()
+            .map_or(self.initially_closed, |(_, closed)| *closed)
+    }
+}
+
+/// The amount of work an issue stands for
+pub(crate) fn weight(issue: &Issue, metric: Metric) -> f64 {
+    match metric {
+        Metric::Hours => issue.estimated_hours.unwrap_or_default(),
+        Metric::Count => 1.0,
+    }
+}
+
+pub(crate) fn created_at(issue: &Issue) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(&issue.created)
+        .ok()
+        .map(|created| created.with_timezone(&Utc))
+}
+
+/// Milestone dates are calendar dates stored as UTC midnight
+pub(crate) fn milestone_date(date: Option<DateTime<Utc>>) -> Option<NaiveDate> {
+    date.map(|date| date.date_naive())
+}
+
+/// The last instant of a day in the local time zone
+pub(crate) fn end_of_local_day(date: NaiveDate) -> DateTime<Utc> {
+    let next = date
+        .succ_opt()
+        .unwrap_or(date)
+        .and_hms_opt(0, 0, 0)
+        .expect("midnight is a valid time");
+    Local
+        .from_local_datetime(&next)
+        .earliest()
+        .map_or_else(|| next.and_utc(), |local| local.with_timezone(&Utc))
+        - chrono::Duration::seconds(1)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use serde_json::json;
+
+    pub(crate) fn issue(id: u32, status: u32, estimated: Option<f64>, created: &str) -> Issue {
+        serde_json::from_value(json!({
+            "id": id, "projectId": 1, "issueKey": format!("BLG-{id}"), "keyId": id,
+            "summary": format!("Issue {id}"), "description": "",
+            "issueType": {"id": 1, "projectId": 1, "name": "Task", "color": "#7ea800", "displayOrder": 0},
+            "status": {"id": status, "projectId": 1, "name": "Status", "color": "#ed8077", "displayOrder": 0},
+            "category": [], "versions": [], "milestone": [],
+            "estimatedHours": estimated,
+            "createdUser": {"id": 1, "userId": "john", "name": "John", "roleType": 1, "mailAddress": "john@example.com"},
+            "created": created,
+            "updated": created
+        }))
+        .unwrap()
+    }
+
+    pub(crate) fn status_comment(id: u32, created: &str, from: &str, to: &str) -> Comment {
+        serde_json::from_value(json!({
+            "id": id, "content": null,
+            "changeLog": [{"field": "status", "originalValue": from, "newValue": to}],
+            "createdUser": {"id": 1, "userId": "john", "name": "John", "roleType": 1, "mailAddress": "john@example.com"},
+            "created": created, "updated": created,
+            "stars": [], "notifications": []
+        }))
+        .unwrap()
+    }
+
+    fn at(timestamp: &str) -> DateTime<Utc> {
+        timestamp.parse().unwrap()
+    }
+
+    #[test]
+    fn test_status_history_follows_reopening() {
+        let issue = issue(1, 2, None, "2024-01-01T00:00:00Z");
+        let comments = vec![
+            status_comment(3, "2024-01-05T10:00:00Z", "Closed", "In Progress"),
+            status_comment(2, "2024-01-03T10:00:00Z", "Open", "Closed"),
+        ];
+        let history = StatusHistory::new(&issue, &comments, "Closed");
+
+        assert!(!history.is_closed_at(at("2024-01-02T00:00:00Z")));
+        assert!(history.is_closed_at(at("2024-01-04T00:00:00Z")));
+        assert!(!history.is_closed_at(at("2024-01-06T00:00:00Z")));
+    }
+
+    #[test]
+    fn test_status_history_knows_the_name_closed_had_before_a_rename() {
+        let issue = issue(1, CLOSED_STATUS_ID, None, "2024-01-01T00:00:00Z");
+        let comments = vec![
+            status_comment(2, "2024-01-03T10:00:00Z", "Open", "Closed"),
+            status_comment(3, "2024-01-05T10:00:00Z", "Closed", "Open"),
+            status_comment(4, "2024-01-07T10:00:00Z", "Open", "Closed"),
+        ];
+        // The Closed status has been renamed to "Done" since
+        let history = StatusHistory::new(&issue, &comments, "Done");
+
+        assert!(!history.is_closed_at(at("2024-01-02T00:00:00Z")));
+        assert!(history.is_closed_at(at("2024-01-04T00:00:00Z")));
+        assert!(!history.is_closed_at(at("2024-01-06T00:00:00Z")));
+        assert!(history.is_closed_at(at("2024-01-08T00:00:00Z")));
+    }
+
+    #[test]
+    fn test_status_history_without_changes_uses_current_status() {
+        let closed = issue(1, CLOSED_STATUS_ID, None, "2024-01-01T00:00:00Z");
+        let history = StatusHistory::new(&closed, &[], "Closed");
+        assert!(history.is_closed_at(at("2024-01-01T00:00:00Z")));
+    }
+}
@@ -0,0 +1,13 @@
+//! Subcommand modules for reports
+//!
+//! - `data`: Fetching issues and rebuilding their status history
+//! - `render`: CSV, ASCII and SVG output
+//! - `burndown`: Remaining work per day of a milestone
+//! - `velocity`: Committed and completed work of recent milestones
+//! - `timesheet`: Hours logged per user and day
+
+pub mod burndown;
+pub(crate) mod data;
+pub(crate) mod render;
+pub mod timesheet;
+pub mod velocity;
@@ -0,0 +1,236 @@
+//! CSV, ASCII and SVG output shared by the reports
+
+use std::fmt::Write;
+
+/// Width of the longest ASCII bar in characters
+const BAR_WIDTH: f64 = 40.0;
+
+const SVG_WIDTH: f64 = 720.0;
+const SVG_HEIGHT: f64 = 360.0;
+const MARGIN: f64 = 48.0;
+
+/// Join fields into a CSV line, quoting those that need it
+pub(crate) fn csv_row<S: AsRef<str>>(fields: &[S]) -> String {
+    fields
+        .iter()
+        .map(|field| {
+            let field = field.as_ref();
+            if field.contains([',', '"', '\n']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Horizontal bar scaled so the largest value spans the full width
+pub(crate) fn ascii_bar(value: f64, max: f64) -> String {
+    if max <= 0.0 || value <= 0.0 {
+        return String::new();
+    }
+    let width = (value / max * BAR_WIDTH).round().max(1.0) as usize;
+    "█".repeat(width)
+}
+
+/// A named series of an SVG chart
+pub(crate) struct Series<'a> {
+    pub name: &'a str,
+    pub color: &'a str,
+    pub dashed: bool,
+    pub values: Vec<Option<f64>>,
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn series_max(series: &[Series]) -> f64 {
+    series
+        .iter()
+        .flat_map(|s| s.values.iter().flatten())
+        .fold(0.0_f64, |max, value| max.max(*value))
+}
+
+fn open_svg(title: &str, max: f64) -> String {
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{SVG_WIDTH}" height="{SVG_HEIGHT}" font-family="sans-serif" font-size="11">"#
+    );
+    let _ = writeln!(
+        svg,
+        r#"<text x="{MARGIN}" y="20" font-size="14">{}</text>"#,
+        escape(title)
+    );
+    let bottom = SVG_HEIGHT - MARGIN;
+    let right = SVG_WIDTH - MARGIN;
+    let _ = writeln!(
+        svg,
+        r##"<path d="M{MARGIN} {MARGIN} V{bottom} H{right}" fill="none" stroke="#888"/>"##
+    );
+    let _ = writeln!(
+        svg,
+        r#"<text x="{}" y="{}" text-anchor="end">{max:.1}</text>"#,
+        MARGIN - 4.0,
+        MARGIN + 4.0
+    );
+    let _ = writeln!(
+        svg,
+        r#"<text x="{}" y="{}" text-anchor="end">0</text>"#,
+        MARGIN - 4.0,
+        bottom + 4.0
+    );
+    svg
+}
+
+fn legend(svg: &mut String, series: &[Series]) {
+    for (i, s) in series.iter().enumerate() {
+        let x = SVG_WIDTH - MARGIN - 140.0;
+        let y = 16.0 + 14.0 * i as f64;
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{x}" y="{}" width="10" height="10" fill="{}"/><text x="{}" y="{}">{}</text>"#,
+            y - 9.0,
+            s.color,
+            x + 14.0,
+            y,
+            escape(s.name)
+        );
+    }
+}
+
+fn y_of(value: f64, max: f64) -> f64 {
+    let plot = SVG_HEIGHT - 2.0 * MARGIN;
+    if max <= 0.0 {
+        SVG_HEIGHT - MARGIN
+    } else {
+        SVG_HEIGHT - MARGIN - value / max * plot
+    }
+}
+
+/// Line chart with one point per label; `None` values leave the line open
+pub(crate) fn svg_line_chart(title: &str, labels: &[String], series: &[Series]) -> String {
+    let max = series_max(series);
+    let mut svg = open_svg(title, max);
+    let plot = SVG_WIDTH - 2.0 * MARGIN;
+    let step = if labels.len() > 1 {
+        plot / (labels.len() - 1) as f64
+    } else {
+        0.0
+    };
+
+    // Label at most ~10 ticks so dates stay readable
+    let every = labels.len().div_ceil(10).max(1);
+    for (i, label) in labels.iter().enumerate().step_by(every) {
+        let _ = writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{}" text-anchor="middle">{}</text>"#,
+            MARGIN + step * i as f64,
+            SVG_HEIGHT - MARGIN + 16.0,
+            escape(label)
+        );
+    }
+
+    for s in series {
+        let points: Vec<String> = s
+            .values
+            .iter()
+            .enumerate()
+            .filter_map(|(i, value)| {
+                value.map(|v| format!("{:.1},{:.1}", MARGIN + step * i as f64, y_of(v, max)))
+            })
+            .collect();
+        let dash = if s.dashed {
+            r#" stroke-dasharray="6 4""#
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            svg,
+            r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="2"{dash}/>"#,
+            points.join(" "),
+            s.color
+        );
+    }
+
+    legend(&mut svg, series);
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Grouped bar chart with one group per label
+pub(crate) fn svg_bar_chart(title: &str, labels: &[String], series: &[Series]) -> String {
+    let max = series_max(series);
+    let mut svg = open_svg(title, max);
+    let group = (SVG_WIDTH - 2.0 * MARGIN) / labels.len().max(1) as f64;
+    let bar = group * 0.8 / series.len().max(1) as f64;
+
+    for (i, label) in labels.iter().enumerate() {
+        let left = MARGIN + group * i as f64 + group * 0.1;
+        for (j, s) in series.iter().enumerate() {
+            let value = s.values.get(i).copied().flatten().unwrap_or_default();
+            let top = y_of(value, max);
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{:.1}" y="{top:.1}" width="{bar:.1}" height="{:.1}" fill="{}"/>"#,
+                left + bar * j as f64,
+                SVG_HEIGHT - MARGIN - top,
+                s.color
+            );
+        }
+        let _ = writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{}" text-anchor="middle">{}</text>"#,
+            left + group * 0.4,
+            SVG_HEIGHT - MARGIN + 16.0,
+            escape(label)
+        );
+    }
+
+    legend(&mut svg, series);
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_row_quotes_when_needed() {
+        assert_eq!(
+            csv_row(&["Sprint 1", "a,b", "say \"hi\""]),
+            "Sprint 1,\"a,b\",\"say \"\"hi\"\"\""
+        );
+    }
+
+    #[test]
+    fn test_ascii_bar_scales_to_max() {
+        assert_eq!(ascii_bar(10.0, 10.0).chars().count(), 40);
+        assert_eq!(ascii_bar(5.0, 10.0).chars().count(), 20);
+        assert_eq!(ascii_bar(0.0, 10.0), "");
+    }
+
+    #[test]
+    fn test_svg_line_chart_skips_missing_points() {
+        let labels = vec!["d1".to_string(), "d2".to_string(), "d3".to_string()];
+        let svg = svg_line_chart(
+            "Burndown <S1>",
+            &labels,
+            &[Series {
+                name: "Remaining",
+                color: "#e30000",
+                dashed: false,
+                values: vec![Some(10.0), Some(5.0), None],
+            }],
+        );
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("Burndown &lt;S1&gt;"));
+        assert!(svg.contains(r#"<polyline points="48.0,48.0 360.0,180.0""#));
+    }
+}
@@ -0,0 +1,535 @@
+//! Hours logged per user and day, rebuilt from `actualHours` changes
+//!
+//! The users' activities tell which issues they touched in the period; the
+//! comment change logs of those issues tell who changed the actual hours,
+//! when, and by how much.
+
+use crate::commands::common::CliResult;
+use crate::commands::report::args::{TimesheetFormat, TimesheetGroup};
+use crate::commands::report::subcommands::render;
+use anyhow::Context;
+use backlog_api_client::ApiError;
+use backlog_api_client::client::BacklogApiClient;
+use backlog_core::User;
+use backlog_core::activity::{Activity, Content};
+use backlog_core::identifier::{ActivityTypeId, Identifier, IssueId, UserId};
+use backlog_issue::GetIssueParams;
+use backlog_issue::models::{Comment, Issue};
+use backlog_user::{GetOwnUserParams, GetUserRecentUpdatesParams};
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use prettytable::{Table, row};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// Activity types that can change an issue's actual hours
+const ISSUE_ACTIVITY_TYPES: [u32; 3] = [1, 2, 3];
+
+/// Maximum number of activities the API returns per request
+const ACTIVITY_PAGE_SIZE: u32 = 100;
+
+/// Hours one user logged on one issue on one day
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TimeEntry {
+    pub date: NaiveDate,
+    pub user_id: UserId,
+    pub user: String,
+    pub project: String,
+    pub issue_key: String,
+    pub issue_type: String,
+    pub category: String,
+    pub hours: f64,
+}
+
+/// Total hours of one group
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct GroupTotal {
+    pub key: String,
+    pub hours: f64,
+}
+
+fn parse_hours(value: Option<&str>) -> f64 {
+    value
+        .and_then(|value| value.trim().parse::<f64>().ok())
+        .unwrap_or_default()
+}
+
+/// Entries for the `actualHours` changes made by `users` between `from` and `to`
+///
+/// Hours set when the issue was created count for its creator on the
+/// creation day.
+pub(crate) fn entries_for_issue(
+    issue: &Issue,
+    comments: &[Comment],
+    users: &HashSet<UserId>,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Vec<TimeEntry> {
+    let local_date = |at: DateTime<Utc>| at.with_timezone(&Local).date_naive();
+    let in_range = |date: NaiveDate| from <= date && date <= to;
+    let category = if issue.category.is_empty() {
+        "(none)".to_string()
+    } else {
+        issue
+            .category
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect::<Vec<_>>()
+            .join("; ")
+    };
+    let entry = |date: NaiveDate, user: &User, hours: f64| TimeEntry {
+        date,
+        user_id: user.id,
+        user: user.name.clone(),
+        project: issue
+            .issue_key
+            .to_string()
+            .rsplit_once('-')
+            .map_or_else(String::new, |(key, _)| key.to_string()),
+        issue_key: issue.issue_key.to_string(),
+        issue_type: issue.issue_type.name.clone(),
+        category: category.clone(),
+        hours,
+    };
+
+    let mut changes: Vec<(&Comment, f64, f64)> = comments
+        .iter()
+        .flat_map(|comment| {
+            comment
+                .change_log
+                .iter()
+                .filter(|change| change.field == "actualHours")
+                .map(move |change| {
+                    (
+                        comment,
+                        parse_hours(change.original_value.as_deref()),
+                        parse_hours(change.new_value.as_deref()),
+                    )
+                })
+        })
+        .collect();
+    changes.sort_by_key(|(comment, _, _)| comment.created);
+
+    let mut entries = Vec::new();
+
+    let initial = changes.first().map_or(
+        issue.actual_hours.unwrap_or_default(),
+        |(_, original, _)| *original,
+    );
+    if let Ok(created) = DateTime::parse_from_rfc3339(&issue.created) {
+        let date = local_date(created.with_timezone(&Utc));
+        if initial != 0.0 && users.contains(&issue.created_user.id) && in_range(date) {
+            entries.push(entry(date, &issue.created_user, initial));
+        }
+    }
+
+    for (comment, original, new) in changes {
+        let date = local_date(comment.created);
+        if new != original && users.contains(&comment.created_user.id) && in_range(date) {
+            entries.push(entry(date, &comment.created_user, new - original));
+        }
+    }
+
+    entries
+}
+
+/// Merge entries of the same day, user and issue
+pub(crate) fn merge_entries(entries: Vec<TimeEntry>) -> Vec<TimeEntry> {
+    let mut merged: BTreeMap<(NaiveDate, String, u32, String), TimeEntry> = BTreeMap::new();
+    for entry in entries {
+        let key = (
+            entry.date,
+            entry.user.clone(),
+            entry.user_id.value(),
+            entry.issue_key.clone(),
+        );
+        merged
+            .entry(key)
+            .and_modify(|existing| existing.hours += entry.hours)
+            .or_insert(entry);
+    }
+    merged.into_values().collect()
+}
+
+/// Total hours per group, sorted by group name
+///
+/// Users are grouped by ID; users sharing a display name get their ID
+/// appended so their rows can be told apart.
+pub(crate) fn group_totals(entries: &[TimeEntry], group: TimesheetGroup) -> Vec<GroupTotal> {
+    let mut totals: BTreeMap<(&str, Option<u32>), f64> = BTreeMap::new();
+    for entry in entries {
+        let key = match group {
+            TimesheetGroup::Entry | TimesheetGroup::User => {
+                (entry.user.as_str(), Some(entry.user_id.value()))
+            }
+            TimesheetGroup::Project => (entry.project.as_str(), None),
+            TimesheetGroup::IssueType => (entry.issue_type.as_str(), None),
+            TimesheetGroup::Category => (entry.category.as_str(), None),
+        };
+        *totals.entry(key).or_default() += entry.hours;
+    }
+
+    let mut name_counts: HashMap<&str, usize> = HashMap::new();
+    for (name, _) in totals.keys() {
+        *name_counts.entry(name).or_default() += 1;
+    }
+    totals
+        .into_iter()
+        .map(|((name, id), hours)| GroupTotal {
+            key: match id {
+                Some(id) if name_counts[name] > 1 => format!("{name} ({id})"),
+                _ => name.to_string(),
+            },
+            hours,
+        })
+        .collect()
+}
+
+/// Issues the users touched between `from` and `to`, newest activity first
+async fn touched_issues(
+    client: &BacklogApiClient,
+    user_id: UserId,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> CliResult<Vec<IssueId>> {
+    let mut issues = Vec::new();
+    let mut max_id = None;
+
+    loop {
+        let params = GetUserRecentUpdatesParams {
+            user_id,
+            activity_type_ids: Some(
+                ISSUE_ACTIVITY_TYPES
+                    .into_iter()
+                    .map(ActivityTypeId::new)
+                    .collect(),
+            ),
+            min_id: None,
+            max_id,
+            count: Some(ACTIVITY_PAGE_SIZE),
+            order: Some("desc".to_string()),
+        };
+        let page: Vec<Activity> = client.user().get_user_recent_updates(params).await?;
+        let page_len = page.len();
+
+        for activity in &page {
+            if activity.created < from {
+                return Ok(issues);
+            }
+            if activity.created <= to
+                && let Content::Standard { id, .. } = &activity.content
+                && let Ok(id) = u32::try_from(*id)
+            {
+                issues.push(IssueId::new(id));
+            }
+        }
+
+        match page.last() {
+            Some(last) if page_len == ACTIVITY_PAGE_SIZE as usize => {
+                max_id = Some(i64::from(last.id.value()))
+            }
+            _ => return Ok(issues),
+        }
+    }
+}
+
+/// Show the hours logged by users between two dates
+pub async fn run(
+    client: &BacklogApiClient,
+    user_ids: Vec<u32>,
+    from: NaiveDate,
+    to: NaiveDate,
+    group: TimesheetGroup,
+    format: TimesheetFormat,
+    concurrency: usize,
+) -> CliResult<()> {
+    if to < from {
+        anyhow::bail!("--to ({to}) is before --from ({from})");
+    }
+    let users: HashSet<UserId> = if user_ids.is_empty() {
+        let myself = client.user().get_own_user(GetOwnUserParams::new()).await?;
+        HashSet::from([myself.id])
+    } else {
+        user_ids.into_iter().map(UserId::new).collect()
+    };
+
+    // Activities are listed by time of the change; widen by a day on both
+    // sides so local days at the edges are fully covered
+    let start =
+        from.and_hms_opt(0, 0, 0).expect("valid time").and_utc() - chrono::Duration::days(1);
+    let end = to.and_hms_opt(0, 0, 0).expect("valid time").and_utc() + chrono::Duration::days(2);
+
+    let mut issue_ids = BTreeSet::new();
+    for user_id in &users {
+        issue_ids.extend(
+            touched_issues(client, *user_id, start, end)
+                .await?
+                .into_iter()
+                .map(|id| id.value()),
+        );
+    }
+
+    let semaphore = Arc::new(Semaphore::new(concurrency));
+    let mut tasks = JoinSet::new();
+    for issue_id in issue_ids {
+        let client = client.clone();
+        let semaphore = Arc::clone(&semaphore);
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let result = async {
+                let issue_id = IssueId::new(issue_id);
+                let issue = match client
+                    .issue()
+                    .get_issue(GetIssueParams::new(issue_id))
+                    .await
+                {
+                    Ok(issue) => issue,
+                    // Deleted since the activity was recorded
+                    Err(
+                        ApiError::HttpStatus { status: 404, .. }
+                        | ApiError::UnparseableErrorResponse { status: 404, .. },
+                    ) => return Ok(None),
+                    Err(e) => return Err(e.into()),
+                };
+                let comments = client.issue().get_all_comments(issue_id).await?;
+                CliResult::Ok(Some((issue, comments)))
+            }
+            .await;
+            (issue_id, result)
+        });
+    }
+
+    let mut entries = Vec::new();
+    let mut deleted = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        let (issue_id, result) = joined?;
+        match result.with_context(|| format!("Failed to fetch issue {issue_id}"))? {
+            Some((issue, comments)) => {
+                entries.extend(entries_for_issue(&issue, &comments, &users, from, to))
+            }
+            None => deleted.push(issue_id),
+        }
+    }
+    deleted.sort();
+    for issue_id in &deleted {
+        eprintln!("Warning: skipped issue {issue_id}, which no longer exists");
+    }
+    let entries = merge_entries(entries);
+    let total: f64 = entries.iter().map(|e| e.hours).sum();
+
+    match (group, format) {
+        (TimesheetGroup::Entry, TimesheetFormat::Table) => {
+            let mut table = Table::new();
+            table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+            table.set_titles(row![
+                "Date",
+                "User",
+                "Issue",
+                "Issue Type",
+                "Category",
+                "Hours"
+            ]);
+            for e in &entries {
+                table.add_row(row![
+                    e.date,
+                    e.user,
+                    e.issue_key,
+                    e.issue_type,
+                    e.category,
+                    r -> format!("{:.2}", e.hours)
+                ]);
+            }
+            table.printstd();
+            println!("Total: {total:.2} hours from {from} to {to}");
+        }
+        (TimesheetGroup::Entry, TimesheetFormat::Csv) => {
+            println!(
+                "{}",
+                render::csv_row(&[
+                    "date",
+                    "user",
+                    "project",
+                    "issue",
+                    "issue_type",
+                    "category",
+                    "hours"
+                ])
+            );
+            for e in &entries {
+                println!(
+                    "{}",
+                    render::csv_row(&[
+                        e.date.to_string(),
+                        e.user.clone(),
+                        e.project.clone(),
+                        e.issue_key.clone(),
+                        e.issue_type.clone(),
+                        e.category.clone(),
+                        e.hours.to_string(),
+                    ])
+                );
+            }
+        }
+        (TimesheetGroup::Entry, TimesheetFormat::Json) => {
+            println!("{}", serde_json::to_string_pretty(&entries)?);
+        }
+        (group, TimesheetFormat::Table) => {
+            let mut table = Table::new();
+            table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+            table.set_titles(row![group.title(), "Hours"]);
+            for t in group_totals(&entries, group) {
+                table.add_row(row![t.key, r -> format!("{:.2}", t.hours)]);
+            }
+            table.printstd();
+            println!("Total: {total:.2} hours from {from} to {to}");
+        }
+        (group, TimesheetFormat::Csv) => {
+            println!(
+                "{}",
+                render::csv_row(&[
+                    group.title().to_lowercase().replace(' ', "_"),
+                    "hours".to_string()
+                ])
+            );
+            for t in group_totals(&entries, group) {
+                println!("{}", render::csv_row(&[t.key, t.hours.to_string()]));
+            }
+        }
+        (group, TimesheetFormat::Json) => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&group_totals(&entries, group))?
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn issue(actual: Option<f64>, created_by: u32) -> Issue {
+        serde_json::from_value(json!({
+            "id": 1, "projectId": 1, "issueKey": "BLG-1", "keyId": 1,
+            "summary": "Issue", "description": "",
+            "issueType": {"id": 1, "projectId": 1, "name": "Task", "color": "#7ea800", "displayOrder": 0},
+            "status": {"id": 1, "projectId": 1, "name": "Open", "color": "#ed8077", "displayOrder": 0},
+            "category": [{"id": 1, "projectId": 1, "name": "Backend", "displayOrder": 0}],
+            "versions": [], "milestone": [],
+            "actualHours": actual,
+            "createdUser": {"id": created_by, "userId": "u", "name": format!("User {created_by}"), "roleType": 1, "mailAddress": "u@example.com"},
+            "created": "2024-01-10T12:00:00Z",
+            "updated": "2024-01-10T12:00:00Z"
+        }))
+        .unwrap()
+    }
+
+    fn hours_comment(id: u32, user: u32, created: &str, from: &str, to: &str) -> Comment {
+        serde_json::from_value(json!({
+            "id": id, "content": null,
+            "changeLog": [{"field": "actualHours", "originalValue": from, "newValue": to}],
+            "createdUser": {"id": user, "userId": "u", "name": format!("User {user}"), "roleType": 1, "mailAddress": "u@example.com"},
+            "created": created, "updated": created,
+            "stars": [], "notifications": []
+        }))
+        .unwrap()
+    }
+
+    fn date(value: &str) -> NaiveDate {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_entries_for_issue_takes_deltas_of_selected_users() {
+        let comments = vec![
+            hours_comment(1, 7, "2024-01-11T12:00:00Z", "1", "3.5"),
+            hours_comment(2, 8, "2024-01-12T12:00:00Z", "3.5", "5"),
+            hours_comment(3, 7, "2024-01-20T12:00:00Z", "5", "6"),
+        ];
+        let users = HashSet::from([UserId::new(7)]);
+
+        let entries = entries_for_issue(
+            &issue(Some(6.0), 7),
+            &comments,
+            &users,
+            date("2024-01-01"),
+            date("2024-01-15"),
+        );
+
+        let hours: Vec<f64> = entries.iter().map(|e| e.hours).collect();
+        assert_eq!(hours, vec![1.0, 2.5]);
+        assert_eq!(entries[0].user, "User 7");
+        assert_eq!(entries[0].project, "BLG");
+        assert_eq!(entries[0].category, "Backend");
+    }
+
+    #[test]
+    fn test_group_totals() {
+        let comments = vec![
+            hours_comment(1, 7, "2024-01-11T12:00:00Z", "", "2"),
+            hours_comment(2, 8, "2024-01-11T13:00:00Z", "2", "5"),
+        ];
+        let users = HashSet::from([UserId::new(7), UserId::new(8)]);
+        let entries = merge_entries(entries_for_issue(
+            &issue(None, 1),
+            &comments,
+            &users,
+            date("2024-01-01"),
+            date("2024-01-31"),
+        ));
+
+        let by_user = group_totals(&entries, TimesheetGroup::User);
+        assert_eq!(
+            by_user,
+            vec![
+                GroupTotal {
+                    key: "User 7".to_string(),
+                    hours: 2.0
+                },
+                GroupTotal {
+                    key: "User 8".to_string(),
+                    hours: 3.0
+                },
+            ]
+        );
+        let by_type = group_totals(&entries, TimesheetGroup::IssueType);
+        assert_eq!(
+            by_type,
+            vec![GroupTotal {
+                key: "Task".to_string(),
+                hours: 5.0
+            }]
+        );
+    }
+
+    #[test]
+    fn test_group_totals_keeps_users_with_the_same_name_apart() {
+        let mut namesake = hours_comment(2, 8, "2024-01-11T13:00:00Z", "2", "5");
+        namesake.created_user.name = "User 7".to_string();
+        let comments = vec![
+            hours_comment(1, 7, "2024-01-11T12:00:00Z", "", "2"),
+            namesake,
+        ];
+        let users = HashSet::from([UserId::new(7), UserId::new(8)]);
+        let entries = merge_entries(entries_for_issue(
+            &issue(None, 1),
+            &comments,
+            &users,
+            date("2024-01-01"),
+            date("2024-01-31"),
+        ));
+
+        assert_eq!(entries.len(), 2);
+        let keys: Vec<String> = group_totals(&entries, TimesheetGroup::User)
+            .into_iter()
+            .map(|t| t.key)
+            .collect();
+        assert_eq!(keys, vec!["User 7 (7)", "User 7 (8)"]);
+    }
+}
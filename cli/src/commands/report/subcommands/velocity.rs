@@ -0,0 +1,262 @@
+//! Committed and completed work of recent milestones
+
+use super::data::{self, WorkItem};
+use super::render::{self, Series};
+use crate::commands::common::CliResult;
+use crate::commands::report::args::{Metric, ReportFormat};
+use anyhow::Context;
+use backlog_api_client::backlog_project::GetMilestoneListParams;
+use backlog_api_client::client::BacklogApiClient;
+use backlog_api_client::{Milestone, ProjectIdOrKey};
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use prettytable::{Table, row};
+
+/// Work planned for and finished in one milestone
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SprintVelocity {
+    pub name: String,
+    pub start: Option<NaiveDate>,
+    pub due: NaiveDate,
+    pub committed: f64,
+    pub completed: f64,
+}
+
+/// The last `count` milestones whose due date has passed, oldest first
+pub(crate) fn finished_milestones(
+    milestones: Vec<Milestone>,
+    today: NaiveDate,
+    count: usize,
+) -> Vec<Milestone> {
+    let mut finished: Vec<Milestone> = milestones
+        .into_iter()
+        .filter(|m| data::milestone_date(m.release_due_date).is_some_and(|due| due < today))
+        .collect();
+    finished.sort_by_key(|m| m.release_due_date);
+    let skip = finished.len().saturating_sub(count);
+    finished.split_off(skip)
+}
+
+/// Work of a milestone's issues, and of those that were closed at the end
+/// of its due date
+pub(crate) fn velocity(
+    milestone: &Milestone,
+    items: &[WorkItem],
+    end_of_day: impl Fn(NaiveDate) -> DateTime<Utc>,
+) -> SprintVelocity {
+    let due = data::milestone_date(milestone.release_due_date).unwrap_or_default();
+    let end = end_of_day(due);
+    SprintVelocity {
+        name: milestone.name.clone(),
+        start: data::milestone_date(milestone.start_date),
+        due,
+        committed: items.iter().map(|item| item.weight).sum(),
+        completed: items
+            .iter()
+            .filter(|item| item.history.is_closed_at(end))
+            .map(|item| item.weight)
+            .sum(),
+    }
+}
+
+/// Show the velocity of the last finished milestones of a project
+pub async fn run(
+    client: &BacklogApiClient,
+    project: &str,
+    last: usize,
+    metric: Metric,
+    format: ReportFormat,
+    concurrency: usize,
+) -> CliResult<()> {
+    let project_id_or_key = project
+        .parse::<ProjectIdOrKey>()
+        .with_context(|| format!("Failed to parse project '{project}'"))?;
+    let milestones = client
+        .project()
+        .get_version_milestone_list(GetMilestoneListParams::new(project_id_or_key))
+        .await?;
+    let milestones = finished_milestones(milestones, Local::now().date_naive(), last);
+    if milestones.is_empty() {
+        println!("No finished milestones with a release due date in {project}");
+        return Ok(());
+    }
+
+    let closed_name = data::closed_status_name(client, milestones[0].project_id).await?;
+    let mut sprints = Vec::with_capacity(milestones.len());
+    for milestone in &milestones {
+        let issues = data::milestone_issues(client, milestone).await?;
+        let items: Vec<WorkItem> = data::fetch_comments(client, issues, concurrency)
+            .await?
+            .into_iter()
+            .map(|(issue, comments)| WorkItem::new(&issue, &comments, &closed_name, metric))
+            .collect();
+        sprints.push(velocity(milestone, &items, data::end_of_local_day));
+    }
+    let average = sprints.iter().map(|s| s.completed).sum::<f64>() / sprints.len() as f64;
+
+    let unit = metric.unit();
+    match format {
+        ReportFormat::Table => {
+            let mut table = Table::new();
+            table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+            table.set_titles(row![
+                "Milestone",
+                "Start",
+                "Due",
+                format!("Committed ({unit})"),
+                format!("Completed ({unit})"),
+                "Done"
+            ]);
+            for sprint in &sprints {
+                table.add_row(row![
+                    sprint.name,
+                    sprint.start.map_or_else(String::new, |d| d.to_string()),
+                    sprint.due,
+                    metric.format(sprint.committed),
+                    metric.format(sprint.completed),
+                    percent(sprint)
+                ]);
+            }
+            table.printstd();
+            println!("Average velocity: {} {unit}", metric.format(average));
+        }
+        ReportFormat::Csv => {
+            println!(
+                "{}",
+                render::csv_row(&["milestone", "start", "due", "committed", "completed"])
+            );
+            for sprint in &sprints {
+                println!(
+                    "{}",
+                    render::csv_row(&[
+                        sprint.name.clone(),
+                        sprint.start.map_or_else(String::new, |d| d.to_string()),
+                        sprint.due.to_string(),
+                        sprint.committed.to_string(),
+                        sprint.completed.to_string(),
+                    ])
+                );
+            }
+        }
+        ReportFormat::Ascii => {
+            println!("Completed {unit} per milestone");
+            let max = sprints.iter().map(|s| s.completed).fold(0.0, f64::max);
+            let width = sprints
+                .iter()
+                .map(|s| s.name.chars().count())
+                .max()
+                .unwrap_or(0);
+            for sprint in &sprints {
+                println!(
+                    "{:<width$} {} {} / {}",
+                    sprint.name,
+                    render::ascii_bar(sprint.completed, max),
+                    metric.format(sprint.completed),
+                    metric.format(sprint.committed)
+                );
+            }
+            println!("Average velocity: {} {unit}", metric.format(average));
+        }
+        ReportFormat::Svg => {
+            let labels: Vec<String> = sprints.iter().map(|s| s.name.clone()).collect();
+            print!(
+                "{}",
+                render::svg_bar_chart(
+                    &format!("Velocity of {project} ({unit})"),
+                    &labels,
+                    &[
+                        Series {
+                            name: "Committed",
+                            color: "#b0b0b0",
+                            dashed: false,
+                            values: sprints.iter().map(|s| Some(s.committed)).collect(),
+                        },
+                        Series {
+                            name: "Completed",
+                            color: "#4caf93",
+                            dashed: false,
+                            values: sprints.iter().map(|s| Some(s.completed)).collect(),
+                        },
+                    ],
+                )
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn percent(sprint: &SprintVelocity) -> String {
+    if sprint.committed > 0.0 {
+        format!("{:.0}%", sprint.completed / sprint.committed * 100.0)
+    } else {
+        "-".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::report::subcommands::data::tests::{issue, status_comment};
+    use backlog_core::identifier::Identifier;
+    use backlog_issue::models::CLOSED_STATUS_ID;
+
+    fn milestone(id: u32, due: Option<&str>) -> Milestone {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "projectId": 1, "name": format!("Sprint {id}"), "description": null,
+            "startDate": null, "releaseDueDate": due, "archived": false, "displayOrder": id
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_finished_milestones_keeps_the_latest() {
+        let milestones = vec![
+            milestone(3, Some("2024-03-01T00:00:00Z")),
+            milestone(1, Some("2024-01-01T00:00:00Z")),
+            milestone(2, Some("2024-02-01T00:00:00Z")),
+            milestone(4, Some("2024-04-01T00:00:00Z")),
+            milestone(5, None),
+        ];
+        let finished = finished_milestones(milestones, "2024-03-15".parse().unwrap(), 2);
+        let ids: Vec<u32> = finished.iter().map(|m| m.id.value()).collect();
+        assert_eq!(ids, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_velocity_counts_issues_closed_by_the_due_date() {
+        let closed_in_time = [status_comment(1, "2024-01-10T00:00:00Z", "Open", "Closed")];
+        let reopened_later = [
+            status_comment(2, "2024-01-12T00:00:00Z", "Open", "Closed"),
+            status_comment(3, "2024-02-01T00:00:00Z", "Closed", "Open"),
+        ];
+        let closed_late = [status_comment(4, "2024-01-20T00:00:00Z", "Open", "Closed")];
+        let issues = [
+            (
+                issue(1, CLOSED_STATUS_ID, Some(3.0), "2024-01-01T00:00:00Z"),
+                &closed_in_time[..],
+            ),
+            (
+                issue(2, 2, Some(5.0), "2024-01-01T00:00:00Z"),
+                &reopened_later[..],
+            ),
+            (
+                issue(3, CLOSED_STATUS_ID, Some(2.0), "2024-01-01T00:00:00Z"),
+                &closed_late[..],
+            ),
+            (issue(4, 1, None, "2024-01-01T00:00:00Z"), &[][..]),
+        ];
+        let sprint = milestone(1, Some("2024-01-14T00:00:00Z"));
+        let end_of_utc_day = |day: NaiveDate| day.and_hms_opt(23, 59, 59).unwrap().and_utc();
+
+        let items = |metric| -> Vec<WorkItem> {
+            issues
+                .iter()
+                .map(|(issue, comments)| WorkItem::new(issue, comments, "Closed", metric))
+                .collect()
+        };
+        let hours = velocity(&sprint, &items(Metric::Hours), end_of_utc_day);
+        assert_eq!((hours.committed, hours.completed), (10.0, 8.0));
+        let count = velocity(&sprint, &items(Metric::Count), end_of_utc_day);
+        assert_eq!((count.committed, count.completed), (4.0, 2.0));
+    }
+}
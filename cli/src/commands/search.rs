@@ -0,0 +1,96 @@
+//! Search across issues, wikis, documents and pull requests
+
+use crate::commands::common::{CliResult, resolve_projects, truncate_text};
+use backlog_api_client::client::BacklogApiClient;
+use backlog_api_client::search::{SearchKind, SearchParams};
+use clap::{Parser, ValueEnum};
+use prettytable::{Table, row};
+
+#[derive(Parser)]
+pub struct SearchArgs {
+    /// Words to search for
+    #[clap(required = true)]
+    keyword: Vec<String>,
+    /// Only search these kinds (repeatable; default: all)
+    #[clap(long = "kind", value_enum)]
+    kinds: Vec<KindArg>,
+    /// Only search these projects (repeatable; default: all your active projects)
+    #[clap(long = "project")]
+    projects: Vec<String>,
+    /// Maximum number of results; pull requests are only matched among the
+    /// latest <LIMIT> of each repository
+    #[clap(long, default_value_t = 20, value_parser = clap::value_parser!(u8).range(1..=100))]
+    limit: u8,
+    /// Output as JSON
+    #[clap(long)]
+    json: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum KindArg {
+    Issue,
+    Wiki,
+    Document,
+    #[clap(alias = "pull-request")]
+    Pr,
+}
+
+impl From<KindArg> for SearchKind {
+    fn from(kind: KindArg) -> Self {
+        match kind {
+            KindArg::Issue => SearchKind::Issue,
+            KindArg::Wiki => SearchKind::Wiki,
+            KindArg::Document => SearchKind::Document,
+            KindArg::Pr => SearchKind::PullRequest,
+        }
+    }
+}
+
+pub async fn execute(client: &BacklogApiClient, args: SearchArgs) -> CliResult<()> {
+    let keyword = args.keyword.join(" ");
+    let projects = resolve_projects(client, &args.projects).await?;
+    let params = SearchParams::new(&keyword, projects)
+        .kinds(args.kinds.into_iter().map(SearchKind::from).collect())
+        .count(args.limit);
+    let mut results = client.search(params).await?;
+    results.hits.truncate(args.limit.into());
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+        return Ok(());
+    }
+
+    for failure in &results.failures {
+        eprintln!(
+            "Warning: {} search failed{}: {}",
+            failure.kind,
+            failure
+                .project
+                .as_deref()
+                .map_or_else(String::new, |project| format!(" in {project}")),
+            failure.message
+        );
+    }
+    if results.hits.is_empty() {
+        println!("No results for '{keyword}'");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    table.set_titles(row!["Kind", "Project", "Key", "Title", "Updated", "URL"]);
+    for hit in &results.hits {
+        table.add_row(row![
+            hit.kind,
+            hit.project,
+            hit.key,
+            truncate_text(&hit.title, 50),
+            hit.updated.map_or_else(String::new, |updated| updated
+                .format("%Y-%m-%d")
+                .to_string()),
+            hit.url
+        ]);
+    }
+    table.printstd();
+    Ok(())
+}
@@ -1,8 +1,11 @@
-#[cfg(all(feature = "star", feature = "star_writable"))]
-use backlog_api_client::AddStarParams;
-#[cfg(feature = "star")]
-use backlog_api_client::StarApi;
+//! Stars as quick bookmarks: star, unstar and list what you starred
+
+use crate::commands::common::{CliResult, truncate_text};
+use backlog_api_client::client::BacklogApiClient;
+use backlog_api_client::stars::{GivenStars, StarredItem};
+use backlog_user::GetOwnUserParams;
 use clap::{Args, Subcommand};
+use prettytable::{Table, row};
 
 #[derive(Args)]
 pub struct StarArgs {
@@ -13,13 +16,38 @@ pub struct StarArgs {
 #[derive(Subcommand)]
 pub enum StarCommands {
     #[cfg(feature = "star_writable")]
-    /// Add a star to a resource
+    /// Star an issue, comment, wiki page, pull request or pull request comment
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
     Add {
+        /// Issue key (BLG-12), comment (BLG-12#comment-34) or the URL of an issue,
+        /// comment, wiki page, pull request or pull request comment
+        #[clap(required = true)]
+        target: Option<String>,
         #[clap(subcommand)]
-        target: StarTarget,
+        by_id: Option<StarTarget>,
+    },
+    #[cfg(feature = "star_writable")]
+    /// Remove your star from an issue, comment, wiki page, pull request or pull request comment
+    Remove {
+        /// Issue key (BLG-12), comment (BLG-12#comment-34) or the URL of an issue,
+        /// comment, wiki page, pull request or pull request comment
+        target: String,
+    },
+    /// List what you starred, newest first
+    Mine {
+        /// Latest received stars looked through per user of the space (max 100)
+        #[clap(long, default_value_t = 100)]
+        per_user: u32,
+        /// Only issues, comments, wikis, pull requests or pull request comments
+        #[clap(long, value_parser = ["issue", "comment", "wiki", "pr", "pr-comment"])]
+        kind: Option<String>,
+        /// Output as JSON
+        #[clap(long)]
+        json: bool,
     },
 }
 
+/// Star a resource by its numeric ID
 #[cfg(feature = "star_writable")]
 #[derive(Subcommand)]
 pub enum StarTarget {
@@ -52,16 +80,57 @@ pub enum StarTarget {
     },
 }
 
-#[cfg(feature = "star")]
-pub async fn handle_star_command(api: &StarApi, command: &StarCommands) -> anyhow::Result<()> {
+pub async fn handle_star_command(
+    client: &BacklogApiClient,
+    command: &StarCommands,
+) -> CliResult<()> {
     match command {
         #[cfg(feature = "star_writable")]
-        StarCommands::Add { target } => handle_add_star(api, target).await,
+        StarCommands::Add {
+            by_id: Some(target),
+            ..
+        } => add_by_id(client, target).await,
+        #[cfg(feature = "star_writable")]
+        StarCommands::Add { target, .. } => {
+            add(client, target.as_deref().unwrap_or_default()).await
+        }
+        #[cfg(feature = "star_writable")]
+        StarCommands::Remove { target } => remove(client, target).await,
+        StarCommands::Mine {
+            per_user,
+            kind,
+            json,
+        } => mine(client, *per_user, kind.as_deref(), *json).await,
+    }
+}
+
+#[cfg(feature = "star_writable")]
+async fn add(client: &BacklogApiClient, target: &str) -> CliResult<()> {
+    let item: StarredItem = target.parse().map_err(anyhow::Error::msg)?;
+    let myself = client.user().get_own_user(GetOwnUserParams::new()).await?;
+    let entity = client.starred_entity(&item).await?;
+    if entity.star_by(myself.id).is_some() {
+        println!(
+            "Already starred {} ({} star(s))",
+            entity.title(),
+            entity.star_count()
+        );
+        return Ok(());
     }
+
+    client.star().add_star(entity.add_star_params()).await?;
+    println!(
+        "Starred {} ({} star(s))",
+        entity.title(),
+        entity.star_count() + 1
+    );
+    Ok(())
 }
 
 #[cfg(feature = "star_writable")]
-async fn handle_add_star(api: &StarApi, target: &StarTarget) -> anyhow::Result<()> {
+async fn add_by_id(client: &BacklogApiClient, target: &StarTarget) -> CliResult<()> {
+    use backlog_api_client::AddStarParams;
+
     let params = match target {
         StarTarget::Issue { issue_id } => AddStarParams::issue(*issue_id),
         StarTarget::Comment {
@@ -75,7 +144,87 @@ async fn handle_add_star(api: &StarApi, target: &StarTarget) -> anyhow::Result<(
         }
     };
 
-    api.add_star(params).await?;
+    client.star().add_star(params).await?;
     println!("Star added successfully");
     Ok(())
 }
+
+#[cfg(feature = "star_writable")]
+async fn remove(client: &BacklogApiClient, target: &str) -> CliResult<()> {
+    let item: StarredItem = target.parse().map_err(anyhow::Error::msg)?;
+    let myself = client.user().get_own_user(GetOwnUserParams::new()).await?;
+    let entity = client.starred_entity(&item).await?;
+    let Some(star_id) = entity.star_by(myself.id) else {
+        anyhow::bail!("You have not starred {}", entity.title());
+    };
+
+    client.star().remove_star(star_id).await?;
+    println!(
+        "Removed your star from {} ({} star(s) left)",
+        entity.title(),
+        entity.star_count().saturating_sub(1)
+    );
+    Ok(())
+}
+
+async fn mine(
+    client: &BacklogApiClient,
+    per_user: u32,
+    kind: Option<&str>,
+    json: bool,
+) -> CliResult<()> {
+    let myself = client.user().get_own_user(GetOwnUserParams::new()).await?;
+    let mut given = client.stars_given(myself.id, per_user).await?;
+    if let Some(kind) = kind {
+        let kind = match kind {
+            "pr" => "pull request",
+            "pr-comment" => "pull request comment",
+            kind => kind,
+        };
+        given
+            .stars
+            .retain(|star| star.item.as_ref().is_some_and(|item| item.kind() == kind));
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&given)?);
+        return Ok(());
+    }
+    for failure in &given.failures {
+        eprintln!(
+            "Warning: stars received by {}: {}",
+            failure.user, failure.message
+        );
+    }
+    if given.stars.is_empty() {
+        println!("No stars found");
+        return Ok(());
+    }
+    star_table(&given).printstd();
+    let counts: Vec<String> = given
+        .count_by_kind()
+        .into_iter()
+        .map(|(kind, count)| format!("{count} {kind}"))
+        .collect();
+    println!("{} star(s): {}", given.stars.len(), counts.join(", "));
+    Ok(())
+}
+
+fn star_table(given: &GivenStars) -> Table {
+    let mut table = Table::new();
+    table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    table.set_titles(row!["Starred", "Item", "Title", "URL"]);
+    for star in &given.stars {
+        table.add_row(row![
+            star.created
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d"),
+            star.item
+                .as_ref()
+                .map_or_else(|| "other".to_string(), StarredItem::to_string),
+            truncate_text(&star.title, 60),
+            star.url
+        ]);
+    }
+    table
+}
@@ -1,7 +1,12 @@
 use crate::commands::common::CliResult;
-use backlog_api_client::{ListTeamsOrder, ListTeamsParams, ListTeamsResponse, TeamApi};
+use backlog_api_client::client::BacklogApiClient;
+use backlog_api_client::{ListTeamsOrder, ListTeamsParams, ListTeamsResponse};
+#[cfg(feature = "team_writable")]
+use backlog_core::id::UserId;
 use backlog_core::{id::TeamId, identifier::Identifier};
 use backlog_team::api::{GetTeamIconParams, GetTeamParams};
+#[cfg(feature = "team_writable")]
+use backlog_team::{TeamResponse, api::TeamApi};
 use clap::{Parser, ValueEnum};
 use std::path::PathBuf;
 
@@ -48,6 +53,80 @@ pub enum TeamCommands {
         #[clap(short, long)]
         output: PathBuf,
     },
+    /// Create a team
+    #[cfg(feature = "team_writable")]
+    Add {
+        /// Team name
+        #[clap(name = "NAME")]
+        name: String,
+
+        /// Members as user IDs, login IDs or mail addresses (comma-separated)
+        #[clap(short, long, value_delimiter = ',')]
+        members: Vec<String>,
+    },
+    /// Rename a team or replace its member list
+    #[cfg(feature = "team_writable")]
+    Update {
+        /// Team ID or name
+        #[clap(name = "TEAM")]
+        team: String,
+
+        /// New team name
+        #[clap(short, long)]
+        name: Option<String>,
+
+        /// Full member list as user IDs, login IDs or mail addresses (comma-separated)
+        #[clap(short, long, value_delimiter = ',')]
+        members: Option<Vec<String>>,
+    },
+    /// Delete a team
+    #[cfg(feature = "team_writable")]
+    Delete {
+        /// Team ID or name
+        #[clap(name = "TEAM")]
+        team: String,
+
+        /// Delete without confirmation
+        #[clap(short, long)]
+        force: bool,
+    },
+    /// Add users to a team, keeping the current members
+    #[cfg(feature = "team_writable")]
+    AddMember {
+        /// Team ID or name
+        #[clap(name = "TEAM")]
+        team: String,
+
+        /// Users to add (user IDs, login IDs or mail addresses)
+        #[clap(name = "USER", required = true)]
+        users: Vec<String>,
+    },
+    /// Remove users from a team, keeping the other members
+    #[cfg(feature = "team_writable")]
+    RemoveMember {
+        /// Team ID or name
+        #[clap(name = "TEAM")]
+        team: String,
+
+        /// Users to remove (user IDs, login IDs or mail addresses)
+        #[clap(name = "USER", required = true)]
+        users: Vec<String>,
+    },
+    /// Make a team's members match a list of users in a file
+    #[cfg(feature = "team_writable")]
+    Sync {
+        /// Team ID or name
+        #[clap(name = "TEAM")]
+        team: String,
+
+        /// File with one user ID, login ID or mail address per line; `#` starts a comment
+        #[clap(long, value_name = "FILE")]
+        members_file: PathBuf,
+
+        /// Show the changes without applying them
+        #[clap(long)]
+        dry_run: bool,
+    },
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
@@ -63,7 +142,8 @@ pub enum OutputFormat {
     Csv,
 }
 
-pub async fn handle_team_command(api: TeamApi, args: TeamArgs) -> CliResult<()> {
+pub async fn handle_team_command(client: &BacklogApiClient, args: TeamArgs) -> CliResult<()> {
+    let api = client.team();
     match args.command {
         TeamCommands::Show { team_id } => {
             let params = GetTeamParams {
@@ -128,10 +208,266 @@ pub async fn handle_team_command(api: TeamApi, args: TeamArgs) -> CliResult<()>
             std::fs::write(&output, &icon.bytes)?;
             println!("✅ Team icon saved to: {}", output.display());
         }
+        #[cfg(feature = "team_writable")]
+        TeamCommands::Add { name, members } => {
+            let members = resolve_users(client, &members).await?;
+            let team = api
+                .add_team(backlog_team::api::AddTeamParams { name, members })
+                .await?;
+            println!(
+                "✅ Team created: {} (ID: {}, {} members)",
+                team.name,
+                team.id.value(),
+                team.members.len()
+            );
+        }
+        #[cfg(feature = "team_writable")]
+        TeamCommands::Update {
+            team,
+            name,
+            members,
+        } => {
+            if name.is_none() && members.is_none() {
+                anyhow::bail!("Nothing to update. Specify --name and/or --members");
+            }
+            let current = resolve_team(&api, &team).await?;
+            let members = match members {
+                Some(members) => Some(non_empty_members(resolve_users(client, &members).await?)?),
+                None => None,
+            };
+            let team = update_team(&api, current.id, name, members).await?;
+            println!(
+                "✅ Team updated: {} (ID: {}, {} members)",
+                team.name,
+                team.id.value(),
+                team.members.len()
+            );
+        }
+        #[cfg(feature = "team_writable")]
+        TeamCommands::Delete { team, force } => {
+            let team = resolve_team(&api, &team).await?;
+            println!(
+                "⚠️  WARNING: This will permanently delete team {} (ID: {}, {} members)!",
+                team.name,
+                team.id.value(),
+                team.members.len()
+            );
+            if !force {
+                println!("Are you sure you want to continue? Type 'yes' to confirm:");
+                let mut confirmation = String::new();
+                std::io::stdin().read_line(&mut confirmation)?;
+                if confirmation.trim() != "yes" {
+                    println!("Team deletion cancelled.");
+                    return Ok(());
+                }
+            }
+            let team = api
+                .delete_team(backlog_team::api::DeleteTeamParams { team_id: team.id })
+                .await?;
+            println!("✅ Team deleted: {} (ID: {})", team.name, team.id.value());
+        }
+        #[cfg(feature = "team_writable")]
+        TeamCommands::AddMember { team, users } => {
+            let team = resolve_team(&api, &team).await?;
+            let users = resolve_users(client, &users).await?;
+            let current = member_ids(&team);
+            let mut members = current.clone();
+            members.extend(users.iter().filter(|id| !current.contains(id)));
+            converge(&api, &team, members, false).await?;
+        }
+        #[cfg(feature = "team_writable")]
+        TeamCommands::RemoveMember { team, users } => {
+            let team = resolve_team(&api, &team).await?;
+            let users = resolve_users(client, &users).await?;
+            let members = member_ids(&team)
+                .into_iter()
+                .filter(|id| !users.contains(id))
+                .collect();
+            converge(&api, &team, members, false).await?;
+        }
+        #[cfg(feature = "team_writable")]
+        TeamCommands::Sync {
+            team,
+            members_file,
+            dry_run,
+        } => {
+            let text = std::fs::read_to_string(&members_file)
+                .map_err(|e| anyhow::anyhow!("Failed to read {}: {e}", members_file.display()))?;
+            let team = resolve_team(&api, &team).await?;
+            let members = resolve_users(client, &parse_members_file(&text)).await?;
+            converge(&api, &team, members, dry_run).await?;
+        }
     }
     Ok(())
 }
 
+/// Find a team by numeric ID, or by exact name among all teams
+#[cfg(feature = "team_writable")]
+async fn resolve_team(api: &TeamApi, team: &str) -> CliResult<TeamResponse> {
+    if let Ok(id) = team.parse::<u32>() {
+        let params = GetTeamParams {
+            team_id: TeamId::new(id),
+        };
+        return Ok(api.get_team(params).await?);
+    }
+
+    const PAGE_SIZE: u32 = 100;
+    let mut matches = Vec::new();
+    let mut offset = 0;
+    loop {
+        let params = ListTeamsParams {
+            order: Some(ListTeamsOrder::Asc),
+            offset: Some(offset),
+            count: Some(PAGE_SIZE),
+        };
+        let page = api.list_teams(params).await?;
+        let page_len = page.len() as u32;
+        matches.extend(page.into_iter().filter(|t| t.team.name == team));
+        if page_len < PAGE_SIZE {
+            break;
+        }
+        offset += PAGE_SIZE;
+    }
+
+    match matches.len() {
+        0 => anyhow::bail!("Team not found: '{team}'"),
+        1 => Ok(matches.remove(0).team),
+        n => anyhow::bail!("{n} teams are named '{team}'; use the team ID instead"),
+    }
+}
+
+/// Resolve user references to user IDs
+///
+/// A numeric reference is a user ID; anything else is matched against login
+/// IDs and mail addresses. Every reference must match a user in the space.
+#[cfg(feature = "team_writable")]
+async fn resolve_users(client: &BacklogApiClient, refs: &[String]) -> CliResult<Vec<UserId>> {
+    if refs.is_empty() {
+        return Ok(Vec::new());
+    }
+    let users = client
+        .user()
+        .get_user_list(backlog_user::GetUserListParams::new())
+        .await?;
+
+    let mut ids = Vec::new();
+    let mut unknown = Vec::new();
+    for user_ref in refs.iter().map(|r| r.trim()).filter(|r| !r.is_empty()) {
+        let found = match user_ref.parse::<u32>() {
+            Ok(id) => users.iter().find(|u| u.id.value() == id),
+            Err(_) => users.iter().find(|u| {
+                u.user_id.as_deref() == Some(user_ref)
+                    || u.mail_address.eq_ignore_ascii_case(user_ref)
+            }),
+        };
+        match found {
+            Some(user) if !ids.contains(&user.id) => ids.push(user.id),
+            Some(_) => {}
+            None => unknown.push(user_ref.to_string()),
+        }
+    }
+
+    if !unknown.is_empty() {
+        anyhow::bail!("Unknown user(s): {}", unknown.join(", "));
+    }
+    Ok(ids)
+}
+
+/// User references from a members file: one per line, `#` comments and blank lines ignored
+#[cfg(feature = "team_writable")]
+fn parse_members_file(text: &str) -> Vec<String> {
+    text.lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(feature = "team_writable")]
+fn member_ids(team: &TeamResponse) -> Vec<UserId> {
+    team.members.iter().map(|m| m.id).collect()
+}
+
+// The API treats an empty `members[]` as "unchanged", so a team cannot be emptied
+#[cfg(feature = "team_writable")]
+fn non_empty_members(members: Vec<UserId>) -> CliResult<Vec<UserId>> {
+    if members.is_empty() {
+        anyhow::bail!("A team must keep at least one member; delete the team instead");
+    }
+    Ok(members)
+}
+
+#[cfg(feature = "team_writable")]
+async fn update_team(
+    api: &TeamApi,
+    team_id: TeamId,
+    name: Option<String>,
+    members: Option<Vec<UserId>>,
+) -> CliResult<TeamResponse> {
+    let params = backlog_team::api::UpdateTeamParams {
+        team_id,
+        name,
+        members,
+    };
+    Ok(api.update_team(params).await?)
+}
+
+/// Replace the team's member list with `members`, printing the difference
+#[cfg(feature = "team_writable")]
+async fn converge(
+    api: &TeamApi,
+    team: &TeamResponse,
+    members: Vec<UserId>,
+    dry_run: bool,
+) -> CliResult<()> {
+    let current = member_ids(team);
+    let added: Vec<_> = members.iter().filter(|id| !current.contains(id)).collect();
+    let removed: Vec<_> = team
+        .members
+        .iter()
+        .filter(|m| !members.contains(&m.id))
+        .collect();
+
+    if added.is_empty() && removed.is_empty() {
+        println!(
+            "✅ Team {} is already up to date ({} members)",
+            team.name,
+            current.len()
+        );
+        return Ok(());
+    }
+
+    for id in &added {
+        println!("  + user {}", id.value());
+    }
+    for member in &removed {
+        println!(
+            "  - {} ({})",
+            member.name,
+            member.user_id.as_deref().unwrap_or("N/A")
+        );
+    }
+    if dry_run {
+        println!(
+            "Would add {} and remove {} member(s) of team {}",
+            added.len(),
+            removed.len(),
+            team.name
+        );
+        return Ok(());
+    }
+
+    let (added, removed) = (added.len(), removed.len());
+    let members = non_empty_members(members)?;
+    let updated = update_team(api, team.id, None, Some(members)).await?;
+    println!(
+        "✅ Team {} updated: {added} added, {removed} removed, {} members",
+        updated.name,
+        updated.members.len()
+    );
+    Ok(())
+}
+
 fn display_teams_table(teams: &ListTeamsResponse) {
     use prettytable::{Cell, Row, Table, format};
 
@@ -190,3 +526,26 @@ fn escape_csv(s: &str) -> String {
         s.to_string()
     }
 }
+
+#[cfg(all(test, feature = "team_writable"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_members_file() {
+        let text = "# Platform team\nalice\n\n  42  \nbob@example.com # on loan\n#carol\n";
+        assert_eq!(
+            parse_members_file(text),
+            vec!["alice", "42", "bob@example.com"]
+        );
+    }
+
+    #[test]
+    fn test_non_empty_members() {
+        assert!(non_empty_members(Vec::new()).is_err());
+        assert_eq!(
+            non_empty_members(vec![UserId::new(1)]).unwrap(),
+            vec![UserId::new(1)]
+        );
+    }
+}
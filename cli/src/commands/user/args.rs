@@ -30,6 +30,72 @@ pub enum UserCommands {
         #[clap(short, long, value_name = "FILE_PATH")]
         output: PathBuf,
     },
+    /// Add a user, or many users from a CSV file
+    ///
+    /// The initial password is read from BLG_PASSWORD or from the first line
+    /// of stdin, never from the command line.
+    #[cfg(feature = "user_writable")]
+    Add {
+        /// Login ID of the new user
+        #[clap(long, required_unless_present = "from_csv")]
+        user_id: Option<String>,
+        /// Display name
+        #[clap(long, required_unless_present = "from_csv")]
+        name: Option<String>,
+        /// Mail address
+        #[clap(long, required_unless_present = "from_csv")]
+        mail_address: Option<String>,
+        /// Role (admin, user, reporter, viewer, guest or 1-5); default for CSV rows without a role
+        #[clap(long, default_value = "user")]
+        role: String,
+        /// Add users from a CSV file with columns userId,password,name,mailAddress[,role]
+        #[clap(long, value_name = "FILE", conflicts_with_all = ["user_id", "name", "mail_address"])]
+        from_csv: Option<PathBuf>,
+        /// Validate the input and show what would be done without calling the API
+        #[clap(long)]
+        dry_run: bool,
+    },
+    /// Update a user, or many users from a CSV file
+    #[cfg(feature = "user_writable")]
+    Update {
+        /// User ID
+        #[clap(name = "USER_ID", required_unless_present = "from_csv")]
+        user_id: Option<u32>,
+        /// New display name
+        #[clap(long)]
+        name: Option<String>,
+        /// Change the password, read from BLG_PASSWORD or from the first line of stdin
+        #[clap(long)]
+        password: bool,
+        /// New mail address
+        #[clap(long)]
+        mail_address: Option<String>,
+        /// New role (admin, user, reporter, viewer, guest or 1-5)
+        #[clap(long)]
+        role: Option<String>,
+        /// Update users from a CSV file with an id column and any of name,password,mailAddress,role
+        #[clap(long, value_name = "FILE", conflicts_with_all = ["USER_ID", "name", "password", "mail_address", "role"])]
+        from_csv: Option<PathBuf>,
+        /// Validate the input and show what would be done without calling the API
+        #[clap(long)]
+        dry_run: bool,
+    },
+    /// Delete a user, or many users listed in a CSV file
+    #[cfg(feature = "user_writable")]
+    Delete {
+        /// User ID
+        #[clap(name = "USER_ID", required_unless_present = "from_csv")]
+        user_id: Option<u32>,
+        /// Delete users listed in the id column of a CSV file
+        #[clap(long, value_name = "FILE", conflicts_with = "USER_ID")]
+        from_csv: Option<PathBuf>,
+        /// Delete without confirmation
+        #[clap(short, long)]
+        force: bool,
+        /// Validate the input and show what would be done without calling the API
+        #[clap(long)]
+        dry_run: bool,
+    },
     /// Get user star count
     StarCount {
         /// User ID
@@ -19,6 +19,70 @@ pub async fn execute(client: &BacklogApiClient, args: UserArgs) -> CliResult<()>
             subcommands::info::icon(client, user_id, output).await?
         }
 
+        // User management (from subcommands::manage)
+        #[cfg(feature = "user_writable")]
+        UserCommands::Add {
+            user_id,
+            name,
+            mail_address,
+            role,
+            from_csv,
+            dry_run,
+        } => match from_csv {
+            Some(path) => subcommands::manage::add_from_csv(client, &path, &role, dry_run).await?,
+            None => {
+                let user = subcommands::manage::NewUser {
+                    user_id: user_id.unwrap_or_default(),
+                    password: subcommands::manage::read_password()?,
+                    name: name.unwrap_or_default(),
+                    mail_address: mail_address.unwrap_or_default(),
+                    role,
+                };
+                subcommands::manage::add(client, user, dry_run).await?
+            }
+        },
+        #[cfg(feature = "user_writable")]
+        UserCommands::Update {
+            user_id,
+            name,
+            password,
+            mail_address,
+            role,
+            from_csv,
+            dry_run,
+        } => match (from_csv, user_id) {
+            (Some(path), _) => subcommands::manage::update_from_csv(client, &path, dry_run).await?,
+            (None, Some(user_id)) => {
+                let password = password
+                    .then(subcommands::manage::read_password)
+                    .transpose()?;
+                let changes = subcommands::manage::UserChanges {
+                    user_id,
+                    name,
+                    password,
+                    mail_address,
+                    role,
+                };
+                subcommands::manage::update(client, changes, dry_run).await?
+            }
+            (None, None) => anyhow::bail!("USER_ID or --from-csv is required"),
+        },
+        #[cfg(feature = "user_writable")]
+        UserCommands::Delete {
+            user_id,
+            from_csv,
+            force,
+            dry_run,
+        } => match (from_csv, user_id) {
+            (Some(path), _) => {
+                subcommands::manage::delete_from_csv(client, &path, force, dry_run).await?
+            }
+            (None, Some(user_id)) => {
+                subcommands::manage::delete(client, user_id, force, dry_run).await?
+            }
+            (None, None) => anyhow::bail!("USER_ID or --from-csv is required"),
+        },
+
         // Star operations (from subcommands::stars)
         UserCommands::StarCount {
             user_id,
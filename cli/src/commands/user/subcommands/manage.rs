@@ -0,0 +1,514 @@
+//! User administration
+//!
+//! Adds, updates and deletes users either one at a time or in bulk from a CSV
+//! file. Bulk input is validated completely before any request is sent, so a
+//! typo in the last row does not leave a half-imported file behind.
+
+use crate::commands::common::{CliResult, parse_csv};
+use anyhow::Context;
+use backlog_api_client::client::BacklogApiClient;
+use backlog_api_client::{AddUserParams, ApiError, DeleteUserParams, UpdateUserParams};
+use backlog_core::identifier::{Identifier, UserId};
+use backlog_core::{Role, User};
+use backlog_user::GetUserListParams;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::path::Path;
+use std::str::FromStr;
+
+/// A user to add, as given on the command line or in a CSV row
+pub(crate) struct NewUser {
+    pub user_id: String,
+    pub password: String,
+    pub name: String,
+    pub mail_address: String,
+    pub role: String,
+}
+
+impl NewUser {
+    fn to_params(&self) -> CliResult<AddUserParams> {
+        for (column, value) in [
+            ("userId", &self.user_id),
+            ("password", &self.password),
+            ("name", &self.name),
+            ("mailAddress", &self.mail_address),
+        ] {
+            if value.trim().is_empty() {
+                anyhow::bail!("{column} must not be empty");
+            }
+        }
+        Ok(AddUserParams::new(
+            self.user_id.trim(),
+            &self.password,
+            self.name.trim(),
+            self.mail_address.trim(),
+            parse_role(&self.role)?,
+        ))
+    }
+}
+
+/// Changes to apply to an existing user; `None` leaves the field unchanged
+pub(crate) struct UserChanges {
+    pub user_id: u32,
+    pub name: Option<String>,
+    pub password: Option<String>,
+    pub mail_address: Option<String>,
+    pub role: Option<String>,
+}
+
+impl UserChanges {
+    fn to_params(&self) -> CliResult<UpdateUserParams> {
+        let mut params = UpdateUserParams::new(UserId::new(self.user_id));
+        if let Some(name) = &self.name {
+            params = params.name(name.trim());
+        }
+        if let Some(password) = &self.password {
+            params = params.password(password);
+        }
+        if let Some(mail_address) = &self.mail_address {
+            params = params.mail_address(mail_address.trim());
+        }
+        if let Some(role) = &self.role {
+            params = params.role_type(parse_role(role)?);
+        }
+        if params.name.is_none()
+            && params.password.is_none()
+            && params.mail_address.is_none()
+            && params.role_type.is_none()
+        {
+            anyhow::bail!("nothing to update for user {}", self.user_id);
+        }
+        Ok(params)
+    }
+}
+
+/// Parse a role given by name (`admin`, `user`, ...) or by number (1-5)
+fn parse_role(role: &str) -> CliResult<Role> {
+    let role = role.trim();
+    if let Ok(id) = role.parse::<i32>() {
+        return Role::try_from(id).map_err(|_| anyhow::anyhow!("Invalid role: '{role}'"));
+    }
+    Role::from_str(&role.to_lowercase()).map_err(|_| {
+        anyhow::anyhow!("Invalid role: '{role}' (expected admin, user, reporter, viewer or guest)")
+    })
+}
+
+/// Look up a CSV column by name, ignoring case
+fn column<'a>(record: &'a HashMap<String, String>, name: &str) -> Option<&'a str> {
+    record
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+/// Optional CSV cell; missing columns and empty cells mean "unchanged"
+fn optional_column(record: &HashMap<String, String>, name: &str) -> Option<String> {
+    column(record, name)
+        .filter(|value| !value.trim().is_empty())
+        .map(str::to_string)
+}
+
+fn parse_user_id(value: Option<&str>) -> CliResult<u32> {
+    let value = value.ok_or_else(|| anyhow::anyhow!("missing id column"))?;
+    value
+        .trim()
+        .parse()
+        .with_context(|| format!("invalid user id '{value}'"))
+}
+
+/// Read a CSV file and convert every row, reporting all invalid rows at once
+fn read_rows<T>(
+    path: &Path,
+    mut convert: impl FnMut(&HashMap<String, String>) -> CliResult<T>,
+) -> CliResult<Vec<T>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let records = parse_csv(&text).with_context(|| format!("Invalid CSV: {}", path.display()))?;
+
+    let mut rows = Vec::new();
+    let mut errors = Vec::new();
+    for (i, record) in records.iter().enumerate() {
+        match convert(record) {
+            Ok(row) => rows.push(row),
+            Err(e) => errors.push(format!("  Row {}: {e}", i + 1)),
+        }
+    }
+    if !errors.is_empty() {
+        anyhow::bail!(
+            "{} invalid row(s) in {}, nothing was changed:\n{}",
+            errors.len(),
+            path.display(),
+            errors.join("\n")
+        );
+    }
+    if rows.is_empty() {
+        anyhow::bail!("No rows found in {}", path.display());
+    }
+    Ok(rows)
+}
+
+/// Send one request per item, printing each outcome, and fail if any request failed
+async fn run_bulk<P, F, Fut>(verb: &str, items: Vec<(String, P)>, action: F) -> CliResult<()>
+where
+    F: Fn(P) -> Fut,
+    Fut: Future<Output = Result<User, ApiError>>,
+{
+    let total = items.len();
+    let mut failed = 0;
+    for (label, params) in items {
+        match action(params).await {
+            Ok(user) => println!("  ✅ {label}: [{}] {}", user.id, user.name),
+            Err(e) => {
+                eprintln!("  ❌ {label}: {e}");
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        anyhow::bail!("Failed to {verb} {failed} of {total} users");
+    }
+    println!("✅ {total} users {verb}d successfully");
+    Ok(())
+}
+
+/// Read a password from `BLG_PASSWORD`, or else from the first line of stdin
+///
+/// Passwords are never taken from the command line, where `ps` and the shell
+/// history would reveal them.
+pub(crate) fn read_password() -> CliResult<String> {
+    use std::io::{IsTerminal, Write};
+
+    if let Ok(password) = std::env::var("BLG_PASSWORD")
+        && !password.is_empty()
+    {
+        return Ok(password);
+    }
+    if std::io::stdin().is_terminal() {
+        eprint!("Password: ");
+        std::io::stderr().flush()?;
+    }
+    let mut line = String::new();
+    std::io::stdin()
+        .read_line(&mut line)
+        .context("Failed to read the password from stdin")?;
+    let password = line.trim_end_matches(['\r', '\n']);
+    if password.is_empty() {
+        anyhow::bail!("No password given; set BLG_PASSWORD or pass it on stdin");
+    }
+    Ok(password.to_string())
+}
+
+fn confirm(prompt: &str) -> CliResult<bool> {
+    println!("{prompt} Type 'yes' to confirm:");
+    let mut confirmation = String::new();
+    std::io::stdin()
+        .read_line(&mut confirmation)
+        .context("Failed to read confirmation")?;
+    Ok(confirmation.trim() == "yes")
+}
+
+fn print_user(user: &User) {
+    println!("User ID: {}", user.id);
+    if let Some(login_id) = &user.user_id {
+        println!("Login ID: {login_id}");
+    }
+    println!("Name: {}", user.name);
+    println!("Email: {}", user.mail_address);
+    println!("Role: {}", user.role_type);
+}
+
+/// Add a single user
+pub(crate) async fn add(client: &BacklogApiClient, user: NewUser, dry_run: bool) -> CliResult<()> {
+    let params = user.to_params()?;
+    if dry_run {
+        println!(
+            "Would add user {} ({}, {}) as {}",
+            params.user_id, params.name, params.mail_address, params.role_type
+        );
+        return Ok(());
+    }
+
+    let user = client.user().add_user(params).await?;
+    println!("✅ User added successfully");
+    print_user(&user);
+    Ok(())
+}
+
+/// Add users from a CSV file with columns `userId,password,name,mailAddress[,role]`
+///
+/// Rows whose login ID already exists in the space are skipped, so an
+/// onboarding file can be re-run after fixing failed rows.
+pub(crate) async fn add_from_csv(
+    client: &BacklogApiClient,
+    path: &Path,
+    default_role: &str,
+    dry_run: bool,
+) -> CliResult<()> {
+    parse_role(default_role)?;
+    let mut seen = HashSet::new();
+    let rows = read_rows(path, |record| {
+        let user = NewUser {
+            user_id: column(record, "userId").unwrap_or_default().to_string(),
+            password: column(record, "password").unwrap_or_default().to_string(),
+            name: column(record, "name").unwrap_or_default().to_string(),
+            mail_address: column(record, "mailAddress")
+                .unwrap_or_default()
+                .to_string(),
+            role: optional_column(record, "role").unwrap_or_else(|| default_role.to_string()),
+        };
+        let params = user.to_params()?;
+        if !seen.insert(params.user_id.clone()) {
+            anyhow::bail!("duplicate userId '{}'", params.user_id);
+        }
+        Ok(params)
+    })?;
+
+    let existing: HashSet<String> = client
+        .user()
+        .get_user_list(GetUserListParams::new())
+        .await?
+        .into_iter()
+        .filter_map(|user| user.user_id)
+        .collect();
+
+    let mut items = Vec::new();
+    for params in rows {
+        if existing.contains(&params.user_id) {
+            println!("  ⏭️  {}: already exists, skipped", params.user_id);
+        } else {
+            items.push((params.user_id.clone(), params));
+        }
+    }
+
+    if items.is_empty() {
+        println!("✅ All users in {} already exist", path.display());
+        return Ok(());
+    }
+    if dry_run {
+        println!("Would add {} users:", items.len());
+        for (_, params) in &items {
+            println!(
+                "  {} ({}, {}) as {}",
+                params.user_id, params.name, params.mail_address, params.role_type
+            );
+        }
+        return Ok(());
+    }
+
+    println!("Adding {} users from {}", items.len(), path.display());
+    run_bulk("add", items, |params| async move {
+        client.user().add_user(params).await
+    })
+    .await
+}
+
+/// Update a single user
+pub(crate) async fn update(
+    client: &BacklogApiClient,
+    changes: UserChanges,
+    dry_run: bool,
+) -> CliResult<()> {
+    let params = changes.to_params()?;
+    if dry_run {
+        println!(
+            "Would update user {}: {}",
+            changes.user_id,
+            describe(&params)
+        );
+        return Ok(());
+    }
+
+    let user = client.user().update_user(params).await?;
+    println!("✅ User updated successfully");
+    print_user(&user);
+    Ok(())
+}
+
+/// Update users from a CSV file with an `id` column and any of
+/// `name,password,mailAddress,role`; empty cells leave the field unchanged
+pub(crate) async fn update_from_csv(
+    client: &BacklogApiClient,
+    path: &Path,
+    dry_run: bool,
+) -> CliResult<()> {
+    let rows = read_rows(path, |record| {
+        UserChanges {
+            user_id: parse_user_id(column(record, "id"))?,
+            name: optional_column(record, "name"),
+            password: optional_column(record, "password"),
+            mail_address: optional_column(record, "mailAddress"),
+            role: optional_column(record, "role"),
+        }
+        .to_params()
+    })?;
+
+    let items: Vec<_> = rows
+        .into_iter()
+        .map(|params| (format!("user {}", params.user_id), params))
+        .collect();
+    if dry_run {
+        println!("Would update {} users:", items.len());
+        for (label, params) in &items {
+            println!("  {label}: {}", describe(params));
+        }
+        return Ok(());
+    }
+
+    println!("Updating {} users from {}", items.len(), path.display());
+    run_bulk("update", items, |params| async move {
+        client.user().update_user(params).await
+    })
+    .await
+}
+
+/// Summary of the fields an update changes, without revealing the password
+fn describe(params: &UpdateUserParams) -> String {
+    let mut fields = Vec::new();
+    if let Some(name) = &params.name {
+        fields.push(format!("name={name}"));
+    }
+    if params.password.is_some() {
+        fields.push("password=********".to_string());
+    }
+    if let Some(mail_address) = &params.mail_address {
+        fields.push(format!("mailAddress={mail_address}"));
+    }
+    if let Some(role) = params.role_type {
+        fields.push(format!("role={role}"));
+    }
+    fields.join(", ")
+}
+
+/// Delete a single user after confirmation
+pub(crate) async fn delete(
+    client: &BacklogApiClient,
+    user_id: u32,
+    force: bool,
+    dry_run: bool,
+) -> CliResult<()> {
+    let users = known_users(client, &[user_id]).await?;
+    let label = &users[0].0;
+    if dry_run {
+        println!("Would delete {label}");
+        return Ok(());
+    }
+
+    println!("⚠️  WARNING: This will permanently delete {label} from the space!");
+    if !force && !confirm("Are you sure you want to continue?")? {
+        println!("User deletion cancelled.");
+        return Ok(());
+    }
+
+    let user = client
+        .user()
+        .delete_user(DeleteUserParams::new(UserId::new(user_id)))
+        .await?;
+    println!("✅ User deleted successfully");
+    print_user(&user);
+    Ok(())
+}
+
+/// Delete the users listed in the `id` column of a CSV file after one confirmation
+pub(crate) async fn delete_from_csv(
+    client: &BacklogApiClient,
+    path: &Path,
+    force: bool,
+    dry_run: bool,
+) -> CliResult<()> {
+    let mut seen = HashSet::new();
+    let ids: Vec<u32> = read_rows(path, |record| parse_user_id(column(record, "id")))?
+        .into_iter()
+        .filter(|id| seen.insert(*id))
+        .collect();
+    let users = known_users(client, &ids).await?;
+
+    println!(
+        "{} {} users:",
+        if dry_run { "Would delete" } else { "Deleting" },
+        users.len()
+    );
+    for (label, _) in &users {
+        println!("  {label}");
+    }
+    if dry_run {
+        return Ok(());
+    }
+
+    println!("⚠️  WARNING: This will permanently delete these users from the space!");
+    if !force && !confirm("Are you sure you want to continue?")? {
+        println!("User deletion cancelled.");
+        return Ok(());
+    }
+
+    run_bulk("delete", users, |user_id| async move {
+        client
+            .user()
+            .delete_user(DeleteUserParams::new(UserId::new(user_id)))
+            .await
+    })
+    .await
+}
+
+/// Resolve user IDs to display labels, failing before any deletion if one is unknown
+async fn known_users(client: &BacklogApiClient, ids: &[u32]) -> CliResult<Vec<(String, u32)>> {
+    let users: HashMap<u32, User> = client
+        .user()
+        .get_user_list(GetUserListParams::new())
+        .await?
+        .into_iter()
+        .map(|user| (user.id.value(), user))
+        .collect();
+
+    let unknown: Vec<String> = ids
+        .iter()
+        .filter(|id| !users.contains_key(id))
+        .map(u32::to_string)
+        .collect();
+    if !unknown.is_empty() {
+        anyhow::bail!("Unknown user ID(s): {}", unknown.join(", "));
+    }
+
+    Ok(ids
+        .iter()
+        .map(|id| {
+            let user = &users[id];
+            let login_id = user.user_id.as_deref().unwrap_or("N/A");
+            (format!("[{id}] {} ({login_id})", user.name), *id)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_role() {
+        assert_eq!(parse_role("admin").unwrap(), Role::Admin);
+        assert_eq!(parse_role(" Reporter ").unwrap(), Role::Reporter);
+        assert_eq!(parse_role("4").unwrap(), Role::Viewer);
+        assert!(parse_role("owner").is_err());
+        assert!(parse_role("0").is_err());
+    }
+
+    #[test]
+    fn test_update_requires_changes() {
+        let changes = UserChanges {
+            user_id: 1,
+            name: None,
+            password: None,
+            mail_address: None,
+            role: None,
+        };
+        assert!(changes.to_params().is_err());
+
+        let changes = UserChanges {
+            role: Some("viewer".to_string()),
+            password: Some("secret".to_string()),
+            ..changes
+        };
+        let params = changes.to_params().unwrap();
+        assert_eq!(describe(&params), "password=********, role=viewer");
+    }
+}
@@ -1,4 +1,6 @@
 pub(crate) mod info;
+#[cfg(feature = "user_writable")]
+pub(crate) mod manage;
 pub(crate) mod notifications;
 pub(crate) mod stars;
 pub(crate) mod watchings;
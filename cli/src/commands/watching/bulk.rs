@@ -0,0 +1,502 @@
+//! Watching workflows over many issues at once
+//!
+//! Requests run with bounded concurrency; a request rejected by the rate
+//! limit (HTTP 429) is retried once the limit resets, or after a growing
+//! delay when the response does not say when that is.
+
+use crate::commands::common::{CliResult, truncate_text};
+use backlog_api_client::client::BacklogApiClient;
+use backlog_api_client::{ApiError, Watching, WatchingOrder, WatchingSort};
+use backlog_user::{GetOwnUserParams, GetWatchingListParams};
+use prettytable::{Table, row};
+use std::future::Future;
+use std::time::Duration;
+
+#[cfg(feature = "watching_writable")]
+use crate::commands::common::resolve_projects;
+#[cfg(feature = "watching_writable")]
+use backlog_api_client::{AddWatchingParams, GetIssueListParamsBuilder, Issue, IssueIdOrKey};
+#[cfg(feature = "watching_writable")]
+use backlog_core::identifier::{Identifier, IssueId, IssueTypeId, StatusId, UserId};
+#[cfg(feature = "watching_writable")]
+use chrono::{DateTime, Utc};
+#[cfg(feature = "watching_writable")]
+use std::collections::HashSet;
+#[cfg(feature = "watching_writable")]
+use std::sync::Arc;
+#[cfg(feature = "watching_writable")]
+use tokio::sync::Semaphore;
+#[cfg(feature = "watching_writable")]
+use tokio::task::JoinSet;
+
+/// Maximum number of watchings or issues the list APIs return per request
+const PAGE_SIZE: u8 = 100;
+
+/// Seconds to wait before each retry of a rate limited request without `X-RateLimit-Reset`
+const RATE_LIMIT_DELAYS: [u64; 4] = [1, 2, 4, 8];
+/// Longest wait for a rate limit to reset before retrying anyway
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60);
+
+/// Statuses every project has for resolved and closed issues
+#[cfg(feature = "watching_writable")]
+const DONE_STATUS_IDS: [u32; 2] = [3, 4];
+
+/// Run `request` again while the rate limit rejects it
+async fn with_rate_limit<T, F, Fut>(mut request: F) -> Result<T, ApiError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, ApiError>>,
+{
+    for delay in RATE_LIMIT_DELAYS {
+        match request().await {
+            Err(
+                ApiError::HttpStatus {
+                    status: 429,
+                    rate_limit_reset,
+                    ..
+                }
+                | ApiError::UnparseableErrorResponse {
+                    status: 429,
+                    rate_limit_reset,
+                    ..
+                },
+            ) => {
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                tokio::time::sleep(rate_limit_wait(rate_limit_reset, now, delay)).await;
+            }
+            result => return result,
+        }
+    }
+    request().await
+}
+
+/// Time until the rate limit resets at `reset` (Unix seconds), at least a
+/// second and at most [`MAX_RATE_LIMIT_WAIT`], or `fallback` seconds when
+/// the reset time is unknown
+fn rate_limit_wait(reset: Option<u64>, now: u64, fallback: u64) -> Duration {
+    match reset {
+        Some(reset) => {
+            Duration::from_secs(reset.saturating_sub(now).max(1)).min(MAX_RATE_LIMIT_WAIT)
+        }
+        None => Duration::from_secs(fallback),
+    }
+}
+
+/// Run `operation` for every item with at most `concurrency` in flight,
+/// returning each item with its result in completion order
+#[cfg(feature = "watching_writable")]
+async fn run_limited<T, F, Fut>(
+    items: Vec<T>,
+    concurrency: u8,
+    operation: F,
+) -> Vec<(T, Result<(), ApiError>)>
+where
+    T: Send + 'static,
+    F: Fn(&T) -> Fut,
+    Fut: Future<Output = Result<(), ApiError>> + Send + 'static,
+{
+    let semaphore = Arc::new(Semaphore::new(concurrency.max(1).into()));
+    let mut tasks = JoinSet::new();
+    for item in items {
+        let semaphore = Arc::clone(&semaphore);
+        let future = operation(&item);
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            (item, future.await)
+        });
+    }
+    let mut results = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        results.push(joined.unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic())));
+    }
+    results
+}
+
+/// All watchings of the current user, most recently updated issue first
+async fn my_watchings(client: &BacklogApiClient, unread_only: bool) -> CliResult<Vec<Watching>> {
+    let myself = client.user().get_own_user(GetOwnUserParams::new()).await?;
+    let mut watchings = Vec::new();
+    loop {
+        let mut params = GetWatchingListParams::builder()
+            .order(WatchingOrder::Desc)
+            .sort(WatchingSort::IssueUpdated)
+            .count(PAGE_SIZE)
+            .offset(watchings.len() as u64);
+        if unread_only {
+            params = params.resource_already_read(false);
+        }
+        let params = params.build()?;
+        let page = with_rate_limit(|| {
+            let (user, params) = (client.user(), params.clone());
+            async move { user.get_watching_list(myself.id, params).await }
+        })
+        .await?;
+        let full = page.len() == usize::from(PAGE_SIZE);
+        watchings.extend(page);
+        if !full {
+            return Ok(watchings);
+        }
+    }
+}
+
+fn watching_table(watchings: &[&Watching]) -> Table {
+    let mut table = Table::new();
+    table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    table.set_titles(row!["Watching", "Issue", "Summary", "Status", "Updated"]);
+    for watching in watchings {
+        let (key, summary, status) = watching.issue.as_ref().map_or_else(
+            || (String::new(), String::new(), String::new()),
+            |issue| {
+                (
+                    issue.issue_key.to_string(),
+                    truncate_text(&issue.summary, 50),
+                    issue.status.name.clone(),
+                )
+            },
+        );
+        table.add_row(row![
+            watching.id,
+            key,
+            summary,
+            status,
+            watching
+                .last_content_updated
+                .unwrap_or(watching.updated)
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+        ]);
+    }
+    table
+}
+
+/// Print the failed items and fail if there were any
+#[cfg(feature = "watching_writable")]
+fn report_failures(failures: &[String], action: &str) -> CliResult<()> {
+    for failure in failures {
+        eprintln!("Failed to {action} {failure}");
+    }
+    if !failures.is_empty() {
+        anyhow::bail!("Failed to {action} {} item(s)", failures.len());
+    }
+    Ok(())
+}
+
+/// Issue list filters of `blg watching add --query`
+#[cfg(feature = "watching_writable")]
+pub(crate) struct IssueQuery {
+    pub projects: Vec<String>,
+    pub status_ids: Vec<u32>,
+    pub assignee_ids: Vec<u32>,
+    pub issue_type_ids: Vec<u32>,
+    pub keyword: Option<String>,
+    pub limit: u32,
+}
+
+/// Issues matching the query, up to its limit
+#[cfg(feature = "watching_writable")]
+async fn matching_issues(client: &BacklogApiClient, query: &IssueQuery) -> CliResult<Vec<Issue>> {
+    let project_ids: Vec<_> = if query.projects.is_empty() {
+        Vec::new()
+    } else {
+        resolve_projects(client, &query.projects)
+            .await?
+            .into_iter()
+            .map(|project| project.id)
+            .collect()
+    };
+    let mut issues = Vec::new();
+    while (issues.len() as u32) < query.limit {
+        let mut builder = GetIssueListParamsBuilder::default();
+        if !project_ids.is_empty() {
+            builder.project_id(project_ids.clone());
+        }
+        if !query.status_ids.is_empty() {
+            builder.status_id(
+                query
+                    .status_ids
+                    .iter()
+                    .copied()
+                    .map(StatusId::new)
+                    .collect::<Vec<_>>(),
+            );
+        }
+        if !query.assignee_ids.is_empty() {
+            builder.assignee_id(
+                query
+                    .assignee_ids
+                    .iter()
+                    .copied()
+                    .map(UserId::new)
+                    .collect::<Vec<_>>(),
+            );
+        }
+        if !query.issue_type_ids.is_empty() {
+            builder.issue_type_id(
+                query
+                    .issue_type_ids
+                    .iter()
+                    .copied()
+                    .map(IssueTypeId::new)
+                    .collect::<Vec<_>>(),
+            );
+        }
+        if let Some(keyword) = &query.keyword {
+            builder.keyword(keyword.clone());
+        }
+        let count = (query.limit - issues.len() as u32).min(u32::from(PAGE_SIZE));
+        builder
+            .sort("updated")
+            .order("desc")
+            .offset(issues.len() as u32)
+            .count(count);
+        let params = builder.build()?;
+        let page = with_rate_limit(|| {
+            let (issue, params) = (client.issue(), params.clone());
+            async move { issue.get_issue_list(params).await }
+        })
+        .await?;
+        let full = page.len() as u32 == count;
+        issues.extend(page);
+        if !full {
+            break;
+        }
+    }
+    Ok(issues)
+}
+
+/// Watch every issue matching `query` that is not watched yet
+#[cfg(feature = "watching_writable")]
+pub(crate) async fn add_matching(
+    client: &BacklogApiClient,
+    query: IssueQuery,
+    note: Option<String>,
+    dry_run: bool,
+    concurrency: u8,
+) -> CliResult<()> {
+    let has_filter = !query.projects.is_empty()
+        || !query.status_ids.is_empty()
+        || !query.assignee_ids.is_empty()
+        || !query.issue_type_ids.is_empty()
+        || query.keyword.is_some();
+    if !has_filter {
+        anyhow::bail!(
+            "--query needs at least one filter (--project, --status-id, --assignee-id, --issue-type-id or --keyword)"
+        );
+    }
+
+    let issues = matching_issues(client, &query).await?;
+    let watched: HashSet<IssueId> = my_watchings(client, false)
+        .await?
+        .iter()
+        .filter_map(|watching| watching.issue.as_ref().map(|issue| issue.id))
+        .collect();
+    let (already, unwatched): (Vec<Issue>, Vec<Issue>) = issues
+        .into_iter()
+        .partition(|issue| watched.contains(&issue.id));
+
+    if dry_run {
+        for issue in &unwatched {
+            println!("Would watch {} {}", issue.issue_key, issue.summary);
+        }
+        println!(
+            "{} matching issue(s) to watch, {} already watched",
+            unwatched.len(),
+            already.len()
+        );
+        return Ok(());
+    }
+
+    let results = run_limited(unwatched, concurrency, |issue| {
+        let api = client.watching();
+        let mut params = AddWatchingParams::new(IssueIdOrKey::Id(issue.id));
+        if let Some(note) = &note {
+            params = params.with_note(note.clone());
+        }
+        async move {
+            with_rate_limit(|| api.add(params.clone()))
+                .await
+                .map(|_| ())
+        }
+    })
+    .await;
+
+    let mut added = 0;
+    let mut failures = Vec::new();
+    for (issue, result) in results {
+        match result {
+            Ok(()) => {
+                added += 1;
+                println!("Watching {} {}", issue.issue_key, issue.summary);
+            }
+            Err(e) => failures.push(format!("{}: {e}", issue.issue_key)),
+        }
+    }
+    println!(
+        "Watched {added} issue(s), {} already watched",
+        already.len()
+    );
+    report_failures(&failures, "watch")
+}
+
+/// Whether the watched issue is resolved or closed and untouched since `cutoff`
+#[cfg(feature = "watching_writable")]
+pub(crate) fn is_stale(watching: &Watching, cutoff: DateTime<Utc>) -> bool {
+    watching.issue.as_ref().is_some_and(|issue| {
+        DONE_STATUS_IDS.contains(&issue.status.id.value())
+            && DateTime::parse_from_rfc3339(&issue.updated)
+                .is_ok_and(|updated| updated.with_timezone(&Utc) < cutoff)
+    })
+}
+
+/// Remove watchings on issues resolved or closed more than `days` ago
+#[cfg(feature = "watching_writable")]
+pub(crate) async fn prune(
+    client: &BacklogApiClient,
+    days: u32,
+    dry_run: bool,
+    concurrency: u8,
+) -> CliResult<()> {
+    let cutoff = Utc::now() - chrono::Duration::days(days.into());
+    let watchings = my_watchings(client, false).await?;
+    let stale: Vec<&Watching> = watchings
+        .iter()
+        .filter(|watching| is_stale(watching, cutoff))
+        .collect();
+    if stale.is_empty() {
+        println!("No watchings on issues resolved or closed more than {days} day(s) ago");
+        return Ok(());
+    }
+    watching_table(&stale).printstd();
+    if dry_run {
+        println!("Would remove {} watching(s)", stale.len());
+        return Ok(());
+    }
+
+    let stale: Vec<Watching> = stale.into_iter().cloned().collect();
+    let results = run_limited(stale, concurrency, |watching| {
+        let (api, id) = (client.watching(), watching.id);
+        async move { with_rate_limit(|| api.delete(id)).await.map(|_| ()) }
+    })
+    .await;
+    let mut removed = 0;
+    let mut failures = Vec::new();
+    for (watching, result) in results {
+        match result {
+            Ok(()) => removed += 1,
+            Err(e) => failures.push(format!("watching {}: {e}", watching.id)),
+        }
+    }
+    println!("Removed {removed} watching(s)");
+    report_failures(&failures, "remove")
+}
+
+/// Show watched issues updated since they were last read, returning them
+pub(crate) async fn unread(client: &BacklogApiClient) -> CliResult<Vec<Watching>> {
+    let watchings = my_watchings(client, true).await?;
+    if watchings.is_empty() {
+        println!("No unread watchings");
+    } else {
+        watching_table(&watchings.iter().collect::<Vec<_>>()).printstd();
+    }
+    Ok(watchings)
+}
+
+/// Mark the watchings as read
+#[cfg(feature = "watching_writable")]
+pub(crate) async fn mark_read(
+    client: &BacklogApiClient,
+    watchings: Vec<Watching>,
+    concurrency: u8,
+) -> CliResult<()> {
+    if watchings.is_empty() {
+        return Ok(());
+    }
+    let results = run_limited(watchings, concurrency, |watching| {
+        let (api, id) = (client.watching(), watching.id);
+        async move { with_rate_limit(|| api.mark_as_read(id)).await }
+    })
+    .await;
+    let mut marked = 0;
+    let mut failures = Vec::new();
+    for (watching, result) in results {
+        match result {
+            Ok(()) => marked += 1,
+            Err(e) => failures.push(format!("watching {}: {e}", watching.id)),
+        }
+    }
+    println!("Marked {marked} watching(s) as read");
+    report_failures(&failures, "mark as read")
+}
+
+#[cfg(all(test, feature = "watching_writable"))]
+mod tests {
+    use super::*;
+
+    fn watching(status_id: u32, updated: &str) -> Watching {
+        serde_json::from_value(serde_json::json!({
+            "id": 1, "resourceAlreadyRead": true, "note": null, "type": "issue",
+            "issue": {
+                "id": 10, "projectId": 1, "issueKey": "BLG-1", "keyId": 1,
+                "issueType": {"id": 1, "projectId": 1, "name": "Task", "color": "#7ea800", "displayOrder": 0},
+                "summary": "Done", "description": "", "resolution": null,
+                "priority": {"id": 3, "name": "Normal"},
+                "status": {"id": status_id, "projectId": 1, "name": "Status", "color": "#ffffff", "displayOrder": 0},
+                "assignee": null, "category": [], "versions": [], "milestone": [],
+                "startDate": null, "dueDate": null, "estimatedHours": null, "actualHours": null,
+                "parentIssueId": null,
+                "createdUser": {"id": 1, "userId": "admin", "name": "Admin", "roleType": 1, "mailAddress": "admin@example.com"},
+                "created": "2024-01-01T00:00:00Z", "updatedUser": null, "updated": updated,
+                "customFields": [], "attachments": [], "sharedFiles": [], "stars": []
+            },
+            "lastContentUpdated": null,
+            "created": "2024-01-01T00:00:00Z",
+            "updated": "2024-01-01T00:00:00Z"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_is_stale() {
+        let cutoff = DateTime::parse_from_rfc3339("2024-03-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert!(is_stale(&watching(4, "2024-02-01T00:00:00Z"), cutoff));
+        assert!(is_stale(&watching(3, "2024-02-01T00:00:00Z"), cutoff));
+        assert!(!is_stale(&watching(4, "2024-03-02T00:00:00Z"), cutoff));
+        assert!(!is_stale(&watching(2, "2024-02-01T00:00:00Z"), cutoff));
+    }
+
+    #[test]
+    fn test_rate_limit_wait() {
+        assert_eq!(rate_limit_wait(Some(130), 100, 1), Duration::from_secs(30));
+        assert_eq!(rate_limit_wait(Some(100), 100, 1), Duration::from_secs(1));
+        assert_eq!(rate_limit_wait(Some(90), 100, 1), Duration::from_secs(1));
+        assert_eq!(rate_limit_wait(Some(3700), 100, 1), MAX_RATE_LIMIT_WAIT);
+        assert_eq!(rate_limit_wait(None, 100, 4), Duration::from_secs(4));
+    }
+
+    #[tokio::test]
+    async fn test_with_rate_limit_retries_unparseable_429() {
+        let mut attempts = 0;
+        let result = with_rate_limit(|| {
+            attempts += 1;
+            let attempt = attempts;
+            async move {
+                if attempt == 1 {
+                    Err(ApiError::UnparseableErrorResponse {
+                        status: 429,
+                        body: "Too Many Requests".to_string(),
+                        rate_limit_reset: None,
+                    })
+                } else {
+                    Ok(attempt)
+                }
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 2);
+    }
+}
@@ -1,3 +1,6 @@
+mod bulk;
+
+use backlog_api_client::client::BacklogApiClient;
 use backlog_core::IssueIdOrKey;
 use backlog_core::identifier::WatchingId;
 use clap::{Args, Subcommand};
@@ -13,7 +16,7 @@ pub enum WatchingSubcommand {
     /// Get details of a specific watching
     Get(GetWatchingArgs),
 
-    /// Add a new watching for an issue
+    /// Add a new watching for an issue, or for every issue matching --query
     #[cfg(feature = "watching_writable")]
     Add(AddWatchingArgs),
 
@@ -28,6 +31,13 @@ pub enum WatchingSubcommand {
     /// Mark a watching as read
     #[cfg(feature = "watching_writable")]
     MarkRead(MarkAsReadArgs),
+
+    /// Remove watchings on issues resolved or closed more than N days ago
+    #[cfg(feature = "watching_writable")]
+    Prune(PruneWatchingArgs),
+
+    /// Show watched issues updated since they were last read
+    Unread(UnreadWatchingArgs),
 }
 
 #[derive(Args, Debug, Clone)]
@@ -40,11 +50,48 @@ pub struct GetWatchingArgs {
 #[derive(Args, Debug, Clone)]
 pub struct AddWatchingArgs {
     /// The ID or key of the issue to watch
-    pub issue: String,
+    #[arg(required_unless_present = "query", conflicts_with = "query")]
+    pub issue: Option<String>,
 
     /// Optional note for the watching
     #[arg(short, long)]
     pub note: Option<String>,
+
+    /// Watch every issue matching the filters below instead of a single issue
+    #[arg(long)]
+    pub query: bool,
+
+    /// Project ID or key to search (repeatable)
+    #[arg(long = "project", requires = "query")]
+    pub projects: Vec<String>,
+
+    /// Status IDs to match (comma-separated)
+    #[arg(long, value_delimiter = ',', requires = "query")]
+    pub status_id: Vec<u32>,
+
+    /// Assignee user IDs to match (comma-separated)
+    #[arg(long, value_delimiter = ',', requires = "query")]
+    pub assignee_id: Vec<u32>,
+
+    /// Issue type IDs to match (comma-separated)
+    #[arg(long, value_delimiter = ',', requires = "query")]
+    pub issue_type_id: Vec<u32>,
+
+    /// Keyword to match
+    #[arg(long, requires = "query")]
+    pub keyword: Option<String>,
+
+    /// Maximum number of matching issues to consider
+    #[arg(long, default_value_t = 500, requires = "query")]
+    pub limit: u32,
+
+    /// Show the issues that would be watched without adding anything
+    #[arg(long, requires = "query")]
+    pub dry_run: bool,
+
+    /// Number of requests to run at once
+    #[arg(short = 'j', long, default_value_t = 4, value_parser = clap::value_parser!(u8).range(1..=16))]
+    pub concurrency: u8,
 }
 
 #[cfg(feature = "watching_writable")]
@@ -72,13 +119,39 @@ pub struct MarkAsReadArgs {
     pub watching_id: u32,
 }
 
-pub async fn handle_watching_command(command: WatchingCommand) -> anyhow::Result<()> {
-    use backlog_api_client::client::BacklogApiClient;
-    use std::env;
+#[cfg(feature = "watching_writable")]
+#[derive(Args, Debug, Clone)]
+pub struct PruneWatchingArgs {
+    /// Only remove watchings on issues not updated for this many days
+    #[arg(long, default_value_t = 30)]
+    pub days: u32,
+
+    /// Show the watchings that would be removed without removing them
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Number of requests to run at once
+    #[arg(short = 'j', long, default_value_t = 4, value_parser = clap::value_parser!(u8).range(1..=16))]
+    pub concurrency: u8,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct UnreadWatchingArgs {
+    /// Mark the listed watchings as read
+    #[cfg(feature = "watching_writable")]
+    #[arg(long)]
+    pub mark_read: bool,
+
+    /// Number of requests to run at once when marking as read
+    #[cfg(feature = "watching_writable")]
+    #[arg(short = 'j', long, default_value_t = 4, value_parser = clap::value_parser!(u8).range(1..=16))]
+    pub concurrency: u8,
+}
 
-    let base_url = env::var("BACKLOG_BASE_URL")?;
-    let api_key = env::var("BACKLOG_API_KEY")?;
-    let client = BacklogApiClient::new(&base_url)?.with_api_key(api_key);
+pub async fn handle_watching_command(
+    client: &BacklogApiClient,
+    command: WatchingCommand,
+) -> anyhow::Result<()> {
     let api = client.watching();
 
     match command.command {
@@ -118,7 +191,25 @@ pub async fn handle_watching_command(command: WatchingCommand) -> anyhow::Result
         WatchingSubcommand::Add(args) => {
             use backlog_watching::AddWatchingParams;
 
-            let issue_id_or_key = parse_issue_id_or_key(&args.issue)?;
+            let Some(issue) = args.issue else {
+                let query = bulk::IssueQuery {
+                    projects: args.projects,
+                    status_ids: args.status_id,
+                    assignee_ids: args.assignee_id,
+                    issue_type_ids: args.issue_type_id,
+                    keyword: args.keyword,
+                    limit: args.limit,
+                };
+                return bulk::add_matching(
+                    client,
+                    query,
+                    args.note,
+                    args.dry_run,
+                    args.concurrency,
+                )
+                .await;
+            };
+            let issue_id_or_key = parse_issue_id_or_key(&issue)?;
             let mut params = AddWatchingParams::new(issue_id_or_key);
 
             if let Some(note) = args.note {
@@ -166,6 +257,21 @@ pub async fn handle_watching_command(command: WatchingCommand) -> anyhow::Result
             api.mark_as_read(WatchingId::from(args.watching_id)).await?;
             println!("Successfully marked watching {} as read", args.watching_id);
         }
+
+        #[cfg(feature = "watching_writable")]
+        WatchingSubcommand::Prune(args) => {
+            bulk::prune(client, args.days, args.dry_run, args.concurrency).await?;
+        }
+
+        WatchingSubcommand::Unread(args) => {
+            let watchings = bulk::unread(client).await?;
+            #[cfg(feature = "watching_writable")]
+            if args.mark_read {
+                bulk::mark_read(client, watchings, args.concurrency).await?;
+            }
+            #[cfg(not(feature = "watching_writable"))]
+            let _ = (watchings, args);
+        }
     }
 
     Ok(())
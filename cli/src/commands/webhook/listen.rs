@@ -0,0 +1,258 @@
+//! Local receiver for developing against webhook payloads
+
+use crate::commands::common::truncate_text;
+use anyhow::{Context, Result};
+use backlog_core::activity::Activity;
+use backlog_core::identifier::Identifier;
+use clap::Args;
+use serde_json::Value;
+use std::time::Instant;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+use super::payload::activity_type_name;
+
+/// Largest request body accepted; Backlog payloads stay far below this
+const MAX_BODY_SIZE: usize = 16 * 1024 * 1024;
+
+#[derive(Args)]
+pub struct ListenArgs {
+    /// Port to listen on
+    #[arg(short, long, default_value_t = 8080)]
+    pub port: u16,
+
+    /// Address to bind to
+    #[arg(long, default_value = "127.0.0.1")]
+    pub host: String,
+
+    /// Forward every payload to this URL as well
+    #[arg(long)]
+    pub forward: Option<String>,
+
+    /// Print the whole payload as JSON below its summary
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug)]
+struct Request {
+    method: String,
+    path: String,
+    content_type: Option<String>,
+    body: Vec<u8>,
+}
+
+async fn read_request<R: AsyncRead + Unpin>(stream: R) -> Result<Request> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).await?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        anyhow::bail!("Malformed request line: {}", line.trim_end());
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+
+    let mut content_length = 0;
+    let mut content_type = None;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).await? == 0 || line.trim_end().is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = value.parse().context("Invalid Content-Length")?;
+        } else if name.eq_ignore_ascii_case("content-type") {
+            content_type = Some(value.to_string());
+        }
+    }
+    if content_length > MAX_BODY_SIZE {
+        anyhow::bail!("Request body of {content_length} bytes is too large");
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).await?;
+    Ok(Request {
+        method,
+        path,
+        content_type,
+        body,
+    })
+}
+
+/// `SHOP-123 Summary` for issues, the name or summary of anything else
+fn subject(payload: &Value) -> Option<String> {
+    let content = &payload["content"];
+    let title = ["summary", "name", "title"]
+        .iter()
+        .find_map(|field| content[field].as_str())
+        .filter(|title| !title.is_empty());
+    let key = payload["project"]["projectKey"]
+        .as_str()
+        .zip(content["key_id"].as_u64())
+        .map(|(project, key_id)| format!("{project}-{key_id}"));
+    match (key, title) {
+        (Some(key), Some(title)) => Some(format!("{key} {title}")),
+        (key, title) => key.or_else(|| title.map(str::to_string)),
+    }
+}
+
+/// Summary of one received payload, a few lines long
+fn describe(body: &[u8]) -> String {
+    let payload: Value = match serde_json::from_slice(body) {
+        Ok(payload) => payload,
+        Err(e) => {
+            let text = String::from_utf8_lossy(body);
+            return format!("Not JSON ({e}): {}", truncate_text(&text, 200));
+        }
+    };
+    let activity: Activity = match serde_json::from_value(payload.clone()) {
+        Ok(activity) => activity,
+        Err(e) => return format!("Not a Backlog activity ({e}):\n{payload:#}"),
+    };
+
+    let mut lines = vec![format!(
+        "#{} {} ({}) · {} · by {} at {}",
+        activity.id.value(),
+        activity_type_name(activity.type_id.into()),
+        activity.type_id,
+        activity.project_name().unwrap_or("Unknown project"),
+        activity.created_user.name,
+        activity.created.format("%Y-%m-%d %H:%M:%S"),
+    )];
+    if let Some(subject) = subject(&payload) {
+        lines.push(format!("  {subject}"));
+    }
+    if let Some(comment) = payload["content"]["comment"]["content"]
+        .as_str()
+        .filter(|comment| !comment.is_empty())
+    {
+        lines.push(format!("  Comment: {}", truncate_text(comment, 100)));
+    }
+    for change in payload["content"]["changes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+    {
+        lines.push(format!(
+            "  {}: {} → {}",
+            change["field"].as_str().unwrap_or("?"),
+            change["old_value"].as_str().unwrap_or(""),
+            change["new_value"].as_str().unwrap_or(""),
+        ));
+    }
+    if !activity.notifications.is_empty() {
+        lines.push(format!(
+            "  Notifies {} user(s)",
+            activity.notifications.len()
+        ));
+    }
+    lines.join("\n")
+}
+
+async fn forward(client: &reqwest::Client, url: &str, request: &Request) -> String {
+    let started = Instant::now();
+    let mut builder = client.post(url).body(request.body.clone());
+    if let Some(content_type) = &request.content_type {
+        builder = builder.header(reqwest::header::CONTENT_TYPE, content_type);
+    }
+    match builder.send().await {
+        Ok(response) => format!(
+            "  → {url}: {} in {} ms",
+            response.status(),
+            started.elapsed().as_millis()
+        ),
+        Err(e) => format!("  → {url}: failed: {e}"),
+    }
+}
+
+/// Print the webhook payloads posted to the local port until interrupted
+pub async fn listen(args: &ListenArgs) -> Result<()> {
+    let address = format!("{}:{}", args.host, args.port);
+    let listener = TcpListener::bind(&address)
+        .await
+        .with_context(|| format!("Failed to listen on {address}"))?;
+    println!("Listening for webhooks on http://{address}/ (Ctrl+C to stop)");
+    if let Some(url) = &args.forward {
+        println!("Forwarding payloads to {url}");
+    }
+
+    let forward_to = args.forward.clone();
+    let print_json = args.json;
+    let client = reqwest::Client::new();
+    loop {
+        let (mut stream, peer) = listener.accept().await?;
+        let forward_to = forward_to.clone();
+        let client = client.clone();
+        tokio::spawn(async move {
+            let (reader, mut writer) = stream.split();
+            let request = match read_request(reader).await {
+                Ok(request) => request,
+                Err(e) => {
+                    eprintln!("Warning: bad request from {peer}: {e:#}");
+                    return;
+                }
+            };
+            let status = if request.method == "POST" {
+                "200 OK"
+            } else {
+                "405 Method Not Allowed"
+            };
+            let response =
+                format!("HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+            let _ = writer.write_all(response.as_bytes()).await;
+            let _ = writer.shutdown().await;
+            if request.method != "POST" {
+                println!("{} {} from {peer} ignored", request.method, request.path);
+                return;
+            }
+
+            let mut output = format!("── POST {} from {peer} ──\n", request.path);
+            output.push_str(&describe(&request.body));
+            if print_json && let Ok(payload) = serde_json::from_slice::<Value>(&request.body) {
+                output.push_str(&format!("\n{payload:#}"));
+            }
+            if let Some(url) = &forward_to {
+                output.push('\n');
+                output.push_str(&forward(&client, url, &request).await);
+            }
+            println!("{output}\n");
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_read_request() {
+        let raw = b"POST /hook HTTP/1.1\r\nHost: localhost\r\ncontent-type: application/json\r\nContent-Length: 7\r\n\r\n{\"a\":1}trailing";
+        let request = read_request(&raw[..]).await.unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/hook");
+        assert_eq!(request.content_type.as_deref(), Some("application/json"));
+        assert_eq!(request.body, b"{\"a\":1}");
+
+        assert!(read_request(&b"\r\n"[..]).await.is_err());
+    }
+
+    #[test]
+    fn test_describe() {
+        let summary = describe(include_bytes!("fixtures/issue.json"));
+        assert_eq!(
+            summary,
+            "#1800002 IssueUpdated (2) · Online Shop · by Takada at 2024-05-14 02:12:48\n  \
+             SHOP-123 Checkout fails with an expired coupon\n  \
+             Comment: Fixed in the coupon validator, please verify.\n  \
+             status: 2 → 3\n  \
+             assigner: Takada → Suzuki\n  \
+             Notifies 1 user(s)"
+        );
+        assert!(describe(b"hello").starts_with("Not JSON"));
+        assert!(describe(b"{\"ok\":true}").starts_with("Not a Backlog activity"));
+    }
+}
@@ -6,6 +6,15 @@ use backlog_core::{
 };
 use clap::{Parser, Subcommand, ValueEnum};
 use prettytable::{Cell, Row, Table, row};
+use std::time::{Duration, Instant};
+
+pub mod listen;
+mod payload;
+
+pub use listen::ListenArgs;
+
+/// How long `webhook test` waits for the hook URL to answer
+const TEST_TIMEOUT: Duration = Duration::from_secs(10);
 
 #[derive(Parser)]
 pub struct WebhookArgs {
@@ -110,6 +119,22 @@ pub enum WebhookCommands {
         #[arg(short = 'w', long)]
         webhook_id: u32,
     },
+    /// Receive webhook payloads on a local port and print them
+    Listen(ListenArgs),
+    /// Post a sample payload for each activity type of a webhook to its URL
+    Test {
+        /// Project ID or key
+        #[arg(short, long)]
+        project: String,
+
+        /// Webhook ID
+        #[arg(short = 'w', long)]
+        webhook_id: u32,
+
+        /// Print the payloads instead of sending them
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 #[derive(Debug, Clone, Copy, ValueEnum)]
@@ -174,6 +199,12 @@ pub async fn execute(client: &BacklogApiClient, args: WebhookArgs) -> Result<()>
             project,
             webhook_id,
         } => delete_webhook(client, &project, webhook_id).await,
+        WebhookCommands::Listen(_) => unreachable!("handled before the client is created"),
+        WebhookCommands::Test {
+            project,
+            webhook_id,
+            dry_run,
+        } => test_webhook(client, &project, webhook_id, dry_run).await,
     }
 }
 
@@ -370,6 +401,78 @@ fn display_webhook_csv(webhook: &Webhook) {
     );
 }
 
+async fn test_webhook(
+    client: &BacklogApiClient,
+    project: &str,
+    webhook_id: u32,
+    dry_run: bool,
+) -> Result<()> {
+    let project_id_or_key: ProjectIdOrKey = project
+        .parse()
+        .with_context(|| format!("Invalid project: '{project}'"))?;
+    let webhook = client
+        .webhook()
+        .get_webhook(project_id_or_key, WebhookId::new(webhook_id))
+        .await?;
+    let type_ids = payload::subscribed_types(&webhook);
+    if type_ids.is_empty() {
+        println!("Webhook {} is not sent for any activity type.", webhook.id);
+        return Ok(());
+    }
+
+    let now = chrono::Utc::now();
+    if dry_run {
+        let payloads: Vec<_> = type_ids
+            .iter()
+            .filter_map(|&type_id| payload::synthesize(type_id, now))
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&payloads)?);
+        return Ok(());
+    }
+
+    println!(
+        "Sending {} payload(s) to {}",
+        type_ids.len(),
+        webhook.hook_url
+    );
+    let http = reqwest::Client::builder().timeout(TEST_TIMEOUT).build()?;
+    let mut table = Table::new();
+    table.set_format(*prettytable::format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    table.set_titles(row!["Type", "Activity", "Status", "Latency"]);
+    let mut failed = 0;
+    for type_id in type_ids {
+        let name = payload::activity_type_name(type_id.into());
+        let Some(body) = payload::synthesize(type_id, now) else {
+            table.add_row(row![type_id, name, "no sample payload", "-"]);
+            continue;
+        };
+        let started = Instant::now();
+        let result = http.post(&webhook.hook_url).json(&body).send().await;
+        let latency = format!("{} ms", started.elapsed().as_millis());
+        match result {
+            Ok(response) => {
+                if !response.status().is_success() {
+                    failed += 1;
+                }
+                table.add_row(row![type_id, name, response.status(), latency]);
+            }
+            Err(e) => {
+                failed += 1;
+                table.add_row(row![type_id, name, format!("failed: {e}"), latency]);
+            }
+        }
+    }
+    table.printstd();
+
+    if failed > 0 {
+        anyhow::bail!(
+            "{failed} payload(s) were not accepted by {}",
+            webhook.hook_url
+        );
+    }
+    Ok(())
+}
+
 #[cfg(feature = "webhook_writable")]
 #[allow(clippy::too_many_arguments)]
 async fn add_webhook(
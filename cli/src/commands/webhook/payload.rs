@@ -0,0 +1,92 @@
+//! Recorded webhook payloads used by `blg webhook test`
+//!
+//! Each fixture is a payload Backlog sent for one activity of its group; the
+//! other types of the group reuse it with their own `type`.
+
+use backlog_api_client::Webhook;
+use backlog_core::ActiveTypeId;
+use chrono::{DateTime, Utc};
+use serde_json::{Value, json};
+
+const ISSUE: &str = include_str!("fixtures/issue.json");
+const ISSUE_MULTI_UPDATE: &str = include_str!("fixtures/issue_multi_update.json");
+const WIKI: &str = include_str!("fixtures/wiki.json");
+const FILE: &str = include_str!("fixtures/file.json");
+const SVN: &str = include_str!("fixtures/svn.json");
+const GIT: &str = include_str!("fixtures/git.json");
+const PROJECT_MEMBER: &str = include_str!("fixtures/project_member.json");
+const PULL_REQUEST: &str = include_str!("fixtures/pull_request.json");
+const MILESTONE: &str = include_str!("fixtures/milestone.json");
+const PROJECT_GROUP: &str = include_str!("fixtures/project_group.json");
+
+/// Highest activity type ID Backlog sends to webhooks
+const LAST_ACTIVITY_TYPE: u32 = 26;
+
+fn fixture(type_id: u32) -> Option<&'static str> {
+    Some(match type_id {
+        1..=4 | 17 => ISSUE,
+        5..=7 => WIKI,
+        8..=10 => FILE,
+        11 => SVN,
+        12 | 13 => GIT,
+        14 => ISSUE_MULTI_UPDATE,
+        15 | 16 => PROJECT_MEMBER,
+        18..=21 => PULL_REQUEST,
+        22..=24 => MILESTONE,
+        25 | 26 => PROJECT_GROUP,
+        _ => return None,
+    })
+}
+
+/// Activity types the webhook is sent for; every type when it listens to all events
+pub(crate) fn subscribed_types(webhook: &Webhook) -> Vec<u32> {
+    if webhook.all_event {
+        (1..=LAST_ACTIVITY_TYPE).collect()
+    } else {
+        webhook.activity_type_ids.clone()
+    }
+}
+
+/// `IssueCreated` for 1, `Unknown` for types this client does not know
+pub(crate) fn activity_type_name(type_id: i64) -> String {
+    serde_json::from_value::<ActiveTypeId>(json!(type_id))
+        .map_or_else(|_| "Unknown".to_string(), |name| format!("{name:?}"))
+}
+
+/// Payload Backlog would send for an activity of `type_id` created at `now`
+pub(crate) fn synthesize(type_id: u32, now: DateTime<Utc>) -> Option<Value> {
+    let mut payload: Value =
+        serde_json::from_str(fixture(type_id)?).expect("webhook fixtures are valid JSON");
+    payload["type"] = json!(type_id);
+    payload["created"] = json!(now.format("%Y-%m-%dT%H:%M:%SZ").to_string());
+    // Nothing changed yet on creation and nothing is left to comment on after deletion
+    if matches!(type_id, 1 | 4)
+        && let Some(content) = payload["content"].as_object_mut()
+    {
+        content.remove("comment");
+        content.remove("changes");
+    }
+    Some(payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backlog_core::activity::Activity;
+
+    #[test]
+    fn test_synthesize_every_activity_type() {
+        let now = Utc::now();
+        for type_id in 1..=LAST_ACTIVITY_TYPE {
+            let payload = synthesize(type_id, now).unwrap();
+            let activity: Activity = serde_json::from_value(payload.clone())
+                .unwrap_or_else(|e| panic!("type {type_id}: {e}"));
+            assert_eq!(activity.type_id as u32, type_id);
+            assert_eq!(activity.created.timestamp(), now.timestamp());
+            assert_ne!(activity_type_name(type_id.into()), "Unknown");
+        }
+        assert!(synthesize(1, now).unwrap()["content"]["changes"].is_null());
+        assert!(synthesize(2, now).unwrap()["content"]["changes"].is_array());
+        assert!(synthesize(LAST_ACTIVITY_TYPE + 1, now).is_none());
+    }
+}
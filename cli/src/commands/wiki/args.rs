@@ -88,6 +88,9 @@ pub enum WikiCommands {
         /// Send email notification
         #[clap(long)]
         mail_notify: Option<bool>,
+        /// Treat the content as Markdown and convert it to Backlog notation if the project uses it
+        #[clap(long)]
+        markdown: bool,
     },
     /// Update a wiki page
     #[cfg(feature = "wiki_writable")]
@@ -104,6 +107,9 @@ pub enum WikiCommands {
         /// Send email notification of update
         #[clap(long)]
         mail_notify: Option<bool>,
+        /// Treat the content as Markdown and convert it to Backlog notation if the project uses it
+        #[clap(long)]
+        markdown: bool,
     },
     /// Delete a wiki page
     #[cfg(feature = "wiki_writable")]
@@ -162,6 +168,31 @@ pub enum WikiCommands {
         #[clap(long, value_enum)]
         order: Option<HistoryOrderCli>,
     },
+    /// Show a unified diff between two versions of a wiki page
+    Diff {
+        /// Wiki ID
+        #[clap(name = "WIKI_ID")]
+        wiki_id: u32,
+        /// Older version number
+        #[clap(long)]
+        from: u32,
+        /// Newer version number (defaults to the current page content)
+        #[clap(long)]
+        to: Option<u32>,
+    },
+    /// Restore the name and content of a wiki page from a previous version
+    #[cfg(feature = "wiki_writable")]
+    Restore {
+        /// Wiki ID
+        #[clap(name = "WIKI_ID")]
+        wiki_id: u32,
+        /// Version number to restore
+        #[clap(long)]
+        version: u32,
+        /// Send email notification of update
+        #[clap(long)]
+        mail_notify: Option<bool>,
+    },
 }
 
 #[cfg(feature = "wiki")]
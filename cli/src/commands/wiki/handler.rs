@@ -29,6 +29,17 @@ pub async fn execute(client: &BacklogApiClient, args: WikiArgs) -> CliResult<()>
             order,
         } => subcommands::list::history(client, wiki_id, min_id, max_id, count, order).await?,
 
+        // Version history (from subcommands::history)
+        WikiCommands::Diff { wiki_id, from, to } => {
+            subcommands::history::diff(client, wiki_id, from, to).await?
+        }
+        #[cfg(feature = "wiki_writable")]
+        WikiCommands::Restore {
+            wiki_id,
+            version,
+            mail_notify,
+        } => subcommands::history::restore(client, wiki_id, version, mail_notify).await?,
+
         // CRUD operations (from subcommands::crud)
         #[cfg(feature = "wiki_writable")]
         WikiCommands::Create {
@@ -36,14 +47,21 @@ pub async fn execute(client: &BacklogApiClient, args: WikiArgs) -> CliResult<()>
             name,
             content,
             mail_notify,
-        } => subcommands::crud::create(client, project_id, name, content, mail_notify).await?,
+            markdown,
+        } => {
+            subcommands::crud::create(client, project_id, name, content, mail_notify, markdown)
+                .await?
+        }
         #[cfg(feature = "wiki_writable")]
         WikiCommands::Update {
             wiki_id,
             name,
             content,
             mail_notify,
-        } => subcommands::crud::update(client, wiki_id, name, content, mail_notify).await?,
+            markdown,
+        } => {
+            subcommands::crud::update(client, wiki_id, name, content, mail_notify, markdown).await?
+        }
         #[cfg(feature = "wiki_writable")]
         WikiCommands::Delete {
             wiki_id,
@@ -1,7 +1,9 @@
 use crate::commands::common::CliResult;
+#[cfg(feature = "wiki_writable")]
+use crate::commands::common::markdown_for_project;
 use backlog_api_client::client::BacklogApiClient;
 use backlog_core::identifier::{Identifier, ProjectId, WikiId};
-use backlog_wiki::{AddWikiParams, DeleteWikiParams, UpdateWikiParams};
+use backlog_wiki::{AddWikiParams, DeleteWikiParams, GetWikiDetailParams, UpdateWikiParams};
 use std::str::FromStr;
 
 /// Create a new wiki page
@@ -12,10 +14,18 @@ pub(crate) async fn create(
     name: String,
     content: String,
     mail_notify: Option<bool>,
+    markdown: bool,
 ) -> CliResult<()> {
     println!("Creating new wiki page in project: {project_id}");
 
-    let params = AddWikiParams::new(ProjectId::from_str(&project_id)?, name, content);
+    let project_id = ProjectId::from_str(&project_id)?;
+    let content = if markdown {
+        markdown_for_project(client, project_id, &content).await?
+    } else {
+        content
+    };
+
+    let params = AddWikiParams::new(project_id, name, content);
 
     let params = if let Some(mail_notify) = mail_notify {
         params.mail_notify(mail_notify)
@@ -45,6 +55,7 @@ pub(crate) async fn update(
     name: Option<String>,
     content: Option<String>,
     mail_notify: Option<bool>,
+    markdown: bool,
 ) -> CliResult<()> {
     println!("Updating wiki ID: {wiki_id}");
 
@@ -56,6 +67,15 @@ pub(crate) async fn update(
     }
 
     if let Some(content) = content {
+        let content = if markdown {
+            let wiki = client
+                .wiki()
+                .get_wiki_detail(GetWikiDetailParams::new(WikiId::new(wiki_id)))
+                .await?;
+            markdown_for_project(client, wiki.project_id, &content).await?
+        } else {
+            content
+        };
         params = params.content(content);
     }
 
@@ -0,0 +1,101 @@
+use crate::commands::common::CliResult;
+use backlog_api_client::client::BacklogApiClient;
+use backlog_core::identifier::{Identifier, WikiId};
+use backlog_wiki::{GetWikiDetailParams, WikiHistory, WikiVersionDiff};
+
+#[cfg(feature = "wiki_writable")]
+use backlog_wiki::UpdateWikiParams;
+
+/// Fetch a single version of a wiki page, failing if it does not exist
+async fn fetch_version(
+    client: &BacklogApiClient,
+    wiki_id: u32,
+    version: u32,
+) -> CliResult<WikiHistory> {
+    client
+        .wiki()
+        .get_wiki_version(WikiId::new(wiki_id), version)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Version {version} not found in history of wiki {wiki_id}"))
+}
+
+/// Show a unified diff between two versions of a wiki page
+pub(crate) async fn diff(
+    client: &BacklogApiClient,
+    wiki_id: u32,
+    from: u32,
+    to: Option<u32>,
+) -> CliResult<()> {
+    let from_entry = fetch_version(client, wiki_id, from).await?;
+
+    let diff = match to {
+        Some(to) => {
+            let to_entry = fetch_version(client, wiki_id, to).await?;
+            WikiVersionDiff::between(&from_entry, &to_entry)
+        }
+        None => {
+            let current = client
+                .wiki()
+                .get_wiki_detail(GetWikiDetailParams::new(WikiId::new(wiki_id)))
+                .await?;
+            WikiVersionDiff::against_current(&from_entry, &current)
+        }
+    };
+
+    let to_label = diff
+        .to_version
+        .map(|v| format!("version {v}"))
+        .unwrap_or_else(|| "current".to_string());
+    println!(
+        "Wiki {} diff: version {} → {to_label} (+{} -{})",
+        diff.wiki_id.value(),
+        diff.from_version,
+        diff.added_lines,
+        diff.removed_lines
+    );
+
+    if diff.from_name != diff.to_name {
+        println!("Name: {} → {}", diff.from_name, diff.to_name);
+    }
+
+    if diff.is_unchanged() {
+        println!("No differences");
+    } else {
+        print!("{}", diff.diff);
+    }
+
+    Ok(())
+}
+
+/// Restore the name and content of a wiki page from a previous version
+#[cfg(feature = "wiki_writable")]
+pub(crate) async fn restore(
+    client: &BacklogApiClient,
+    wiki_id: u32,
+    version: u32,
+    mail_notify: Option<bool>,
+) -> CliResult<()> {
+    println!("Restoring wiki ID {wiki_id} to version {version}");
+
+    let entry = fetch_version(client, wiki_id, version).await?;
+
+    let mut params = UpdateWikiParams::new(WikiId::new(wiki_id))
+        .name(entry.name)
+        .content(entry.content);
+    if let Some(mail_notify) = mail_notify {
+        params = params.mail_notify(mail_notify);
+    }
+
+    let wiki_detail = client.wiki().update_wiki(params).await?;
+    println!("✅ Wiki restored successfully");
+    println!("ID: {}", wiki_detail.id.value());
+    println!("Name: {}", wiki_detail.name);
+    println!(
+        "Restored from version {} (by {} at {})",
+        entry.version,
+        entry.created_user.name,
+        entry.created.format("%Y-%m-%d %H:%M:%S")
+    );
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
 pub mod attachments;
 pub mod crud;
+pub mod history;
 pub mod list;
 pub mod shared_files;
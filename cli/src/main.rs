@@ -2,9 +2,13 @@
     feature = "project",
     feature = "issue",
     feature = "document",
+    feature = "file",
     feature = "team",
     feature = "star",
     feature = "rate-limit",
+    feature = "report",
+    feature = "search",
+    feature = "digest",
     feature = "watching",
     feature = "webhook",
     feature = "user",
@@ -33,6 +37,8 @@ use commands::watching::handle_watching_command;
 use commands::wiki::WikiArgs;
 
 use backlog_api_client::client::BacklogApiClient;
+#[cfg(feature = "project")]
+use clap::CommandFactory;
 use clap::{Args, Parser};
 use std::env;
 
@@ -51,12 +57,18 @@ enum Commands {
     /// Manage pull requests
     #[cfg(feature = "git")]
     Pr(commands::git::PrArgs),
+    /// Set up the local git checkout
+    #[cfg(feature = "git")]
+    Git(commands::git::GitArgs),
     /// Manage issues
     #[cfg(feature = "issue")]
     Issue(commands::issue::IssueArgs),
     /// Manage documents
     #[cfg(feature = "document")]
     Document(DocumentArgs),
+    /// Browse, download and mirror shared files
+    #[cfg(feature = "file")]
+    File(commands::file::FileArgs),
     /// Manage space
     #[cfg(feature = "space")]
     Space(commands::space::SpaceArgs),
@@ -66,6 +78,9 @@ enum Commands {
     /// Manage users
     #[cfg(feature = "user")]
     User(UserArgs),
+    /// Work through unread notifications grouped by issue and pull request
+    #[cfg(feature = "notifications")]
+    Notifications(commands::notifications::NotificationsArgs),
     /// Manage wikis
     #[cfg(feature = "wiki")]
     Wiki(WikiArgs),
@@ -78,6 +93,15 @@ enum Commands {
     /// Manage stars
     #[cfg(feature = "star")]
     Star(StarArgs),
+    /// Burndown and velocity reports
+    #[cfg(feature = "report")]
+    Report(commands::report::ReportArgs),
+    /// Search issues, wikis, documents and pull requests at once
+    #[cfg(feature = "search")]
+    Search(commands::search::SearchArgs),
+    /// Summarize recent activity as Markdown for chat, or as JSON
+    #[cfg(feature = "digest")]
+    Digest(commands::digest::DigestArgs),
     /// View rate limit information
     #[cfg(feature = "rate-limit")]
     RateLimit(RateLimitArgs),
@@ -87,6 +111,9 @@ enum Commands {
     /// Manage webhooks
     #[cfg(feature = "webhook")]
     Webhook(commands::webhook::WebhookArgs),
+    /// Generate shell completions with values from the space
+    #[cfg(feature = "project")]
+    Completions(commands::completions::CompletionsArgs),
 }
 
 #[cfg(feature = "rate-limit")]
@@ -103,8 +130,11 @@ struct WatchingArgs {
     command: commands::watching::WatchingSubcommand,
 }
 
-#[tokio::main]
-async fn main() -> anyhow::Result<()> {
+/// Create an API client from `BACKLOG_BASE_URL` and `BACKLOG_API_KEY`
+///
+/// The space is never taken from a git remote, so the API key is only sent
+/// to the space it was configured for.
+fn client_from_env() -> anyhow::Result<BacklogApiClient> {
     use anyhow::Context;
 
     let base_url =
@@ -112,9 +142,30 @@ async fn main() -> anyhow::Result<()> {
     let api_key =
         env::var("BACKLOG_API_KEY").context("BACKLOG_API_KEY environment variable not set")?;
 
-    let client = BacklogApiClient::new(&base_url)?.with_api_key(api_key);
+    Ok(BacklogApiClient::new(&base_url)?.with_api_key(api_key))
+}
 
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
+
+    // Completion scripts are generated without credentials
+    #[cfg(feature = "project")]
+    if let Commands::Completions(args) = &cli.command {
+        return commands::completions::execute(args, Cli::command()).await;
+    }
+    #[cfg(feature = "git")]
+    if let Commands::Git(args) = &cli.command {
+        return commands::git::execute_git(args);
+    }
+    #[cfg(feature = "webhook")]
+    if let Commands::Webhook(args) = &cli.command
+        && let commands::webhook::WebhookCommands::Listen(listen_args) = &args.command
+    {
+        return commands::webhook::listen::listen(listen_args).await;
+    }
+
+    let client = client_from_env()?;
     match cli.command {
         #[cfg(feature = "git")]
         Commands::Repo(repo_args) => {
@@ -132,6 +183,10 @@ async fn main() -> anyhow::Result<()> {
         Commands::Document(document_args) => {
             commands::document::execute(&client, document_args).await?;
         }
+        #[cfg(feature = "file")]
+        Commands::File(file_args) => {
+            commands::file::execute(&client, file_args).await?;
+        }
         #[cfg(feature = "space")]
         Commands::Space(space_args) => {
             commands::space::execute(&client, space_args).await?;
@@ -144,6 +199,10 @@ async fn main() -> anyhow::Result<()> {
         Commands::User(user_args) => {
             commands::user::execute(&client, user_args).await?;
         }
+        #[cfg(feature = "notifications")]
+        Commands::Notifications(notifications_args) => {
+            commands::notifications::execute(&client, notifications_args).await?;
+        }
         #[cfg(feature = "wiki")]
         Commands::Wiki(wiki_args) => {
             commands::wiki::execute(&client, wiki_args).await?;
@@ -154,11 +213,23 @@ async fn main() -> anyhow::Result<()> {
         }
         #[cfg(feature = "team")]
         Commands::Team(team_args) => {
-            handle_team_command(client.team(), team_args).await?;
+            handle_team_command(&client, team_args).await?;
         }
         #[cfg(feature = "star")]
         Commands::Star(star_args) => {
-            handle_star_command(&client.star(), &star_args.command).await?;
+            handle_star_command(&client, &star_args.command).await?;
+        }
+        #[cfg(feature = "report")]
+        Commands::Report(report_args) => {
+            commands::report::execute(&client, report_args).await?;
+        }
+        #[cfg(feature = "search")]
+        Commands::Search(search_args) => {
+            commands::search::execute(&client, search_args).await?;
+        }
+        #[cfg(feature = "digest")]
+        Commands::Digest(digest_args) => {
+            commands::digest::execute(&client, digest_args).await?;
         }
         #[cfg(feature = "rate-limit")]
         Commands::RateLimit(rate_limit_args) => {
@@ -166,15 +237,22 @@ async fn main() -> anyhow::Result<()> {
         }
         #[cfg(feature = "watching")]
         Commands::Watching(watching_args) => {
-            handle_watching_command(commands::watching::WatchingCommand {
-                command: watching_args.command,
-            })
+            handle_watching_command(
+                &client,
+                commands::watching::WatchingCommand {
+                    command: watching_args.command,
+                },
+            )
             .await?;
         }
         #[cfg(feature = "webhook")]
         Commands::Webhook(webhook_args) => {
             commands::webhook::execute(&client, webhook_args).await?;
         }
+        #[cfg(feature = "git")]
+        Commands::Git(_) => unreachable!("handled before the client is created"),
+        #[cfg(feature = "project")]
+        Commands::Completions(_) => unreachable!("handled before the client is created"),
     }
 
     Ok(())
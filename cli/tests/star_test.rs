@@ -8,9 +8,12 @@ struct Cli {
 
 #[derive(Subcommand, Debug)]
 enum StarCommands {
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
     Add {
+        #[clap(required = true)]
+        target: Option<String>,
         #[clap(subcommand)]
-        target: StarTarget,
+        by_id: Option<StarTarget>,
     },
 }
 
@@ -27,8 +30,9 @@ enum StarTarget {
 fn test_star_add_issue() {
     let args = Cli::try_parse_from(["prog", "add", "issue", "123"]).unwrap();
     match args.command {
-        StarCommands::Add { target } => {
-            assert_eq!(target, StarTarget::Issue { issue_id: 123 });
+        StarCommands::Add { target, by_id } => {
+            assert_eq!(target, None);
+            assert_eq!(by_id.unwrap(), StarTarget::Issue { issue_id: 123 });
         }
     }
 }
@@ -37,9 +41,10 @@ fn test_star_add_issue() {
 fn test_star_add_comment() {
     let args = Cli::try_parse_from(["prog", "add", "comment", "100", "200"]).unwrap();
     match args.command {
-        StarCommands::Add { target } => {
+        StarCommands::Add { target, by_id } => {
+            assert_eq!(target, None);
             assert_eq!(
-                target,
+                by_id.unwrap(),
                 StarTarget::Comment {
                     issue_id: 100,
                     comment_id: 200
@@ -53,8 +58,9 @@ fn test_star_add_comment() {
 fn test_star_add_wiki() {
     let args = Cli::try_parse_from(["prog", "add", "wiki", "456"]).unwrap();
     match args.command {
-        StarCommands::Add { target } => {
-            assert_eq!(target, StarTarget::Wiki { wiki_id: 456 });
+        StarCommands::Add { target, by_id } => {
+            assert_eq!(target, None);
+            assert_eq!(by_id.unwrap(), StarTarget::Wiki { wiki_id: 456 });
         }
     }
 }
@@ -63,8 +69,9 @@ fn test_star_add_wiki() {
 fn test_star_add_pr() {
     let args = Cli::try_parse_from(["prog", "add", "pr", "789"]).unwrap();
     match args.command {
-        StarCommands::Add { target } => {
-            assert_eq!(target, StarTarget::Pr { pr_id: 789 });
+        StarCommands::Add { target, by_id } => {
+            assert_eq!(target, None);
+            assert_eq!(by_id.unwrap(), StarTarget::Pr { pr_id: 789 });
         }
     }
 }
@@ -73,8 +80,35 @@ fn test_star_add_pr() {
 fn test_star_add_pr_comment() {
     let args = Cli::try_parse_from(["prog", "add", "pr-comment", "321"]).unwrap();
     match args.command {
-        StarCommands::Add { target } => {
-            assert_eq!(target, StarTarget::PrComment { pr_comment_id: 321 });
+        StarCommands::Add { target, by_id } => {
+            assert_eq!(target, None);
+            assert_eq!(by_id.unwrap(), StarTarget::PrComment { pr_comment_id: 321 });
         }
     }
 }
+
+#[test]
+fn test_star_add_target() {
+    for target in [
+        "BLG-12",
+        "BLG-12#comment-34",
+        "https://example.backlog.com/git/BLG/app/pullRequests/7",
+    ] {
+        let args = Cli::try_parse_from(["prog", "add", target]).unwrap();
+        match args.command {
+            StarCommands::Add {
+                target: parsed,
+                by_id,
+            } => {
+                assert_eq!(parsed.as_deref(), Some(target));
+                assert_eq!(by_id, None);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_star_add_requires_a_target() {
+    assert!(Cli::try_parse_from(["prog", "add"]).is_err());
+    assert!(Cli::try_parse_from(["prog", "add", "issue"]).is_err());
+}
@@ -1,7 +1,10 @@
-use backlog_api_core::Result;
-use client::Client;
+use backlog_api_core::{IntoDownloadRequest, Result};
+use client::{Client, DownloadProgress, StreamedDownload};
+use std::path::Path;
+use tokio::io::AsyncWrite;
 use url::Url;
 
+#[derive(Clone)]
 pub struct BacklogApiClient {
     client: Client,
 }
@@ -25,6 +28,47 @@ impl BacklogApiClient {
         self
     }
 
+    /// The space URL, e.g. `https://example.backlog.com/`
+    pub fn base_url(&self) -> &Url {
+        self.client.base_url()
+    }
+
+    /// Streams a download into `writer` instead of buffering it in memory
+    ///
+    /// Accepts any download parameters, e.g. `GetAttachmentFileParams` or
+    /// `GetFileParams`.
+    pub async fn download_to_writer<P, W, F>(
+        &self,
+        params: P,
+        writer: &mut W,
+        on_progress: F,
+    ) -> Result<StreamedDownload>
+    where
+        P: IntoDownloadRequest,
+        W: AsyncWrite + Unpin,
+        F: FnMut(DownloadProgress),
+    {
+        self.client
+            .download_to_writer(params, writer, on_progress)
+            .await
+    }
+
+    /// Streams a download to `path`, resuming a partial `<path>.part` file
+    pub async fn download_to_path<P, F>(
+        &self,
+        params: P,
+        path: impl AsRef<Path>,
+        on_progress: F,
+    ) -> Result<StreamedDownload>
+    where
+        P: IntoDownloadRequest + Clone,
+        F: FnMut(DownloadProgress),
+    {
+        self.client
+            .download_to_path(params, path, on_progress)
+            .await
+    }
+
     #[cfg(feature = "issue")]
     pub fn issue(&self) -> backlog_issue::IssueApi {
         backlog_issue::IssueApi::new(self.client.clone())
@@ -0,0 +1,840 @@
+//! Digest of what happened in a space or projects over a period
+//!
+//! Pages the recent updates of the space, of each project or of a user back
+//! to the start of the period and aggregates them: issues created, closed
+//! and reassigned, status transitions, comments per issue, wiki pages
+//! edited, pull requests opened and merged, and activity per user. The
+//! digest serializes to JSON and renders as Markdown for posting to chat.
+
+use crate::client::BacklogApiClient;
+use backlog_api_core::Result;
+use backlog_core::CLOSED_STATUS_ID;
+use backlog_core::activity::{Activity, Change, Content};
+use backlog_core::identifier::{ActivityId, Identifier, ProjectId, UserId};
+use backlog_project::{GetProjectRecentUpdatesParams, GetStatusListParams, Project};
+use backlog_space::GetSpaceRecentUpdatesParams;
+use backlog_user::GetUserRecentUpdatesParams;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{self, Write};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// Maximum number of requests in flight
+const DIGEST_CONCURRENCY: usize = 6;
+
+/// Maximum number of activities the recent updates APIs return per request
+const PAGE_SIZE: u32 = 100;
+
+/// Pages fetched per source before the rest of the period is given up
+const MAX_PAGES: usize = 20;
+
+/// Pull request status after a merge
+const MERGED_STATUS_ID: u32 = 3;
+
+const ISSUE_CREATED: i32 = 1;
+const ISSUE_UPDATED: i32 = 2;
+const ISSUE_COMMENTED: i32 = 3;
+const WIKI_CREATED: i32 = 5;
+const WIKI_UPDATED: i32 = 6;
+const PULL_REQUEST_ADDED: i32 = 18;
+const PULL_REQUEST_UPDATED: i32 = 19;
+
+/// Start of the period for `24h`, `90m`, `7d`, `2w`, a date or an RFC 3339 time
+pub fn parse_since(value: &str, now: DateTime<Utc>) -> std::result::Result<DateTime<Utc>, String> {
+    let value = value.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).expect("valid time").and_utc());
+    }
+    let invalid = || {
+        format!(
+            "Invalid period '{value}' (expected e.g. 90m, 24h, 7d, 2w or a date like 2024-05-01)"
+        )
+    };
+    let unit_at = value
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(invalid)?;
+    let amount: i64 = value[..unit_at].parse().map_err(|_| invalid())?;
+    let period = match &value[unit_at..] {
+        "m" => Duration::minutes(amount),
+        "h" => Duration::hours(amount),
+        "d" => Duration::days(amount),
+        "w" => Duration::weeks(amount),
+        _ => return Err(invalid()),
+    };
+    Ok(now - period)
+}
+
+/// Which activities the digest covers
+#[derive(Debug, Clone)]
+pub struct DigestParams {
+    /// Start of the period
+    pub since: DateTime<Utc>,
+    /// End of the period; now if `None`
+    pub until: Option<DateTime<Utc>>,
+    /// Projects to cover; empty means the whole space
+    pub projects: Vec<Project>,
+    /// Only activities by this user
+    pub user: Option<UserId>,
+}
+
+impl DigestParams {
+    pub fn new(since: DateTime<Utc>) -> Self {
+        Self {
+            since,
+            until: None,
+            projects: Vec::new(),
+            user: None,
+        }
+    }
+
+    pub fn until(mut self, until: DateTime<Utc>) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    pub fn projects(mut self, projects: Vec<Project>) -> Self {
+        self.projects = projects;
+        self
+    }
+
+    pub fn user(mut self, user: UserId) -> Self {
+        self.user = Some(user);
+        self
+    }
+}
+
+/// An issue and who acted on it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestIssue {
+    pub key: String,
+    pub summary: String,
+    pub user: String,
+    pub url: String,
+}
+
+/// An issue moved from one status or assignee to another
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestChange {
+    pub issue: DigestIssue,
+    pub from: String,
+    pub to: String,
+}
+
+/// Comments added to one issue
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueComments {
+    pub key: String,
+    pub summary: String,
+    pub url: String,
+    pub count: usize,
+    /// Commenters in order of their first comment
+    pub users: Vec<String>,
+}
+
+/// Edits of one wiki page
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WikiPageEdits {
+    pub project: String,
+    pub name: String,
+    /// Whether the page was created in the period
+    pub created: bool,
+    pub edits: usize,
+    pub users: Vec<String>,
+}
+
+/// A pull request and who opened or merged it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestPullRequest {
+    pub project: String,
+    pub number: u64,
+    pub summary: String,
+    pub user: String,
+}
+
+/// What one user did in the period
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserActivity {
+    pub name: String,
+    pub activities: usize,
+    pub issues_created: usize,
+    pub issues_closed: usize,
+    pub comments: usize,
+    pub wiki_edits: usize,
+    pub pull_requests_opened: usize,
+    pub pull_requests_merged: usize,
+}
+
+/// A request that failed without failing the whole digest
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestFailure {
+    /// Project key, or the name of another source
+    pub source: String,
+    pub message: String,
+}
+
+/// Activities of a period aggregated by what changed, oldest first
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Digest {
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    /// Keys of the projects covered; empty for the whole space
+    pub projects: Vec<String>,
+    pub activity_count: usize,
+    pub issues_created: Vec<DigestIssue>,
+    pub issues_closed: Vec<DigestIssue>,
+    pub issues_reassigned: Vec<DigestChange>,
+    pub status_transitions: Vec<DigestChange>,
+    pub comments: Vec<IssueComments>,
+    pub wiki_pages: Vec<WikiPageEdits>,
+    pub pull_requests_opened: Vec<DigestPullRequest>,
+    pub pull_requests_merged: Vec<DigestPullRequest>,
+    /// Most active first
+    pub users: Vec<UserActivity>,
+    /// Set when the oldest activities of the period were not fetched
+    pub truncated: bool,
+    pub failures: Vec<DigestFailure>,
+}
+
+fn failure(source: impl Into<String>, error: impl fmt::Display) -> DigestFailure {
+    DigestFailure {
+        source: source.into(),
+        message: error.to_string(),
+    }
+}
+
+/// Where activities are listed from
+#[derive(Debug, Clone)]
+enum Source {
+    Space,
+    Project(ProjectId, String),
+    User(UserId),
+}
+
+impl Source {
+    fn name(&self) -> String {
+        match self {
+            Source::Space => "space".to_string(),
+            Source::Project(_, key) => key.clone(),
+            Source::User(user_id) => format!("user {}", user_id.value()),
+        }
+    }
+}
+
+/// Status names by project ID and status ID
+type StatusNames = HashMap<(u32, u32), String>;
+
+fn find_change<'a>(changes: &'a [Change], field: &str) -> Option<&'a Change> {
+    changes.iter().find(|change| change.field == field)
+}
+
+/// Aggregates activities into the digest sections
+struct Aggregator<'a> {
+    base_url: &'a url::Url,
+    statuses: &'a StatusNames,
+    digest: Digest,
+    comments: BTreeMap<String, IssueComments>,
+    comment_order: Vec<String>,
+    wiki_pages: BTreeMap<(String, String), WikiPageEdits>,
+    wiki_order: Vec<(String, String)>,
+    users: HashMap<String, UserActivity>,
+}
+
+impl<'a> Aggregator<'a> {
+    fn status_name(&self, project_id: Option<u32>, value: &str) -> String {
+        value
+            .parse::<u32>()
+            .ok()
+            .zip(project_id)
+            .and_then(|(status_id, project_id)| self.statuses.get(&(project_id, status_id)))
+            .cloned()
+            .unwrap_or_else(|| value.to_string())
+    }
+
+    fn user(&mut self, name: &str) -> &mut UserActivity {
+        self.users
+            .entry(name.to_string())
+            .or_insert_with(|| UserActivity {
+                name: name.to_string(),
+                ..Default::default()
+            })
+    }
+
+    fn add(&mut self, activity: &Activity) {
+        let Content::Standard {
+            key_id,
+            summary,
+            comment,
+            changes,
+            name,
+            number,
+            ..
+        } = &activity.content
+        else {
+            self.user(&activity.created_user.name).activities += 1;
+            return;
+        };
+        let user = activity.created_user.name.clone();
+        self.user(&user).activities += 1;
+        let project = activity.project_key().unwrap_or_default().to_string();
+        let summary = summary.clone().unwrap_or_default();
+        let changes = changes.as_deref().unwrap_or_default();
+        let has_comment = comment
+            .as_ref()
+            .is_some_and(|comment| !comment.content.trim().is_empty());
+
+        match activity.type_id {
+            ISSUE_CREATED | ISSUE_UPDATED | ISSUE_COMMENTED => {
+                let Some(key_id) = key_id else { return };
+                let key = format!("{project}-{key_id}");
+                let path = format!("view/{key}");
+                let issue = DigestIssue {
+                    url: self
+                        .base_url
+                        .join(&path)
+                        .map_or(path, |url| url.to_string()),
+                    key,
+                    summary,
+                    user: user.clone(),
+                };
+                if activity.type_id == ISSUE_CREATED {
+                    self.user(&user).issues_created += 1;
+                    self.digest.issues_created.push(issue.clone());
+                }
+                if let Some(change) = find_change(changes, "assigner") {
+                    self.digest.issues_reassigned.push(DigestChange {
+                        issue: issue.clone(),
+                        from: change.old_value.clone(),
+                        to: change.new_value.clone(),
+                    });
+                }
+                if let Some(change) = find_change(changes, "status") {
+                    let project_id = activity.project_id();
+                    self.digest.status_transitions.push(DigestChange {
+                        issue: issue.clone(),
+                        from: self.status_name(project_id, &change.old_value),
+                        to: self.status_name(project_id, &change.new_value),
+                    });
+                    if change.new_value.parse() == Ok(CLOSED_STATUS_ID) {
+                        self.user(&user).issues_closed += 1;
+                        self.digest
+                            .issues_closed
+                            .retain(|closed| closed.key != issue.key);
+                        self.digest.issues_closed.push(issue.clone());
+                    }
+                }
+                if has_comment && activity.type_id != ISSUE_CREATED {
+                    self.user(&user).comments += 1;
+                    let entry = self.comments.entry(issue.key.clone()).or_insert_with(|| {
+                        self.comment_order.push(issue.key.clone());
+                        IssueComments {
+                            key: issue.key.clone(),
+                            summary: issue.summary.clone(),
+                            url: issue.url.clone(),
+                            count: 0,
+                            users: Vec::new(),
+                        }
+                    });
+                    entry.count += 1;
+                    if !entry.users.contains(&user) {
+                        entry.users.push(user.clone());
+                    }
+                }
+            }
+            WIKI_CREATED | WIKI_UPDATED => {
+                let name = name.clone().unwrap_or_default();
+                self.user(&user).wiki_edits += 1;
+                let page = (project.clone(), name.clone());
+                let entry = self.wiki_pages.entry(page.clone()).or_insert_with(|| {
+                    self.wiki_order.push(page);
+                    WikiPageEdits {
+                        project,
+                        name,
+                        created: false,
+                        edits: 0,
+                        users: Vec::new(),
+                    }
+                });
+                entry.created |= activity.type_id == WIKI_CREATED;
+                entry.edits += 1;
+                if !entry.users.contains(&user) {
+                    entry.users.push(user);
+                }
+            }
+            PULL_REQUEST_ADDED | PULL_REQUEST_UPDATED => {
+                let pull_request = DigestPullRequest {
+                    project,
+                    number: number
+                        .and_then(|number| u64::try_from(number).ok())
+                        .unwrap_or(0),
+                    summary,
+                    user: user.clone(),
+                };
+                if activity.type_id == PULL_REQUEST_ADDED {
+                    self.user(&user).pull_requests_opened += 1;
+                    self.digest.pull_requests_opened.push(pull_request);
+                } else if find_change(changes, "status")
+                    .is_some_and(|change| change.new_value.parse() == Ok(MERGED_STATUS_ID))
+                {
+                    self.user(&user).pull_requests_merged += 1;
+                    self.digest.pull_requests_merged.push(pull_request);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn finish(mut self) -> Digest {
+        self.digest.comments = self
+            .comment_order
+            .iter()
+            .filter_map(|key| self.comments.remove(key))
+            .collect();
+        self.digest.wiki_pages = self
+            .wiki_order
+            .iter()
+            .filter_map(|page| self.wiki_pages.remove(page))
+            .collect();
+        let mut users: Vec<UserActivity> = self.users.into_values().collect();
+        users.sort_by(|a, b| b.activities.cmp(&a.activities).then(a.name.cmp(&b.name)));
+        self.digest.users = users;
+        self.digest
+    }
+}
+
+impl BacklogApiClient {
+    /// Summarize the activities of a period
+    ///
+    /// Sources that fail to list (e.g. a project the user cannot see) and
+    /// status lists that fail to load are reported in [`Digest::failures`];
+    /// unresolved statuses keep their ID.
+    pub async fn digest(&self, params: DigestParams) -> Result<Digest> {
+        let until = params.until.unwrap_or_else(Utc::now);
+        let sources: Vec<Source> = match params.user {
+            Some(user_id) => vec![Source::User(user_id)],
+            None if params.projects.is_empty() => vec![Source::Space],
+            None => params
+                .projects
+                .iter()
+                .map(|project| Source::Project(project.id, project.project_key.to_string()))
+                .collect(),
+        };
+        let project_ids: HashSet<u32> = params
+            .projects
+            .iter()
+            .map(|project| project.id.value())
+            .collect();
+
+        let semaphore = Arc::new(Semaphore::new(DIGEST_CONCURRENCY));
+        let mut tasks = JoinSet::new();
+        for source in sources {
+            let client = self.clone();
+            let semaphore = Arc::clone(&semaphore);
+            let since = params.since;
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                let listed = client.activities_since(&source, since).await;
+                (source, listed)
+            });
+        }
+
+        let mut failures = Vec::new();
+        let mut truncated = false;
+        let mut activities = Vec::new();
+        let mut seen = HashSet::new();
+        while let Some(joined) = tasks.join_next().await {
+            let (source, listed) =
+                joined.unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic()));
+            match listed {
+                Ok((listed, cut_off)) => {
+                    truncated |= cut_off;
+                    activities.extend(listed.into_iter().filter(|activity| {
+                        activity.created >= params.since
+                            && activity.created <= until
+                            && (project_ids.is_empty()
+                                || activity
+                                    .project_id()
+                                    .is_some_and(|id| project_ids.contains(&id)))
+                            && seen.insert(activity.id)
+                    }));
+                }
+                Err(e) => failures.push(failure(source.name(), e)),
+            }
+        }
+        activities.sort_by_key(|activity| (activity.created, activity.id.value()));
+
+        let statuses = self.status_names(&activities, &mut failures).await;
+        let mut aggregator = Aggregator {
+            base_url: self.base_url(),
+            statuses: &statuses,
+            digest: Digest {
+                since: params.since,
+                until,
+                projects: params
+                    .projects
+                    .iter()
+                    .map(|project| project.project_key.to_string())
+                    .collect(),
+                activity_count: activities.len(),
+                issues_created: Vec::new(),
+                issues_closed: Vec::new(),
+                issues_reassigned: Vec::new(),
+                status_transitions: Vec::new(),
+                comments: Vec::new(),
+                wiki_pages: Vec::new(),
+                pull_requests_opened: Vec::new(),
+                pull_requests_merged: Vec::new(),
+                users: Vec::new(),
+                truncated,
+                failures,
+            },
+            comments: BTreeMap::new(),
+            comment_order: Vec::new(),
+            wiki_pages: BTreeMap::new(),
+            wiki_order: Vec::new(),
+            users: HashMap::new(),
+        };
+        for activity in &activities {
+            aggregator.add(activity);
+        }
+        Ok(aggregator.finish())
+    }
+
+    /// Activities of `source` back to `since`, newest first, and whether the
+    /// page limit cut the period short
+    async fn activities_since(
+        &self,
+        source: &Source,
+        since: DateTime<Utc>,
+    ) -> Result<(Vec<Activity>, bool)> {
+        let mut activities = Vec::new();
+        let mut max_id: Option<ActivityId> = None;
+        for _ in 0..MAX_PAGES {
+            let page = self.recent_updates(source, max_id).await?;
+            let full = page.len() as u32 == PAGE_SIZE;
+            let oldest = page.last().map(|activity| (activity.id, activity.created));
+            activities.extend(page.into_iter().filter(|activity| {
+                max_id.is_none_or(|max_id| activity.id.value() < max_id.value())
+            }));
+            match oldest {
+                Some((id, created)) if full && created >= since => max_id = Some(id),
+                _ => return Ok((activities, false)),
+            }
+        }
+        Ok((activities, true))
+    }
+
+    async fn recent_updates(
+        &self,
+        source: &Source,
+        max_id: Option<ActivityId>,
+    ) -> Result<Vec<Activity>> {
+        let order = Some("desc".to_string());
+        match source {
+            Source::Space => {
+                self.space()
+                    .get_space_recent_updates(GetSpaceRecentUpdatesParams {
+                        max_id,
+                        count: Some(PAGE_SIZE),
+                        order,
+                        ..Default::default()
+                    })
+                    .await
+            }
+            Source::Project(project_id, _) => {
+                let mut params = GetProjectRecentUpdatesParams::new(*project_id);
+                params.max_id = max_id;
+                params.count = Some(PAGE_SIZE);
+                params.order = order;
+                self.project().get_project_recent_updates(params).await
+            }
+            Source::User(user_id) => {
+                self.user()
+                    .get_user_recent_updates(GetUserRecentUpdatesParams {
+                        user_id: *user_id,
+                        activity_type_ids: None,
+                        min_id: None,
+                        max_id: max_id.map(|id| i64::from(id.value())),
+                        count: Some(PAGE_SIZE),
+                        order,
+                    })
+                    .await
+            }
+        }
+    }
+
+    /// Status names of the projects whose issues changed status
+    async fn status_names(
+        &self,
+        activities: &[Activity],
+        failures: &mut Vec<DigestFailure>,
+    ) -> StatusNames {
+        let mut projects = BTreeMap::new();
+        for activity in activities {
+            if let Content::Standard {
+                changes: Some(changes),
+                ..
+            } = &activity.content
+                && matches!(
+                    activity.type_id,
+                    ISSUE_CREATED | ISSUE_UPDATED | ISSUE_COMMENTED
+                )
+                && find_change(changes, "status").is_some()
+                && let Some(project_id) = activity.project_id()
+            {
+                projects.insert(
+                    project_id,
+                    activity.project_key().unwrap_or_default().to_string(),
+                );
+            }
+        }
+
+        let semaphore = Arc::new(Semaphore::new(DIGEST_CONCURRENCY));
+        let mut tasks = JoinSet::new();
+        for (project_id, key) in projects {
+            let client = self.clone();
+            let semaphore = Arc::clone(&semaphore);
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                let params = GetStatusListParams::new(ProjectId::new(project_id));
+                (key, client.project().get_status_list(params).await)
+            });
+        }
+        let mut names = StatusNames::new();
+        while let Some(joined) = tasks.join_next().await {
+            let (key, listed) =
+                joined.unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic()));
+            match listed {
+                Ok(statuses) => {
+                    names.extend(statuses.into_iter().map(|status| {
+                        ((status.project_id.value(), status.id.value()), status.name)
+                    }))
+                }
+                Err(e) => failures.push(failure(format!("{key} statuses"), e)),
+            }
+        }
+        names
+    }
+}
+
+fn user_list(users: &[String]) -> String {
+    users.join(", ")
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {noun}")
+    } else {
+        format!("{count} {noun}s")
+    }
+}
+
+impl Digest {
+    /// The digest as Markdown, leaving out empty sections
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let scope = if self.projects.is_empty() {
+            "the space".to_string()
+        } else {
+            self.projects.join(", ")
+        };
+        let _ = writeln!(
+            out,
+            "## Backlog digest for {scope}\n\n{} – {} UTC · {}",
+            self.since.format("%Y-%m-%d %H:%M"),
+            self.until.format("%Y-%m-%d %H:%M"),
+            match self.activity_count {
+                1 => "1 activity".to_string(),
+                count => format!("{count} activities"),
+            },
+        );
+        if self.truncated {
+            out.push_str("\n_Older activities of the period were left out._\n");
+        }
+        if self.activity_count == 0 {
+            out.push_str("\nNothing happened.\n");
+        }
+
+        let issue_link =
+            |issue: &DigestIssue| format!("[{}]({}) {}", issue.key, issue.url, issue.summary);
+        let mut section = |title: &str, lines: Vec<String>| {
+            if !lines.is_empty() {
+                let _ = write!(out, "\n### {title} ({})\n\n", lines.len());
+                for line in lines {
+                    let _ = writeln!(out, "- {line}");
+                }
+            }
+        };
+        section(
+            "Issues created",
+            self.issues_created
+                .iter()
+                .map(|issue| format!("{} — {}", issue_link(issue), issue.user))
+                .collect(),
+        );
+        section(
+            "Issues closed",
+            self.issues_closed
+                .iter()
+                .map(|issue| format!("{} — {}", issue_link(issue), issue.user))
+                .collect(),
+        );
+        section(
+            "Reassigned",
+            self.issues_reassigned
+                .iter()
+                .map(|change| {
+                    let from = if change.from.is_empty() {
+                        "nobody"
+                    } else {
+                        &change.from
+                    };
+                    let to = if change.to.is_empty() {
+                        "nobody"
+                    } else {
+                        &change.to
+                    };
+                    format!("{}: {from} → {to}", issue_link(&change.issue))
+                })
+                .collect(),
+        );
+        section(
+            "Status changes",
+            self.status_transitions
+                .iter()
+                .map(|change| {
+                    format!(
+                        "{}: {} → {} — {}",
+                        issue_link(&change.issue),
+                        change.from,
+                        change.to,
+                        change.issue.user
+                    )
+                })
+                .collect(),
+        );
+        section(
+            "Comments",
+            self.comments
+                .iter()
+                .map(|comments| {
+                    format!(
+                        "[{}]({}) {}: {} by {}",
+                        comments.key,
+                        comments.url,
+                        comments.summary,
+                        plural(comments.count, "comment"),
+                        user_list(&comments.users)
+                    )
+                })
+                .collect(),
+        );
+        section(
+            "Wiki pages",
+            self.wiki_pages
+                .iter()
+                .map(|page| {
+                    format!(
+                        "{} / {}: {}{} by {}",
+                        page.project,
+                        page.name,
+                        if page.created { "created, " } else { "" },
+                        plural(page.edits, "edit"),
+                        user_list(&page.users)
+                    )
+                })
+                .collect(),
+        );
+        let pull_request_line = |pull_request: &DigestPullRequest| {
+            format!(
+                "{} #{} {} — {}",
+                pull_request.project, pull_request.number, pull_request.summary, pull_request.user
+            )
+        };
+        section(
+            "Pull requests opened",
+            self.pull_requests_opened
+                .iter()
+                .map(pull_request_line)
+                .collect(),
+        );
+        section(
+            "Pull requests merged",
+            self.pull_requests_merged
+                .iter()
+                .map(pull_request_line)
+                .collect(),
+        );
+        section(
+            "By user",
+            self.users
+                .iter()
+                .map(|user| {
+                    let counts = [
+                        (user.issues_created, "created"),
+                        (user.issues_closed, "closed"),
+                        (user.comments, "commented"),
+                        (user.wiki_edits, "wiki edits"),
+                        (user.pull_requests_opened, "PRs opened"),
+                        (user.pull_requests_merged, "PRs merged"),
+                    ]
+                    .iter()
+                    .filter(|(count, _)| *count > 0)
+                    .map(|(count, label)| format!("{label} {count}"))
+                    .collect::<Vec<_>>();
+                    let details = if counts.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" ({})", counts.join(", "))
+                    };
+                    format!("**{}**: {}{details}", user.name, user.activities)
+                })
+                .collect(),
+        );
+        section(
+            "Failed",
+            self.failures
+                .iter()
+                .map(|failure| format!("{}: {}", failure.source, failure.message))
+                .collect(),
+        );
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_since() {
+        let now = DateTime::parse_from_rfc3339("2024-05-14T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let at = |value: &str| parse_since(value, now).map(|time| time.to_rfc3339());
+        assert_eq!(at("24h").unwrap(), "2024-05-13T12:00:00+00:00");
+        assert_eq!(at("90m").unwrap(), "2024-05-14T10:30:00+00:00");
+        assert_eq!(at("2w").unwrap(), "2024-04-30T12:00:00+00:00");
+        assert_eq!(at("2024-05-01").unwrap(), "2024-05-01T00:00:00+00:00");
+        assert_eq!(
+            at("2024-05-13T09:00:00+09:00").unwrap(),
+            "2024-05-13T00:00:00+00:00"
+        );
+        assert!(at("h").is_err());
+        assert!(at("3y").is_err());
+        assert!(at("yesterday").is_err());
+    }
+}
@@ -0,0 +1,306 @@
+//! Activity stream for spaces that cannot receive webhooks
+//!
+//! Polls the space's recent updates in ascending order with `minId` set to
+//! the last activity seen. The interval starts short, doubles while nothing
+//! happens and resets once something does; a full page is followed by an
+//! immediate poll. Activities are deduplicated by ID, and the cursor can be
+//! persisted to a file so a restarted watcher resumes where it stopped.
+
+use crate::client::BacklogApiClient;
+use backlog_api_core::{Error as ApiError, Result};
+use backlog_core::activity::Activity;
+use backlog_core::identifier::{ActivityId, ActivityTypeId, Identifier, ProjectId, UserId};
+use backlog_core::{ProjectIdOrKey, ProjectKey};
+use backlog_space::{GetSpaceRecentUpdatesParams, SpaceApi};
+use futures_util::Stream;
+use std::collections::{HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Maximum number of activities the recent updates API returns per request
+const PAGE_SIZE: u32 = 100;
+
+/// Number of activity IDs remembered for deduplication
+const SEEN_CAPACITY: usize = 1000;
+
+/// Which activities to stream and how often to poll
+#[derive(Debug, Clone)]
+pub struct EventParams {
+    /// Only activities of these projects; empty means all
+    pub projects: Vec<ProjectIdOrKey>,
+    /// Only activities of these types; empty means all
+    pub activity_type_ids: Vec<ActivityTypeId>,
+    /// Only activities by these users; empty means all
+    pub user_ids: Vec<UserId>,
+    /// Interval after a poll that found something
+    pub min_interval: Duration,
+    /// Upper bound the interval grows to while nothing happens
+    pub max_interval: Duration,
+    /// File the last handed out activity ID is kept in
+    pub cursor_file: Option<PathBuf>,
+    /// Where to start when there is no cursor file; the latest activity if `None`
+    pub start_after: Option<ActivityId>,
+}
+
+impl Default for EventParams {
+    fn default() -> Self {
+        Self {
+            projects: Vec::new(),
+            activity_type_ids: Vec::new(),
+            user_ids: Vec::new(),
+            min_interval: Duration::from_secs(5),
+            max_interval: Duration::from_secs(60),
+            cursor_file: None,
+            start_after: None,
+        }
+    }
+}
+
+impl EventParams {
+    pub fn projects(mut self, projects: Vec<ProjectIdOrKey>) -> Self {
+        self.projects = projects;
+        self
+    }
+
+    pub fn activity_type_ids(mut self, activity_type_ids: Vec<ActivityTypeId>) -> Self {
+        self.activity_type_ids = activity_type_ids;
+        self
+    }
+
+    pub fn user_ids(mut self, user_ids: Vec<UserId>) -> Self {
+        self.user_ids = user_ids;
+        self
+    }
+
+    /// Poll between `min` and `max`; `max` is raised to `min` if lower
+    pub fn interval(mut self, min: Duration, max: Duration) -> Self {
+        self.min_interval = min;
+        self.max_interval = max.max(min);
+        self
+    }
+
+    pub fn cursor_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.cursor_file = Some(path.into());
+        self
+    }
+
+    pub fn start_after(mut self, activity_id: ActivityId) -> Self {
+        self.start_after = Some(activity_id);
+        self
+    }
+
+    fn matches_project(&self, activity: &Activity) -> bool {
+        let id_matches = |id: &ProjectId| activity.project_id() == Some(id.value());
+        let key_matches = |key: &ProjectKey| {
+            activity
+                .project_key()
+                .is_some_and(|activity_key| activity_key.eq_ignore_ascii_case(key.as_ref()))
+        };
+        self.projects.is_empty()
+            || self.projects.iter().any(|project| match project {
+                ProjectIdOrKey::Id(id) => id_matches(id),
+                ProjectIdOrKey::Key(key) => key_matches(key),
+                ProjectIdOrKey::EitherIdOrKey(id, key) => id_matches(id) || key_matches(key),
+            })
+    }
+
+    /// Whether the activity passes all filters
+    pub fn matches(&self, activity: &Activity) -> bool {
+        let type_id = ActivityTypeId::new(activity.type_id as u32);
+        self.matches_project(activity)
+            && (self.activity_type_ids.is_empty() || self.activity_type_ids.contains(&type_id))
+            && (self.user_ids.is_empty() || self.user_ids.contains(&activity.created_user.id))
+    }
+}
+
+/// Last activity ID stored in `path`, `None` if the file does not exist yet
+pub fn read_cursor(path: &Path) -> Result<Option<ActivityId>> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(ApiError::FileRead {
+                path: path.display().to_string(),
+                message: e.to_string(),
+            });
+        }
+    };
+    let id = text.trim().parse::<u32>().map_err(|e| ApiError::FileRead {
+        path: path.display().to_string(),
+        message: format!("invalid activity ID: {e}"),
+    })?;
+    Ok(Some(ActivityId::new(id)))
+}
+
+/// Store `activity_id` in `path`, replacing the file at once
+pub fn write_cursor(path: &Path, activity_id: ActivityId) -> Result<()> {
+    let write_error = |e: std::io::Error| ApiError::FileWrite {
+        path: path.display().to_string(),
+        message: e.to_string(),
+    };
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(parent).map_err(write_error)?;
+    }
+    let temporary = path.with_extension("tmp");
+    std::fs::write(&temporary, format!("{}\n", activity_id.value())).map_err(write_error)?;
+    std::fs::rename(&temporary, path).map_err(write_error)
+}
+
+struct EventPoller {
+    space: SpaceApi,
+    params: EventParams,
+    /// Last activity handed out or skipped; `None` until the start is known
+    cursor: Option<ActivityId>,
+    /// Cursor not yet written to the cursor file
+    unsaved: bool,
+    seen: HashSet<ActivityId>,
+    seen_order: VecDeque<ActivityId>,
+    pending: VecDeque<Activity>,
+    interval: Duration,
+    /// Time to wait before the next poll; `None` before the first one
+    wait: Option<Duration>,
+}
+
+impl EventPoller {
+    fn new(space: SpaceApi, params: EventParams) -> Self {
+        let interval = params.min_interval;
+        Self {
+            space,
+            params,
+            cursor: None,
+            unsaved: false,
+            seen: HashSet::new(),
+            seen_order: VecDeque::new(),
+            pending: VecDeque::new(),
+            interval,
+            wait: None,
+        }
+    }
+
+    fn remember(&mut self, activity_id: ActivityId) -> bool {
+        if !self.seen.insert(activity_id) {
+            return false;
+        }
+        self.seen_order.push_back(activity_id);
+        if self.seen_order.len() > SEEN_CAPACITY
+            && let Some(oldest) = self.seen_order.pop_front()
+        {
+            self.seen.remove(&oldest);
+        }
+        true
+    }
+
+    fn save_cursor(&mut self) -> Result<()> {
+        if let (true, Some(path), Some(cursor)) =
+            (self.unsaved, &self.params.cursor_file, self.cursor)
+        {
+            write_cursor(path, cursor)?;
+        }
+        self.unsaved = false;
+        Ok(())
+    }
+
+    /// The persisted cursor, the configured start or the latest activity
+    async fn initial_cursor(&self) -> Result<ActivityId> {
+        if let Some(path) = &self.params.cursor_file
+            && let Some(cursor) = read_cursor(path)?
+        {
+            return Ok(cursor);
+        }
+        if let Some(start) = self.params.start_after {
+            return Ok(start);
+        }
+        let latest = self
+            .space
+            .get_space_recent_updates(GetSpaceRecentUpdatesParams {
+                count: Some(1),
+                order: Some("desc".to_string()),
+                ..Default::default()
+            })
+            .await?;
+        Ok(latest
+            .first()
+            .map_or_else(|| ActivityId::new(0), |activity| activity.id))
+    }
+
+    async fn poll(&mut self) -> Result<()> {
+        let cursor = match self.cursor {
+            Some(cursor) => cursor,
+            None => {
+                let cursor = self.initial_cursor().await?;
+                self.cursor = Some(cursor);
+                cursor
+            }
+        };
+        let page = self
+            .space
+            .get_space_recent_updates(GetSpaceRecentUpdatesParams {
+                activity_type_ids: (!self.params.activity_type_ids.is_empty())
+                    .then(|| self.params.activity_type_ids.clone()),
+                min_id: Some(cursor),
+                count: Some(PAGE_SIZE),
+                order: Some("asc".to_string()),
+                ..Default::default()
+            })
+            .await?;
+        let full = page.len() as u32 == PAGE_SIZE;
+
+        let mut fresh: Vec<Activity> = page
+            .into_iter()
+            .filter(|activity| activity.id.value() > cursor.value())
+            .collect();
+        fresh.sort_by_key(|activity| activity.id.value());
+        fresh.retain(|activity| self.remember(activity.id));
+
+        self.interval = if fresh.is_empty() {
+            (self.interval * 2).min(self.params.max_interval)
+        } else {
+            self.params.min_interval
+        };
+        self.wait = Some(if full { Duration::ZERO } else { self.interval });
+        self.pending.extend(fresh);
+        Ok(())
+    }
+
+    async fn next_event(&mut self) -> Result<Activity> {
+        loop {
+            while let Some(activity) = self.pending.pop_front() {
+                self.cursor = Some(activity.id);
+                self.unsaved = true;
+                if self.params.matches(&activity) {
+                    self.save_cursor()?;
+                    return Ok(activity);
+                }
+            }
+            self.save_cursor()?;
+
+            if let Some(wait) = self.wait {
+                tokio::time::sleep(wait).await;
+            }
+            if let Err(e) = self.poll().await {
+                // Back off fully so a failing server is not hammered
+                self.interval = self.params.max_interval;
+                self.wait = Some(self.interval);
+                return Err(e);
+            }
+        }
+    }
+}
+
+impl BacklogApiClient {
+    /// Stream the space's activities as they happen
+    ///
+    /// The stream never ends. A failed poll yields its error and the stream
+    /// keeps polling after the maximum interval, so callers decide whether
+    /// errors are fatal.
+    pub fn events(&self, params: EventParams) -> impl Stream<Item = Result<Activity>> + use<> {
+        let poller = EventPoller::new(self.space(), params);
+        futures_util::stream::unfold(poller, |mut poller| async move {
+            let event = poller.next_event().await;
+            Some((event, poller))
+        })
+    }
+}
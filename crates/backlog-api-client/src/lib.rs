@@ -1,5 +1,16 @@
 pub mod client;
+#[cfg(feature = "digest")]
+pub mod digest;
+#[cfg(feature = "events")]
+pub mod events;
+#[cfg(feature = "pr-inbox")]
+pub mod pr_inbox;
+#[cfg(feature = "search")]
+pub mod search;
+#[cfg(feature = "stars")]
+pub mod stars;
 pub use ::client::DownloadedFile; // Re-export DownloadedFile from the client crate
+pub use ::client::{DownloadProgress, StreamedDownload, discard_partial_download};
 pub use backlog_api_core::Error as ApiError;
 pub use backlog_api_core::bytes; // Re-export bytes
 
@@ -40,7 +51,10 @@ pub use backlog_document::{
 #[cfg(feature = "file")]
 pub use backlog_file::models::FileContent;
 #[cfg(feature = "file")]
-pub use backlog_file::{FileApi, GetSharedFilesListParams, GetSharedFilesListResponse, SharedFile};
+pub use backlog_file::{
+    FileApi, GetFileParams, GetSharedFilesListParams, GetSharedFilesListParamsBuilder,
+    GetSharedFilesListResponse, SharedFile,
+};
 
 // Issue module (from backlog_issue)
 #[cfg(feature = "issue")]
@@ -66,15 +80,16 @@ pub use backlog_issue::{LinkSharedFilesToIssueParams, LinkSharedFilesToIssuePara
 // Git module (from backlog_git)
 #[cfg(feature = "git")]
 pub use backlog_git::{
-    ChangeLog, DownloadPullRequestAttachmentParams, DownloadPullRequestAttachmentResponse,
-    GetPullRequestAttachmentListParams, GetPullRequestAttachmentListResponse,
-    GetPullRequestCommentCountParams, GetPullRequestCommentCountResponse,
-    GetPullRequestCommentListParams, GetPullRequestCommentListResponse, GetPullRequestCountParams,
-    GetPullRequestCountResponse, GetPullRequestListParams, GetPullRequestListResponse,
-    GetPullRequestParams, GetPullRequestResponse, GetRepositoryListParams,
-    GetRepositoryListResponse, GetRepositoryParams, GetRepositoryResponse, GitApi, Notification,
-    PrCommentOrder, PullRequest, PullRequestAttachment, PullRequestComment,
-    PullRequestCommentCount, PullRequestCount, Repository, Star,
+    ChangeLog, CodeLocation, CompactPrComment, DownloadPullRequestAttachmentParams,
+    DownloadPullRequestAttachmentResponse, GetPullRequestAttachmentListParams,
+    GetPullRequestAttachmentListResponse, GetPullRequestCommentCountParams,
+    GetPullRequestCommentCountResponse, GetPullRequestCommentListParams,
+    GetPullRequestCommentListResponse, GetPullRequestCountParams, GetPullRequestCountResponse,
+    GetPullRequestListParams, GetPullRequestListResponse, GetPullRequestParams,
+    GetPullRequestResponse, GetRepositoryListParams, GetRepositoryListResponse,
+    GetRepositoryParams, GetRepositoryResponse, GitApi, Notification, PrCommentOrder,
+    PrCommentThread, PullRequest, PullRequestAttachment, PullRequestComment,
+    PullRequestCommentCount, PullRequestCount, Repository, Star, comment_thread,
 };
 
 #[cfg(all(feature = "git", feature = "git_writable"))]
@@ -101,7 +116,10 @@ pub use backlog_space::{
 
 // Space writable operations (from backlog_space)
 #[cfg(all(feature = "space", feature = "space_writable"))]
-pub use backlog_space::{UpdateSpaceNotificationParams, UploadAttachmentParams};
+pub use backlog_space::{
+    AttachmentInfo, AttachmentSource, AttachmentUpload, MAX_ATTACHMENT_SIZE,
+    UpdateSpaceNotificationParams, UploadAttachmentParams,
+};
 
 // User module (from backlog_user)
 #[cfg(feature = "user")]
@@ -111,6 +129,10 @@ pub use backlog_user::{
     Order as WatchingOrder, StarCount, StarOrder, UserApi, WatchingSort,
 };
 
+// User writable operations (from backlog_user)
+#[cfg(all(feature = "user", feature = "user_writable"))]
+pub use backlog_user::{AddUserParams, DeleteUserParams, UpdateUserParams};
+
 // Activity module (from backlog_activity)
 #[cfg(feature = "activity")]
 pub use backlog_activity::ActivityApi;
@@ -136,12 +158,15 @@ pub use backlog_team::{
     api::{GetTeamParams, GetTeamResponse, ListTeamsOrder, ListTeamsParams, ListTeamsResponse},
 };
 
+#[cfg(all(feature = "team", feature = "team_writable"))]
+pub use backlog_team::api::{AddTeamParams, DeleteTeamParams, UpdateTeamParams};
+
 // Star module (from backlog_star)
 #[cfg(feature = "star")]
 pub use backlog_star::StarApi;
 
 #[cfg(all(feature = "star", feature = "star_writable"))]
-pub use backlog_star::AddStarParams;
+pub use backlog_star::{AddStarParams, RemoveStarParams};
 
 // Rate Limit module (from backlog_rate_limit)
 #[cfg(feature = "rate-limit")]
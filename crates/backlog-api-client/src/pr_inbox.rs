@@ -0,0 +1,547 @@
+//! Pull requests awaiting the current user's attention
+//!
+//! Lists the pull requests of every repository in the given projects
+//! concurrently and sorts them into those assigned to the user, those the
+//! user created and other recently updated ones. Each entry is enriched with
+//! its comment count, age, related issue status and unread notifications.
+
+use crate::client::BacklogApiClient;
+use backlog_api_core::Result;
+use backlog_core::identifier::{Identifier, IssueId, StatusId, UserId};
+use backlog_git::{
+    GetPullRequestCommentCountParams, GetPullRequestListParams, GetRepositoryListParams,
+    PullRequest, Repository,
+};
+use backlog_issue::GetIssueListParamsBuilder;
+use backlog_project::Project;
+use backlog_user::{GetNotificationsParams, GetOwnUserParams};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// Maximum number of requests in flight
+const INBOX_CONCURRENCY: usize = 6;
+
+/// Maximum number of IDs the issue list accepts per request
+const ISSUE_BATCH_SIZE: usize = 100;
+
+/// Number of latest notifications checked for unread pull request updates
+const NOTIFICATION_COUNT: u8 = 100;
+
+/// Pull request status as used by the `statusId[]` filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PullRequestState {
+    Open,
+    Closed,
+    Merged,
+}
+
+impl PullRequestState {
+    pub fn status_id(self) -> StatusId {
+        StatusId::new(match self {
+            PullRequestState::Open => 1,
+            PullRequestState::Closed => 2,
+            PullRequestState::Merged => 3,
+        })
+    }
+}
+
+impl fmt::Display for PullRequestState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PullRequestState::Open => write!(f, "open"),
+            PullRequestState::Closed => write!(f, "closed"),
+            PullRequestState::Merged => write!(f, "merged"),
+        }
+    }
+}
+
+impl FromStr for PullRequestState {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "open" => Ok(PullRequestState::Open),
+            "closed" => Ok(PullRequestState::Closed),
+            "merged" => Ok(PullRequestState::Merged),
+            other => Err(format!(
+                "Unknown pull request status '{other}' (expected open, closed or merged)"
+            )),
+        }
+    }
+}
+
+/// Which pull requests to collect
+#[derive(Debug, Clone)]
+pub struct PrInboxParams {
+    /// Projects whose repositories are listed
+    pub projects: Vec<Project>,
+    /// Repository names to include; empty means all
+    pub repositories: Vec<String>,
+    /// Statuses to include; empty means open only
+    pub states: Vec<PullRequestState>,
+    /// Days within which other pull requests count as recently updated
+    pub recent_days: u32,
+    /// Maximum number of pull requests fetched per repository (1-100)
+    pub count: u8,
+}
+
+impl PrInboxParams {
+    pub fn new(projects: Vec<Project>) -> Self {
+        Self {
+            projects,
+            repositories: Vec::new(),
+            states: Vec::new(),
+            recent_days: 7,
+            count: 100,
+        }
+    }
+
+    pub fn repositories(mut self, repositories: Vec<String>) -> Self {
+        self.repositories = repositories;
+        self
+    }
+
+    pub fn states(mut self, states: Vec<PullRequestState>) -> Self {
+        self.states = states;
+        self
+    }
+
+    pub fn recent_days(mut self, recent_days: u32) -> Self {
+        self.recent_days = recent_days;
+        self
+    }
+
+    pub fn count(mut self, count: u8) -> Self {
+        self.count = count.clamp(1, 100);
+        self
+    }
+
+    fn includes_repository(&self, repository: &Repository) -> bool {
+        self.repositories.is_empty()
+            || self
+                .repositories
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(&repository.name))
+    }
+}
+
+/// The issue a pull request is related to
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InboxIssue {
+    pub key: String,
+    pub summary: String,
+    pub status: String,
+}
+
+/// One pull request with the details needed to triage it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InboxPullRequest {
+    pub project: String,
+    pub repository: String,
+    pub number: u64,
+    pub summary: String,
+    pub status: String,
+    pub author: Option<String>,
+    pub assignee: Option<String>,
+    pub base: String,
+    pub branch: String,
+    pub created: Option<DateTime<Utc>>,
+    pub updated: Option<DateTime<Utc>>,
+    /// Whole days since the pull request was opened
+    pub age_days: Option<i64>,
+    /// `None` when the count could not be fetched
+    pub comment_count: Option<u32>,
+    pub issue: Option<InboxIssue>,
+    /// Unread notifications about this pull request
+    pub unread_notifications: u32,
+    pub url: String,
+}
+
+/// A request that failed without failing the whole inbox
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrInboxFailure {
+    /// Project key, `PROJECT/repository`, or the name of another source
+    pub source: String,
+    pub message: String,
+}
+
+/// Pull requests grouped by the user's involvement, most recently updated first
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrInbox {
+    pub assigned_to_me: Vec<InboxPullRequest>,
+    pub created_by_me: Vec<InboxPullRequest>,
+    pub recently_updated: Vec<InboxPullRequest>,
+    pub failures: Vec<PrInboxFailure>,
+}
+
+/// Which inbox group a pull request belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InboxGroup {
+    AssignedToMe,
+    CreatedByMe,
+    RecentlyUpdated,
+}
+
+/// The group of a pull request, or `None` if it does not concern the user
+///
+/// Assignment wins over authorship; other pull requests are kept only when
+/// updated after `since`.
+pub fn classify(
+    pull_request: &PullRequest,
+    me: UserId,
+    since: DateTime<Utc>,
+) -> Option<InboxGroup> {
+    if pull_request.assignee.as_ref().map(|user| user.id) == Some(me) {
+        Some(InboxGroup::AssignedToMe)
+    } else if pull_request.created_user.as_ref().map(|user| user.id) == Some(me) {
+        Some(InboxGroup::CreatedByMe)
+    } else if pull_request
+        .updated
+        .or(pull_request.created)
+        .is_some_and(|updated| updated >= since)
+    {
+        Some(InboxGroup::RecentlyUpdated)
+    } else {
+        None
+    }
+}
+
+/// Run futures with at most [`INBOX_CONCURRENCY`] in flight, in completion order
+async fn run_limited<T, F>(futures: Vec<F>) -> Vec<T>
+where
+    T: Send + 'static,
+    F: Future<Output = T> + Send + 'static,
+{
+    let semaphore = Arc::new(Semaphore::new(INBOX_CONCURRENCY));
+    let mut tasks = JoinSet::new();
+    for future in futures {
+        let semaphore = Arc::clone(&semaphore);
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            future.await
+        });
+    }
+    let mut outputs = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        outputs.push(joined.unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic())));
+    }
+    outputs
+}
+
+fn failure(source: impl Into<String>, error: impl fmt::Display) -> PrInboxFailure {
+    PrInboxFailure {
+        source: source.into(),
+        message: error.to_string(),
+    }
+}
+
+/// A pull request with the project and repository it was listed from
+struct Listed {
+    project: Project,
+    repository: Repository,
+    pull_request: PullRequest,
+    group: InboxGroup,
+}
+
+impl BacklogApiClient {
+    /// Collect the pull requests concerning the current user
+    ///
+    /// Failing listings (e.g. a project without Git) and failed enrichment
+    /// requests are reported in [`PrInbox::failures`].
+    pub async fn pr_inbox(&self, params: PrInboxParams) -> Result<PrInbox> {
+        let me = self.user().get_own_user(GetOwnUserParams::new()).await?.id;
+        let mut inbox = PrInbox::default();
+        let now = Utc::now();
+        let since = now - Duration::days(i64::from(params.recent_days));
+        let states = if params.states.is_empty() {
+            vec![PullRequestState::Open]
+        } else {
+            params.states.clone()
+        };
+
+        let listings = params
+            .projects
+            .iter()
+            .map(|project| {
+                let (client, project) = (self.clone(), project.clone());
+                async move {
+                    let repositories = client
+                        .git()
+                        .get_repository_list(GetRepositoryListParams::new(project.id))
+                        .await;
+                    (project, repositories)
+                }
+            })
+            .collect();
+        let mut repositories = Vec::new();
+        for (project, listed) in run_limited(listings).await {
+            match listed {
+                Ok(listed) => repositories.extend(
+                    listed
+                        .into_iter()
+                        .filter(|repository| params.includes_repository(repository))
+                        .map(|repository| (project.clone(), repository)),
+                ),
+                Err(e) => inbox
+                    .failures
+                    .push(failure(project.project_key.to_string(), e)),
+            }
+        }
+
+        let status_ids: Vec<StatusId> = states.iter().map(|state| state.status_id()).collect();
+        let listings = repositories
+            .into_iter()
+            .map(|(project, repository)| {
+                let client = self.clone();
+                let params = GetPullRequestListParams::new(project.id, repository.id)
+                    .status_ids(status_ids.clone())
+                    .count(params.count);
+                async move {
+                    let pull_requests = client.git().get_pull_request_list(params).await;
+                    (project, repository, pull_requests)
+                }
+            })
+            .collect();
+        let mut listed = Vec::new();
+        for (project, repository, pull_requests) in run_limited(listings).await {
+            match pull_requests {
+                Ok(pull_requests) => {
+                    for pull_request in pull_requests {
+                        if let Some(group) = classify(&pull_request, me, since) {
+                            listed.push(Listed {
+                                project: project.clone(),
+                                repository: repository.clone(),
+                                pull_request,
+                                group,
+                            });
+                        }
+                    }
+                }
+                Err(e) => inbox.failures.push(failure(
+                    format!("{}/{}", project.project_key, repository.name),
+                    e,
+                )),
+            }
+        }
+
+        let comment_counts = self.comment_counts(&listed).await;
+        let issues = self.related_issues(&listed, &mut inbox.failures).await;
+        let unread = self
+            .unread_pull_request_notifications(&mut inbox.failures)
+            .await;
+
+        for Listed {
+            project,
+            repository,
+            pull_request,
+            group,
+        } in listed
+        {
+            let key = project.project_key.to_string();
+            let path = format!(
+                "git/{key}/{}/pullRequests/{}",
+                repository.name, pull_request.number
+            );
+            let entry = InboxPullRequest {
+                project: key,
+                repository: repository.name,
+                number: pull_request.number.value(),
+                comment_count: comment_counts.get(&pull_request.id.value()).copied(),
+                issue: pull_request
+                    .related_issue
+                    .as_ref()
+                    .and_then(|issue| issues.get(&issue.id.value()).cloned()),
+                unread_notifications: unread
+                    .get(&u64::from(pull_request.id.value()))
+                    .copied()
+                    .unwrap_or(0),
+                url: self
+                    .base_url()
+                    .join(&path)
+                    .map_or(path, |url| url.to_string()),
+                age_days: pull_request
+                    .created
+                    .map(|created| (now - created).num_days()),
+                summary: pull_request.summary,
+                status: pull_request.status.name,
+                author: pull_request.created_user.map(|user| user.name),
+                assignee: pull_request.assignee.map(|user| user.name),
+                base: pull_request.base,
+                branch: pull_request.branch,
+                created: pull_request.created,
+                updated: pull_request.updated,
+            };
+            match group {
+                InboxGroup::AssignedToMe => inbox.assigned_to_me.push(entry),
+                InboxGroup::CreatedByMe => inbox.created_by_me.push(entry),
+                InboxGroup::RecentlyUpdated => inbox.recently_updated.push(entry),
+            }
+        }
+        for group in [
+            &mut inbox.assigned_to_me,
+            &mut inbox.created_by_me,
+            &mut inbox.recently_updated,
+        ] {
+            group.sort_by_key(|pr| std::cmp::Reverse(pr.updated.or(pr.created)));
+        }
+        Ok(inbox)
+    }
+
+    /// Comment counts by pull request ID; failed counts are left out
+    async fn comment_counts(&self, listed: &[Listed]) -> HashMap<u32, u32> {
+        let requests = listed
+            .iter()
+            .map(|entry| {
+                let client = self.clone();
+                let id = entry.pull_request.id.value();
+                let params = GetPullRequestCommentCountParams::new(
+                    entry.project.id,
+                    entry.repository.id,
+                    entry.pull_request.number,
+                );
+                async move {
+                    let count = client.git().get_pull_request_comment_count(params).await;
+                    (id, count)
+                }
+            })
+            .collect();
+        run_limited(requests)
+            .await
+            .into_iter()
+            .filter_map(|(id, count)| count.ok().map(|count| (id, count.count)))
+            .collect()
+    }
+
+    /// Related issues by issue ID, fetched in batches
+    async fn related_issues(
+        &self,
+        listed: &[Listed],
+        failures: &mut Vec<PrInboxFailure>,
+    ) -> HashMap<u32, InboxIssue> {
+        let mut ids: Vec<IssueId> = listed
+            .iter()
+            .filter_map(|entry| entry.pull_request.related_issue.as_ref())
+            .map(|issue| issue.id)
+            .collect();
+        ids.sort_by_key(|id| id.value());
+        ids.dedup();
+
+        let mut issues = HashMap::new();
+        for batch in ids.chunks(ISSUE_BATCH_SIZE) {
+            let fetched = match GetIssueListParamsBuilder::default()
+                .id(batch.to_vec())
+                .count(ISSUE_BATCH_SIZE as u32)
+                .build()
+            {
+                Ok(params) => self.issue().get_issue_list(params).await,
+                Err(e) => Err(e),
+            };
+            match fetched {
+                Ok(fetched) => issues.extend(fetched.into_iter().map(|issue| {
+                    (
+                        issue.id.value(),
+                        InboxIssue {
+                            key: issue.issue_key.to_string(),
+                            summary: issue.summary,
+                            status: issue.status.name,
+                        },
+                    )
+                })),
+                Err(e) => failures.push(failure("issues", e)),
+            }
+        }
+        issues
+    }
+
+    /// Unread notification counts by pull request ID
+    async fn unread_pull_request_notifications(
+        &self,
+        failures: &mut Vec<PrInboxFailure>,
+    ) -> HashMap<u64, u32> {
+        let params = GetNotificationsParams::new().with_count(NOTIFICATION_COUNT);
+        let notifications = match self.user().get_notifications(params).await {
+            Ok(notifications) => notifications,
+            Err(e) => {
+                failures.push(failure("notifications", e));
+                return HashMap::new();
+            }
+        };
+        let mut unread = HashMap::new();
+        for notification in notifications.iter().filter(|n| !n.already_read) {
+            if let Some(id) = notification
+                .pull_request
+                .as_ref()
+                .and_then(|pull_request| pull_request.get("id"))
+                .and_then(|id| id.as_u64())
+            {
+                *unread.entry(id).or_insert(0) += 1;
+            }
+        }
+        unread
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pull_request(assignee: Option<u32>, author: u32, updated: &str) -> PullRequest {
+        let user = |id: u32| {
+            serde_json::json!({
+                "id": id, "userId": format!("user{id}"), "name": format!("User {id}"),
+                "roleType": 1, "mailAddress": format!("user{id}@example.com")
+            })
+        };
+        serde_json::from_value(serde_json::json!({
+            "id": 100, "projectId": 1, "repositoryId": 5, "number": 7,
+            "summary": "Fix login", "description": null, "base": "main", "branch": "fix",
+            "status": {"id": 1, "name": "Open"},
+            "assignee": assignee.map(user),
+            "createdUser": user(author),
+            "created": "2024-01-01T00:00:00Z",
+            "updated": updated
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_classify_prefers_assignment_over_authorship() {
+        let me = UserId::new(1);
+        let since = "2024-01-10T00:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let old = "2024-01-02T00:00:00Z";
+        let new = "2024-01-11T00:00:00Z";
+
+        assert_eq!(
+            classify(&pull_request(Some(1), 1, old), me, since),
+            Some(InboxGroup::AssignedToMe)
+        );
+        assert_eq!(
+            classify(&pull_request(Some(2), 1, old), me, since),
+            Some(InboxGroup::CreatedByMe)
+        );
+        assert_eq!(
+            classify(&pull_request(None, 2, new), me, since),
+            Some(InboxGroup::RecentlyUpdated)
+        );
+        assert_eq!(classify(&pull_request(Some(2), 2, old), me, since), None);
+    }
+
+    #[test]
+    fn test_pull_request_state_from_str() {
+        assert_eq!("Merged".parse(), Ok(PullRequestState::Merged));
+        assert_eq!(PullRequestState::Closed.status_id(), StatusId::new(2));
+        assert!("draft".parse::<PullRequestState>().is_err());
+    }
+}
@@ -0,0 +1,615 @@
+//! Search across issues, wikis, documents and pull requests
+//!
+//! Each kind is searched with its own listing API. Issues, wikis and
+//! documents are filtered by the server's `keyword` parameter; pull requests
+//! have none, so only the latest [`SearchParams::count`] pull requests of
+//! each repository are fetched and matched locally. Wiki listings carry no
+//! content, so the most recently updated matching pages are loaded to build
+//! their snippets. Hits are normalized into [`SearchHit`] and ranked by match
+//! quality and recency.
+
+use crate::client::BacklogApiClient;
+use backlog_api_core::Result;
+use backlog_core::identifier::Identifier;
+use backlog_document::{DocumentOrder, DocumentSortKey, ListDocumentsParams};
+use backlog_git::{GetPullRequestListParams, GetRepositoryListParams};
+use backlog_issue::GetIssueListParamsBuilder;
+use backlog_project::Project;
+use backlog_wiki::{GetWikiDetailParams, GetWikiListParams};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// Maximum number of listing requests in flight
+const SEARCH_CONCURRENCY: usize = 6;
+
+/// Characters of context shown around a match
+const SNIPPET_LENGTH: usize = 160;
+
+/// Age in days at which the recency bonus has halved
+const RECENCY_HALF_LIFE_DAYS: f64 = 30.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SearchKind {
+    Issue,
+    Wiki,
+    Document,
+    PullRequest,
+}
+
+impl SearchKind {
+    pub const ALL: [SearchKind; 4] = [
+        SearchKind::Issue,
+        SearchKind::Wiki,
+        SearchKind::Document,
+        SearchKind::PullRequest,
+    ];
+}
+
+impl fmt::Display for SearchKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SearchKind::Issue => write!(f, "issue"),
+            SearchKind::Wiki => write!(f, "wiki"),
+            SearchKind::Document => write!(f, "document"),
+            SearchKind::PullRequest => write!(f, "pull_request"),
+        }
+    }
+}
+
+impl FromStr for SearchKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('-', "_").as_str() {
+            "issue" | "issues" => Ok(SearchKind::Issue),
+            "wiki" | "wikis" => Ok(SearchKind::Wiki),
+            "document" | "documents" | "doc" => Ok(SearchKind::Document),
+            "pull_request" | "pull_requests" | "pullrequest" | "pr" => Ok(SearchKind::PullRequest),
+            other => Err(format!(
+                "Unknown search kind '{other}' (expected issue, wiki, document or pull_request)"
+            )),
+        }
+    }
+}
+
+/// What to search for and where
+#[derive(Debug, Clone)]
+pub struct SearchParams {
+    pub keyword: String,
+    /// Projects to search in
+    pub projects: Vec<Project>,
+    /// Kinds to search for; empty means all
+    pub kinds: Vec<SearchKind>,
+    /// Maximum number of hits fetched per kind and request (1-100)
+    ///
+    /// Pull requests are matched among the latest `count` of each
+    /// repository, so older matching pull requests are not found.
+    pub count: u8,
+}
+
+impl SearchParams {
+    pub fn new(keyword: impl Into<String>, projects: Vec<Project>) -> Self {
+        Self {
+            keyword: keyword.into(),
+            projects,
+            kinds: Vec::new(),
+            count: 20,
+        }
+    }
+
+    pub fn kinds(mut self, kinds: Vec<SearchKind>) -> Self {
+        self.kinds = kinds;
+        self
+    }
+
+    pub fn count(mut self, count: u8) -> Self {
+        self.count = count.clamp(1, 100);
+        self
+    }
+
+    fn includes(&self, kind: SearchKind) -> bool {
+        self.kinds.is_empty() || self.kinds.contains(&kind)
+    }
+}
+
+/// One search result, whatever its kind
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHit {
+    pub kind: SearchKind,
+    pub project: String,
+    /// Issue key, wiki or document ID, or `repository#number`
+    pub key: String,
+    pub title: String,
+    pub snippet: String,
+    pub updated: Option<DateTime<Utc>>,
+    pub url: String,
+    pub score: f64,
+}
+
+/// A listing that failed without failing the whole search
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchFailure {
+    pub kind: SearchKind,
+    /// Project key, or `None` for requests spanning all projects
+    pub project: Option<String>,
+    pub message: String,
+}
+
+/// Ranked hits, best first
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResults {
+    pub hits: Vec<SearchHit>,
+    pub failures: Vec<SearchFailure>,
+}
+
+/// A hit before it is scored
+struct RawHit {
+    kind: SearchKind,
+    project: String,
+    key: String,
+    title: String,
+    body: String,
+    updated: Option<DateTime<Utc>>,
+    path: String,
+    /// Whether the server already matched the keyword
+    server_matched: bool,
+}
+
+type Outcome = std::result::Result<Vec<RawHit>, SearchFailure>;
+
+impl BacklogApiClient {
+    /// Search all requested kinds in the given projects concurrently
+    ///
+    /// A failing listing (e.g. a project without Git) is reported in
+    /// [`SearchResults::failures`] instead of failing the search.
+    pub async fn search(&self, params: SearchParams) -> Result<SearchResults> {
+        let keyword = params.keyword.trim().to_string();
+        if keyword.is_empty() || params.projects.is_empty() {
+            return Ok(SearchResults::default());
+        }
+
+        let semaphore = Arc::new(Semaphore::new(SEARCH_CONCURRENCY));
+        let keys: Arc<HashMap<u32, String>> = Arc::new(
+            params
+                .projects
+                .iter()
+                .map(|p| (p.id.value(), p.project_key.to_string()))
+                .collect(),
+        );
+        let mut tasks: JoinSet<Vec<Outcome>> = JoinSet::new();
+
+        macro_rules! spawn {
+            ($body:expr) => {{
+                let semaphore = Arc::clone(&semaphore);
+                tasks.spawn(async move {
+                    let _permit = semaphore.acquire_owned().await;
+                    $body.await
+                });
+            }};
+        }
+
+        if params.includes(SearchKind::Issue) {
+            let (client, keyword, keys) = (self.clone(), keyword.clone(), Arc::clone(&keys));
+            let project_ids = params.projects.iter().map(|p| p.id).collect::<Vec<_>>();
+            let count = params.count;
+            spawn!(async move {
+                vec![search_issues(&client, &keyword, project_ids, count, &keys).await]
+            });
+        }
+        if params.includes(SearchKind::Document) {
+            let (client, keyword, keys) = (self.clone(), keyword.clone(), Arc::clone(&keys));
+            let project_ids = params.projects.iter().map(|p| p.id).collect::<Vec<_>>();
+            let count = params.count;
+            spawn!(async move {
+                vec![search_documents(&client, &keyword, project_ids, count, &keys).await]
+            });
+        }
+        // Wikis and pull requests take a permit per request they make, as
+        // they fan out into one request per page or repository
+        for project in &params.projects {
+            if params.includes(SearchKind::Wiki) {
+                let (client, keyword, project) = (self.clone(), keyword.clone(), project.clone());
+                let (semaphore, count) = (Arc::clone(&semaphore), params.count);
+                tasks.spawn(async move {
+                    vec![search_wikis(&client, &keyword, &project, count, &semaphore).await]
+                });
+            }
+            if params.includes(SearchKind::PullRequest) {
+                let (client, project, count) = (self.clone(), project.clone(), params.count);
+                let semaphore = Arc::clone(&semaphore);
+                tasks.spawn(async move {
+                    search_pull_requests(&client, &project, count, &semaphore).await
+                });
+            }
+        }
+
+        let now = Utc::now();
+        let mut results = SearchResults::default();
+        while let Some(joined) = tasks.join_next().await {
+            let outcomes = joined.unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic()));
+            for outcome in outcomes {
+                match outcome {
+                    Ok(raw) => {
+                        for hit in raw {
+                            if let Some(hit) = self.score(hit, &keyword, now) {
+                                results.hits.push(hit);
+                            }
+                        }
+                    }
+                    Err(failure) => results.failures.push(failure),
+                }
+            }
+        }
+        rank(&mut results.hits);
+        Ok(results)
+    }
+
+    fn score(&self, raw: RawHit, keyword: &str, now: DateTime<Utc>) -> Option<SearchHit> {
+        let quality = match_quality(keyword, &raw.title, &raw.body);
+        if quality == 0.0 && !raw.server_matched {
+            return None;
+        }
+        let url = self
+            .base_url()
+            .join(&raw.path)
+            .map_or_else(|_| raw.path.clone(), |url| url.to_string());
+        Some(SearchHit {
+            kind: raw.kind,
+            project: raw.project,
+            key: raw.key,
+            snippet: snippet(&raw.body, keyword),
+            score: quality + recency(raw.updated, now),
+            title: raw.title,
+            updated: raw.updated,
+            url,
+        })
+    }
+}
+
+fn failure(kind: SearchKind, project: Option<&str>, error: impl fmt::Display) -> SearchFailure {
+    SearchFailure {
+        kind,
+        project: project.map(str::to_string),
+        message: error.to_string(),
+    }
+}
+
+async fn search_issues(
+    client: &BacklogApiClient,
+    keyword: &str,
+    project_ids: Vec<backlog_core::identifier::ProjectId>,
+    count: u8,
+    keys: &HashMap<u32, String>,
+) -> Outcome {
+    let params = GetIssueListParamsBuilder::default()
+        .project_id(project_ids)
+        .keyword(keyword)
+        .sort("updated")
+        .order("desc")
+        .count(u32::from(count))
+        .build()
+        .map_err(|e| failure(SearchKind::Issue, None, e))?;
+    let issues = client
+        .issue()
+        .get_issue_list(params)
+        .await
+        .map_err(|e| failure(SearchKind::Issue, None, e))?;
+    Ok(issues
+        .into_iter()
+        .map(|issue| RawHit {
+            kind: SearchKind::Issue,
+            project: keys
+                .get(&issue.project_id.value())
+                .cloned()
+                .unwrap_or_default(),
+            key: issue.issue_key.to_string(),
+            path: format!("view/{}", issue.issue_key),
+            title: issue.summary,
+            body: issue.description,
+            updated: DateTime::parse_from_rfc3339(&issue.updated)
+                .ok()
+                .map(|updated| updated.with_timezone(&Utc)),
+            server_matched: true,
+        })
+        .collect())
+}
+
+async fn search_documents(
+    client: &BacklogApiClient,
+    keyword: &str,
+    project_ids: Vec<backlog_core::identifier::ProjectId>,
+    count: u8,
+    keys: &HashMap<u32, String>,
+) -> Outcome {
+    let params = ListDocumentsParams {
+        project_ids: Some(project_ids),
+        keyword: Some(keyword.to_string()),
+        sort: Some(DocumentSortKey::Updated),
+        order: Some(DocumentOrder::Desc),
+        offset: Some(0),
+        count: Some(u32::from(count)),
+    };
+    let documents = client
+        .document()
+        .list_documents(params)
+        .await
+        .map_err(|e| failure(SearchKind::Document, None, e))?;
+    Ok(documents
+        .into_iter()
+        .map(|document| {
+            let project = keys
+                .get(&document.project_id.value())
+                .cloned()
+                .unwrap_or_default();
+            RawHit {
+                kind: SearchKind::Document,
+                key: document.id.to_string(),
+                path: format!("document/{project}/{}", document.id),
+                project,
+                title: document.title,
+                body: document.plain,
+                updated: Some(document.updated),
+                server_matched: true,
+            }
+        })
+        .collect())
+}
+
+/// Matching wiki pages, with the content of the `count` most recently
+/// updated ones loaded for their snippets
+async fn search_wikis(
+    client: &BacklogApiClient,
+    keyword: &str,
+    project: &Project,
+    count: u8,
+    semaphore: &Arc<Semaphore>,
+) -> Outcome {
+    let key = project.project_key.to_string();
+    let params = GetWikiListParams::new()
+        .project_id_or_key(project.id)
+        .keyword(keyword);
+    let mut wikis = {
+        let _permit = semaphore.acquire().await;
+        client
+            .wiki()
+            .get_wiki_list(params)
+            .await
+            .map_err(|e| failure(SearchKind::Wiki, Some(&key), e))?
+    };
+    wikis.sort_by_key(|wiki| std::cmp::Reverse(wiki.updated));
+    wikis.truncate(count.into());
+
+    let mut tasks = JoinSet::new();
+    for wiki in wikis {
+        let (client, semaphore) = (client.clone(), Arc::clone(semaphore));
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            // A page that cannot be loaded is still a hit, just without a snippet
+            let content = client
+                .wiki()
+                .get_wiki_detail(GetWikiDetailParams::new(wiki.id))
+                .await
+                .map(|detail| detail.content)
+                .unwrap_or_default();
+            (wiki, content)
+        });
+    }
+
+    let mut hits = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        let (wiki, content) = joined.unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic()));
+        hits.push(RawHit {
+            kind: SearchKind::Wiki,
+            project: key.clone(),
+            key: wiki.id.to_string(),
+            path: format!("alias/wiki/{}", wiki.id),
+            title: wiki.name,
+            body: content,
+            updated: Some(wiki.updated),
+            server_matched: true,
+        });
+    }
+    Ok(hits)
+}
+
+/// The latest `count` pull requests of every repository, matched locally
+async fn search_pull_requests(
+    client: &BacklogApiClient,
+    project: &Project,
+    count: u8,
+    semaphore: &Arc<Semaphore>,
+) -> Vec<Outcome> {
+    let key = project.project_key.to_string();
+    let repositories = {
+        let _permit = semaphore.acquire().await;
+        match client
+            .git()
+            .get_repository_list(GetRepositoryListParams::new(project.id))
+            .await
+        {
+            Ok(repositories) => repositories,
+            Err(e) => return vec![Err(failure(SearchKind::PullRequest, Some(&key), e))],
+        }
+    };
+
+    let mut tasks = JoinSet::new();
+    for repository in repositories {
+        let (client, semaphore, key) = (client.clone(), Arc::clone(semaphore), key.clone());
+        let project_id = project.id;
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let mut params = GetPullRequestListParams::new(project_id, repository.id);
+            params.count = Some(count);
+            client
+                .git()
+                .get_pull_request_list(params)
+                .await
+                .map(|pull_requests| {
+                    pull_requests
+                        .into_iter()
+                        .map(|pr| RawHit {
+                            kind: SearchKind::PullRequest,
+                            project: key.clone(),
+                            key: format!("{}#{}", repository.name, pr.number),
+                            path: format!(
+                                "git/{key}/{}/pullRequests/{}",
+                                repository.name, pr.number
+                            ),
+                            title: pr.summary,
+                            body: pr.description.unwrap_or_default(),
+                            updated: pr.updated.or(pr.created),
+                            server_matched: false,
+                        })
+                        .collect()
+                })
+                .map_err(|e| failure(SearchKind::PullRequest, Some(&key), e))
+        });
+    }
+
+    let mut outcomes = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        outcomes.push(joined.unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic())));
+    }
+    outcomes
+}
+
+fn terms(keyword: &str) -> Vec<String> {
+    keyword.split_whitespace().map(str::to_lowercase).collect()
+}
+
+/// How well a title and body match the keyword, from 0 (no match) to 3
+///
+/// An exact title scores highest, then the whole phrase in the title, then
+/// every term in the title; otherwise the share of terms found anywhere.
+pub fn match_quality(keyword: &str, title: &str, body: &str) -> f64 {
+    let phrase = keyword.trim().to_lowercase();
+    let terms = terms(keyword);
+    if terms.is_empty() {
+        return 0.0;
+    }
+    let title = title.to_lowercase();
+    let body = body.to_lowercase();
+
+    if title.trim() == phrase {
+        3.0
+    } else if title.contains(&phrase) {
+        2.0
+    } else if terms.iter().all(|term| title.contains(term.as_str())) {
+        1.5
+    } else {
+        let found = terms
+            .iter()
+            .filter(|term| title.contains(term.as_str()) || body.contains(term.as_str()))
+            .count();
+        found as f64 / terms.len() as f64
+    }
+}
+
+/// Bonus between 0 and 1 that halves every [`RECENCY_HALF_LIFE_DAYS`]
+pub fn recency(updated: Option<DateTime<Utc>>, now: DateTime<Utc>) -> f64 {
+    let Some(updated) = updated else {
+        return 0.0;
+    };
+    let days = (now - updated).num_seconds().max(0) as f64 / 86_400.0;
+    0.5_f64.powf(days / RECENCY_HALF_LIFE_DAYS)
+}
+
+/// Sort hits best first, newest first among equal scores
+pub fn rank(hits: &mut [SearchHit]) {
+    hits.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| b.updated.cmp(&a.updated))
+    });
+}
+
+/// A single-line excerpt of `text` around the first matching term
+pub fn snippet(text: &str, keyword: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let chars: Vec<char> = text.chars().collect();
+    if chars.len() <= SNIPPET_LENGTH {
+        return text;
+    }
+
+    let lower: Vec<char> = chars.iter().flat_map(|c| c.to_lowercase()).collect();
+    // Lowercasing may change the length; only search when positions line up
+    let position = (lower.len() == chars.len())
+        .then(|| {
+            terms(keyword)
+                .iter()
+                .filter_map(|term| {
+                    let term: Vec<char> = term.chars().collect();
+                    lower.windows(term.len()).position(|window| window == term)
+                })
+                .min()
+        })
+        .flatten()
+        .unwrap_or(0);
+
+    let start = position.saturating_sub(SNIPPET_LENGTH / 4);
+    let end = (start + SNIPPET_LENGTH).min(chars.len());
+    let start = end.saturating_sub(SNIPPET_LENGTH);
+    let mut excerpt: String = chars[start..end].iter().collect();
+    if start > 0 {
+        excerpt.insert(0, '…');
+    }
+    if end < chars.len() {
+        excerpt.push('…');
+    }
+    excerpt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_quality_prefers_title_matches() {
+        assert_eq!(match_quality("login bug", "Login bug", ""), 3.0);
+        assert_eq!(match_quality("login bug", "Fix login bug on iOS", ""), 2.0);
+        assert_eq!(match_quality("login bug", "Bug in the login form", ""), 1.5);
+        assert_eq!(match_quality("login bug", "Form", "login fails"), 0.5);
+        assert_eq!(match_quality("login bug", "Form", "nothing"), 0.0);
+    }
+
+    #[test]
+    fn test_recency_halves_per_half_life() {
+        let now: DateTime<Utc> = "2024-03-01T00:00:00Z".parse().unwrap();
+        assert_eq!(recency(Some(now), now), 1.0);
+        let month_ago = now - chrono::Duration::days(30);
+        assert!((recency(Some(month_ago), now) - 0.5).abs() < 1e-9);
+        assert_eq!(recency(None, now), 0.0);
+    }
+
+    #[test]
+    fn test_snippet_centers_on_first_match() {
+        let text = format!("{} needle {}", "a ".repeat(200), "b ".repeat(200));
+        let excerpt = snippet(&text, "Needle");
+        assert!(excerpt.starts_with('…') && excerpt.ends_with('…'));
+        assert!(excerpt.contains("needle"));
+        assert_eq!(excerpt.chars().count(), SNIPPET_LENGTH + 2);
+
+        assert_eq!(snippet("short\n text", "x"), "short text");
+    }
+
+    #[test]
+    fn test_search_kind_from_str() {
+        assert_eq!("pr".parse::<SearchKind>(), Ok(SearchKind::PullRequest));
+        assert_eq!(
+            "pull-request".parse::<SearchKind>(),
+            Ok(SearchKind::PullRequest)
+        );
+        assert_eq!("Wiki".parse::<SearchKind>(), Ok(SearchKind::Wiki));
+        assert!("file".parse::<SearchKind>().is_err());
+    }
+}
@@ -0,0 +1,653 @@
+//! Stars on issues, comments, wiki pages and pull request comments
+//!
+//! [`StarredItem`] parses what can be starred from an issue key such as
+//! `BLG-12` or `BLG-12#comment-34`, or from the web URL of an issue, comment,
+//! wiki page or pull request comment, which is also what a star links to.
+//! [`BacklogApiClient::starred_entity`] loads the item with its stars.
+//!
+//! Backlog lists only the stars a user received, so
+//! [`BacklogApiClient::stars_given`] collects the stars a user gave from
+//! the received stars of every user in the space.
+
+use crate::client::BacklogApiClient;
+use backlog_api_core::{Error as ApiError, Result};
+use backlog_core::identifier::{
+    CommentId, Identifier, PullRequestCommentId, StarId, UserId, WikiId,
+};
+use backlog_core::{IssueKey, Star};
+use backlog_git::{
+    GetPullRequestCommentListParams, GetPullRequestParams, PrCommentOrder, PullRequest,
+    PullRequestComment,
+};
+use backlog_issue::{Comment, GetCommentParams, GetIssueParams, Issue};
+use backlog_user::{GetUserListParams, GetUserStarsParams, StarOrder};
+use backlog_wiki::{GetWikiDetailParams, GetWikiListParams, WikiDetail};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use url::Url;
+
+/// Maximum number of requests in flight
+const STARS_CONCURRENCY: usize = 6;
+
+/// Maximum number of stars the received stars API returns per request
+const MAX_STARS_PER_USER: u32 = 100;
+
+/// Something that can be starred, as written by a user or linked by a star
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(
+    tag = "type",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum StarredItem {
+    Issue {
+        issue_key: IssueKey,
+    },
+    IssueComment {
+        issue_key: IssueKey,
+        comment_id: CommentId,
+    },
+    Wiki {
+        wiki_id: WikiId,
+    },
+    /// A wiki page addressed by name, as in `/wiki/BLG/Release+notes`
+    WikiPage {
+        project_key: String,
+        name: String,
+    },
+    PullRequest {
+        project_key: String,
+        repository: String,
+        number: u64,
+    },
+    PullRequestComment {
+        project_key: String,
+        repository: String,
+        number: u64,
+        comment_id: PullRequestCommentId,
+    },
+}
+
+impl StarredItem {
+    /// What kind of item this is, for grouping and counting
+    pub fn kind(&self) -> &'static str {
+        match self {
+            StarredItem::Issue { .. } => "issue",
+            StarredItem::IssueComment { .. } => "comment",
+            StarredItem::Wiki { .. } | StarredItem::WikiPage { .. } => "wiki",
+            StarredItem::PullRequest { .. } => "pull request",
+            StarredItem::PullRequestComment { .. } => "pull request comment",
+        }
+    }
+}
+
+impl fmt::Display for StarredItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StarredItem::Issue { issue_key } => write!(f, "{issue_key}"),
+            StarredItem::IssueComment {
+                issue_key,
+                comment_id,
+            } => write!(f, "{issue_key}#comment-{comment_id}"),
+            StarredItem::Wiki { wiki_id } => write!(f, "wiki {wiki_id}"),
+            StarredItem::WikiPage { project_key, name } => write!(f, "{project_key} wiki {name}"),
+            StarredItem::PullRequest {
+                project_key,
+                repository,
+                number,
+            } => write!(f, "{project_key}/{repository}#{number}"),
+            StarredItem::PullRequestComment {
+                project_key,
+                repository,
+                number,
+                comment_id,
+            } => write!(
+                f,
+                "{project_key}/{repository}#{number} comment {comment_id}"
+            ),
+        }
+    }
+}
+
+impl FromStr for StarredItem {
+    type Err = String;
+
+    /// Parse `BLG-12`, `BLG-12#comment-34` or the URL of an issue, comment
+    /// (`/view/BLG-12#comment-34`), wiki page (`/alias/wiki/56` or
+    /// `/wiki/BLG/Page`), pull request (`/git/BLG/app/pullRequests/7`) or
+    /// pull request comment (`/git/BLG/app/pullRequests/7#comment-89`)
+    fn from_str(target: &str) -> std::result::Result<Self, Self::Err> {
+        let target = target.trim();
+        let invalid = || {
+            format!(
+                "Cannot star '{target}' (expected an issue key like BLG-12 or BLG-12#comment-34, or the URL of an issue, comment, wiki page, pull request or pull request comment)"
+            )
+        };
+        let (segments, fragment): (Vec<String>, Option<String>) = match Url::parse(target) {
+            Ok(url) => (
+                url.path_segments()
+                    .map(|segments| {
+                        segments
+                            .filter(|segment| !segment.is_empty())
+                            .map(str::to_string)
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                url.fragment().map(str::to_string),
+            ),
+            Err(_) => {
+                let (key, fragment) = target
+                    .split_once('#')
+                    .map_or((target, None), |(key, fragment)| (key, Some(fragment)));
+                (
+                    vec!["view".to_string(), key.to_string()],
+                    fragment.map(str::to_string),
+                )
+            }
+        };
+        let comment_id = match fragment.as_deref() {
+            None | Some("") => None,
+            Some(fragment) => Some(
+                fragment
+                    .strip_prefix("comment-")
+                    .and_then(|id| id.parse::<u32>().ok())
+                    .ok_or_else(invalid)?,
+            ),
+        };
+        let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+
+        match (segments.as_slice(), comment_id) {
+            (["view", key], comment_id) => {
+                let issue_key = IssueKey::from_str(&percent_decode(key)).map_err(|_| invalid())?;
+                Ok(match comment_id {
+                    Some(id) => StarredItem::IssueComment {
+                        issue_key,
+                        comment_id: CommentId::new(id),
+                    },
+                    None => StarredItem::Issue { issue_key },
+                })
+            }
+            (["alias", "wiki", id], None) => Ok(StarredItem::Wiki {
+                wiki_id: WikiId::new(id.parse().map_err(|_| invalid())?),
+            }),
+            (["wiki", project_key, name @ ..], None) if !name.is_empty() => {
+                Ok(StarredItem::WikiPage {
+                    project_key: percent_decode(project_key),
+                    name: name
+                        .iter()
+                        .map(|segment| decode_wiki_name(segment))
+                        .collect::<Vec<_>>()
+                        .join("/"),
+                })
+            }
+            (["git", project_key, repository, "pullRequests", number], None) => {
+                Ok(StarredItem::PullRequest {
+                    project_key: percent_decode(project_key),
+                    repository: percent_decode(repository),
+                    number: number.parse().map_err(|_| invalid())?,
+                })
+            }
+            (["git", project_key, repository, "pullRequests", number], Some(id)) => {
+                Ok(StarredItem::PullRequestComment {
+                    project_key: percent_decode(project_key),
+                    repository: percent_decode(repository),
+                    number: number.parse().map_err(|_| invalid())?,
+                    comment_id: PullRequestCommentId::new(id),
+                })
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// Decode `%XX` escapes of a URL path segment
+fn percent_decode(segment: &str) -> String {
+    percent_encoding::percent_decode_str(segment)
+        .decode_utf8_lossy()
+        .into_owned()
+}
+
+/// Decode a wiki page name segment, where spaces may also appear as `+`
+fn decode_wiki_name(segment: &str) -> String {
+    percent_decode(&segment.replace('+', " "))
+}
+
+/// A starrable item loaded with its stars
+#[derive(Debug, Clone)]
+pub enum StarredEntity {
+    Issue(Box<Issue>),
+    IssueComment {
+        issue: Box<Issue>,
+        comment: Box<Comment>,
+    },
+    Wiki(Box<WikiDetail>),
+    PullRequest {
+        project_key: String,
+        repository: String,
+        pull_request: Box<PullRequest>,
+    },
+    PullRequestComment {
+        project_key: String,
+        repository: String,
+        number: u64,
+        comment: Box<PullRequestComment>,
+    },
+}
+
+impl StarredEntity {
+    /// One-line description, e.g. `BLG-12 Checkout fails`
+    pub fn title(&self) -> String {
+        match self {
+            StarredEntity::Issue(issue) => format!("{} {}", issue.issue_key, issue.summary),
+            StarredEntity::IssueComment { issue, comment } => format!(
+                "{}#comment-{} on {}",
+                issue.issue_key, comment.id, issue.summary
+            ),
+            StarredEntity::Wiki(wiki) => format!("wiki {}", wiki.name),
+            StarredEntity::PullRequest {
+                project_key,
+                repository,
+                pull_request,
+            } => format!(
+                "{project_key}/{repository}#{} {}",
+                pull_request.number, pull_request.summary
+            ),
+            StarredEntity::PullRequestComment {
+                project_key,
+                repository,
+                number,
+                comment,
+            } => format!("{project_key}/{repository}#{number} comment {}", comment.id),
+        }
+    }
+
+    /// Presenter and ID of each star given to the item
+    fn stars(&self) -> Vec<(UserId, StarId)> {
+        let core = |stars: &[Star]| -> Vec<(UserId, StarId)> {
+            stars
+                .iter()
+                .map(|star| (star.presenter.id, star.id))
+                .collect()
+        };
+        let git = |stars: &[backlog_git::Star]| -> Vec<(UserId, StarId)> {
+            stars
+                .iter()
+                .map(|star| (star.presenter.id, star.id))
+                .collect()
+        };
+        match self {
+            StarredEntity::Issue(issue) => core(&issue.stars),
+            StarredEntity::IssueComment { comment, .. } => core(&comment.stars),
+            StarredEntity::Wiki(wiki) => core(&wiki.stars),
+            StarredEntity::PullRequest { pull_request, .. } => git(&pull_request.stars),
+            StarredEntity::PullRequestComment { comment, .. } => git(&comment.stars),
+        }
+    }
+
+    /// Number of stars given to the item
+    pub fn star_count(&self) -> usize {
+        self.stars().len()
+    }
+
+    /// ID of the star `user` gave the item, if any
+    pub fn star_by(&self, user: UserId) -> Option<StarId> {
+        self.stars()
+            .into_iter()
+            .find_map(|(presenter, id)| (presenter == user).then_some(id))
+    }
+
+    /// Parameters for starring the item
+    #[cfg(feature = "star_writable")]
+    pub fn add_star_params(&self) -> backlog_star::AddStarParams {
+        use backlog_star::AddStarParams;
+        match self {
+            StarredEntity::Issue(issue) => AddStarParams::issue(issue.id),
+            StarredEntity::IssueComment { issue, comment } => {
+                AddStarParams::comment(issue.id, comment.id)
+            }
+            StarredEntity::Wiki(wiki) => AddStarParams::wiki(wiki.id),
+            StarredEntity::PullRequest { pull_request, .. } => {
+                AddStarParams::pull_request(pull_request.id)
+            }
+            StarredEntity::PullRequestComment { comment, .. } => {
+                AddStarParams::pull_request_comment(comment.id)
+            }
+        }
+    }
+}
+
+/// A star the user gave, resolved to what it links to
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GivenStar {
+    pub id: StarId,
+    /// Title of the starred page without the trailing ` | screen - Backlog`
+    pub title: String,
+    pub url: String,
+    /// `None` when the URL is not of a starrable item this module knows
+    pub item: Option<StarredItem>,
+    pub comment: Option<String>,
+    /// Name of the user who received the star
+    pub receiver: String,
+    pub created: DateTime<Utc>,
+}
+
+/// A user whose received stars failed to load
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StarsFailure {
+    pub user: String,
+    pub message: String,
+}
+
+/// Stars a user gave, newest first
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GivenStars {
+    pub stars: Vec<GivenStar>,
+    pub failures: Vec<StarsFailure>,
+}
+
+impl GivenStars {
+    /// Number of stars per [`StarredItem::kind`], with `other` for unknown links
+    pub fn count_by_kind(&self) -> BTreeMap<&'static str, usize> {
+        let mut counts = BTreeMap::new();
+        for star in &self.stars {
+            let kind = star.item.as_ref().map_or("other", StarredItem::kind);
+            *counts.entry(kind).or_default() += 1;
+        }
+        counts
+    }
+}
+
+/// `[BLG-1] first issue | 課題の表示 - Backlog` without the screen name
+fn page_title(title: &str) -> String {
+    title
+        .rsplit_once(" | ")
+        .map_or(title, |(title, _)| title)
+        .trim()
+        .to_string()
+}
+
+fn not_found(message: String) -> ApiError {
+    ApiError::HttpStatus {
+        status: 404,
+        errors: Vec::new(),
+        errors_summary: message,
+        rate_limit_reset: None,
+    }
+}
+
+impl BacklogApiClient {
+    /// Load a starrable item with its stars
+    pub async fn starred_entity(&self, item: &StarredItem) -> Result<StarredEntity> {
+        match item {
+            StarredItem::Issue { issue_key } => {
+                let issue = self
+                    .issue()
+                    .get_issue(GetIssueParams::new(issue_key.clone()))
+                    .await?;
+                Ok(StarredEntity::Issue(Box::new(issue)))
+            }
+            StarredItem::IssueComment {
+                issue_key,
+                comment_id,
+            } => {
+                let issue = self
+                    .issue()
+                    .get_issue(GetIssueParams::new(issue_key.clone()))
+                    .await?;
+                let comment = self
+                    .issue()
+                    .get_comment(GetCommentParams::new(issue.id, *comment_id))
+                    .await?;
+                Ok(StarredEntity::IssueComment {
+                    issue: Box::new(issue),
+                    comment: Box::new(comment),
+                })
+            }
+            StarredItem::Wiki { wiki_id } => {
+                let wiki = self
+                    .wiki()
+                    .get_wiki_detail(GetWikiDetailParams::new(*wiki_id))
+                    .await?;
+                Ok(StarredEntity::Wiki(Box::new(wiki)))
+            }
+            StarredItem::WikiPage { project_key, name } => {
+                let mut params = GetWikiListParams::new();
+                params.project_id_or_key = Some(project_key.parse()?);
+                params.keyword = Some(name.clone());
+                let page = self
+                    .wiki()
+                    .get_wiki_list(params)
+                    .await?
+                    .into_iter()
+                    .find(|page| page.name == *name)
+                    .ok_or_else(|| not_found(format!("No wiki page '{name}' in {project_key}")))?;
+                let wiki = self
+                    .wiki()
+                    .get_wiki_detail(GetWikiDetailParams::new(page.id))
+                    .await?;
+                Ok(StarredEntity::Wiki(Box::new(wiki)))
+            }
+            StarredItem::PullRequest {
+                project_key,
+                repository,
+                number,
+            } => {
+                let params = GetPullRequestParams::new(
+                    project_key.parse::<backlog_core::ProjectIdOrKey>()?,
+                    repository.parse::<backlog_core::RepositoryIdOrName>()?,
+                    *number,
+                );
+                let pull_request = self.git().get_pull_request(params).await?;
+                Ok(StarredEntity::PullRequest {
+                    project_key: project_key.clone(),
+                    repository: repository.clone(),
+                    pull_request: Box::new(pull_request),
+                })
+            }
+            StarredItem::PullRequestComment {
+                project_key,
+                repository,
+                number,
+                comment_id,
+            } => {
+                let params = GetPullRequestCommentListParams::new(
+                    project_key.parse::<backlog_core::ProjectIdOrKey>()?,
+                    repository.parse::<backlog_core::RepositoryIdOrName>()?,
+                    *number,
+                )
+                .min_id(comment_id.value().saturating_sub(1))
+                .count(1)
+                .order(PrCommentOrder::Asc);
+                let comment = self
+                    .git()
+                    .get_pull_request_comment_list(params)
+                    .await?
+                    .into_iter()
+                    .find(|comment| comment.id == *comment_id)
+                    .ok_or_else(|| not_found(format!("No comment {comment_id} on {item}")))?;
+                Ok(StarredEntity::PullRequestComment {
+                    project_key: project_key.clone(),
+                    repository: repository.clone(),
+                    number: *number,
+                    comment: Box::new(comment),
+                })
+            }
+        }
+    }
+
+    /// Stars `presenter` gave, newest first
+    ///
+    /// Reads the latest `per_user` (at most 100) stars each user of the
+    /// space received; older stars are not found. Users whose stars fail to
+    /// load are reported in [`GivenStars::failures`].
+    pub async fn stars_given(&self, presenter: UserId, per_user: u32) -> Result<GivenStars> {
+        let users = self.user().get_user_list(GetUserListParams::new()).await?;
+        let per_user = per_user.clamp(1, MAX_STARS_PER_USER);
+
+        let semaphore = Arc::new(Semaphore::new(STARS_CONCURRENCY));
+        let mut tasks = JoinSet::new();
+        for user in users {
+            let client = self.clone();
+            let semaphore = Arc::clone(&semaphore);
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                let params = GetUserStarsParams::new(user.id)
+                    .with_count(per_user)
+                    .with_order(StarOrder::Desc);
+                let listed = client.user().get_user_stars(params).await;
+                (user.name, listed)
+            });
+        }
+
+        let mut stars = Vec::new();
+        let mut failures = Vec::new();
+        while let Some(joined) = tasks.join_next().await {
+            let (receiver, listed) =
+                joined.unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic()));
+            match listed {
+                Ok(listed) => stars.extend(
+                    listed
+                        .into_iter()
+                        .filter(|star| star.presenter.id == presenter)
+                        .map(|star| GivenStar {
+                            id: star.id,
+                            title: page_title(&star.title),
+                            item: star.url.parse().ok(),
+                            url: star.url,
+                            comment: star.comment,
+                            receiver: receiver.clone(),
+                            created: star.created,
+                        }),
+                ),
+                Err(e) => failures.push(StarsFailure {
+                    user: receiver,
+                    message: e.to_string(),
+                }),
+            }
+        }
+        stars.sort_by(|a, b| {
+            b.created
+                .cmp(&a.created)
+                .then(b.id.value().cmp(&a.id.value()))
+        });
+        failures.sort_by(|a, b| a.user.cmp(&b.user));
+        Ok(GivenStars { stars, failures })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue_key(key: &str) -> IssueKey {
+        key.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_issue_and_comment_keys() {
+        assert_eq!(
+            "BLG-12".parse::<StarredItem>().unwrap(),
+            StarredItem::Issue {
+                issue_key: issue_key("BLG-12")
+            }
+        );
+        assert_eq!(
+            "BLG-12#comment-34".parse::<StarredItem>().unwrap(),
+            StarredItem::IssueComment {
+                issue_key: issue_key("BLG-12"),
+                comment_id: CommentId::new(34)
+            }
+        );
+        assert!("BLG-12#note".parse::<StarredItem>().is_err());
+        assert!("not a key".parse::<StarredItem>().is_err());
+    }
+
+    #[test]
+    fn test_parse_urls() {
+        assert_eq!(
+            "https://example.backlog.com/view/BLG-12#comment-34"
+                .parse::<StarredItem>()
+                .unwrap(),
+            StarredItem::IssueComment {
+                issue_key: issue_key("BLG-12"),
+                comment_id: CommentId::new(34)
+            }
+        );
+        assert_eq!(
+            "https://example.backlog.com/alias/wiki/56"
+                .parse::<StarredItem>()
+                .unwrap(),
+            StarredItem::Wiki {
+                wiki_id: WikiId::new(56)
+            }
+        );
+        assert_eq!(
+            "https://example.backlog.com/wiki/BLG/Guides/Release%20notes"
+                .parse::<StarredItem>()
+                .unwrap(),
+            StarredItem::WikiPage {
+                project_key: "BLG".to_string(),
+                name: "Guides/Release notes".to_string()
+            }
+        );
+        // `+` is a space in wiki page names, `%2B` a plus sign
+        assert_eq!(
+            "https://example.backlog.com/wiki/BLG/C%2B%2B+tips/%E8%A8%AD%E8%A8%88"
+                .parse::<StarredItem>()
+                .unwrap(),
+            StarredItem::WikiPage {
+                project_key: "BLG".to_string(),
+                name: "C++ tips/設計".to_string()
+            }
+        );
+        assert_eq!(
+            "https://example.backlog.com/git/BLG/app/pullRequests/7#comment-89"
+                .parse::<StarredItem>()
+                .unwrap(),
+            StarredItem::PullRequestComment {
+                project_key: "BLG".to_string(),
+                repository: "app".to_string(),
+                number: 7,
+                comment_id: PullRequestCommentId::new(89)
+            }
+        );
+        assert_eq!(
+            "https://example.backlog.com/git/BLG/app/pullRequests/7"
+                .parse::<StarredItem>()
+                .unwrap(),
+            StarredItem::PullRequest {
+                project_key: "BLG".to_string(),
+                repository: "app".to_string(),
+                number: 7
+            }
+        );
+        assert!(
+            "https://example.backlog.com/git/BLG/app/pullRequests/seven"
+                .parse::<StarredItem>()
+                .is_err()
+        );
+        assert!(
+            "https://example.backlog.com/projects/BLG"
+                .parse::<StarredItem>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_page_title() {
+        assert_eq!(
+            page_title("[BLG-1] first issue | 課題の表示 - Backlog"),
+            "[BLG-1] first issue"
+        );
+        assert_eq!(page_title("Home"), "Home");
+    }
+}
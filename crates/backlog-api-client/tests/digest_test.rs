@@ -0,0 +1,211 @@
+//! Tests for the activity digest.
+
+mod common;
+
+use backlog_api_client::backlog_project::Project;
+use backlog_api_client::digest::DigestParams;
+use chrono::{DateTime, Utc};
+use common::*;
+use wiremock::MockServer;
+use wiremock::matchers::query_param;
+
+fn project(id: u32, key: &str) -> Project {
+    serde_json::from_value(json!({
+        "id": id, "projectKey": key, "name": key, "chartEnabled": true,
+        "subtaskingEnabled": true, "projectLeaderCanEditProjectLeader": false,
+        "useWiki": true, "useFileSharing": true, "useWikiTreeView": false,
+        "useOriginalImageSizeAtWiki": false, "textFormattingRule": "markdown",
+        "archived": false, "displayOrder": 0, "useDevAttributes": true
+    }))
+    .unwrap()
+}
+
+fn activity(
+    id: u32,
+    type_id: u32,
+    user: &str,
+    created: &str,
+    content: serde_json::Value,
+) -> serde_json::Value {
+    json!({
+        "id": id,
+        "project": {"id": 1, "projectKey": "SHOP", "name": "Shop"},
+        "type": type_id,
+        "content": content,
+        "notifications": [],
+        "createdUser": {
+            "id": 1, "userId": user.to_lowercase(), "name": user,
+            "roleType": 1, "mailAddress": "user@example.com"
+        },
+        "created": created
+    })
+}
+
+fn at(time: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(time)
+        .unwrap()
+        .with_timezone(&Utc)
+}
+
+#[tokio::test]
+async fn test_digest_aggregates_project_activities() {
+    let server = MockServer::start().await;
+    let client = setup_api_client(&server).await;
+
+    let activities = json!([
+        activity(
+            8,
+            19,
+            "Suzuki",
+            "2024-05-14T09:00:00Z",
+            json!({
+                "id": 311, "number": 12, "summary": "Validate coupon expiry",
+                "changes": [{"field": "status", "new_value": "3", "old_value": "1", "type": "standard"}]
+            })
+        ),
+        activity(
+            7,
+            18,
+            "Takada",
+            "2024-05-14T08:00:00Z",
+            json!({
+                "id": 311, "number": 12, "summary": "Validate coupon expiry"
+            })
+        ),
+        activity(
+            6,
+            6,
+            "Takada",
+            "2024-05-14T07:00:00Z",
+            json!({
+                "id": 55, "name": "Release notes", "content": "...", "diff": "+ fix"
+            })
+        ),
+        activity(
+            5,
+            3,
+            "Suzuki",
+            "2024-05-14T06:00:00Z",
+            json!({
+                "id": 4729, "key_id": 123, "summary": "Checkout fails",
+                "comment": {"id": 2, "content": "Verified"}
+            })
+        ),
+        activity(
+            4,
+            2,
+            "Takada",
+            "2024-05-14T05:00:00Z",
+            json!({
+                "id": 4729, "key_id": 123, "summary": "Checkout fails",
+                "comment": {"id": 1, "content": "Fixed"},
+                "changes": [
+                    {"field": "status", "new_value": "4", "old_value": "2", "type": "standard"},
+                    {"field": "assigner", "new_value": "Suzuki", "old_value": "Takada", "type": "standard"}
+                ]
+            })
+        ),
+        activity(
+            3,
+            1,
+            "Takada",
+            "2024-05-14T04:00:00Z",
+            json!({
+                "id": 4729, "key_id": 123, "summary": "Checkout fails", "description": ""
+            })
+        ),
+        activity(
+            2,
+            1,
+            "Takada",
+            "2024-05-12T04:00:00Z",
+            json!({
+                "id": 4700, "key_id": 100, "summary": "Too old", "description": ""
+            })
+        ),
+    ]);
+    Mock::given(method("GET"))
+        .and(path("/api/v2/projects/1/activities"))
+        .and(query_param("order", "desc"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&activities))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/projects/1/statuses"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"id": 2, "projectId": 1, "name": "In Progress", "color": "#4488c5", "displayOrder": 2000},
+            {"id": 4, "projectId": 1, "name": "Closed", "color": "#b0be3c", "displayOrder": 4000}
+        ])))
+        .mount(&server)
+        .await;
+
+    let params = DigestParams::new(at("2024-05-13T12:00:00Z"))
+        .until(at("2024-05-14T12:00:00Z"))
+        .projects(vec![project(1, "SHOP")]);
+    let digest = client.digest(params).await.unwrap();
+
+    assert_eq!(digest.projects, vec!["SHOP"]);
+    assert_eq!(digest.activity_count, 6);
+    assert!(!digest.truncated);
+    assert!(digest.failures.is_empty());
+
+    assert_eq!(digest.issues_created.len(), 1);
+    assert_eq!(digest.issues_created[0].key, "SHOP-123");
+    assert!(digest.issues_created[0].url.ends_with("/view/SHOP-123"));
+    assert_eq!(digest.issues_closed.len(), 1);
+    assert_eq!(digest.issues_closed[0].user, "Takada");
+    assert_eq!(digest.issues_reassigned[0].to, "Suzuki");
+    assert_eq!(digest.status_transitions[0].from, "In Progress");
+    assert_eq!(digest.status_transitions[0].to, "Closed");
+    assert_eq!(digest.comments.len(), 1);
+    assert_eq!(digest.comments[0].count, 2);
+    assert_eq!(digest.comments[0].users, vec!["Takada", "Suzuki"]);
+    assert_eq!(digest.wiki_pages[0].name, "Release notes");
+    assert_eq!(digest.wiki_pages[0].edits, 1);
+    assert_eq!(digest.pull_requests_opened[0].number, 12);
+    assert_eq!(digest.pull_requests_merged[0].user, "Suzuki");
+
+    assert_eq!(digest.users[0].name, "Takada");
+    assert_eq!(digest.users[0].activities, 4);
+    assert_eq!(digest.users[1].comments, 1);
+
+    let markdown = digest.to_markdown();
+    assert!(
+        markdown.contains("## Backlog digest for SHOP"),
+        "{markdown}"
+    );
+    assert!(markdown.contains("### Issues closed (1)"), "{markdown}");
+    assert!(
+        markdown.contains("In Progress → Closed — Takada"),
+        "{markdown}"
+    );
+    assert!(
+        markdown.contains("2 comments by Takada, Suzuki"),
+        "{markdown}"
+    );
+    assert!(!markdown.contains("Too old"), "{markdown}");
+}
+
+#[tokio::test]
+async fn test_digest_reports_failed_sources() {
+    let server = MockServer::start().await;
+    let client = setup_api_client(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v2/projects/2/activities"))
+        .respond_with(ResponseTemplate::new(403).set_body_json(json!({
+            "errors": [{"message": "No permission", "code": 11, "moreInfo": ""}]
+        })))
+        .mount(&server)
+        .await;
+
+    let params = DigestParams::new(at("2024-05-13T12:00:00Z"))
+        .until(at("2024-05-14T12:00:00Z"))
+        .projects(vec![project(2, "OPS")]);
+    let digest = client.digest(params).await.unwrap();
+
+    assert_eq!(digest.activity_count, 0);
+    assert_eq!(digest.failures.len(), 1);
+    assert_eq!(digest.failures[0].source, "OPS");
+    assert!(digest.to_markdown().contains("Nothing happened."));
+}
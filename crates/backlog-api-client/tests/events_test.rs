@@ -0,0 +1,140 @@
+//! Tests for the polling activity stream.
+
+mod common;
+
+use backlog_api_client::events::{EventParams, read_cursor};
+use backlog_core::ProjectIdOrKey;
+use backlog_core::identifier::{ActivityId, Identifier};
+use common::*;
+use futures_util::StreamExt;
+use std::time::Duration;
+use wiremock::MockServer;
+use wiremock::matchers::query_param;
+
+fn activity(id: u32, project_key: &str, user_id: u32) -> serde_json::Value {
+    json!({
+        "id": id,
+        "project": {"id": if project_key == "BLG" { 1 } else { 2 }, "projectKey": project_key, "name": project_key},
+        "type": 2,
+        "content": {"id": 100 + id, "key_id": id, "summary": format!("Issue {id}")},
+        "notifications": [],
+        "createdUser": {
+            "id": user_id, "userId": format!("user{user_id}"), "name": format!("User {user_id}"),
+            "roleType": 1, "mailAddress": format!("user{user_id}@example.com")
+        },
+        "created": "2024-01-01T00:00:00Z"
+    })
+}
+
+async fn mount_page(server: &MockServer, min_id: u32, page: serde_json::Value) {
+    Mock::given(method("GET"))
+        .and(path("/api/v2/space/activities"))
+        .and(query_param("minId", min_id.to_string()))
+        .and(query_param("order", "asc"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(page))
+        .mount(server)
+        .await;
+}
+
+fn fast(params: EventParams) -> EventParams {
+    params.interval(Duration::from_millis(10), Duration::from_millis(20))
+}
+
+#[tokio::test]
+async fn test_events_filter_and_persist_cursor() {
+    let server = MockServer::start().await;
+    mount_page(
+        &server,
+        10,
+        json!([
+            activity(13, "BLG", 2),
+            activity(11, "BLG", 1),
+            activity(12, "WEB", 1)
+        ]),
+    )
+    .await;
+    mount_page(&server, 13, json!([])).await;
+    let client = setup_api_client(&server).await;
+    let directory = tempfile::tempdir().unwrap();
+    let cursor_file = directory.path().join("state").join("cursor");
+
+    let params = fast(EventParams::default())
+        .projects(vec!["BLG".parse::<ProjectIdOrKey>().unwrap()])
+        .cursor_file(&cursor_file)
+        .start_after(ActivityId::new(10));
+    let ids: Vec<u32> = client
+        .events(params)
+        .take(2)
+        .map(|event| event.unwrap().id.value())
+        .collect()
+        .await;
+
+    // Oldest first, and the other project's activity is skipped
+    assert_eq!(ids, vec![11, 13]);
+    assert_eq!(
+        read_cursor(&cursor_file).unwrap(),
+        Some(ActivityId::new(13))
+    );
+}
+
+#[tokio::test]
+async fn test_events_resume_from_cursor_without_duplicates() {
+    let server = MockServer::start().await;
+    // The activity at the cursor comes back and is not handed out again
+    mount_page(
+        &server,
+        13,
+        json!([
+            activity(13, "BLG", 2),
+            activity(14, "BLG", 2),
+            activity(15, "BLG", 3)
+        ]),
+    )
+    .await;
+    mount_page(
+        &server,
+        15,
+        json!([activity(15, "BLG", 3), activity(16, "BLG", 2)]),
+    )
+    .await;
+    let client = setup_api_client(&server).await;
+    let directory = tempfile::tempdir().unwrap();
+    let cursor_file = directory.path().join("cursor");
+    std::fs::write(&cursor_file, "13\n").unwrap();
+
+    let params = fast(EventParams::default())
+        .user_ids(vec![backlog_core::identifier::UserId::new(2)])
+        .cursor_file(&cursor_file)
+        .start_after(ActivityId::new(1));
+    let ids: Vec<u32> = client
+        .events(params)
+        .take(2)
+        .map(|event| event.unwrap().id.value())
+        .collect()
+        .await;
+
+    assert_eq!(ids, vec![14, 16]);
+    assert_eq!(
+        read_cursor(&cursor_file).unwrap(),
+        Some(ActivityId::new(16))
+    );
+}
+
+#[tokio::test]
+async fn test_events_start_at_latest_activity() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/space/activities"))
+        .and(query_param("order", "desc"))
+        .and(query_param("count", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([activity(20, "BLG", 1)])))
+        .mount(&server)
+        .await;
+    mount_page(&server, 20, json!([activity(21, "WEB", 1)])).await;
+    let client = setup_api_client(&server).await;
+
+    let mut events = Box::pin(client.events(fast(EventParams::default())));
+    let event = events.next().await.unwrap().unwrap();
+
+    assert_eq!(event.id.value(), 21);
+}
@@ -0,0 +1,184 @@
+//! Tests for the pull request inbox across repositories.
+
+mod common;
+
+use backlog_api_client::backlog_project::Project;
+use backlog_api_client::pr_inbox::PrInboxParams;
+use chrono::{Duration, Utc};
+use common::*;
+use wiremock::MockServer;
+use wiremock::matchers::query_param;
+
+fn user(id: u32) -> serde_json::Value {
+    json!({
+        "id": id, "userId": format!("user{id}"), "name": format!("User {id}"),
+        "roleType": 1, "mailAddress": format!("user{id}@example.com")
+    })
+}
+
+fn project() -> Project {
+    serde_json::from_value(json!({
+        "id": 1, "projectKey": "BLG", "name": "Backlog", "chartEnabled": true,
+        "subtaskingEnabled": true, "projectLeaderCanEditProjectLeader": false,
+        "useWiki": true, "useFileSharing": true, "useWikiTreeView": false,
+        "useOriginalImageSizeAtWiki": false, "textFormattingRule": "markdown",
+        "archived": false, "displayOrder": 0, "useDevAttributes": true
+    }))
+    .unwrap()
+}
+
+fn pull_request(
+    id: u32,
+    number: u32,
+    assignee: Option<u32>,
+    author: u32,
+    updated: chrono::DateTime<Utc>,
+) -> serde_json::Value {
+    json!({
+        "id": id, "projectId": 1, "repositoryId": 5, "number": number,
+        "summary": format!("PR {number}"), "description": null,
+        "base": "main", "branch": format!("topic-{number}"),
+        "status": {"id": 1, "name": "Open"},
+        "assignee": assignee.map(user),
+        "issue": if number == 1 { json!({"id": 10}) } else { json!(null) },
+        "createdUser": user(author),
+        "created": (updated - Duration::days(3)).to_rfc3339(),
+        "updated": updated.to_rfc3339()
+    })
+}
+
+async fn mount_all(server: &MockServer) {
+    let now = Utc::now();
+    Mock::given(method("GET"))
+        .and(path("/api/v2/users/myself"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(user(1)))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/projects/1/git/repositories"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"id": 5, "projectId": 1, "name": "app"},
+            {"id": 6, "projectId": 1, "name": "web"}
+        ])))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/projects/1/git/repositories/5/pullRequests"))
+        .and(query_param("statusId[]", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            pull_request(100, 1, Some(1), 2, now - Duration::days(1)),
+            pull_request(101, 2, None, 1, now - Duration::days(2)),
+            pull_request(102, 3, Some(2), 2, now - Duration::hours(5)),
+            pull_request(103, 4, Some(2), 2, now - Duration::days(30))
+        ])))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/projects/1/git/repositories/6/pullRequests"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(server)
+        .await;
+    for number in 1..=3 {
+        Mock::given(method("GET"))
+            .and(path(format!(
+                "/api/v2/projects/1/git/repositories/5/pullRequests/{number}/comments/count"
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"count": number * 2})))
+            .mount(server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path("/api/v2/issues"))
+        .and(query_param("id[]", "10"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+            "id": 10, "projectId": 1, "issueKey": "BLG-10", "keyId": 10,
+            "summary": "Login fails", "description": "",
+            "issueType": {"id": 1, "projectId": 1, "name": "Bug", "color": "#990000", "displayOrder": 0},
+            "status": {"id": 2, "projectId": 1, "name": "In Progress", "color": "#4488c5", "displayOrder": 1},
+            "category": [], "versions": [], "milestone": [],
+            "createdUser": user(2),
+            "created": "2024-01-01T00:00:00Z",
+            "updated": "2024-01-02T00:00:00Z"
+        }])))
+        .mount(server)
+        .await;
+    let notification = |id: u32, already_read: bool| {
+        json!({
+            "id": id, "alreadyRead": already_read, "reason": 10, "resourceAlreadyRead": already_read,
+            "project": {
+                "id": 1, "projectKey": "BLG", "name": "Backlog", "chartEnabled": true,
+                "subtaskingEnabled": true, "projectLeaderCanEditProjectLeader": false,
+                "useWiki": true, "useFileSharing": true, "useWikiTreeView": false,
+                "useOriginalImageSizeAtWiki": false, "textFormattingRule": "markdown",
+                "archived": false, "displayOrder": 0, "useDevAttributes": true
+            },
+            "pullRequest": {"id": 100, "number": 1},
+            "sender": user(2),
+            "created": "2024-01-02T00:00:00Z"
+        })
+    };
+    Mock::given(method("GET"))
+        .and(path("/api/v2/notifications"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            notification(1, false),
+            notification(2, false),
+            notification(3, true)
+        ])))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_pr_inbox_groups_and_enriches_pull_requests() {
+    let server = MockServer::start().await;
+    mount_all(&server).await;
+    let client = setup_api_client(&server).await;
+
+    let inbox = client
+        .pr_inbox(PrInboxParams::new(vec![project()]))
+        .await
+        .unwrap();
+
+    assert_eq!(inbox.assigned_to_me.len(), 1);
+    let assigned = &inbox.assigned_to_me[0];
+    assert_eq!(assigned.number, 1);
+    assert_eq!(assigned.comment_count, Some(2));
+    assert_eq!(assigned.unread_notifications, 2);
+    assert_eq!(assigned.age_days, Some(4));
+    let issue = assigned.issue.as_ref().unwrap();
+    assert_eq!(
+        (issue.key.as_str(), issue.status.as_str()),
+        ("BLG-10", "In Progress")
+    );
+    assert_eq!(
+        assigned.url,
+        format!("{}/git/BLG/app/pullRequests/1", server.uri())
+    );
+
+    let numbers = |group: &[backlog_api_client::pr_inbox::InboxPullRequest]| {
+        group.iter().map(|pr| pr.number).collect::<Vec<_>>()
+    };
+    assert_eq!(numbers(&inbox.created_by_me), vec![2]);
+    // The stale pull request nobody asked the user about is left out
+    assert_eq!(numbers(&inbox.recently_updated), vec![3]);
+    assert_eq!(inbox.recently_updated[0].unread_notifications, 0);
+
+    assert_eq!(inbox.failures.len(), 1);
+    assert_eq!(inbox.failures[0].source, "BLG/web");
+}
+
+#[tokio::test]
+async fn test_pr_inbox_filters_repositories() {
+    let server = MockServer::start().await;
+    mount_all(&server).await;
+    let client = setup_api_client(&server).await;
+
+    let inbox = client
+        .pr_inbox(PrInboxParams::new(vec![project()]).repositories(vec!["WEB".to_string()]))
+        .await
+        .unwrap();
+
+    assert!(inbox.assigned_to_me.is_empty());
+    assert_eq!(inbox.failures.len(), 1);
+    assert_eq!(inbox.failures[0].source, "BLG/web");
+}
@@ -0,0 +1,214 @@
+//! Tests for the federated search across issues, wikis, documents and pull requests.
+
+mod common;
+
+use backlog_api_client::backlog_project::Project;
+use backlog_api_client::search::{SearchKind, SearchParams};
+use common::*;
+use wiremock::MockServer;
+use wiremock::matchers::query_param;
+
+fn user() -> serde_json::Value {
+    json!({"id": 1, "userId": "john", "name": "John", "roleType": 1, "mailAddress": "john@example.com"})
+}
+
+fn project() -> Project {
+    serde_json::from_value(json!({
+        "id": 1, "projectKey": "BLG", "name": "Backlog", "chartEnabled": true,
+        "subtaskingEnabled": true, "projectLeaderCanEditProjectLeader": false,
+        "useWiki": true, "useFileSharing": true, "useWikiTreeView": false,
+        "useOriginalImageSizeAtWiki": false, "textFormattingRule": "markdown",
+        "archived": false, "displayOrder": 0, "useDevAttributes": true
+    }))
+    .unwrap()
+}
+
+async fn mount_all(server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/api/v2/issues"))
+        .and(query_param("keyword", "login"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+            "id": 10, "projectId": 1, "issueKey": "BLG-10", "keyId": 10,
+            "summary": "Login fails", "description": "Users cannot log in",
+            "issueType": {"id": 1, "projectId": 1, "name": "Bug", "color": "#990000", "displayOrder": 0},
+            "status": {"id": 1, "projectId": 1, "name": "Open", "color": "#ed8077", "displayOrder": 0},
+            "category": [], "versions": [], "milestone": [],
+            "createdUser": user(),
+            "created": "2024-01-01T00:00:00Z",
+            "updated": "2024-01-02T00:00:00Z"
+        }])))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/documents"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+            "id": "0192f3b1a2", "projectId": 1, "title": "Runbook",
+            "plain": "How to reset a login session", "statusId": 1,
+            "createdUser": user(), "created": "2024-01-01T00:00:00Z",
+            "updatedUser": user(), "updated": "2024-01-03T00:00:00Z"
+        }])))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/wikis"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/projects/1/git/repositories"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+            "id": 5, "projectId": 1, "name": "app"
+        }])))
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/projects/1/git/repositories/5/pullRequests"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {
+                "id": 100, "projectId": 1, "repositoryId": 5, "number": 7,
+                "summary": "Fix login redirect", "description": null,
+                "base": "main", "branch": "fix/login", "status": {"id": 1, "name": "Open"},
+                "updated": "2024-01-04T00:00:00Z"
+            },
+            {
+                "id": 101, "projectId": 1, "repositoryId": 5, "number": 8,
+                "summary": "Bump dependencies", "description": "Routine update",
+                "base": "main", "branch": "deps", "status": {"id": 1, "name": "Open"},
+                "updated": "2024-01-05T00:00:00Z"
+            }
+        ])))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_search_merges_and_ranks_all_kinds() {
+    let server = MockServer::start().await;
+    mount_all(&server).await;
+    let client = setup_api_client(&server).await;
+
+    let results = client
+        .search(SearchParams::new("login", vec![project()]))
+        .await
+        .unwrap();
+
+    let keys: Vec<(SearchKind, &str)> = results
+        .hits
+        .iter()
+        .map(|hit| (hit.kind, hit.key.as_str()))
+        .collect();
+    // Title matches first; the unrelated pull request is dropped
+    assert_eq!(
+        keys,
+        vec![
+            (SearchKind::PullRequest, "app#7"),
+            (SearchKind::Issue, "BLG-10"),
+            (SearchKind::Document, "0192f3b1a2"),
+        ]
+    );
+    assert_eq!(
+        results.hits[0].url,
+        format!("{}/git/BLG/app/pullRequests/7", server.uri())
+    );
+    assert_eq!(results.hits[2].snippet, "How to reset a login session");
+
+    assert_eq!(results.failures.len(), 1);
+    assert_eq!(results.failures[0].kind, SearchKind::Wiki);
+    assert_eq!(results.failures[0].project.as_deref(), Some("BLG"));
+}
+
+#[tokio::test]
+async fn test_search_only_queries_requested_kinds() {
+    let server = MockServer::start().await;
+    mount_all(&server).await;
+    let client = setup_api_client(&server).await;
+
+    let results = client
+        .search(SearchParams::new("login", vec![project()]).kinds(vec![SearchKind::Issue]))
+        .await
+        .unwrap();
+
+    assert_eq!(results.hits.len(), 1);
+    assert_eq!(results.hits[0].url, format!("{}/view/BLG-10", server.uri()));
+    assert!(results.failures.is_empty());
+    let requests = server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 1);
+}
+
+#[tokio::test]
+async fn test_search_builds_wiki_snippets_from_page_content() {
+    let server = MockServer::start().await;
+    let client = setup_api_client(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v2/wikis"))
+        .and(query_param("keyword", "login"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+            "id": 56, "projectId": 1, "name": "Operations", "tags": [{"id": 1, "name": "ops"}],
+            "createdUser": user(), "created": "2024-01-01T00:00:00Z",
+            "updatedUser": user(), "updated": "2024-01-02T00:00:00Z"
+        }])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/wikis/56"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 56, "projectId": 1, "name": "Operations",
+            "content": "Restart the login service first",
+            "createdUser": user(), "created": "2024-01-01T00:00:00Z",
+            "updatedUser": user(), "updated": "2024-01-02T00:00:00Z"
+        })))
+        .mount(&server)
+        .await;
+
+    let results = client
+        .search(SearchParams::new("login", vec![project()]).kinds(vec![SearchKind::Wiki]))
+        .await
+        .unwrap();
+
+    assert_eq!(results.hits.len(), 1);
+    assert_eq!(results.hits[0].key, "56");
+    assert_eq!(results.hits[0].snippet, "Restart the login service first");
+    assert!(results.failures.is_empty());
+}
+
+#[tokio::test]
+async fn test_search_reports_each_failing_repository() {
+    let server = MockServer::start().await;
+    let client = setup_api_client(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v2/projects/1/git/repositories"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            {"id": 5, "projectId": 1, "name": "app"},
+            {"id": 6, "projectId": 1, "name": "api"}
+        ])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/projects/1/git/repositories/5/pullRequests"))
+        .and(query_param("count", "20"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+            "id": 100, "projectId": 1, "repositoryId": 5, "number": 7,
+            "summary": "Fix login redirect", "description": null,
+            "base": "main", "branch": "fix/login", "status": {"id": 1, "name": "Open"},
+            "updated": "2024-01-04T00:00:00Z"
+        }])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/projects/1/git/repositories/6/pullRequests"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&server)
+        .await;
+
+    let results = client
+        .search(SearchParams::new("login", vec![project()]).kinds(vec![SearchKind::PullRequest]))
+        .await
+        .unwrap();
+
+    assert_eq!(results.hits.len(), 1);
+    assert_eq!(results.hits[0].key, "app#7");
+    assert_eq!(results.failures.len(), 1);
+    assert_eq!(results.failures[0].kind, SearchKind::PullRequest);
+}
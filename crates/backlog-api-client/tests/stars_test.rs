@@ -0,0 +1,186 @@
+//! Tests for starred items and the stars a user gave.
+
+mod common;
+
+use backlog_api_client::stars::{StarredEntity, StarredItem};
+use backlog_core::identifier::{Identifier, UserId};
+use common::*;
+use wiremock::MockServer;
+use wiremock::matchers::query_param;
+
+fn user(id: u32, name: &str) -> serde_json::Value {
+    json!({
+        "id": id, "userId": name.to_lowercase(), "name": name,
+        "roleType": 1, "mailAddress": "user@example.com"
+    })
+}
+
+fn star(id: u32, presenter: u32, url: &str, title: &str, created: &str) -> serde_json::Value {
+    json!({
+        "id": id, "comment": null, "url": url, "title": title,
+        "presenter": user(presenter, "Presenter"), "created": created
+    })
+}
+
+#[tokio::test]
+async fn test_stars_given_collects_stars_of_the_presenter() {
+    let server = MockServer::start().await;
+    let client = setup_api_client(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v2/users"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            user(1, "Takada"),
+            user(2, "Suzuki"),
+            user(3, "Gone")
+        ])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/users/1/stars"))
+        .and(query_param("order", "desc"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            star(
+                11,
+                2,
+                "https://example.backlog.com/view/BLG-1#comment-5",
+                "[BLG-1] Checkout fails | 課題の表示 - Backlog",
+                "2024-05-14T09:00:00Z"
+            ),
+            star(
+                10,
+                1,
+                "https://example.backlog.com/view/BLG-2",
+                "[BLG-2] Self star | 課題の表示 - Backlog",
+                "2024-05-14T08:00:00Z"
+            ),
+        ])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/users/2/stars"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([star(
+            20,
+            2,
+            "https://example.backlog.com/alias/wiki/56",
+            "Release notes | Wiki - Backlog",
+            "2024-05-15T09:00:00Z"
+        )])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/users/3/stars"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+            "errors": [{"message": "No user.", "code": 6, "moreInfo": ""}]
+        })))
+        .mount(&server)
+        .await;
+
+    let given = client.stars_given(UserId::new(2), 20).await.unwrap();
+
+    assert_eq!(given.stars.len(), 2);
+    assert_eq!(given.stars[0].title, "Release notes");
+    assert_eq!(given.stars[0].receiver, "Suzuki");
+    assert_eq!(given.stars[1].title, "[BLG-1] Checkout fails");
+    assert_eq!(given.stars[1].receiver, "Takada");
+    assert_eq!(
+        given.stars[1].item,
+        Some("BLG-1#comment-5".parse::<StarredItem>().unwrap())
+    );
+    assert_eq!(given.count_by_kind().get("wiki"), Some(&1));
+    assert_eq!(given.count_by_kind().get("comment"), Some(&1));
+    assert_eq!(given.failures.len(), 1);
+    assert_eq!(given.failures[0].user, "Gone");
+}
+
+#[tokio::test]
+async fn test_starred_entity_loads_comment_with_stars() {
+    let server = MockServer::start().await;
+    let client = setup_api_client(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v2/issues/BLG-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 4729, "projectId": 1, "issueKey": "BLG-1", "keyId": 1,
+            "issueType": {"id": 1, "projectId": 1, "name": "Bug", "color": "#990000", "displayOrder": 0},
+            "summary": "Checkout fails", "description": "", "resolution": null,
+            "priority": {"id": 3, "name": "Normal"},
+            "status": {"id": 1, "projectId": 1, "name": "Open", "color": "#ed8077", "displayOrder": 1000},
+            "assignee": null, "category": [], "versions": [], "milestone": [],
+            "startDate": null, "dueDate": null, "estimatedHours": null, "actualHours": null,
+            "parentIssueId": null, "createdUser": user(1, "Takada"),
+            "created": "2024-05-01T00:00:00Z", "updatedUser": null, "updated": "2024-05-01T00:00:00Z",
+            "customFields": [], "attachments": [], "sharedFiles": [], "stars": []
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/issues/4729/comments/5"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 5, "content": "Verified", "changeLog": [],
+            "createdUser": user(1, "Takada"),
+            "created": "2024-05-02T00:00:00Z", "updated": "2024-05-02T00:00:00Z",
+            "stars": [
+                {"id": 11, "comment": null, "url": "https://example.backlog.com/view/BLG-1#comment-5",
+                 "presenter": user(2, "Suzuki"), "created": "2024-05-14T09:00:00Z"}
+            ],
+            "notifications": []
+        })))
+        .mount(&server)
+        .await;
+
+    let item: StarredItem = "https://example.backlog.com/view/BLG-1#comment-5"
+        .parse()
+        .unwrap();
+    let entity = client.starred_entity(&item).await.unwrap();
+
+    assert!(matches!(entity, StarredEntity::IssueComment { .. }));
+    assert_eq!(entity.title(), "BLG-1#comment-5 on Checkout fails");
+    assert_eq!(entity.star_count(), 1);
+    assert_eq!(
+        entity.star_by(UserId::new(2)).map(|id| id.value()),
+        Some(11)
+    );
+    assert_eq!(entity.star_by(UserId::new(1)), None);
+}
+
+#[tokio::test]
+async fn test_starred_entity_loads_pull_request_with_stars() {
+    let server = MockServer::start().await;
+    let client = setup_api_client(&server).await;
+
+    Mock::given(method("GET"))
+        .and(path(
+            "/api/v2/projects/BLG/git/repositories/app/pullRequests/7",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "id": 300, "projectId": 1, "repositoryId": 5, "number": 7,
+            "summary": "Fix checkout", "description": "", "base": "main", "branch": "fix",
+            "status": {"id": 1, "name": "Open"}, "assignee": null, "issue": null,
+            "baseCommit": null, "branchCommit": null, "closeAt": null, "mergeAt": null,
+            "createdUser": user(1, "Takada"), "created": "2024-05-01T00:00:00Z",
+            "updatedUser": null, "updated": "2024-05-01T00:00:00Z",
+            "attachments": [],
+            "stars": [
+                {"id": 21, "comment": null,
+                 "url": "https://example.backlog.com/git/BLG/app/pullRequests/7",
+                 "title": "Fix checkout", "presenter": user(2, "Suzuki"),
+                 "created": "2024-05-14T09:00:00Z"}
+            ]
+        })))
+        .mount(&server)
+        .await;
+
+    let item: StarredItem = "https://example.backlog.com/git/BLG/app/pullRequests/7"
+        .parse()
+        .unwrap();
+    let entity = client.starred_entity(&item).await.unwrap();
+
+    assert!(matches!(entity, StarredEntity::PullRequest { .. }));
+    assert_eq!(entity.title(), "BLG/app#7 Fix checkout");
+    assert_eq!(
+        entity.star_by(UserId::new(2)).map(|id| id.value()),
+        Some(21)
+    );
+    assert_eq!(entity.star_by(UserId::new(1)), None);
+}
@@ -32,6 +32,16 @@ pub enum Error {
     #[error("Failed to read file '{path}': {message}")]
     FileRead { path: String, message: String },
 
+    /// File write operation failed
+    #[error("Failed to write file '{path}': {message}")]
+    FileWrite { path: String, message: String },
+
+    /// File is larger than the attachment size limit
+    #[error(
+        "File '{name}' is {size} bytes, which exceeds the attachment size limit of {limit} bytes"
+    )]
+    AttachmentTooLarge { name: String, size: u64, limit: u64 },
+
     /// HTTP request building failed
     #[error("Failed to build HTTP request: {0}")]
     RequestBuild(String),
@@ -46,16 +56,27 @@ pub enum Error {
 
     /// Error response body could not be parsed
     #[error("HTTP error {status} with unparseable body: {body}")]
-    UnparseableErrorResponse { status: u16, body: String },
+    UnparseableErrorResponse {
+        status: u16,
+        body: String,
+        /// Unix time in seconds when the rate limit resets (`X-RateLimit-Reset`)
+        rate_limit_reset: Option<u64>,
+    },
 
     #[error("Serialization error: {0}")]
     SerializationError(String),
 
+    /// Responses that are valid on their own but contradict each other
+    #[error("Inconsistent API response: {0}")]
+    InconsistentResponse(String),
+
     #[error("Backlog API Error (HTTP {status}): {errors_summary}")]
     HttpStatus {
         status: u16,
         errors: Vec<BacklogApiErrorEntry>,
         errors_summary: String, // Pre-formatted summary of errors
+        /// Unix time in seconds when the rate limit resets (`X-RateLimit-Reset`)
+        rate_limit_reset: Option<u64>,
     },
 }
 
@@ -111,6 +132,7 @@ mod tests {
                 more_info: None,
             }],
             errors_summary: "Not found".to_string(),
+            rate_limit_reset: None,
         };
         let err_string = err.to_string();
         assert!(
@@ -112,6 +112,18 @@ pub trait IntoUploadRequest {
     fn additional_fields(&self) -> Vec<(String, String)> {
         Vec::new()
     }
+
+    /// Returns the file content when uploading from memory.
+    ///
+    /// When `Some`, `file_path` only supplies the file name and is not read.
+    fn file_content(&self) -> Option<&[u8]> {
+        None
+    }
+
+    /// Returns the MIME type of the file part (default: detected from the name and content).
+    fn content_type(&self) -> Option<&str> {
+        None
+    }
 }
 
 #[cfg(test)]
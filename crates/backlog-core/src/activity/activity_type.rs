@@ -53,6 +53,10 @@ impl Activity {
     pub fn project_name(&self) -> Option<&str> {
         self.project.get("name").and_then(|v| v.as_str())
     }
+
+    pub fn project_key(&self) -> Option<&str> {
+        self.project.get("projectKey").and_then(|v| v.as_str())
+    }
 }
 
 #[cfg(feature = "typed-activity")]
@@ -65,6 +69,10 @@ impl Activity {
     pub fn project_name(&self) -> Option<&str> {
         Some(&self.project.name)
     }
+
+    pub fn project_key(&self) -> Option<&str> {
+        Some(&self.project.project_key)
+    }
 }
 
 #[cfg(test)]
@@ -100,6 +108,8 @@ mod tests {
                 description: Some("Test Description".to_string()),
                 comment: None,
                 changes: None,
+                name: None,
+                number: None,
             },
             notifications: vec![],
             created_user: User {
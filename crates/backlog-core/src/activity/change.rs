@@ -4,7 +4,9 @@ use serde::{Deserialize, Serialize};
 #[serde(rename_all = "camelCase")]
 pub struct Change {
     pub field: String,
+    #[serde(alias = "new_value")]
     pub new_value: String,
+    #[serde(alias = "old_value")]
     pub old_value: String,
     #[serde(rename = "type")]
     pub change_type: String,
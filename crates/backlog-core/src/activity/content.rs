@@ -9,15 +9,21 @@ use super::{Change, Comment, GroupProjectActivity};
 #[non_exhaustive]
 pub enum Content {
     /// Standard content with comment and changes
+    ///
+    /// Covers issues, wiki pages (`name`) and pull requests (`number`).
     Standard {
         id: i64,
-        #[serde(rename = "keyId")]
+        #[serde(rename = "keyId", alias = "key_id")]
         key_id: Option<i64>,
         summary: Option<String>,
         description: Option<String>,
         #[serde(default, deserialize_with = "deserialize_comment")]
         comment: Option<Comment>,
         changes: Option<Vec<Change>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        number: Option<i64>,
     },
     /// User management content
     UserManagement {
@@ -170,6 +176,8 @@ mod tests {
                 old_value: "Open".to_string(),
                 change_type: "standard".to_string(),
             }]),
+            name: None,
+            number: None,
         };
 
         let json = serde_json::to_string(&content).unwrap();
@@ -240,6 +248,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_content_deserialization_snake_case() {
+        let json = r#"{
+            "id": 311,
+            "key_id": 123,
+            "number": 12,
+            "summary": "Validate coupon expiry",
+            "changes": [
+                {"field": "status", "new_value": "3", "old_value": "1", "type": "standard"}
+            ]
+        }"#;
+
+        let content: Content = serde_json::from_str(json).unwrap();
+        match content {
+            Content::Standard {
+                key_id,
+                number,
+                changes,
+                ..
+            } => {
+                assert_eq!(key_id, Some(123));
+                assert_eq!(number, Some(12));
+                let changes = changes.unwrap();
+                assert_eq!(changes[0].new_value, "3");
+                assert_eq!(changes[0].old_value, "1");
+            }
+            _ => panic!("Expected Standard content"),
+        }
+    }
+
     #[test]
     fn test_content_with_empty_comment() {
         let json = r#"{
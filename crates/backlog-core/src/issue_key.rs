@@ -10,6 +10,11 @@ static ISSUE_KEY_REGEXP: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new(r"^([_A-Z0-9]{1,25})-([1-9][0-9]*)$").expect("valid regex pattern")
 });
 
+static ISSUE_KEY_IN_TEXT_REGEXP: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:^|[^_A-Za-z0-9])([A-Z][_A-Z0-9]{0,24}-[1-9][0-9]*)(?:$|[^A-Za-z0-9])")
+        .expect("valid regex pattern")
+});
+
 #[derive(Clone, Debug, PartialEq, Hash, Eq)]
 pub struct IssueKey {
     project_key: ProjectKey,
@@ -30,6 +35,17 @@ impl IssueKey {
             key_id,
         }
     }
+
+    /// Finds the first issue key in free text, such as a branch name or a
+    /// commit message.
+    ///
+    /// The key must not be glued to a surrounding word: `feature/BLG-3`
+    /// contains `BLG-3`, while `fixBLG-3` and `UTF-8bit` contain no key.
+    pub fn find_in(text: &str) -> Option<Self> {
+        ISSUE_KEY_IN_TEXT_REGEXP
+            .captures_iter(text)
+            .find_map(|cap| Self::from_str(&cap[1]).ok())
+    }
 }
 
 impl From<IssueKey> for String {
@@ -178,3 +194,25 @@ fn test_issue_key_from_str_max_valid() {
     let issue_key = result.unwrap();
     assert_eq!(issue_key.key_id.get(), u32::MAX);
 }
+
+#[test]
+fn test_issue_key_find_in() {
+    let find = |text| IssueKey::find_in(text).map(|key| key.to_string());
+    assert_eq!(
+        find("feature/BLG-12-login-form"),
+        Some("BLG-12".to_string())
+    );
+    assert_eq!(find("BLG_2-7"), Some("BLG_2-7".to_string()));
+    assert_eq!(find("Fix BLG-3 and BLG-4"), Some("BLG-3".to_string()));
+    assert_eq!(find("fixBLG-3"), None);
+    assert_eq!(find("bump-2"), None);
+    assert_eq!(find("release/v1-0"), None);
+    assert_eq!(find("BLG-3_login"), Some("BLG-3".to_string()));
+    assert_eq!(find("UTF-8bit text"), None);
+    assert_eq!(
+        find("check SHA-256sums for BLG-5"),
+        Some("BLG-5".to_string())
+    );
+    assert_eq!(find("BLG-12a"), None);
+    assert_eq!(find(&format!("{}-1", "A".repeat(26))), None);
+}
@@ -8,6 +8,7 @@ pub mod identifier;
 mod issue_id_or_key;
 mod issue_key;
 mod language;
+pub mod notation;
 mod nulab_account;
 mod project_id_or_key;
 mod project_key;
@@ -38,6 +39,9 @@ pub use star::Star;
 pub use text_formatting_rule::TextFormattingRule;
 pub use user::User;
 
+/// Status ID of Backlog's built-in "Closed" status, shared by every project.
+pub const CLOSED_STATUS_ID: u32 = 4;
+
 // Re-export identifiers under `id` namespace
 pub mod id {
     pub use crate::identifier::{
@@ -0,0 +1,565 @@
+//! Conversion between Backlog wiki notation and Markdown
+//!
+//! Projects format issue descriptions, comments and wiki pages with either
+//! [`TextFormattingRule::Backlog`] or [`TextFormattingRule::Markdown`]. The
+//! converters handle headings, bullet and numbered lists, code blocks,
+//! quotes, links, images, tables, bold/italic/strikethrough and line breaks.
+//! Anything else, such as `&color(red){...}` or raw HTML, is left unchanged.
+//!
+//! ```
+//! use backlog_core::TextFormattingRule;
+//! use backlog_core::notation::convert;
+//!
+//! let backlog = convert(
+//!     "## Steps\n- open **settings**",
+//!     TextFormattingRule::Markdown,
+//!     TextFormattingRule::Backlog,
+//! );
+//! assert_eq!(backlog, "** Steps\n- open ''settings''");
+//! ```
+
+use crate::TextFormattingRule;
+use regex::{Captures, Regex};
+use std::sync::LazyLock;
+
+/// Convert `text` written with the `from` rule into the `to` rule
+pub fn convert(text: &str, from: TextFormattingRule, to: TextFormattingRule) -> String {
+    match (from, to) {
+        (TextFormattingRule::Markdown, TextFormattingRule::Backlog) => markdown_to_backlog(text),
+        (TextFormattingRule::Backlog, TextFormattingRule::Markdown) => backlog_to_markdown(text),
+        _ => text.to_string(),
+    }
+}
+
+// Links, images and URLs are converted as a whole so that emphasis markers
+// inside URLs are never touched.
+static MD_LINK: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"!\[([^\]]*)\]\(([^)\s]+)(?:\s+"[^"]*")?\)|\[([^\]]+)\]\(([^)\s]+)(?:\s+"[^"]*")?\)|<((?:https?|ftp|mailto):[^>\s]+)>|(?:https?|ftp)://[^\s<>\]]+"#,
+    )
+    .expect("valid regex")
+});
+static MD_BOLD: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\*\*(\S(?:.*?\S)?)\*\*|__(\S(?:.*?\S)?)__").expect("valid regex")
+});
+static MD_ITALIC: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\*(\S(?:[^*]*?\S)?)\*|(?:^|\b)_(\S(?:[^_]*?\S)?)_(?:\b|$)").expect("valid regex")
+});
+static MD_STRIKE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"~~(\S(?:.*?\S)?)~~").expect("valid regex"));
+static MD_BR: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)<br\s*/?>").expect("valid regex"));
+static MD_HEADING: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(#{1,6})\s+(.*?)(?:\s+#+)?\s*$").expect("valid regex"));
+static MD_LIST: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(\s*)(?:([-*+])|\d+[.)])\s+(.*)$").expect("valid regex"));
+static MD_RULE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^ {0,3}(?:(?:\*[ \t]*){3,}|(?:-[ \t]*){3,}|(?:_[ \t]*){3,})$")
+        .expect("valid regex")
+});
+static MD_TABLE_SEPARATOR: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\s*\|?\s*:?-+:?\s*(?:\|\s*:?-+:?\s*)*\|?\s*$").expect("valid regex")
+});
+
+static BACKLOG_LINK: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\[\[([^\]]+?)\]\]|#(?:image|thumbnail)\(([^)]+)\)").expect("valid regex")
+});
+static BACKLOG_LINK_TARGET: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:(.+?)[>:])?((?:https?|ftp)://\S+|mailto:\S+)$").expect("valid regex")
+});
+static BACKLOG_ITALIC: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"'''(.+?)'''").expect("valid regex"));
+static BACKLOG_BOLD: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"''(.+?)''").expect("valid regex"));
+static BACKLOG_STRIKE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"%%(.+?)%%").expect("valid regex"));
+static BACKLOG_HEADING: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(\*{1,6})\s+(.*)$").expect("valid regex"));
+static BACKLOG_LIST: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(-+|\++)\s+(.*)$").expect("valid regex"));
+
+/// Convert Markdown into Backlog notation
+pub fn markdown_to_backlog(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut out = Vec::with_capacity(lines.len());
+    let mut list_indents = Vec::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+
+        if let Some(fence) = code_fence(line) {
+            out.push("{code}".to_string());
+            i += 1;
+            while i < lines.len() && !closes_fence(lines[i], fence) {
+                out.push(lines[i].to_string());
+                i += 1;
+            }
+            out.push("{/code}".to_string());
+            i += 1;
+            list_indents.clear();
+            continue;
+        }
+
+        if is_markdown_table_row(line)
+            && lines
+                .get(i + 1)
+                .is_some_and(|next| MD_TABLE_SEPARATOR.is_match(next))
+        {
+            out.push(format!("{}h", backlog_table_row(line)));
+            i += 2;
+            while i < lines.len() && is_markdown_table_row(lines[i]) {
+                out.push(backlog_table_row(lines[i]));
+                i += 1;
+            }
+            continue;
+        }
+
+        if MD_RULE.is_match(line) {
+            out.push("----".to_string());
+            list_indents.clear();
+        } else if let Some(caps) = MD_HEADING.captures(line) {
+            out.push(format!(
+                "{} {}",
+                "*".repeat(caps[1].len()),
+                markdown_inline(&caps[2])
+            ));
+        } else if let Some(caps) = MD_LIST.captures(line) {
+            let level = list_level(&mut list_indents, indent_width(&caps[1]));
+            let marker = if caps.get(2).is_some() { "-" } else { "+" };
+            out.push(format!(
+                "{} {}",
+                marker.repeat(level),
+                markdown_inline(&caps[3])
+            ));
+        } else {
+            if line.trim().is_empty() {
+                list_indents.clear();
+            }
+            out.push(markdown_inline(line.trim_end()));
+        }
+        i += 1;
+    }
+    out.join("\n")
+}
+
+/// Convert Backlog notation into Markdown
+pub fn backlog_to_markdown(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut out = Vec::with_capacity(lines.len());
+    let mut in_quote = false;
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim();
+
+        if let Some(rest) = trimmed.strip_prefix("{code") {
+            if let Some(inline) = rest
+                .strip_prefix('}')
+                .and_then(|rest| rest.strip_suffix("{/code}"))
+            {
+                out.push(format!("`{inline}`"));
+                i += 1;
+                continue;
+            }
+            let language = rest
+                .strip_prefix(':')
+                .and_then(|rest| rest.strip_suffix('}'))
+                .unwrap_or_default();
+            out.push(format!("```{language}"));
+            i += 1;
+            while i < lines.len() && lines[i].trim() != "{/code}" {
+                out.push(lines[i].to_string());
+                i += 1;
+            }
+            out.push("```".to_string());
+            i += 1;
+            continue;
+        }
+        if trimmed == "{quote}" {
+            in_quote = true;
+            i += 1;
+            continue;
+        }
+        if trimmed == "{/quote}" {
+            in_quote = false;
+            i += 1;
+            continue;
+        }
+
+        if is_backlog_table_row(trimmed) {
+            let mut rows = Vec::new();
+            while i < lines.len() && is_backlog_table_row(lines[i].trim()) {
+                rows.push(lines[i].trim());
+                i += 1;
+            }
+            let prefix = if in_quote { "> " } else { "" };
+            for row in markdown_table(&rows) {
+                out.push(format!("{prefix}{row}"));
+            }
+            continue;
+        }
+
+        let converted = if let Some(caps) = BACKLOG_HEADING.captures(line) {
+            format!("{} {}", "#".repeat(caps[1].len()), backlog_inline(&caps[2]))
+        } else if let Some(caps) = BACKLOG_LIST.captures(line) {
+            let marker = &caps[1];
+            let indent = "    ".repeat(marker.len() - 1);
+            let bullet = if marker.starts_with('-') { "-" } else { "1." };
+            format!("{indent}{bullet} {}", backlog_inline(&caps[2]))
+        } else {
+            backlog_inline(line)
+        };
+        out.push(if in_quote {
+            format!("> {converted}")
+        } else {
+            converted
+        });
+        i += 1;
+    }
+    out.join("\n")
+}
+
+/// The fence string of a line opening a fenced code block
+fn code_fence(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    let marker = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = trimmed.chars().take_while(|c| *c == marker).count();
+    (len >= 3).then(|| &trimmed[..len])
+}
+
+fn closes_fence(line: &str, fence: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.starts_with(fence) && trimmed.chars().all(|c| fence.starts_with(c))
+}
+
+fn indent_width(whitespace: &str) -> usize {
+    whitespace
+        .chars()
+        .map(|c| if c == '\t' { 4 } else { 1 })
+        .sum()
+}
+
+/// Nesting level of a list item, tracking the indentation of enclosing items
+fn list_level(indents: &mut Vec<usize>, indent: usize) -> usize {
+    while indents.last().is_some_and(|&top| indent < top) {
+        indents.pop();
+    }
+    if indents.last() != Some(&indent) {
+        indents.push(indent);
+    }
+    indents.len()
+}
+
+fn is_markdown_table_row(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.starts_with('|') && trimmed.len() > 1
+}
+
+fn table_cells(row: &str) -> Vec<&str> {
+    let inner = row.trim().trim_start_matches('|');
+    let inner = inner.strip_suffix('|').unwrap_or(inner);
+    inner.split('|').map(str::trim).collect()
+}
+
+fn backlog_table_row(line: &str) -> String {
+    let cells: Vec<String> = table_cells(line).into_iter().map(markdown_inline).collect();
+    format!("|{}|", cells.join("|"))
+}
+
+fn is_backlog_table_row(line: &str) -> bool {
+    line.starts_with('|') && (line.ends_with('|') || line.ends_with("|h")) && line.len() > 1
+}
+
+/// Markdown tables need a header row; without a Backlog header row an empty
+/// one is emitted
+fn markdown_table(rows: &[&str]) -> Vec<String> {
+    let mut out = Vec::with_capacity(rows.len() + 2);
+    let mut rows = rows.iter().map(|row| match row.strip_suffix('h') {
+        Some(header) => (true, table_cells(header)),
+        None => (false, table_cells(row)),
+    });
+    let Some((is_header, first)) = rows.next() else {
+        return out;
+    };
+    let format_row = |cells: &[&str]| {
+        let cells: Vec<String> = cells.iter().map(|cell| backlog_inline(cell)).collect();
+        format!("| {} |", cells.join(" | "))
+    };
+    let separator = format!("|{}", " --- |".repeat(first.len()));
+
+    if is_header {
+        out.push(format_row(&first));
+        out.push(separator);
+    } else {
+        out.push(format!("|{}", "   |".repeat(first.len())));
+        out.push(separator);
+        out.push(format_row(&first));
+    }
+    out.extend(rows.map(|(_, cells)| format_row(&cells)));
+    out
+}
+
+/// Convert inline Markdown, leaving code spans as they are
+fn markdown_inline(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('`') {
+        let ticks = rest[start..].chars().take_while(|c| *c == '`').count();
+        let fence = &rest[start..start + ticks];
+        let Some(end) = rest[start + ticks..].find(fence) else {
+            break;
+        };
+        out.push_str(&markdown_spans(&rest[..start]));
+        let close = start + ticks + end + ticks;
+        out.push_str(&rest[start..close]);
+        rest = &rest[close..];
+    }
+    out.push_str(&markdown_spans(rest));
+    out
+}
+
+fn markdown_spans(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for caps in MD_LINK.captures_iter(text) {
+        let whole = caps.get(0).expect("match");
+        out.push_str(&markdown_emphasis(&text[last..whole.start()]));
+        if let Some(url) = caps.get(2) {
+            out.push_str(&format!("#image({})", url.as_str()));
+        } else if let (Some(label), Some(url)) = (caps.get(3), caps.get(4)) {
+            out.push_str(&format!(
+                "[[{}>{}]]",
+                markdown_emphasis(label.as_str()),
+                url.as_str()
+            ));
+        } else if let Some(url) = caps.get(5) {
+            out.push_str(url.as_str());
+        } else {
+            out.push_str(whole.as_str());
+        }
+        last = whole.end();
+    }
+    out.push_str(&markdown_emphasis(&text[last..]));
+    out
+}
+
+fn markdown_emphasis(text: &str) -> String {
+    let text = MD_BR.replace_all(text, "&br;");
+    let text = MD_BOLD.replace_all(&text, |caps: &Captures| {
+        format!(
+            "''{}''",
+            caps.get(1).or(caps.get(2)).expect("group").as_str()
+        )
+    });
+    let text = MD_STRIKE.replace_all(&text, "%%$1%%");
+    let text = MD_ITALIC.replace_all(&text, |caps: &Captures| {
+        format!(
+            "'''{}'''",
+            caps.get(1).or(caps.get(2)).expect("group").as_str()
+        )
+    });
+    text.into_owned()
+}
+
+fn backlog_inline(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut last = 0;
+    for caps in BACKLOG_LINK.captures_iter(text) {
+        let whole = caps.get(0).expect("match");
+        out.push_str(&backlog_emphasis(&text[last..whole.start()]));
+        if let Some(image) = caps.get(2) {
+            out.push_str(&format!("![{0}]({0})", image.as_str()));
+        } else if let Some(target) = BACKLOG_LINK_TARGET.captures(&caps[1]) {
+            match target.get(1) {
+                Some(label) => out.push_str(&format!(
+                    "[{}]({})",
+                    backlog_emphasis(label.as_str()),
+                    &target[2]
+                )),
+                None => out.push_str(&format!("<{}>", &target[2])),
+            }
+        } else {
+            // Links to wiki pages work the same way in Markdown projects
+            out.push_str(whole.as_str());
+        }
+        last = whole.end();
+    }
+    out.push_str(&backlog_emphasis(&text[last..]));
+    out
+}
+
+fn backlog_emphasis(text: &str) -> String {
+    let text = text.replace("&br;", "<br>");
+    let text = BACKLOG_ITALIC.replace_all(&text, "*$1*");
+    let text = BACKLOG_BOLD.replace_all(&text, "**$1**");
+    let text = BACKLOG_STRIKE.replace_all(&text, "~~$1~~");
+    text.into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_to_backlog_blocks() {
+        let markdown = "\
+# Title
+## Steps
+
+1. Open settings
+2. Click **Save**
+   - confirm the _dialog_
+- back
+
+```rust
+let x = **not bold**;
+```
+
+> quoted ~~text~~";
+        let expected = "\
+* Title
+** Steps
+
++ Open settings
++ Click ''Save''
+-- confirm the '''dialog'''
+- back
+
+{code}
+let x = **not bold**;
+{/code}
+
+> quoted %%text%%";
+        assert_eq!(markdown_to_backlog(markdown), expected);
+    }
+
+    #[test]
+    fn test_markdown_to_backlog_thematic_breaks_are_not_lists() {
+        for rule in ["* * *", "***", "---", "- - -", "___", "  *  *  *  "] {
+            assert_eq!(markdown_to_backlog(rule), "----", "{rule}");
+        }
+        assert_eq!(markdown_to_backlog("* item"), "- item");
+        assert_eq!(markdown_to_backlog("* * item"), "- * item");
+    }
+
+    #[test]
+    fn test_markdown_to_backlog_links_and_code_spans() {
+        assert_eq!(
+            markdown_to_backlog("See [the **spec**](https://example.com/a_b_c) and ![](shot.png)"),
+            "See [[the ''spec''>https://example.com/a_b_c]] and #image(shot.png)"
+        );
+        assert_eq!(
+            markdown_to_backlog("Run `cargo *test*` at https://ci.example.com/_build_/"),
+            "Run `cargo *test*` at https://ci.example.com/_build_/"
+        );
+        assert_eq!(
+            markdown_to_backlog("line<br>break and <https://example.com>"),
+            "line&br;break and https://example.com"
+        );
+        assert_eq!(markdown_to_backlog("snake_case_name"), "snake_case_name");
+    }
+
+    #[test]
+    fn test_markdown_to_backlog_table() {
+        let markdown = "\
+| Name | Status |
+|------|:------:|
+| API  | **done** |
+| CLI  | wip<br>2nd |";
+        let expected = "\
+|Name|Status|h
+|API|''done''|
+|CLI|wip&br;2nd|";
+        assert_eq!(markdown_to_backlog(markdown), expected);
+    }
+
+    #[test]
+    fn test_backlog_to_markdown_blocks() {
+        let backlog = "\
+* Title
+** Steps
++ Open settings
+++ nested
+- Click ''Save''
+-- confirm the '''dialog'''
+{code:java}
+String s = ''literal'';
+{/code}
+{quote}
+quoted %%text%%
+{/quote}
+----";
+        let expected = "\
+# Title
+## Steps
+1. Open settings
+    1. nested
+- Click **Save**
+    - confirm the *dialog*
+```java
+String s = ''literal'';
+```
+> quoted ~~text~~
+----";
+        assert_eq!(backlog_to_markdown(backlog), expected);
+    }
+
+    #[test]
+    fn test_backlog_to_markdown_links_and_tables() {
+        assert_eq!(
+            backlog_to_markdown(
+                "[[Spec>https://example.com/spec]] [[Docs:https://example.com]] [[https://example.com]] [[WikiPage]] #image(a.png)&br;end"
+            ),
+            "[Spec](https://example.com/spec) [Docs](https://example.com) <https://example.com> [[WikiPage]] ![a.png](a.png)<br>end"
+        );
+
+        let table = "\
+|Name|Status|h
+|API|''done''|";
+        assert_eq!(
+            backlog_to_markdown(table),
+            "| Name | Status |\n| --- | --- |\n| API | **done** |"
+        );
+        assert_eq!(
+            backlog_to_markdown("|a|b|"),
+            "|   |   |\n| --- | --- |\n| a | b |"
+        );
+        assert_eq!(backlog_to_markdown("{code}x = 1{/code}"), "`x = 1`");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let backlog = "\
+* Release
+- ''API'' [[changelog>https://example.com/changes]]
+-- %%old%% new
++ step
+|k|v|h
+|a|b|";
+        let markdown = backlog_to_markdown(backlog);
+        assert_eq!(markdown_to_backlog(&markdown), backlog);
+    }
+
+    #[test]
+    fn test_convert_same_rule_is_identity() {
+        let text = "* not a list";
+        assert_eq!(
+            convert(
+                text,
+                TextFormattingRule::Backlog,
+                TextFormattingRule::Backlog
+            ),
+            text
+        );
+        assert_eq!(
+            convert(
+                text,
+                TextFormattingRule::Markdown,
+                TextFormattingRule::Markdown
+            ),
+            text
+        );
+    }
+}
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 
 use crate::models::{attachment::DocumentAttachment, tag::DocumentTag};
+use crate::prosemirror::{self, Node};
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
@@ -29,3 +30,20 @@ pub struct DocumentDetail {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<DocumentTag>,
 }
+
+impl DocumentDetail {
+    /// Parse the ProseMirror body into a typed node tree.
+    pub fn content(&self) -> serde_json::Result<Node> {
+        Node::from_json(&self.json)
+    }
+
+    /// Render the document body as CommonMark.
+    ///
+    /// Falls back to `plain` when the ProseMirror JSON cannot be parsed.
+    pub fn to_markdown(&self) -> String {
+        match self.content() {
+            Ok(node) => prosemirror::to_markdown(&node),
+            Err(_) => self.plain.clone(),
+        }
+    }
+}
@@ -26,3 +26,97 @@ pub struct DocumentTreeRootNode {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub children: Vec<DocumentTreeNode>,
 }
+
+impl DocumentTreeRootNode {
+    /// Find the documents whose titles along the path match, e.g.
+    /// `/Guides/Onboarding`.
+    ///
+    /// Empty segments are ignored, so leading, trailing and repeated slashes
+    /// are allowed. Siblings may share a title, so every matching branch is
+    /// searched and all matches are returned in tree order; more than one
+    /// match means the path is ambiguous.
+    pub fn find_by_path(&self, path: &str) -> Vec<&DocumentTreeNode> {
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        let mut found = Vec::new();
+        if !segments.is_empty() {
+            collect_path(&self.children, &segments, &mut found);
+        }
+        found
+    }
+}
+
+fn collect_path<'a>(
+    nodes: &'a [DocumentTreeNode],
+    segments: &[&str],
+    found: &mut Vec<&'a DocumentTreeNode>,
+) {
+    let Some((first, rest)) = segments.split_first() else {
+        return;
+    };
+    for node in nodes.iter().filter(|n| n.name == *first) {
+        if rest.is_empty() {
+            found.push(node);
+        } else {
+            collect_path(&node.children, rest, found);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: &str, name: &str, children: Vec<DocumentTreeNode>) -> DocumentTreeNode {
+        DocumentTreeNode {
+            id: DocumentId::unsafe_new(id.to_string()),
+            name: name.to_string(),
+            emoji: None,
+            emoji_type: None,
+            children,
+        }
+    }
+
+    fn sample_tree() -> DocumentTreeRootNode {
+        DocumentTreeRootNode {
+            id: "Active".to_string(),
+            children: vec![
+                node(
+                    "guides",
+                    "Guides",
+                    vec![
+                        node("onboarding", "Onboarding", vec![]),
+                        node("release", "Release", vec![]),
+                    ],
+                ),
+                node("guides2", "Guides", vec![node("faq", "FAQ", vec![])]),
+            ],
+        }
+    }
+
+    fn ids(nodes: Vec<&DocumentTreeNode>) -> Vec<String> {
+        nodes.iter().map(|n| n.id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_find_by_path() {
+        let tree = sample_tree();
+        assert_eq!(ids(tree.find_by_path("/Guides/Onboarding")), ["onboarding"]);
+        assert_eq!(ids(tree.find_by_path("Guides//Release/")), ["release"]);
+    }
+
+    #[test]
+    fn test_find_by_path_searches_every_matching_branch() {
+        let tree = sample_tree();
+        // "FAQ" lives under the second "Guides".
+        assert_eq!(ids(tree.find_by_path("/Guides/FAQ")), ["faq"]);
+        // Both "Guides" match, so the path is ambiguous.
+        assert_eq!(ids(tree.find_by_path("/Guides")), ["guides", "guides2"]);
+    }
+
+    #[test]
+    fn test_find_by_path_missing() {
+        let tree = sample_tree();
+        assert!(tree.find_by_path("/").is_empty());
+        assert!(tree.find_by_path("/Guides/Missing").is_empty());
+    }
+}
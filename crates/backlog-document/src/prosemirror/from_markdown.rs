@@ -0,0 +1,481 @@
+use super::MENTION_SCHEME;
+use super::node::{
+    CodeBlockAttrs, HeadingAttrs, ImageAttrs, LinkAttrs, Mark, MentionAttrs, Node,
+    OrderedListAttrs, TaskItemAttrs,
+};
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+
+/// Parse CommonMark into a `doc` node.
+pub fn from_markdown(markdown: &str) -> Node {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let mut builder = Builder::default();
+    for event in Parser::new_ext(markdown, options) {
+        builder.event(event);
+    }
+    builder.finish()
+}
+
+enum FrameKind {
+    Doc,
+    Paragraph,
+    Heading(u8),
+    Blockquote,
+    List(Option<u64>),
+    Item(Option<bool>),
+    CodeBlock(Option<String>, String),
+    HtmlBlock(String),
+    Table,
+    TableHead,
+    TableRow,
+    TableCell(bool),
+    Image(String, Option<String>),
+    Mention(String),
+    Other,
+}
+
+struct Frame {
+    kind: FrameKind,
+    content: Vec<Node>,
+}
+
+struct Builder {
+    stack: Vec<Frame>,
+    marks: Vec<Mark>,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            stack: vec![Frame {
+                kind: FrameKind::Doc,
+                content: Vec::new(),
+            }],
+            marks: Vec::new(),
+        }
+    }
+}
+
+impl Builder {
+    fn event(&mut self, event: Event<'_>) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => self.text(&text),
+            Event::Code(code) => {
+                let mut marks = self.marks.clone();
+                marks.push(Mark::Code);
+                self.push_text(&code, marks);
+            }
+            Event::InlineHtml(html) => match html.trim() {
+                "<u>" => self.marks.push(Mark::Underline),
+                "</u>" => self.pop_mark(|mark| matches!(mark, Mark::Underline)),
+                "<br>" | "<br/>" | "<br />" => self.push_inline(Node::HardBreak),
+                other => self.text(other),
+            },
+            Event::Html(html) => match &mut self.top().kind {
+                FrameKind::HtmlBlock(buffer) => buffer.push_str(&html),
+                _ => self.text(&html),
+            },
+            Event::SoftBreak => self.text(" "),
+            Event::HardBreak => self.push_inline(Node::HardBreak),
+            Event::Rule => self.top().content.push(Node::HorizontalRule),
+            Event::TaskListMarker(checked) => {
+                if let Some(frame) = self
+                    .stack
+                    .iter_mut()
+                    .rev()
+                    .find(|frame| matches!(frame.kind, FrameKind::Item(_)))
+                {
+                    frame.kind = FrameKind::Item(Some(checked));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag<'_>) {
+        let kind = match tag {
+            Tag::Paragraph => FrameKind::Paragraph,
+            Tag::Heading { level, .. } => FrameKind::Heading(heading_level(level)),
+            Tag::BlockQuote(_) => FrameKind::Blockquote,
+            Tag::List(start) => FrameKind::List(start),
+            Tag::Item => FrameKind::Item(None),
+            Tag::CodeBlock(kind) => {
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => info
+                        .split_whitespace()
+                        .next()
+                        .map(|language| language.to_string()),
+                    CodeBlockKind::Indented => None,
+                };
+                FrameKind::CodeBlock(language, String::new())
+            }
+            Tag::HtmlBlock => FrameKind::HtmlBlock(String::new()),
+            Tag::Table(_) => FrameKind::Table,
+            Tag::TableHead => FrameKind::TableHead,
+            Tag::TableRow => FrameKind::TableRow,
+            Tag::TableCell => {
+                let header = self
+                    .stack
+                    .iter()
+                    .any(|frame| matches!(frame.kind, FrameKind::TableHead));
+                FrameKind::TableCell(header)
+            }
+            Tag::Emphasis => {
+                self.marks.push(Mark::Italic);
+                return;
+            }
+            Tag::Strong => {
+                self.marks.push(Mark::Bold);
+                return;
+            }
+            Tag::Strikethrough => {
+                self.marks.push(Mark::Strike);
+                return;
+            }
+            Tag::Link {
+                dest_url, title, ..
+            } => {
+                if let Some(id) = dest_url.strip_prefix(MENTION_SCHEME) {
+                    FrameKind::Mention(id.to_string())
+                } else {
+                    self.marks.push(Mark::Link {
+                        attrs: LinkAttrs {
+                            href: dest_url.to_string(),
+                            title: non_empty(&title),
+                        },
+                    });
+                    return;
+                }
+            }
+            Tag::Image {
+                dest_url, title, ..
+            } => FrameKind::Image(dest_url.to_string(), non_empty(&title)),
+            _ => FrameKind::Other,
+        };
+        self.stack.push(Frame {
+            kind,
+            content: Vec::new(),
+        });
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Emphasis => return self.pop_mark(|mark| matches!(mark, Mark::Italic)),
+            TagEnd::Strong => return self.pop_mark(|mark| matches!(mark, Mark::Bold)),
+            TagEnd::Strikethrough => return self.pop_mark(|mark| matches!(mark, Mark::Strike)),
+            TagEnd::Link if !matches!(self.top().kind, FrameKind::Mention(_)) => {
+                return self.pop_mark(|mark| matches!(mark, Mark::Link { .. }));
+            }
+            _ => {}
+        }
+
+        if self.stack.len() <= 1 {
+            return;
+        }
+        let Some(Frame { kind, content }) = self.stack.pop() else {
+            return;
+        };
+
+        match kind {
+            FrameKind::Doc | FrameKind::Other => self.top().content.extend(content),
+            FrameKind::Paragraph => {
+                if let [image @ Node::Image { .. }] = content.as_slice() {
+                    let image = image.clone();
+                    self.top().content.push(image);
+                } else {
+                    self.top().content.push(Node::Paragraph { content });
+                }
+            }
+            FrameKind::Heading(level) => self.top().content.push(Node::Heading {
+                attrs: HeadingAttrs { level },
+                content,
+            }),
+            FrameKind::Blockquote => self.top().content.push(Node::Blockquote {
+                content: wrap_inline(content),
+            }),
+            FrameKind::List(start) => {
+                let is_task = !content.is_empty()
+                    && content
+                        .iter()
+                        .all(|item| matches!(item, Node::TaskItem { .. }));
+                let node = if is_task {
+                    Node::TaskList { content }
+                } else {
+                    let content = content.into_iter().map(untask_item).collect();
+                    match start {
+                        Some(start) => Node::OrderedList {
+                            attrs: OrderedListAttrs { start },
+                            content,
+                        },
+                        None => Node::BulletList { content },
+                    }
+                };
+                self.top().content.push(node);
+            }
+            FrameKind::Item(checked) => {
+                let content = wrap_inline(content);
+                let node = match checked {
+                    Some(checked) => Node::TaskItem {
+                        attrs: TaskItemAttrs { checked },
+                        content,
+                    },
+                    None => Node::ListItem { content },
+                };
+                self.top().content.push(node);
+            }
+            FrameKind::CodeBlock(language, code) => {
+                let code = code.strip_suffix('\n').unwrap_or(&code);
+                let content = if code.is_empty() {
+                    Vec::new()
+                } else {
+                    vec![Node::text(code)]
+                };
+                self.top().content.push(Node::CodeBlock {
+                    attrs: CodeBlockAttrs { language },
+                    content,
+                });
+            }
+            FrameKind::HtmlBlock(html) => {
+                let html = html.trim_end();
+                if !html.is_empty() {
+                    self.top().content.push(Node::Paragraph {
+                        content: vec![Node::text(html)],
+                    });
+                }
+            }
+            FrameKind::Table => self.top().content.push(Node::Table { content }),
+            FrameKind::TableHead | FrameKind::TableRow => {
+                self.top().content.push(Node::TableRow { content })
+            }
+            FrameKind::TableCell(header) => {
+                let content = vec![Node::Paragraph { content }];
+                self.top().content.push(if header {
+                    Node::TableHeader { content }
+                } else {
+                    Node::TableCell { content }
+                });
+            }
+            FrameKind::Image(src, title) => {
+                let alt: String = content.iter().map(Node::text_content).collect();
+                self.push_inline(Node::Image {
+                    attrs: ImageAttrs {
+                        src,
+                        alt: non_empty(&alt),
+                        title,
+                    },
+                });
+            }
+            FrameKind::Mention(id) => {
+                let label: String = content.iter().map(Node::text_content).collect();
+                let label = label.strip_prefix('@').unwrap_or(&label);
+                self.push_inline(Node::Mention {
+                    attrs: MentionAttrs {
+                        id,
+                        label: non_empty(label),
+                    },
+                });
+            }
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        match &mut self.top().kind {
+            FrameKind::CodeBlock(_, code) => code.push_str(text),
+            FrameKind::HtmlBlock(html) => html.push_str(text),
+            _ => {
+                let marks = self.marks.clone();
+                self.push_text(text, marks);
+            }
+        }
+    }
+
+    fn push_text(&mut self, text: &str, mut marks: Vec<Mark>) {
+        if text.is_empty() {
+            return;
+        }
+        marks.sort_by_key(Mark::rank);
+        // Text is split at escapes and entities; merge runs with the same marks.
+        if let Some(Node::Text {
+            text: previous,
+            marks: previous_marks,
+        }) = self.top().content.last_mut()
+            && *previous_marks == marks
+        {
+            previous.push_str(text);
+            return;
+        }
+        self.push_inline(Node::Text {
+            text: text.to_string(),
+            marks,
+        });
+    }
+
+    fn push_inline(&mut self, node: Node) {
+        self.top().content.push(node);
+    }
+
+    fn pop_mark(&mut self, predicate: impl Fn(&Mark) -> bool) {
+        if let Some(index) = self.marks.iter().rposition(predicate) {
+            self.marks.remove(index);
+        }
+    }
+
+    fn top(&mut self) -> &mut Frame {
+        self.stack
+            .last_mut()
+            .expect("builder stack always holds the doc frame")
+    }
+
+    fn finish(mut self) -> Node {
+        while self.stack.len() > 1 {
+            if let Some(frame) = self.stack.pop() {
+                self.top().content.extend(frame.content);
+            }
+        }
+        let content = self.stack.pop().map(|frame| frame.content);
+        Node::Doc {
+            content: wrap_inline(content.unwrap_or_default()),
+        }
+    }
+}
+
+/// Wrap runs of inline nodes (as found in tight list items) in paragraphs.
+fn wrap_inline(nodes: Vec<Node>) -> Vec<Node> {
+    let mut blocks = Vec::new();
+    let mut inline = Vec::new();
+    for node in nodes {
+        if node.is_inline() {
+            inline.push(node);
+        } else {
+            if !inline.is_empty() {
+                blocks.push(Node::Paragraph {
+                    content: std::mem::take(&mut inline),
+                });
+            }
+            blocks.push(node);
+        }
+    }
+    if !inline.is_empty() {
+        blocks.push(Node::Paragraph { content: inline });
+    }
+    blocks
+}
+
+fn untask_item(item: Node) -> Node {
+    match item {
+        Node::TaskItem { content, .. } => Node::ListItem { content },
+        other => other,
+    }
+}
+
+fn heading_level(level: HeadingLevel) -> u8 {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+fn non_empty(text: &str) -> Option<String> {
+    if text.is_empty() {
+        None
+    } else {
+        Some(text.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paragraph(content: Vec<Node>) -> Node {
+        Node::Paragraph { content }
+    }
+
+    #[test]
+    fn test_tight_list_items_get_paragraphs() {
+        let doc = from_markdown("- a\n- b\n");
+        let Node::Doc { content } = doc else {
+            panic!("expected doc");
+        };
+        assert_eq!(
+            content,
+            vec![Node::BulletList {
+                content: vec![
+                    Node::ListItem {
+                        content: vec![paragraph(vec![Node::text("a")])],
+                    },
+                    Node::ListItem {
+                        content: vec![paragraph(vec![Node::text("b")])],
+                    },
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_escaped_text_is_merged() {
+        let doc = from_markdown("a\\*b\\_c\n");
+        assert_eq!(
+            doc,
+            Node::Doc {
+                content: vec![paragraph(vec![Node::text("a*b_c")])],
+            }
+        );
+    }
+
+    #[test]
+    fn test_mention_link() {
+        let doc = from_markdown("hi [@john](mention:42)\n");
+        assert_eq!(
+            doc,
+            Node::Doc {
+                content: vec![paragraph(vec![
+                    Node::text("hi "),
+                    Node::Mention {
+                        attrs: MentionAttrs {
+                            id: "42".to_string(),
+                            label: Some("john".to_string()),
+                        },
+                    },
+                ])],
+            }
+        );
+    }
+
+    #[test]
+    fn test_marks_are_sorted() {
+        let doc = from_markdown("*__x__*\n");
+        assert_eq!(
+            doc,
+            Node::Doc {
+                content: vec![paragraph(vec![Node::Text {
+                    text: "x".to_string(),
+                    marks: vec![Mark::Bold, Mark::Italic],
+                }])],
+            }
+        );
+    }
+
+    #[test]
+    fn test_standalone_image_is_block() {
+        let doc = from_markdown("![diagram](/attachments/1 \"Flow\")\n");
+        assert_eq!(
+            doc,
+            Node::Doc {
+                content: vec![Node::Image {
+                    attrs: ImageAttrs {
+                        src: "/attachments/1".to_string(),
+                        alt: Some("diagram".to_string()),
+                        title: Some("Flow".to_string()),
+                    },
+                }],
+            }
+        );
+    }
+}
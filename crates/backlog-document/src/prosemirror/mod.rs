@@ -0,0 +1,20 @@
+//! Typed model of the ProseMirror JSON used by Backlog documents, with
+//! conversion to and from CommonMark.
+//!
+//! Tables, task lists and strikethrough use the GitHub-flavored extensions.
+//! Mentions are written as `[@label](mention:ID)` and underline as `<u>…</u>`
+//! so that they survive a round trip through Markdown.
+
+mod from_markdown;
+mod node;
+mod to_markdown;
+
+pub use from_markdown::from_markdown;
+pub use node::{
+    CodeBlockAttrs, HeadingAttrs, ImageAttrs, LinkAttrs, Mark, MentionAttrs, Node,
+    OrderedListAttrs, TaskItemAttrs,
+};
+pub use to_markdown::to_markdown;
+
+/// URL scheme used to encode mentions as Markdown links.
+pub const MENTION_SCHEME: &str = "mention:";
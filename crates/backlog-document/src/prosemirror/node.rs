@@ -0,0 +1,397 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value as JsonValue;
+
+/// A node in a Backlog document.
+///
+/// Node types that are not part of the model are kept as raw JSON in
+/// [`Node::Unknown`], so deserializing and serializing a document does not
+/// drop content.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Node {
+    Doc {
+        #[serde(default)]
+        content: Vec<Node>,
+    },
+    Paragraph {
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        content: Vec<Node>,
+    },
+    Heading {
+        attrs: HeadingAttrs,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        content: Vec<Node>,
+    },
+    Blockquote {
+        #[serde(default)]
+        content: Vec<Node>,
+    },
+    #[serde(alias = "bullet_list")]
+    BulletList {
+        #[serde(default)]
+        content: Vec<Node>,
+    },
+    #[serde(alias = "ordered_list")]
+    OrderedList {
+        #[serde(default)]
+        attrs: OrderedListAttrs,
+        #[serde(default)]
+        content: Vec<Node>,
+    },
+    #[serde(alias = "list_item")]
+    ListItem {
+        #[serde(default)]
+        content: Vec<Node>,
+    },
+    #[serde(alias = "task_list")]
+    TaskList {
+        #[serde(default)]
+        content: Vec<Node>,
+    },
+    #[serde(alias = "task_item")]
+    TaskItem {
+        #[serde(default)]
+        attrs: TaskItemAttrs,
+        #[serde(default)]
+        content: Vec<Node>,
+    },
+    #[serde(alias = "code_block")]
+    CodeBlock {
+        #[serde(default)]
+        attrs: CodeBlockAttrs,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        content: Vec<Node>,
+    },
+    #[serde(alias = "horizontal_rule")]
+    HorizontalRule,
+    #[serde(alias = "hard_break")]
+    HardBreak,
+    Image {
+        attrs: ImageAttrs,
+    },
+    Table {
+        #[serde(default)]
+        content: Vec<Node>,
+    },
+    #[serde(alias = "table_row")]
+    TableRow {
+        #[serde(default)]
+        content: Vec<Node>,
+    },
+    #[serde(alias = "table_header")]
+    TableHeader {
+        #[serde(default)]
+        content: Vec<Node>,
+    },
+    #[serde(alias = "table_cell")]
+    TableCell {
+        #[serde(default)]
+        content: Vec<Node>,
+    },
+    Mention {
+        attrs: MentionAttrs,
+    },
+    Text {
+        text: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        marks: Vec<Mark>,
+    },
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
+impl Node {
+    /// Parse a node tree from ProseMirror JSON, such as `DocumentDetail::json`.
+    pub fn from_json(json: &JsonValue) -> serde_json::Result<Self> {
+        Node::deserialize(json)
+    }
+
+    /// Serialize the node tree back to ProseMirror JSON.
+    pub fn to_json(&self) -> JsonValue {
+        serde_json::to_value(self).unwrap_or(JsonValue::Null)
+    }
+
+    /// Create a plain text node.
+    pub fn text(text: impl Into<String>) -> Self {
+        Node::Text {
+            text: text.into(),
+            marks: Vec::new(),
+        }
+    }
+
+    /// Returns `true` for nodes that live inside a paragraph.
+    pub fn is_inline(&self) -> bool {
+        matches!(
+            self,
+            Node::Text { .. } | Node::HardBreak | Node::Mention { .. }
+        )
+    }
+
+    /// Child nodes, or an empty slice for leaf nodes.
+    pub fn children(&self) -> &[Node] {
+        match self {
+            Node::Doc { content }
+            | Node::Paragraph { content }
+            | Node::Heading { content, .. }
+            | Node::Blockquote { content }
+            | Node::BulletList { content }
+            | Node::OrderedList { content, .. }
+            | Node::ListItem { content }
+            | Node::TaskList { content }
+            | Node::TaskItem { content, .. }
+            | Node::CodeBlock { content, .. }
+            | Node::Table { content }
+            | Node::TableRow { content }
+            | Node::TableHeader { content }
+            | Node::TableCell { content } => content,
+            _ => &[],
+        }
+    }
+
+    /// Mutable child nodes, or an empty slice for leaf nodes.
+    pub fn children_mut(&mut self) -> &mut [Node] {
+        match self {
+            Node::Doc { content }
+            | Node::Paragraph { content }
+            | Node::Heading { content, .. }
+            | Node::Blockquote { content }
+            | Node::BulletList { content }
+            | Node::OrderedList { content, .. }
+            | Node::ListItem { content }
+            | Node::TaskList { content }
+            | Node::TaskItem { content, .. }
+            | Node::CodeBlock { content, .. }
+            | Node::Table { content }
+            | Node::TableRow { content }
+            | Node::TableHeader { content }
+            | Node::TableCell { content } => content,
+            _ => &mut [],
+        }
+    }
+
+    /// Concatenated text of this node and its descendants.
+    pub fn text_content(&self) -> String {
+        match self {
+            Node::Text { text, .. } => text.clone(),
+            Node::Mention { attrs } => format!("@{}", attrs.display_name()),
+            Node::HardBreak => "\n".to_string(),
+            Node::Unknown(value) => unknown_children(value)
+                .iter()
+                .map(Node::text_content)
+                .collect::<String>(),
+            _ => self.children().iter().map(Node::text_content).collect(),
+        }
+    }
+}
+
+/// Child nodes of an unrecognized node, if it has a `content` array.
+pub(crate) fn unknown_children(value: &JsonValue) -> Vec<Node> {
+    match value.get("content") {
+        Some(content) => Vec::<Node>::deserialize(content).unwrap_or_default(),
+        None => match value.get("text").and_then(JsonValue::as_str) {
+            Some(text) => vec![Node::text(text)],
+            None => Vec::new(),
+        },
+    }
+}
+
+/// Formatting applied to a text node.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Mark {
+    Link {
+        attrs: LinkAttrs,
+    },
+    #[serde(alias = "strong")]
+    Bold,
+    #[serde(alias = "em")]
+    Italic,
+    #[serde(alias = "strikethrough")]
+    Strike,
+    Underline,
+    Code,
+    #[serde(untagged)]
+    Unknown(JsonValue),
+}
+
+impl Mark {
+    /// Nesting order used when writing marks: lower ranks wrap higher ones.
+    pub(crate) fn rank(&self) -> u8 {
+        match self {
+            Mark::Link { .. } => 0,
+            Mark::Bold => 1,
+            Mark::Italic => 2,
+            Mark::Strike => 3,
+            Mark::Underline => 4,
+            Mark::Unknown(_) => 5,
+            Mark::Code => 6,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HeadingAttrs {
+    pub level: u8,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OrderedListAttrs {
+    #[serde(default = "default_start")]
+    pub start: u64,
+}
+
+impl Default for OrderedListAttrs {
+    fn default() -> Self {
+        Self {
+            start: default_start(),
+        }
+    }
+}
+
+fn default_start() -> u64 {
+    1
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct TaskItemAttrs {
+    #[serde(default)]
+    pub checked: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct CodeBlockAttrs {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+/// Image attributes. Attachments embedded in a document are images whose
+/// `src` points at the document attachment.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ImageAttrs {
+    pub src: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alt: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct MentionAttrs {
+    #[serde(deserialize_with = "string_or_number")]
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+impl MentionAttrs {
+    /// The label, or the ID when the mention has no label.
+    pub fn display_name(&self) -> &str {
+        self.label.as_deref().unwrap_or(&self.id)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LinkAttrs {
+    pub href: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+fn string_or_number<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match JsonValue::deserialize(deserializer)? {
+        JsonValue::String(s) => Ok(s),
+        JsonValue::Number(n) => Ok(n.to_string()),
+        other => Err(serde::de::Error::custom(format!(
+            "expected string or number, got {other}"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_deserialize_known_nodes() {
+        let node = Node::from_json(&json!({
+            "type": "doc",
+            "content": [
+                {"type": "heading", "attrs": {"level": 2}, "content": [{"type": "text", "text": "Title"}]},
+                {"type": "paragraph", "content": [
+                    {"type": "text", "text": "bold", "marks": [{"type": "bold"}]},
+                    {"type": "hardBreak"},
+                    {"type": "mention", "attrs": {"id": 42, "label": "john"}}
+                ]},
+                {"type": "horizontalRule"}
+            ]
+        }))
+        .unwrap();
+
+        let Node::Doc { content } = &node else {
+            panic!("expected doc");
+        };
+        assert_eq!(content.len(), 3);
+        assert_eq!(
+            content[0],
+            Node::Heading {
+                attrs: HeadingAttrs { level: 2 },
+                content: vec![Node::text("Title")],
+            }
+        );
+        assert_eq!(content[2], Node::HorizontalRule);
+        assert_eq!(node.text_content(), "Titlebold\n@john");
+    }
+
+    #[test]
+    fn test_snake_case_aliases() {
+        let node = Node::from_json(&json!({
+            "type": "bullet_list",
+            "content": [{"type": "list_item", "content": [
+                {"type": "paragraph", "content": [{"type": "text", "text": "a", "marks": [{"type": "strong"}, {"type": "em"}]}]}
+            ]}]
+        }))
+        .unwrap();
+
+        let Node::BulletList { content } = node else {
+            panic!("expected bullet list");
+        };
+        let Node::ListItem { content } = &content[0] else {
+            panic!("expected list item");
+        };
+        assert_eq!(
+            content[0],
+            Node::Paragraph {
+                content: vec![Node::Text {
+                    text: "a".to_string(),
+                    marks: vec![Mark::Bold, Mark::Italic],
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn test_unknown_nodes_are_preserved() {
+        let json = json!({
+            "type": "doc",
+            "content": [
+                {"type": "callout", "attrs": {"kind": "info"}, "content": [{"type": "text", "text": "note"}]},
+                {"type": "paragraph", "content": [{"type": "text", "text": "x", "marks": [{"type": "highlight", "attrs": {"color": "red"}}]}]}
+            ]
+        });
+
+        let node = Node::from_json(&json).unwrap();
+        assert_eq!(node.to_json(), json);
+        assert_eq!(node.text_content(), "notex");
+    }
+
+    #[test]
+    fn test_serialize_omits_empty_content() {
+        let node = Node::Paragraph { content: vec![] };
+        assert_eq!(node.to_json(), json!({"type": "paragraph"}));
+        assert_eq!(Node::HardBreak.to_json(), json!({"type": "hardBreak"}));
+    }
+}
@@ -0,0 +1,584 @@
+use super::MENTION_SCHEME;
+use super::node::{ImageAttrs, LinkAttrs, Mark, MentionAttrs, Node, unknown_children};
+
+/// Render a node tree as CommonMark.
+///
+/// The output ends with a newline unless the document is empty.
+pub fn to_markdown(node: &Node) -> String {
+    let mut markdown = match node {
+        Node::Doc { content } => render_blocks(content),
+        other => render_blocks(std::slice::from_ref(other)),
+    };
+    if !markdown.is_empty() {
+        markdown.push('\n');
+    }
+    markdown
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ListKind {
+    Bullet,
+    Ordered,
+    Task,
+}
+
+fn list_kind(node: &Node) -> Option<ListKind> {
+    match node {
+        Node::BulletList { .. } => Some(ListKind::Bullet),
+        Node::OrderedList { .. } => Some(ListKind::Ordered),
+        Node::TaskList { .. } => Some(ListKind::Task),
+        _ => None,
+    }
+}
+
+fn render_blocks(nodes: &[Node]) -> String {
+    let mut blocks = Vec::new();
+    let mut previous_list = None;
+    let mut alternate = false;
+
+    for node in nodes {
+        // Adjacent lists of the same kind would merge into one list, so
+        // alternate the bullet character or ordered delimiter between them.
+        let kind = list_kind(node);
+        alternate = kind.is_some() && kind == previous_list && !alternate;
+        if let Some(block) = render_block(node, alternate) {
+            blocks.push(block);
+            previous_list = kind;
+        }
+    }
+
+    blocks.join("\n\n")
+}
+
+fn render_block(node: &Node, alternate: bool) -> Option<String> {
+    let block = match node {
+        Node::Doc { content }
+        | Node::ListItem { content }
+        | Node::TaskItem { content, .. }
+        | Node::TableRow { content }
+        | Node::TableHeader { content }
+        | Node::TableCell { content } => render_blocks(content),
+        Node::Paragraph { content } => render_inline(content, false),
+        Node::Heading { attrs, content } => {
+            let hashes = "#".repeat(attrs.level.clamp(1, 6) as usize);
+            let text = render_inline(content, false);
+            if text.is_empty() {
+                hashes
+            } else {
+                format!("{hashes} {text}")
+            }
+        }
+        Node::Blockquote { content } => render_blocks(content)
+            .lines()
+            .map(|line| {
+                if line.is_empty() {
+                    ">".to_string()
+                } else {
+                    format!("> {line}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Node::BulletList { content } => {
+            let bullet = if alternate { "* " } else { "- " };
+            render_list(content, |_, _| (bullet.to_string(), 2))
+        }
+        Node::OrderedList { attrs, content } => {
+            let delimiter = if alternate { ')' } else { '.' };
+            render_list(content, |index, _| {
+                let marker = format!("{}{delimiter} ", attrs.start + index as u64);
+                let width = marker.len();
+                (marker, width)
+            })
+        }
+        Node::TaskList { content } => {
+            let bullet = if alternate { '*' } else { '-' };
+            render_list(content, |_, item| {
+                let checked = matches!(item, Node::TaskItem { attrs, .. } if attrs.checked);
+                let check = if checked { 'x' } else { ' ' };
+                (format!("{bullet} [{check}] "), 2)
+            })
+        }
+        Node::CodeBlock { attrs, content } => {
+            let code: String = content.iter().map(Node::text_content).collect();
+            let fence = "`".repeat((longest_run(&code, '`') + 1).max(3));
+            let language = attrs.language.as_deref().unwrap_or("");
+            if code.is_empty() {
+                format!("{fence}{language}\n{fence}")
+            } else {
+                format!("{fence}{language}\n{code}\n{fence}")
+            }
+        }
+        Node::HorizontalRule => "---".to_string(),
+        Node::Image { attrs } => render_image(attrs),
+        Node::Table { content } => render_table(content),
+        Node::Text { .. } | Node::Mention { .. } => {
+            render_inline(std::slice::from_ref(node), false)
+        }
+        Node::HardBreak => String::new(),
+        Node::Unknown(value) => render_blocks(&unknown_children(value)),
+    };
+
+    if block.trim().is_empty() {
+        None
+    } else {
+        Some(block)
+    }
+}
+
+/// Render list items, where `marker` returns the item marker and the
+/// indentation used for continuation lines.
+fn render_list(items: &[Node], marker: impl Fn(usize, &Node) -> (String, usize)) -> String {
+    let tight = items.iter().all(|item| {
+        item.children()
+            .iter()
+            .filter(|child| list_kind(child).is_none())
+            .count()
+            <= 1
+    });
+
+    let rendered: Vec<String> = items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let (marker, indent) = marker(index, item);
+            let body = render_item_body(item.children());
+            if body.is_empty() {
+                return marker.trim_end().to_string();
+            }
+            let padding = " ".repeat(indent);
+            body.lines()
+                .enumerate()
+                .map(|(i, line)| {
+                    if i == 0 {
+                        format!("{marker}{line}")
+                    } else if line.is_empty() {
+                        String::new()
+                    } else {
+                        format!("{padding}{line}")
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .collect();
+
+    rendered.join(if tight { "\n" } else { "\n\n" })
+}
+
+fn render_item_body(children: &[Node]) -> String {
+    let mut body = String::new();
+    let mut previous_list = None;
+    let mut alternate = false;
+
+    for child in children {
+        let kind = list_kind(child);
+        alternate = kind.is_some() && kind == previous_list && !alternate;
+        let Some(block) = render_block(child, alternate) else {
+            continue;
+        };
+        if !body.is_empty() {
+            // A nested list directly under a paragraph keeps the list tight.
+            let nested = kind.is_some() && previous_list.is_none();
+            body.push_str(if nested { "\n" } else { "\n\n" });
+        }
+        body.push_str(&block);
+        previous_list = kind;
+    }
+
+    body
+}
+
+fn render_table(rows: &[Node]) -> String {
+    let rows: Vec<Vec<String>> = rows
+        .iter()
+        .filter(|row| matches!(row, Node::TableRow { .. }))
+        .map(|row| {
+            row.children()
+                .iter()
+                .map(|cell| {
+                    cell.children()
+                        .iter()
+                        .map(|block| render_inline(block.children(), true))
+                        .filter(|text| !text.is_empty())
+                        .collect::<Vec<_>>()
+                        .join("<br>")
+                })
+                .collect()
+        })
+        .collect();
+
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    if columns == 0 {
+        return String::new();
+    }
+
+    let format_row = |cells: &[String]| {
+        let cells: Vec<&str> = (0..columns)
+            .map(|i| cells.get(i).map(String::as_str).unwrap_or(""))
+            .collect();
+        format!("| {} |", cells.join(" | "))
+    };
+
+    let mut lines = vec![
+        format_row(&rows[0]),
+        format!("|{}", " --- |".repeat(columns)),
+    ];
+    lines.extend(rows[1..].iter().map(|row| format_row(row)));
+    lines.join("\n")
+}
+
+fn render_image(attrs: &ImageAttrs) -> String {
+    let alt = escape_text(attrs.alt.as_deref().unwrap_or(""), false);
+    format!(
+        "![{alt}]({})",
+        link_destination(&attrs.src, attrs.title.as_deref())
+    )
+}
+
+fn render_mention(attrs: &MentionAttrs) -> String {
+    format!(
+        "[@{}]({MENTION_SCHEME}{})",
+        escape_text(attrs.display_name(), false),
+        attrs.id
+    )
+}
+
+fn link_destination(href: &str, title: Option<&str>) -> String {
+    let href = if href.contains([' ', '(', ')', '<', '>']) {
+        format!("<{}>", href.replace('<', "\\<").replace('>', "\\>"))
+    } else {
+        href.to_string()
+    };
+    match title {
+        Some(title) => format!("{href} \"{}\"", title.replace('"', "\\\"")),
+        None => href,
+    }
+}
+
+fn render_inline(nodes: &[Node], in_table: bool) -> String {
+    let mut writer = InlineWriter {
+        out: String::new(),
+        open: Vec::new(),
+        in_table,
+        line_start: true,
+    };
+    for node in nodes {
+        writer.node(node);
+    }
+    writer.close_from(0);
+    writer.out
+}
+
+/// Writes inline content while keeping track of the marks that are open.
+struct InlineWriter {
+    out: String,
+    open: Vec<Mark>,
+    in_table: bool,
+    line_start: bool,
+}
+
+impl InlineWriter {
+    fn node(&mut self, node: &Node) {
+        match node {
+            Node::Text { text, marks } => self.text(text, marks),
+            Node::HardBreak => {
+                if self.in_table {
+                    self.out.push_str("<br>");
+                } else {
+                    self.out.push_str("\\\n");
+                    self.line_start = true;
+                }
+            }
+            Node::Mention { attrs } => {
+                self.close_from(0);
+                self.out.push_str(&render_mention(attrs));
+                self.line_start = false;
+            }
+            Node::Image { attrs } => {
+                self.close_from(0);
+                self.out.push_str(&render_image(attrs));
+                self.line_start = false;
+            }
+            other => {
+                for child in other.children() {
+                    self.node(child);
+                }
+                if let Node::Unknown(value) = other {
+                    for child in unknown_children(value) {
+                        self.node(&child);
+                    }
+                }
+            }
+        }
+    }
+
+    fn text(&mut self, text: &str, marks: &[Mark]) {
+        if text.is_empty() {
+            return;
+        }
+
+        let mut marks: Vec<&Mark> = marks.iter().collect();
+        marks.sort_by_key(|mark| mark.rank());
+        let is_code = marks.iter().any(|mark| matches!(mark, Mark::Code));
+        let wrapping: Vec<&Mark> = marks
+            .into_iter()
+            .filter(|mark| !matches!(mark, Mark::Code))
+            .collect();
+
+        let common = self
+            .open
+            .iter()
+            .zip(&wrapping)
+            .take_while(|(open, new)| open == *new)
+            .count();
+        self.close_from(common);
+
+        let mut text = text;
+        if wrapping.len() > common {
+            // Emphasis markers must touch non-whitespace, so leading
+            // whitespace is written before the markers are opened.
+            let trimmed = text.trim_start();
+            self.out.push_str(&text[..text.len() - trimmed.len()]);
+            text = trimmed;
+            for mark in &wrapping[common..] {
+                self.out.push_str(open_marker(mark));
+                self.open.push((*mark).clone());
+            }
+        }
+
+        if is_code {
+            self.out.push_str(&code_span(text, self.in_table));
+        } else {
+            let escaped = escape_text(text, self.in_table);
+            if self.line_start {
+                self.out.push_str(&escape_line_start(&escaped));
+            } else {
+                self.out.push_str(&escaped);
+            }
+        }
+        self.line_start = false;
+    }
+
+    fn close_from(&mut self, depth: usize) {
+        if self.open.len() <= depth {
+            return;
+        }
+        let trimmed_len = self.out.trim_end_matches([' ', '\t']).len();
+        let trailing = self.out.split_off(trimmed_len);
+        while self.open.len() > depth {
+            if let Some(mark) = self.open.pop() {
+                self.out.push_str(&close_marker(&mark));
+            }
+        }
+        self.out.push_str(&trailing);
+    }
+}
+
+fn open_marker(mark: &Mark) -> &'static str {
+    match mark {
+        Mark::Link { .. } => "[",
+        Mark::Bold => "**",
+        Mark::Italic => "*",
+        Mark::Strike => "~~",
+        Mark::Underline => "<u>",
+        Mark::Code | Mark::Unknown(_) => "",
+    }
+}
+
+fn close_marker(mark: &Mark) -> String {
+    match mark {
+        Mark::Link {
+            attrs: LinkAttrs { href, title },
+        } => format!("]({})", link_destination(href, title.as_deref())),
+        Mark::Bold => "**".to_string(),
+        Mark::Italic => "*".to_string(),
+        Mark::Strike => "~~".to_string(),
+        Mark::Underline => "</u>".to_string(),
+        Mark::Code | Mark::Unknown(_) => String::new(),
+    }
+}
+
+fn code_span(code: &str, in_table: bool) -> String {
+    let code = if in_table {
+        code.replace('|', "\\|")
+    } else {
+        code.to_string()
+    };
+    let fence = "`".repeat(longest_run(&code, '`') + 1);
+    let needs_padding = code.starts_with('`')
+        || code.ends_with('`')
+        || (code.starts_with(' ') && code.ends_with(' ') && !code.trim().is_empty());
+    if needs_padding {
+        format!("{fence} {code} {fence}")
+    } else {
+        format!("{fence}{code}{fence}")
+    }
+}
+
+fn longest_run(text: &str, ch: char) -> usize {
+    let mut longest = 0;
+    let mut current = 0;
+    for c in text.chars() {
+        if c == ch {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    longest
+}
+
+/// Backslash-escape characters that would otherwise start inline syntax.
+fn escape_text(text: &str, in_table: bool) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut escaped = String::with_capacity(text.len());
+
+    for (i, &c) in chars.iter().enumerate() {
+        let needs_escape = match c {
+            '\\' | '`' | '*' | '[' | ']' | '<' | '~' => true,
+            // Intraword underscores never open emphasis.
+            '_' => {
+                let before = i.checked_sub(1).map(|j| chars[j]);
+                let after = chars.get(i + 1).copied();
+                !(before.is_some_and(char::is_alphanumeric)
+                    && after.is_some_and(char::is_alphanumeric))
+            }
+            '&' => looks_like_entity(&chars[i + 1..]),
+            '|' => in_table,
+            _ => false,
+        };
+        if needs_escape {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+fn looks_like_entity(rest: &[char]) -> bool {
+    let rest = rest.strip_prefix(&['#']).unwrap_or(rest);
+    let name_len = rest
+        .iter()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .count();
+    name_len > 0 && rest.get(name_len) == Some(&';')
+}
+
+/// Escape text at the start of a line that would be read as a block marker.
+fn escape_line_start(text: &str) -> String {
+    if text.starts_with(['#', '>', '-', '+', '=']) {
+        return format!("\\{text}");
+    }
+    let digits = text.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 && text[digits..].starts_with(['.', ')']) {
+        return format!("{}\\{}", &text[..digits], &text[digits..]);
+    }
+    text.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prosemirror::node::{CodeBlockAttrs, HeadingAttrs};
+
+    fn paragraph(content: Vec<Node>) -> Node {
+        Node::Paragraph { content }
+    }
+
+    fn marked(text: &str, marks: Vec<Mark>) -> Node {
+        Node::Text {
+            text: text.to_string(),
+            marks,
+        }
+    }
+
+    #[test]
+    fn test_escape_text() {
+        assert_eq!(escape_text("a*b*c", false), "a\\*b\\*c");
+        assert_eq!(escape_text("snake_case", false), "snake_case");
+        assert_eq!(escape_text("_lead", false), "\\_lead");
+        assert_eq!(escape_text("R&D &amp;", false), "R&D \\&amp;");
+        assert_eq!(escape_text("a|b", false), "a|b");
+        assert_eq!(escape_text("a|b", true), "a\\|b");
+    }
+
+    #[test]
+    fn test_escape_line_start() {
+        assert_eq!(escape_line_start("# not a heading"), "\\# not a heading");
+        assert_eq!(escape_line_start("1. not a list"), "1\\. not a list");
+        assert_eq!(escape_line_start("plain"), "plain");
+    }
+
+    #[test]
+    fn test_marks_share_delimiters() {
+        let doc = paragraph(vec![
+            marked("bold ", vec![Mark::Bold]),
+            marked("both", vec![Mark::Bold, Mark::Italic]),
+            Node::text(" plain"),
+        ]);
+        assert_eq!(to_markdown(&doc), "**bold *both*** plain\n");
+    }
+
+    #[test]
+    fn test_whitespace_moves_outside_markers() {
+        let doc = paragraph(vec![
+            Node::text("a"),
+            marked(" b ", vec![Mark::Italic]),
+            Node::text("c"),
+        ]);
+        assert_eq!(to_markdown(&doc), "a *b* c\n");
+    }
+
+    #[test]
+    fn test_code_span_with_backticks() {
+        let doc = paragraph(vec![marked("a`b", vec![Mark::Code])]);
+        assert_eq!(to_markdown(&doc), "``a`b``\n");
+    }
+
+    #[test]
+    fn test_code_block_fence_longer_than_content() {
+        let doc = Node::CodeBlock {
+            attrs: CodeBlockAttrs {
+                language: Some("md".to_string()),
+            },
+            content: vec![Node::text("```\ninner\n```")],
+        };
+        assert_eq!(to_markdown(&doc), "````md\n```\ninner\n```\n````\n");
+    }
+
+    #[test]
+    fn test_adjacent_lists_alternate_markers() {
+        let item = |text: &str| Node::ListItem {
+            content: vec![paragraph(vec![Node::text(text)])],
+        };
+        let doc = Node::Doc {
+            content: vec![
+                Node::BulletList {
+                    content: vec![item("a")],
+                },
+                Node::BulletList {
+                    content: vec![item("b")],
+                },
+            ],
+        };
+        assert_eq!(to_markdown(&doc), "- a\n\n* b\n");
+    }
+
+    #[test]
+    fn test_empty_paragraphs_are_skipped() {
+        let doc = Node::Doc {
+            content: vec![
+                Node::Heading {
+                    attrs: HeadingAttrs { level: 1 },
+                    content: vec![Node::text("Title")],
+                },
+                paragraph(vec![]),
+                paragraph(vec![Node::text("Body")]),
+            ],
+        };
+        assert_eq!(to_markdown(&doc), "# Title\n\nBody\n");
+    }
+}
@@ -0,0 +1,80 @@
+use backlog_document::prosemirror::{Node, from_markdown, to_markdown};
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+
+/// Fixture pairs of ProseMirror JSON and the CommonMark it converts to.
+const FIXTURES: &[(&str, &str, &str)] = &[
+    (
+        "headings_and_marks",
+        include_str!("fixtures/prosemirror/headings_and_marks.json"),
+        include_str!("fixtures/prosemirror/headings_and_marks.md"),
+    ),
+    (
+        "lists",
+        include_str!("fixtures/prosemirror/lists.json"),
+        include_str!("fixtures/prosemirror/lists.md"),
+    ),
+    (
+        "code_and_quotes",
+        include_str!("fixtures/prosemirror/code_and_quotes.json"),
+        include_str!("fixtures/prosemirror/code_and_quotes.md"),
+    ),
+    (
+        "table",
+        include_str!("fixtures/prosemirror/table.json"),
+        include_str!("fixtures/prosemirror/table.md"),
+    ),
+    (
+        "mentions_and_images",
+        include_str!("fixtures/prosemirror/mentions_and_images.json"),
+        include_str!("fixtures/prosemirror/mentions_and_images.md"),
+    ),
+];
+
+fn parse_fixture(json: &str) -> (Value, Node) {
+    let value: Value = serde_json::from_str(json).unwrap();
+    let node = Node::from_json(&value).unwrap();
+    (value, node)
+}
+
+#[test]
+fn test_fixtures_json_to_markdown() {
+    for (name, json, markdown) in FIXTURES {
+        let (_, node) = parse_fixture(json);
+        assert_eq!(to_markdown(&node), *markdown, "fixture: {name}");
+    }
+}
+
+#[test]
+fn test_fixtures_markdown_to_json() {
+    for (name, json, markdown) in FIXTURES {
+        let (value, node) = parse_fixture(json);
+        let parsed = from_markdown(markdown);
+        assert_eq!(parsed, node, "fixture: {name}");
+        assert_eq!(parsed.to_json(), value, "fixture: {name}");
+    }
+}
+
+#[test]
+fn test_fixtures_json_round_trip() {
+    for (name, json, _) in FIXTURES {
+        let (value, node) = parse_fixture(json);
+        assert_eq!(node.to_json(), value, "fixture: {name}");
+    }
+}
+
+#[test]
+fn test_markdown_round_trip_is_stable() {
+    for (name, _, markdown) in FIXTURES {
+        let once = to_markdown(&from_markdown(markdown));
+        let twice = to_markdown(&from_markdown(&once));
+        assert_eq!(once, twice, "fixture: {name}");
+    }
+}
+
+#[test]
+fn test_empty_document() {
+    let node = Node::from_json(&serde_json::json!({"type": "doc", "content": []})).unwrap();
+    assert_eq!(to_markdown(&node), "");
+    assert_eq!(from_markdown(""), node);
+}
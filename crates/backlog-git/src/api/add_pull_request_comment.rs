@@ -2,7 +2,7 @@ use crate::models::PullRequestComment;
 use backlog_api_core::{HttpMethod, IntoRequest};
 use backlog_core::{
     ProjectIdOrKey, RepositoryIdOrName,
-    identifier::{PullRequestNumber, UserId},
+    identifier::{AttachmentId, PullRequestNumber, UserId},
 };
 use serde::Serialize;
 
@@ -21,6 +21,8 @@ pub struct AddPullRequestCommentParams {
     pub content: String,
     #[form(array, name = "notifiedUserId")]
     pub notified_user_ids: Option<Vec<UserId>>,
+    #[form(array, name = "attachmentId")]
+    pub attachment_ids: Option<Vec<AttachmentId>>,
 }
 
 impl AddPullRequestCommentParams {
@@ -36,6 +38,7 @@ impl AddPullRequestCommentParams {
             number: number.into(),
             content: content.into(),
             notified_user_ids: None,
+            attachment_ids: None,
         }
     }
 
@@ -43,6 +46,12 @@ impl AddPullRequestCommentParams {
         self.notified_user_ids = Some(notified_user_ids);
         self
     }
+
+    /// Attach files uploaded with `POST /api/v2/space/attachment`
+    pub fn attachment_ids(mut self, attachment_ids: Vec<AttachmentId>) -> Self {
+        self.attachment_ids = Some(attachment_ids);
+        self
+    }
 }
 
 impl IntoRequest for AddPullRequestCommentParams {
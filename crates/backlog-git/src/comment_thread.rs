@@ -0,0 +1,343 @@
+//! Compact, threaded form of pull request comments.
+//!
+//! Backlog returns pull request comments as a flat list in which state
+//! changes (assignee, status, ...) are comments carrying a change log.
+//! [`build_threads`] turns that list into a conversation: change logs become
+//! readable transitions, stars and mentions are summarized, and comments that
+//! point at a `path:line` are grouped into one thread per code location,
+//! together with the diff hunk when the content quotes one.
+
+use crate::models::{ChangeLog, PullRequestComment};
+use backlog_core::identifier::Identifier;
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::Serialize;
+use std::sync::LazyLock;
+
+#[cfg(feature = "schemars")]
+use schemars::JsonSchema;
+
+/// `@name` mentions, not preceded by a word character (e.g. in e-mail addresses)
+static MENTION_REGEXP: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:^|[^\w@])@([A-Za-z0-9_][A-Za-z0-9_.\-]*[A-Za-z0-9_]|[A-Za-z0-9_])").unwrap()
+});
+
+/// `path/to/file.ext:42` or `path/to/file.ext#L42`
+static CODE_REFERENCE_REGEXP: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:^|[\s(`\[])((?:[\w.\-]+/)*[\w\-]+\.[A-Za-z0-9]+)(?::|#L)(\d+)").unwrap()
+});
+
+/// Location in the code a comment refers to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct CodeLocation {
+    pub path: String,
+    pub line: u32,
+}
+
+/// A comment with its change log rendered as transitions
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct CompactPrComment {
+    pub id: u32,
+    pub author: String,
+    pub created: DateTime<Utc>,
+    /// Set when the comment was edited after it was posted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edited: Option<DateTime<Utc>>,
+    /// Comment text without the quoted diff hunk, `None` for pure state changes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// Transitions such as "status: Open → Merged"
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub changes: Vec<String>,
+    /// Names of users who starred the comment
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub starred_by: Vec<String>,
+    /// Mentioned user names, without the `@`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub mentions: Vec<String>,
+    /// Diff hunk quoted in the content
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hunk: Option<String>,
+}
+
+/// Comments about the same code location, or the general conversation
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+pub struct PrCommentThread {
+    /// `None` for the general conversation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<CodeLocation>,
+    pub comments: Vec<CompactPrComment>,
+}
+
+/// Describe one change log entry as a transition
+///
+/// Status and assignee changes get wording of their own; other fields show
+/// their old and new value. Descriptions are too long to repeat.
+pub fn describe_change(change: &ChangeLog) -> String {
+    let old = change.original_value.as_deref().filter(|v| !v.is_empty());
+    let new = change.new_value.as_deref().filter(|v| !v.is_empty());
+    match (change.field.as_str(), old, new) {
+        ("status", Some(old), Some(new)) => format!("status: {old} → {new}"),
+        ("assignee", None, Some(new)) => format!("assigned to {new}"),
+        ("assignee", Some(old), None) => format!("unassigned {old}"),
+        ("assignee", Some(old), Some(new)) => format!("assignee: {old} → {new}"),
+        ("description", _, _) => "description updated".to_string(),
+        (field, None, Some(new)) => format!("{field} set to {new}"),
+        (field, Some(old), None) => format!("{field} removed (was {old})"),
+        (field, Some(old), Some(new)) => format!("{field}: {old} → {new}"),
+        (field, None, None) => format!("{field} changed"),
+    }
+}
+
+/// Mentioned names in order of first appearance
+pub fn mentions(content: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for captures in MENTION_REGEXP.captures_iter(content) {
+        let name = &captures[1];
+        if !names.iter().any(|known| known == name) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+/// The first `path:line` reference in the content
+pub fn code_location(content: &str) -> Option<CodeLocation> {
+    let captures = CODE_REFERENCE_REGEXP.captures(content)?;
+    Some(CodeLocation {
+        path: captures[1].to_string(),
+        line: captures[2].parse().ok()?,
+    })
+}
+
+/// Split a quoted diff hunk from the rest of the content
+///
+/// The hunk is either a fenced `diff` block, or lines starting at an `@@`
+/// header up to the first line that is not part of a unified diff. Returns
+/// the remaining text and the hunk, if any.
+pub fn split_diff_hunk(content: &str) -> (String, Option<String>) {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut found = None;
+    for (start, line) in lines.iter().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```diff") || trimmed.starts_with("~~~diff") {
+            let fence = &trimmed[..3];
+            let end = lines[start + 1..]
+                .iter()
+                .position(|line| line.trim_start().starts_with(fence))
+                .map_or(lines.len(), |offset| start + 1 + offset);
+            // The range covers both fences; the hunk excludes them
+            found = Some((start, (end + 1).min(lines.len()), start + 1, end));
+            break;
+        }
+        if line.starts_with("@@ ") {
+            let end = lines[start + 1..]
+                .iter()
+                .position(|line| !(line.starts_with([' ', '+', '-', '\\']) || line.is_empty()))
+                .map_or(lines.len(), |offset| start + 1 + offset);
+            found = Some((start, end, start, end));
+            break;
+        }
+    }
+
+    let Some((cut_start, cut_end, hunk_start, hunk_end)) = found else {
+        return (content.to_string(), None);
+    };
+    let mut hunk = &lines[hunk_start..hunk_end];
+    while let Some((last, rest)) = hunk.split_last()
+        && last.trim().is_empty()
+    {
+        hunk = rest;
+    }
+    let remaining = [&lines[..cut_start], &lines[cut_end..]].concat().join("\n");
+    let hunk = (!hunk.is_empty()).then(|| hunk.join("\n"));
+    (remaining, hunk)
+}
+
+impl From<&PullRequestComment> for CompactPrComment {
+    fn from(comment: &PullRequestComment) -> Self {
+        let (text, hunk) = split_diff_hunk(comment.content.trim());
+        let text = text.trim();
+        Self {
+            id: comment.id.value(),
+            author: comment.created_user.name.clone(),
+            created: comment.created,
+            edited: (comment.updated > comment.created).then_some(comment.updated),
+            content: (!text.is_empty()).then(|| text.to_string()),
+            changes: comment.change_log.iter().map(describe_change).collect(),
+            starred_by: comment
+                .stars
+                .iter()
+                .map(|star| star.presenter.name.clone())
+                .collect(),
+            mentions: mentions(text),
+            hunk,
+        }
+    }
+}
+
+/// Group comments into the general conversation and one thread per code location
+///
+/// Comments are ordered oldest first in every thread. The general
+/// conversation comes first, followed by code threads in the order they were
+/// started. It is omitted when every comment refers to code.
+pub fn build_threads(comments: &[PullRequestComment]) -> Vec<PrCommentThread> {
+    let mut sorted: Vec<&PullRequestComment> = comments.iter().collect();
+    sorted.sort_by_key(|comment| (comment.created, comment.id.value()));
+
+    let mut general = PrCommentThread {
+        location: None,
+        comments: Vec::new(),
+    };
+    let mut code_threads: Vec<PrCommentThread> = Vec::new();
+    for comment in sorted {
+        let compact = CompactPrComment::from(comment);
+        match code_location(&comment.content) {
+            Some(location) => {
+                match code_threads
+                    .iter_mut()
+                    .find(|thread| thread.location.as_ref() == Some(&location))
+                {
+                    Some(thread) => thread.comments.push(compact),
+                    None => code_threads.push(PrCommentThread {
+                        location: Some(location),
+                        comments: vec![compact],
+                    }),
+                }
+            }
+            None => general.comments.push(compact),
+        }
+    }
+
+    let mut threads = Vec::with_capacity(code_threads.len() + 1);
+    if !general.comments.is_empty() {
+        threads.push(general);
+    }
+    threads.extend(code_threads);
+    threads
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(field: &str, old: Option<&str>, new: Option<&str>) -> ChangeLog {
+        ChangeLog {
+            field: field.to_string(),
+            original_value: old.map(str::to_string),
+            new_value: new.map(str::to_string),
+        }
+    }
+
+    fn comment(
+        id: u32,
+        minute: u32,
+        content: &str,
+        change_log: Vec<ChangeLog>,
+    ) -> PullRequestComment {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "content": content,
+            "changeLog": change_log,
+            "createdUser": {"id": 1, "userId": "alice", "name": "Alice", "roleType": 1, "mailAddress": "alice@example.com"},
+            "created": format!("2024-01-01T10:{minute:02}:00Z"),
+            "updated": format!("2024-01-01T10:{minute:02}:00Z"),
+            "stars": [],
+            "notifications": []
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_describe_change() {
+        assert_eq!(
+            describe_change(&change("status", Some("Open"), Some("Merged"))),
+            "status: Open → Merged"
+        );
+        assert_eq!(
+            describe_change(&change("assignee", None, Some("Bob"))),
+            "assigned to Bob"
+        );
+        assert_eq!(
+            describe_change(&change("assignee", Some("Bob"), Some(""))),
+            "unassigned Bob"
+        );
+        assert_eq!(
+            describe_change(&change("description", Some("a"), Some("b"))),
+            "description updated"
+        );
+        assert_eq!(
+            describe_change(&change("base", Some("main"), Some("develop"))),
+            "base: main → develop"
+        );
+    }
+
+    #[test]
+    fn test_mentions_skip_email_addresses() {
+        assert_eq!(
+            mentions("@bob please check, cc @carol. Mail me at alice@example.com @bob"),
+            vec!["bob", "carol"]
+        );
+    }
+
+    #[test]
+    fn test_code_location_and_hunk() {
+        let content = "In src/api/client.rs:42 this leaks:\n\n```diff\n@@ -40,3 +40,4 @@\n let a = 1;\n+let b = a;\n```\nThoughts?";
+        assert_eq!(
+            code_location(content),
+            Some(CodeLocation {
+                path: "src/api/client.rs".to_string(),
+                line: 42
+            })
+        );
+        assert_eq!(
+            split_diff_hunk(content),
+            (
+                "In src/api/client.rs:42 this leaks:\n\nThoughts?".to_string(),
+                Some("@@ -40,3 +40,4 @@\n let a = 1;\n+let b = a;".to_string())
+            )
+        );
+        assert_eq!(
+            code_location("See README.md#L7").map(|location| location.line),
+            Some(7)
+        );
+        assert_eq!(
+            split_diff_hunk("@@ -1 +1 @@\n-old\n+new\n\nAfter").1,
+            Some("@@ -1 +1 @@\n-old\n+new".to_string())
+        );
+        assert_eq!(code_location("Released at 10:30 in v1.2"), None);
+    }
+
+    #[test]
+    fn test_build_threads_groups_by_location() {
+        let comments = vec![
+            comment(3, 3, "src/lib.rs:10 same here", vec![]),
+            comment(1, 1, "src/lib.rs:10 why unwrap?", vec![]),
+            comment(
+                2,
+                2,
+                "",
+                vec![change("status", Some("Open"), Some("Merged"))],
+            ),
+            comment(4, 4, "main.rs:3 nit", vec![]),
+        ];
+        let threads = build_threads(&comments);
+
+        assert_eq!(threads.len(), 3);
+        assert_eq!(threads[0].location, None);
+        assert_eq!(threads[0].comments[0].content, None);
+        assert_eq!(
+            threads[0].comments[0].changes,
+            vec!["status: Open → Merged"]
+        );
+        let ids: Vec<u32> = threads[1].comments.iter().map(|c| c.id).collect();
+        assert_eq!(ids, vec![1, 3]);
+        assert_eq!(threads[2].location.as_ref().unwrap().path, "main.rs");
+    }
+}
@@ -1,5 +1,7 @@
 pub mod api;
+pub mod comment_thread;
 pub mod models;
 
 pub use api::*;
+pub use comment_thread::{CodeLocation, CompactPrComment, PrCommentThread};
 pub use models::*;
@@ -91,7 +91,9 @@ pub struct PullRequest {
     /// The timestamp of when the pull request was last updated.
     pub updated: Option<DateTime<Utc>>,
     // attachments: Vec<Attachment>, // Define Attachment if needed
-    // stars: Vec<Star>, // Define Star if needed
+    /// Stars given to the pull request.
+    #[serde(default)]
+    pub stars: Vec<Star>,
 }
 
 /// Represents the status of a Pull Request.
@@ -1,8 +1,8 @@
 #[cfg(feature = "writable")]
 mod writable_tests {
     use backlog_core::identifier::{
-        Identifier, IssueId, PullRequestAttachmentId, PullRequestCommentId, PullRequestNumber,
-        UserId,
+        AttachmentId, Identifier, IssueId, PullRequestAttachmentId, PullRequestCommentId,
+        PullRequestNumber, UserId,
     };
     use backlog_core::{ProjectIdOrKey, RepositoryIdOrName};
     use backlog_git::api::{
@@ -174,6 +174,22 @@ mod writable_tests {
         assert_eq!(comment.content, "Test comment");
     }
 
+    #[test]
+    fn test_add_pull_request_comment_form_with_attachments() {
+        let params = AddPullRequestCommentParams::new(
+            ProjectIdOrKey::from_str("TEST").expect("TEST is a valid project key"),
+            RepositoryIdOrName::from_str("test-repo")
+                .expect("test-repo is a valid repository name"),
+            PullRequestNumber::new(1),
+            "See attached log",
+        )
+        .attachment_ids(vec![AttachmentId::new(5), AttachmentId::new(6)]);
+
+        let form: Vec<(String, String)> = (&params).into();
+        assert!(form.contains(&("attachmentId[]".to_string(), "5".to_string())));
+        assert!(form.contains(&("attachmentId[]".to_string(), "6".to_string())));
+    }
+
     #[tokio::test]
     async fn test_update_pull_request_success() {
         let mock_server = MockServer::start().await;
@@ -1,5 +1,11 @@
+use crate::models::IssueTreeNode;
 use backlog_api_core::Result;
+use backlog_core::{
+    IssueIdOrKey,
+    identifier::{Identifier, IssueId},
+};
 use client::{Client, DownloadedFile};
+use std::collections::HashSet;
 
 #[cfg(feature = "writable")]
 use super::{
@@ -15,8 +21,9 @@ use super::{
     UpdateCommentResponse, UpdateIssueResponse,
 };
 use super::{
-    CountCommentParams, CountIssueParams, GetAttachmentFileParams, GetAttachmentListParams,
-    GetCommentListParams, GetCommentNotificationsParams, GetCommentParams, GetIssueListParams,
+    CommentOrder, CountCommentParams, CountIssueParams, GetAttachmentFileParams,
+    GetAttachmentListParams, GetCommentListParams, GetCommentListParamsBuilder,
+    GetCommentNotificationsParams, GetCommentParams, GetIssueListParams, GetIssueListParamsBuilder,
     GetIssueParams, GetParticipantListParams, GetRecentlyViewedIssuesParams,
     GetSharedFileListParams,
 };
@@ -26,6 +33,15 @@ use super::{
     GetParticipantListResponse, GetRecentlyViewedIssuesResponse, GetSharedFileListResponse,
 };
 
+/// Maximum number of comments the API returns per request.
+const COMMENT_PAGE_SIZE: u8 = 100;
+
+/// Maximum number of issues the API returns per request.
+const ISSUE_PAGE_SIZE: u32 = 100;
+
+/// Number of parent IDs sent per request when looking up child issues.
+const PARENT_BATCH_SIZE: usize = 20;
+
 pub struct IssueApi(Client);
 
 impl IssueApi {
@@ -43,6 +59,102 @@ impl IssueApi {
         self.0.execute(params).await
     }
 
+    /// Get every issue matching the parameters.
+    ///
+    /// Pages through the issue list with `offset`; any `offset` or `count`
+    /// already set on `params` is replaced.
+    pub async fn get_all_issues(
+        &self,
+        mut params: GetIssueListParams,
+    ) -> Result<GetIssueListResponse> {
+        let mut issues: GetIssueListResponse = Vec::new();
+        params.count = Some(ISSUE_PAGE_SIZE);
+
+        loop {
+            params.offset = Some(issues.len() as u32);
+            let page = self.get_issue_list(params.clone()).await?;
+            let page_len = page.len();
+            issues.extend(page);
+
+            if page_len < ISSUE_PAGE_SIZE as usize {
+                return Ok(issues);
+            }
+        }
+    }
+
+    /// Get the parent/child trees of the issues matching the parameters.
+    ///
+    /// Child issues that do not match the parameters themselves (e.g. ones
+    /// outside a milestone their parent is in) are fetched as well, so every
+    /// subtree is complete.
+    pub async fn get_issue_tree(&self, params: GetIssueListParams) -> Result<Vec<IssueTreeNode>> {
+        let mut issues = self.get_all_issues(params).await?;
+        let roots: Vec<IssueId> = issues.iter().map(|issue| issue.id).collect();
+        self.collect_descendants(roots, &mut issues).await?;
+        Ok(IssueTreeNode::build(issues))
+    }
+
+    /// Get an issue with all of its descendants.
+    pub async fn get_issue_subtree(
+        &self,
+        issue_id_or_key: impl Into<IssueIdOrKey>,
+    ) -> Result<IssueTreeNode> {
+        let root = self
+            .get_issue(GetIssueParams::new(issue_id_or_key.into()))
+            .await?;
+        self.get_issue_descendants(root).await
+    }
+
+    /// Get the subtree below an issue that was already fetched.
+    ///
+    /// Lets callers check the root issue, e.g. its project, before any of
+    /// its descendants are requested.
+    pub async fn get_issue_descendants(&self, root: GetIssueResponse) -> Result<IssueTreeNode> {
+        let root_id = root.id;
+        let mut issues = vec![root];
+        self.collect_descendants(vec![root_id], &mut issues).await?;
+
+        // The root may have a parent of its own; only keep its subtree.
+        let trees = IssueTreeNode::build(issues);
+        trees
+            .iter()
+            .find_map(|tree| tree.find(root_id))
+            .cloned()
+            .ok_or_else(|| {
+                backlog_api_core::Error::InconsistentResponse(format!(
+                    "issue {} is missing from its own subtree",
+                    root_id.value()
+                ))
+            })
+    }
+
+    /// Fetch child issues level by level until no new issues are found.
+    async fn collect_descendants(
+        &self,
+        mut parents: Vec<IssueId>,
+        issues: &mut GetIssueListResponse,
+    ) -> Result<()> {
+        let mut seen: HashSet<IssueId> = issues.iter().map(|issue| issue.id).collect();
+
+        while !parents.is_empty() {
+            let mut next = Vec::new();
+            for batch in parents.chunks(PARENT_BATCH_SIZE) {
+                let params = GetIssueListParamsBuilder::default()
+                    .parent_issue_id(batch.to_vec())
+                    .build()?;
+                for child in self.get_all_issues(params).await? {
+                    if seen.insert(child.id) {
+                        next.push(child.id);
+                        issues.push(child);
+                    }
+                }
+            }
+            parents = next;
+        }
+
+        Ok(())
+    }
+
     /// Count issues based on the provided parameters.
     pub async fn count_issue(&self, params: CountIssueParams) -> Result<CountIssueResponse> {
         self.0.execute(params).await
@@ -113,6 +225,37 @@ impl IssueApi {
         self.0.execute(params).await
     }
 
+    /// Get every comment on an issue, oldest first.
+    ///
+    /// Pages through the comment list with `minId` until a short page is
+    /// returned.
+    pub async fn get_all_comments(
+        &self,
+        issue_id_or_key: impl Into<IssueIdOrKey>,
+    ) -> Result<GetCommentListResponse> {
+        let issue_id_or_key = issue_id_or_key.into();
+        let mut comments: GetCommentListResponse = Vec::new();
+
+        loop {
+            let mut builder = GetCommentListParamsBuilder::default();
+            builder
+                .issue_id_or_key(issue_id_or_key.clone())
+                .count(COMMENT_PAGE_SIZE)
+                .order(CommentOrder::Asc);
+            if let Some(last) = comments.last() {
+                builder.min_id(u64::from(last.id.value()));
+            }
+
+            let page = self.get_comment_list(builder.build()?).await?;
+            let page_len = page.len();
+            comments.extend(page);
+
+            if page_len < usize::from(COMMENT_PAGE_SIZE) {
+                return Ok(comments);
+            }
+        }
+    }
+
     /// Count comments for an issue by its ID or key.
     pub async fn count_comment(&self, params: CountCommentParams) -> Result<CountCommentResponse> {
         self.0.execute(params).await
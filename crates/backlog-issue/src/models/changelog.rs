@@ -1,5 +1,6 @@
 use backlog_core::identifier::{AttachmentId, CustomFieldId};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 // Conditionally import and derive JsonSchema
 #[cfg(feature = "schemars")]
@@ -32,6 +33,33 @@ pub struct ChangeLogEntry {
     pub notification_info: Option<serde_json::Value>,
 }
 
+/// Renders the change for people, e.g. `status: Open → In Progress`.
+///
+/// Attachments and shared files are shown as `+ name` / `- name`, and long
+/// text fields only report that they changed.
+impl fmt::Display for ChangeLogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let original = self.original_value.as_deref().filter(|v| !v.is_empty());
+        let new = self.new_value.as_deref().filter(|v| !v.is_empty());
+
+        match (self.field.as_str(), original, new) {
+            ("description" | "content", _, _) => write!(f, "{}: (updated)", self.field),
+            ("attachment" | "sharedFile" | "file", None, Some(new)) => {
+                write!(f, "{}: + {new}", self.field)
+            }
+            ("attachment" | "sharedFile" | "file", Some(original), None) => {
+                write!(f, "{}: - {original}", self.field)
+            }
+            (field, original, new) => write!(
+                f,
+                "{field}: {} → {}",
+                original.unwrap_or("(none)"),
+                new.unwrap_or("(none)")
+            ),
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "schemars", derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
@@ -47,3 +75,55 @@ pub struct CustomFieldInfo {
     pub id: CustomFieldId,
     pub type_id: CustomFieldTypeId,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(field: &str, original: Option<&str>, new: Option<&str>) -> ChangeLogEntry {
+        ChangeLogEntry {
+            field: field.to_string(),
+            new_value: new.map(str::to_string),
+            original_value: original.map(str::to_string),
+            attachment_info: None,
+            attribute_info: None,
+            notification_info: None,
+        }
+    }
+
+    #[test]
+    fn test_display_value_change() {
+        let change = entry("status", Some("Open"), Some("In Progress"));
+        assert_eq!(change.to_string(), "status: Open → In Progress");
+    }
+
+    #[test]
+    fn test_display_missing_values() {
+        assert_eq!(
+            entry("assigner", None, Some("john")).to_string(),
+            "assigner: (none) → john"
+        );
+        assert_eq!(
+            entry("limitDate", Some("2024-01-31"), Some("")).to_string(),
+            "limitDate: 2024-01-31 → (none)"
+        );
+    }
+
+    #[test]
+    fn test_display_attachments() {
+        assert_eq!(
+            entry("attachment", None, Some("spec.pdf")).to_string(),
+            "attachment: + spec.pdf"
+        );
+        assert_eq!(
+            entry("attachment", Some("old.png"), None).to_string(),
+            "attachment: - old.png"
+        );
+    }
+
+    #[test]
+    fn test_display_description() {
+        let change = entry("description", Some("long\ntext"), Some("longer\ntext"));
+        assert_eq!(change.to_string(), "description: (updated)");
+    }
+}
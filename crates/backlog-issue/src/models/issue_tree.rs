@@ -0,0 +1,188 @@
+use crate::models::Issue;
+use backlog_core::identifier::{Identifier, IssueId};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+pub use backlog_core::CLOSED_STATUS_ID;
+
+/// An issue together with its child issues.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueTreeNode {
+    pub issue: Issue,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<IssueTreeNode>,
+}
+
+/// Totals of an issue and all of its descendants.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueRollup {
+    /// Number of issues in the subtree, including its root.
+    pub issues: u32,
+    /// Number of those issues in the built-in "Closed" status.
+    pub closed: u32,
+    pub estimated_hours: f64,
+    pub actual_hours: f64,
+}
+
+impl IssueRollup {
+    /// Share of closed issues, from 0.0 to 1.0.
+    pub fn progress(&self) -> f64 {
+        if self.issues == 0 {
+            0.0
+        } else {
+            f64::from(self.closed) / f64::from(self.issues)
+        }
+    }
+}
+
+impl IssueTreeNode {
+    /// Arrange a flat list of issues into parent/child trees.
+    ///
+    /// Issues whose parent is not in the list become roots, so a milestone
+    /// can be shown even when some parents are outside it. Input order is
+    /// kept among roots and among siblings.
+    pub fn build(issues: Vec<Issue>) -> Vec<IssueTreeNode> {
+        let ids: HashSet<u32> = issues.iter().map(|issue| issue.id.value()).collect();
+        let mut children: HashMap<u32, Vec<Issue>> = HashMap::new();
+        let mut roots = Vec::new();
+
+        for issue in issues {
+            match parent_id(&issue) {
+                Some(parent) if parent != issue.id.value() && ids.contains(&parent) => {
+                    children.entry(parent).or_default().push(issue)
+                }
+                _ => roots.push(issue),
+            }
+        }
+
+        let mut trees: Vec<IssueTreeNode> = roots
+            .into_iter()
+            .map(|issue| Self::attach(issue, &mut children))
+            .collect();
+
+        // Whatever is left forms a parent cycle; break it at its first issue
+        // so no issue is dropped.
+        while let Some(&parent) = children.keys().min() {
+            let issues = children.remove(&parent).unwrap_or_default();
+            trees.extend(
+                issues
+                    .into_iter()
+                    .map(|issue| Self::attach(issue, &mut children)),
+            );
+        }
+
+        trees
+    }
+
+    fn attach(issue: Issue, children: &mut HashMap<u32, Vec<Issue>>) -> IssueTreeNode {
+        let kids = children.remove(&issue.id.value()).unwrap_or_default();
+        IssueTreeNode {
+            issue,
+            children: kids
+                .into_iter()
+                .map(|child| Self::attach(child, children))
+                .collect(),
+        }
+    }
+
+    /// Sum status and hours over this issue and all of its descendants.
+    pub fn rollup(&self) -> IssueRollup {
+        let mut total = IssueRollup {
+            issues: 1,
+            closed: u32::from(self.issue.status.id.value() == CLOSED_STATUS_ID),
+            estimated_hours: self.issue.estimated_hours.unwrap_or_default(),
+            actual_hours: self.issue.actual_hours.unwrap_or_default(),
+        };
+        for child in &self.children {
+            let sub = child.rollup();
+            total.issues += sub.issues;
+            total.closed += sub.closed;
+            total.estimated_hours += sub.estimated_hours;
+            total.actual_hours += sub.actual_hours;
+        }
+        total
+    }
+
+    /// Find the node of an issue in this subtree.
+    pub fn find(&self, id: IssueId) -> Option<&IssueTreeNode> {
+        if self.issue.id == id {
+            return Some(self);
+        }
+        self.children.iter().find_map(|child| child.find(id))
+    }
+}
+
+fn parent_id(issue: &Issue) -> Option<u32> {
+    issue
+        .parent_issue_id
+        .and_then(|parent| u32::try_from(parent).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn issue(id: u32, parent: Option<u32>, status: u32, estimated: Option<f64>) -> Issue {
+        serde_json::from_value(json!({
+            "id": id, "projectId": 1, "issueKey": format!("BLG-{id}"), "keyId": id,
+            "summary": format!("Issue {id}"), "description": "",
+            "issueType": {"id": 1, "projectId": 1, "name": "Task", "color": "#7ea800", "displayOrder": 0},
+            "status": {"id": status, "projectId": 1, "name": "Status", "color": "#ed8077", "displayOrder": 0},
+            "category": [], "versions": [], "milestone": [],
+            "estimatedHours": estimated, "actualHours": estimated.map(|h| h / 2.0),
+            "parentIssueId": parent,
+            "createdUser": {"id": 1, "userId": "john", "name": "John", "roleType": 1, "mailAddress": "john@example.com"},
+            "created": "2024-03-14T06:35:39Z",
+            "updated": "2024-04-13T06:35:39Z"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_build_nests_children_under_parents() {
+        let trees = IssueTreeNode::build(vec![
+            issue(2, Some(1), 1, None),
+            issue(1, None, 1, None),
+            issue(3, Some(1), 1, None),
+            issue(4, Some(99), 1, None),
+        ]);
+
+        let roots: Vec<u32> = trees.iter().map(|t| t.issue.id.value()).collect();
+        assert_eq!(roots, vec![1, 4]);
+        let children: Vec<u32> = trees[0]
+            .children
+            .iter()
+            .map(|t| t.issue.id.value())
+            .collect();
+        assert_eq!(children, vec![2, 3]);
+        assert!(trees[0].find(IssueId::new(3)).is_some());
+        assert!(trees[1].find(IssueId::new(3)).is_none());
+    }
+
+    #[test]
+    fn test_build_keeps_issues_in_cycles() {
+        let trees =
+            IssueTreeNode::build(vec![issue(1, Some(2), 1, None), issue(2, Some(1), 1, None)]);
+        let total: u32 = trees.iter().map(|t| t.rollup().issues).sum();
+        assert_eq!(total, 2);
+    }
+
+    #[test]
+    fn test_rollup_sums_descendants() {
+        let trees = IssueTreeNode::build(vec![
+            issue(1, None, 2, Some(4.0)),
+            issue(2, Some(1), CLOSED_STATUS_ID, Some(2.0)),
+            issue(3, Some(1), 1, None),
+        ]);
+
+        let rollup = trees[0].rollup();
+        assert_eq!(rollup.issues, 3);
+        assert_eq!(rollup.closed, 1);
+        assert_eq!(rollup.estimated_hours, 6.0);
+        assert_eq!(rollup.actual_hours, 3.0);
+        assert!((rollup.progress() - 1.0 / 3.0).abs() < f64::EPSILON);
+    }
+}
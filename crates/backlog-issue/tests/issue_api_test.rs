@@ -169,6 +169,97 @@ async fn test_get_comment_list_with_params() {
     assert_eq!(comments.len(), 1);
 }
 
+#[tokio::test]
+async fn test_get_all_comments_pages_with_min_id() {
+    let mock_server = wiremock::MockServer::start().await;
+    let issue_api = setup_issue_api(&mock_server).await;
+    let issue_key = "TESTKEY-1";
+
+    let first_page: Vec<Comment> = (1..=100)
+        .map(|id| create_mock_comment(id, "comment", 101, "alice"))
+        .collect();
+    let second_page = vec![
+        create_mock_comment(101, "comment 101", 102, "bob"),
+        create_mock_comment(102, "comment 102", 102, "bob"),
+    ];
+
+    Mock::given(method("GET"))
+        .and(path(format!("/api/v2/issues/{issue_key}/comments")))
+        .and(query_param("count", "100"))
+        .and(query_param("order", "asc"))
+        .and(wiremock::matchers::query_param_is_missing("minId"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&first_page))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/api/v2/issues/{issue_key}/comments")))
+        .and(query_param("minId", "100"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&second_page))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let comments = issue_api
+        .get_all_comments(IssueKey::from_str(issue_key).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(comments.len(), 102);
+    assert_eq!(comments[0].id.value(), 1);
+    assert_eq!(comments[101].id.value(), 102);
+}
+
+fn tree_issue(id: u32, parent: Option<u32>, estimated: f64) -> serde_json::Value {
+    json!({
+        "id": id, "projectId": 1, "issueKey": format!("BLG-{id}"), "keyId": id,
+        "summary": format!("Issue {id}"), "description": "",
+        "issueType": {"id": 1, "projectId": 1, "name": "Task", "color": "#7ea800", "displayOrder": 0},
+        "status": {"id": 1, "projectId": 1, "name": "Open", "color": "#ed8077", "displayOrder": 1000},
+        "category": [], "versions": [], "milestone": [],
+        "estimatedHours": estimated, "parentIssueId": parent,
+        "createdUser": {"id": 1, "userId": "john", "name": "John Doe", "roleType": 1, "mailAddress": "john@example.com"},
+        "created": "2024-03-14T06:35:39Z",
+        "updated": "2024-04-13T06:35:39Z"
+    })
+}
+
+#[tokio::test]
+async fn test_get_issue_subtree_fetches_descendants() {
+    let mock_server = wiremock::MockServer::start().await;
+    let issue_api = setup_issue_api(&mock_server).await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v2/issues/BLG-1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(tree_issue(1, None, 1.0)))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/issues"))
+        .and(query_param("parentIssueId[]", "1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+            tree_issue(2, Some(1), 2.0),
+            tree_issue(3, Some(1), 3.0)
+        ])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/issues"))
+        .and(query_param("parentIssueId[]", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let tree = issue_api
+        .get_issue_subtree(IssueKey::from_str("BLG-1").unwrap())
+        .await
+        .unwrap();
+    assert_eq!(tree.issue.id.value(), 1);
+    assert_eq!(tree.children.len(), 2);
+    assert_eq!(tree.rollup().estimated_hours, 6.0);
+}
+
 #[tokio::test]
 async fn test_get_attachment_list_success() {
     let mock_server = wiremock::MockServer::start().await;
@@ -0,0 +1,157 @@
+use super::{AttachmentInfo, SpaceApi, UploadAttachmentParams};
+use backlog_api_core::{Error as ApiError, Result};
+use backlog_core::identifier::AttachmentId;
+use std::path::PathBuf;
+
+/// Default maximum size of a single attachment (100 MiB)
+pub const MAX_ATTACHMENT_SIZE: u64 = 100 * 1024 * 1024;
+
+/// A file to upload as a space attachment
+#[derive(Debug, Clone)]
+pub enum AttachmentSource {
+    /// A file on the local file system
+    Path(PathBuf),
+    /// In-memory content, e.g. decoded from base64
+    Bytes { file_name: String, content: Vec<u8> },
+}
+
+impl AttachmentSource {
+    /// The file name the attachment is uploaded under
+    pub fn file_name(&self) -> String {
+        match self {
+            Self::Path(path) => path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string()),
+            Self::Bytes { file_name, .. } => file_name.clone(),
+        }
+    }
+
+    fn size(&self) -> Result<u64> {
+        match self {
+            Self::Path(path) => std::fs::metadata(path)
+                .map(|metadata| metadata.len())
+                .map_err(|e| ApiError::FileRead {
+                    path: path.display().to_string(),
+                    message: e.to_string(),
+                }),
+            Self::Bytes { content, .. } => Ok(content.len() as u64),
+        }
+    }
+
+    fn into_params(self) -> UploadAttachmentParams {
+        match self {
+            Self::Path(path) => UploadAttachmentParams::new(path),
+            Self::Bytes { file_name, content } => {
+                UploadAttachmentParams::from_bytes(file_name, content)
+            }
+        }
+    }
+}
+
+/// Uploads files as space attachments for a follow-up call that links them
+///
+/// Backlog keeps uploaded attachments only until they are linked to an issue,
+/// comment or pull request. If the follow-up call fails, the uploads are
+/// orphaned; [`AttachmentUpload::failure_report`] describes them so callers
+/// can tell the user what happened.
+///
+/// ```no_run
+/// use backlog_space::api::{AttachmentSource, AttachmentUpload, SpaceApi};
+///
+/// # async fn example(space: SpaceApi) -> Result<(), Box<dyn std::error::Error>> {
+/// let mut upload = AttachmentUpload::new();
+/// upload
+///     .upload_all(&space, vec![AttachmentSource::Path("screenshot.png".into())])
+///     .await?;
+/// let attachment_ids = upload.ids();
+/// // ... pass attachment_ids to add_issue / add_comment and on failure:
+/// // eprintln!("{}", upload.failure_report(&error));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AttachmentUpload {
+    max_size: u64,
+    uploaded: Vec<AttachmentInfo>,
+}
+
+impl Default for AttachmentUpload {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AttachmentUpload {
+    pub fn new() -> Self {
+        Self {
+            max_size: MAX_ATTACHMENT_SIZE,
+            uploaded: Vec::new(),
+        }
+    }
+
+    /// Set the maximum size of a single file in bytes
+    pub fn max_size(mut self, max_size: u64) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Check the size of every file, then upload them in order
+    ///
+    /// Nothing is uploaded if any file is missing or too large. If an upload
+    /// fails part way, the files uploaded so far remain in [`Self::uploaded`].
+    pub async fn upload_all(
+        &mut self,
+        api: &SpaceApi,
+        sources: Vec<AttachmentSource>,
+    ) -> Result<&[AttachmentInfo]> {
+        for source in &sources {
+            let size = source.size()?;
+            if size > self.max_size {
+                return Err(ApiError::AttachmentTooLarge {
+                    name: source.file_name(),
+                    size,
+                    limit: self.max_size,
+                });
+            }
+        }
+
+        let start = self.uploaded.len();
+        for source in sources {
+            let attachment = api.upload_attachment(source.into_params()).await?;
+            self.uploaded.push(attachment);
+        }
+        Ok(&self.uploaded[start..])
+    }
+
+    /// Attachments uploaded so far
+    pub fn uploaded(&self) -> &[AttachmentInfo] {
+        &self.uploaded
+    }
+
+    /// IDs of the uploaded attachments, for `attachmentId[]` parameters
+    pub fn ids(&self) -> Vec<AttachmentId> {
+        self.uploaded
+            .iter()
+            .map(|attachment| AttachmentId::new(attachment.id))
+            .collect()
+    }
+
+    /// Error message for a failed follow-up call, listing the orphaned uploads
+    pub fn failure_report(&self, error: &impl std::fmt::Display) -> String {
+        if self.uploaded.is_empty() {
+            return error.to_string();
+        }
+        let files: Vec<String> = self
+            .uploaded
+            .iter()
+            .map(|attachment| format!("{} (attachment ID {})", attachment.name, attachment.id))
+            .collect();
+        format!(
+            "{error}. The uploaded file(s) {} were not linked to anything; \
+             Backlog discards unlinked attachments automatically, so retry with the \
+             same attachment IDs soon or upload the files again.",
+            files.join(", ")
+        )
+    }
+}
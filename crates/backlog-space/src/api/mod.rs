@@ -1,3 +1,5 @@
+#[cfg(feature = "writable")]
+mod attachment_upload;
 pub mod get_licence;
 pub mod get_space;
 pub mod get_space_disk_usage;
@@ -12,6 +14,9 @@ mod upload_attachment;
 
 pub use space_api::SpaceApi;
 
+#[cfg(feature = "writable")]
+pub use attachment_upload::{AttachmentSource, AttachmentUpload, MAX_ATTACHMENT_SIZE};
+
 pub use get_licence::{GetLicenceParams, GetLicenceResponse};
 pub use get_space::{GetSpaceParams, GetSpaceResponse};
 pub use get_space_disk_usage::{
@@ -25,4 +30,4 @@ pub use update_space_notification::{
     UpdateSpaceNotificationParams, UpdateSpaceNotificationResponse,
 };
 #[cfg(feature = "writable")]
-pub use upload_attachment::{UploadAttachmentParams, UploadAttachmentResponse};
+pub use upload_attachment::{AttachmentInfo, UploadAttachmentParams, UploadAttachmentResponse};
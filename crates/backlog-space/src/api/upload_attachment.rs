@@ -14,16 +14,40 @@ pub struct AttachmentInfo {
 }
 
 /// Parameters for uploading an attachment
+///
+/// The file is read from `file_path`, or taken from memory when created with
+/// [`UploadAttachmentParams::from_bytes`].
 #[cfg(feature = "writable")]
 #[derive(Debug, Clone)]
 pub struct UploadAttachmentParams {
     pub file_path: PathBuf,
+    pub content: Option<Vec<u8>>,
+    pub content_type: Option<String>,
 }
 
 #[cfg(feature = "writable")]
 impl UploadAttachmentParams {
     pub fn new(file_path: PathBuf) -> Self {
-        Self { file_path }
+        Self {
+            file_path,
+            content: None,
+            content_type: None,
+        }
+    }
+
+    /// Upload in-memory content under the given file name
+    pub fn from_bytes(file_name: impl Into<String>, content: Vec<u8>) -> Self {
+        Self {
+            file_path: PathBuf::from(file_name.into()),
+            content: Some(content),
+            content_type: None,
+        }
+    }
+
+    /// Override the detected MIME type
+    pub fn content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_type = Some(content_type.into());
+        self
     }
 }
 
@@ -36,4 +60,12 @@ impl IntoUploadRequest for UploadAttachmentParams {
     fn file_path(&self) -> &PathBuf {
         &self.file_path
     }
+
+    fn file_content(&self) -> Option<&[u8]> {
+        self.content.as_deref()
+    }
+
+    fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
 }
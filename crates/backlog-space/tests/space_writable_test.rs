@@ -1,6 +1,11 @@
 #[cfg(feature = "writable")]
 mod writable_tests {
-    use backlog_space::api::{SpaceApi, UpdateSpaceNotificationParams, UploadAttachmentParams};
+    use backlog_api_core::Error as ApiError;
+    use backlog_core::identifier::AttachmentId;
+    use backlog_space::api::{
+        AttachmentSource, AttachmentUpload, SpaceApi, UpdateSpaceNotificationParams,
+        UploadAttachmentParams,
+    };
     use client::test_utils::setup_client;
     use std::fs;
     use std::path::PathBuf;
@@ -249,4 +254,92 @@ mod writable_tests {
             backlog_api_core::Error::HttpStatus { status: 400, .. }
         ));
     }
+
+    fn bytes_source(file_name: &str, len: usize) -> AttachmentSource {
+        AttachmentSource::Bytes {
+            file_name: file_name.to_string(),
+            content: vec![b'x'; len],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upload_all_checks_sizes_before_uploading() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v2/space/attachment"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": 1, "name": "small.txt", "size": 4
+            })))
+            .expect(0)
+            .mount(&server)
+            .await;
+        let api = SpaceApi::new(setup_client(&server).await);
+
+        let mut upload = AttachmentUpload::new().max_size(8);
+        let result = upload
+            .upload_all(
+                &api,
+                vec![bytes_source("small.txt", 4), bytes_source("big.bin", 9)],
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ApiError::AttachmentTooLarge { ref name, size: 9, limit: 8 }) if name == "big.bin"
+        ));
+        assert!(upload.uploaded().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_upload_all_and_failure_report() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/v2/space/attachment"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": 42, "name": "log.txt", "size": 4
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let api = SpaceApi::new(setup_client(&server).await);
+
+        let mut upload = AttachmentUpload::new();
+        upload
+            .upload_all(&api, vec![bytes_source("log.txt", 4)])
+            .await
+            .unwrap();
+
+        assert_eq!(upload.ids(), vec![AttachmentId::new(42)]);
+        let report = upload.failure_report(&"Issue type not found");
+        assert!(report.starts_with("Issue type not found. "));
+        assert!(report.contains("log.txt (attachment ID 42)"));
+        assert_eq!(
+            AttachmentUpload::new().failure_report(&"boom"),
+            "boom",
+            "nothing uploaded, nothing to report"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_upload_attachment_from_bytes_detects_content_type() {
+        use wiremock::matchers::body_string_contains;
+
+        let server = MockServer::start().await;
+        let space_api = setup_space_api(&server).await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v2/space/attachment"))
+            .and(body_string_contains("filename=\"pixel.png\""))
+            .and(body_string_contains("Content-Type: image/png"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": 7, "name": "pixel.png", "size": 8
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let params = UploadAttachmentParams::from_bytes("pixel.png", b"png data".to_vec());
+        let attachment = space_api.upload_attachment(params).await.unwrap();
+        assert_eq!(attachment.id, 7);
+    }
 }
@@ -0,0 +1,51 @@
+#[cfg(feature = "writable")]
+use backlog_api_core::{HttpMethod, IntoRequest};
+#[cfg(feature = "writable")]
+use backlog_core::identifier::StarId;
+
+/// Parameters for removing a star.
+///
+/// # Example
+/// ```no_run
+/// # use backlog_star::api::RemoveStarParams;
+/// let params = RemoveStarParams::new(75u32);
+/// ```
+#[cfg(feature = "writable")]
+#[derive(Debug, Clone)]
+pub struct RemoveStarParams {
+    /// The ID of the star to remove.
+    pub star_id: StarId,
+}
+
+#[cfg(feature = "writable")]
+impl RemoveStarParams {
+    /// Creates parameters for removing the star with the given ID.
+    pub fn new(star_id: impl Into<StarId>) -> Self {
+        Self {
+            star_id: star_id.into(),
+        }
+    }
+}
+
+#[cfg(feature = "writable")]
+impl IntoRequest for RemoveStarParams {
+    fn method(&self) -> HttpMethod {
+        HttpMethod::Delete
+    }
+
+    fn path(&self) -> String {
+        format!("/api/v2/stars/{}", self.star_id)
+    }
+}
+
+#[cfg(all(test, feature = "writable"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_star_request() {
+        let params = RemoveStarParams::new(75u32);
+        assert_eq!(params.method(), HttpMethod::Delete);
+        assert_eq!(params.path(), "/api/v2/stars/75");
+    }
+}
@@ -2,6 +2,8 @@ use client::Client;
 
 #[cfg(feature = "writable")]
 use backlog_api_core::Result;
+#[cfg(feature = "writable")]
+use backlog_core::identifier::StarId;
 
 /// API client for star-related operations.
 #[derive(Debug)]
@@ -38,4 +40,29 @@ impl StarApi {
     pub async fn add_star(&self, params: super::AddStarParams) -> Result<()> {
         self.0.execute_no_content(params).await
     }
+
+    /// Removes a star.
+    /// Corresponds to `DELETE /api/v2/stars/:starId`.
+    ///
+    /// # Arguments
+    /// * `star_id` - The ID of the star to remove
+    ///
+    /// # Returns
+    /// Returns `Ok(())` on success (204 No Content), or an error if the operation fails.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use backlog_star::StarApi;
+    /// # use backlog_core::identifier::StarId;
+    /// # async fn example(api: StarApi) -> Result<(), Box<dyn std::error::Error>> {
+    /// api.remove_star(StarId::new(75)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "writable")]
+    pub async fn remove_star(&self, star_id: impl Into<StarId>) -> Result<()> {
+        self.0
+            .execute_no_content(super::RemoveStarParams::new(star_id))
+            .await
+    }
 }
@@ -3,4 +3,4 @@ pub mod api;
 pub use api::StarApi;
 
 #[cfg(feature = "writable")]
-pub use api::{AddStarParams, StarTarget};
+pub use api::{AddStarParams, RemoveStarParams, StarTarget};
@@ -270,4 +270,52 @@ mod tests {
             backlog_api_core::Error::HttpStatus { status: 500, .. }
         ));
     }
+
+    #[tokio::test]
+    async fn test_remove_star_success() {
+        let mock_server = MockServer::start().await;
+        let api = setup_star_api(&mock_server).await;
+
+        Mock::given(method("DELETE"))
+            .and(path("/api/v2/stars/75"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+
+        api.remove_star(75u32)
+            .await
+            .expect("remove_star should succeed");
+    }
+
+    #[tokio::test]
+    async fn test_remove_star_not_found() {
+        let mock_server = MockServer::start().await;
+        let api = setup_star_api(&mock_server).await;
+
+        let error_response = r#"{
+            "errors": [
+                {
+                    "message": "No star.",
+                    "code": 6,
+                    "moreInfo": ""
+                }
+            ]
+        }"#;
+
+        Mock::given(method("DELETE"))
+            .and(path("/api/v2/stars/999"))
+            .respond_with(ResponseTemplate::new(404).set_body_string(error_response))
+            .mount(&mock_server)
+            .await;
+
+        let err = api
+            .remove_star(999u32)
+            .await
+            .expect_err("should fail for unknown star");
+        assert!(matches!(
+            err,
+            backlog_api_core::Error::HttpStatus { status: 404, .. }
+        ));
+    }
 }
@@ -0,0 +1,56 @@
+#[cfg(feature = "writable")]
+use crate::models::TeamResponse;
+#[cfg(feature = "writable")]
+use backlog_api_core::{HttpMethod, IntoRequest};
+#[cfg(feature = "writable")]
+use backlog_core::{id::UserId, identifier::Identifier};
+#[cfg(feature = "writable")]
+use serde::Serialize;
+
+/// Response type for adding a team.
+#[cfg(feature = "writable")]
+pub type AddTeamResponse = TeamResponse;
+
+/// Parameters for adding a team.
+///
+/// # Required Permissions
+/// - Administrator
+/// - Project Administrator
+#[cfg(feature = "writable")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct AddTeamParams {
+    /// Name of the team.
+    pub name: String,
+    /// Users to add to the team.
+    pub members: Vec<UserId>,
+}
+
+#[cfg(feature = "writable")]
+impl From<&AddTeamParams> for Vec<(String, String)> {
+    fn from(params: &AddTeamParams) -> Self {
+        let mut seq = vec![("name".to_string(), params.name.clone())];
+        seq.extend(
+            params
+                .members
+                .iter()
+                .map(|id| ("members[]".to_string(), id.value().to_string())),
+        );
+        seq
+    }
+}
+
+#[cfg(feature = "writable")]
+impl IntoRequest for AddTeamParams {
+    fn method(&self) -> HttpMethod {
+        HttpMethod::Post
+    }
+
+    fn path(&self) -> String {
+        "/api/v2/teams".to_string()
+    }
+
+    fn to_form(&self) -> impl Serialize {
+        let params: Vec<(String, String)> = self.into();
+        params
+    }
+}
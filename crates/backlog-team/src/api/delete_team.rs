@@ -0,0 +1,32 @@
+#[cfg(feature = "writable")]
+use crate::models::TeamResponse;
+#[cfg(feature = "writable")]
+use backlog_api_core::{HttpMethod, IntoRequest};
+#[cfg(feature = "writable")]
+use backlog_core::id::TeamId;
+
+/// Response type for deleting a team.
+#[cfg(feature = "writable")]
+pub type DeleteTeamResponse = TeamResponse;
+
+/// Parameters for deleting a team.
+///
+/// # Required Permissions
+/// - Administrator
+/// - Project Administrator (only teams they created)
+#[cfg(feature = "writable")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeleteTeamParams {
+    pub team_id: TeamId,
+}
+
+#[cfg(feature = "writable")]
+impl IntoRequest for DeleteTeamParams {
+    fn method(&self) -> HttpMethod {
+        HttpMethod::Delete
+    }
+
+    fn path(&self) -> String {
+        format!("/api/v2/teams/{}", self.team_id)
+    }
+}
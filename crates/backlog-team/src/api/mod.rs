@@ -1,9 +1,21 @@
+#[cfg(feature = "writable")]
+mod add_team;
+#[cfg(feature = "writable")]
+mod delete_team;
 mod get_team;
 mod get_team_icon;
 mod list_teams;
 mod team_api;
+#[cfg(feature = "writable")]
+mod update_team;
 
+#[cfg(feature = "writable")]
+pub use add_team::*;
+#[cfg(feature = "writable")]
+pub use delete_team::*;
 pub use get_team::*;
 pub use get_team_icon::*;
 pub use list_teams::*;
 pub use team_api::*;
+#[cfg(feature = "writable")]
+pub use update_team::*;
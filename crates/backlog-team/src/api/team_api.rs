@@ -1,3 +1,8 @@
+#[cfg(feature = "writable")]
+use crate::api::{
+    AddTeamParams, AddTeamResponse, DeleteTeamParams, DeleteTeamResponse, UpdateTeamParams,
+    UpdateTeamResponse,
+};
 use crate::api::{
     GetTeamIconParams, GetTeamParams, GetTeamResponse, ListTeamsParams, ListTeamsResponse,
 };
@@ -137,4 +142,114 @@ impl TeamApi {
     pub async fn get_team_icon(&self, params: GetTeamIconParams) -> Result<DownloadedFile> {
         self.0.download_file(params).await
     }
+
+    /// Adds a new team.
+    ///
+    /// This API requires administrator or project administrator permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Name and members of the new team
+    ///
+    /// # Returns
+    ///
+    /// Returns the created team if successful.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// * The user doesn't have administrator or project administrator permission (403)
+    /// * A member does not exist in the space (400)
+    /// * The API request fails
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use backlog_team::api::{AddTeamParams, TeamApi};
+    /// use backlog_core::id::UserId;
+    ///
+    /// # async fn example(api: TeamApi) -> Result<(), Box<dyn std::error::Error>> {
+    /// let params = AddTeamParams {
+    ///     name: "Design".to_string(),
+    ///     members: vec![UserId::new(1), UserId::new(2)],
+    /// };
+    /// let team = api.add_team(params).await?;
+    /// println!("Created team {} (ID: {})", team.name, team.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Corresponds to `POST /api/v2/teams`.
+    #[cfg(feature = "writable")]
+    pub async fn add_team(&self, params: AddTeamParams) -> Result<AddTeamResponse> {
+        self.0.execute(params).await
+    }
+
+    /// Updates a team's name or member list.
+    ///
+    /// The member list is replaced as a whole; see [`UpdateTeamParams`].
+    /// This API requires administrator or project administrator permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Team ID and the fields to change
+    ///
+    /// # Returns
+    ///
+    /// Returns the updated team if successful.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// * The user doesn't have permission to edit the team (403)
+    /// * The team is not found (404)
+    /// * The API request fails
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use backlog_team::api::{TeamApi, UpdateTeamParams};
+    /// use backlog_core::id::TeamId;
+    ///
+    /// # async fn example(api: TeamApi) -> Result<(), Box<dyn std::error::Error>> {
+    /// let params = UpdateTeamParams {
+    ///     team_id: TeamId::new(123),
+    ///     name: Some("Product Design".to_string()),
+    ///     members: None,
+    /// };
+    /// api.update_team(params).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Corresponds to `PATCH /api/v2/teams/:teamId`.
+    #[cfg(feature = "writable")]
+    pub async fn update_team(&self, params: UpdateTeamParams) -> Result<UpdateTeamResponse> {
+        self.0.execute(params).await
+    }
+
+    /// Deletes a team.
+    ///
+    /// This API requires administrator or project administrator permission.
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - Parameters for deleting a team
+    ///
+    /// # Returns
+    ///
+    /// Returns the deleted team if successful.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if:
+    /// * The user doesn't have permission to delete the team (403)
+    /// * The team is not found (404)
+    /// * The API request fails
+    ///
+    /// Corresponds to `DELETE /api/v2/teams/:teamId`.
+    #[cfg(feature = "writable")]
+    pub async fn delete_team(&self, params: DeleteTeamParams) -> Result<DeleteTeamResponse> {
+        self.0.execute(params).await
+    }
 }
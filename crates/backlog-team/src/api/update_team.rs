@@ -0,0 +1,68 @@
+#[cfg(feature = "writable")]
+use crate::models::TeamResponse;
+#[cfg(feature = "writable")]
+use backlog_api_core::{HttpMethod, IntoRequest};
+#[cfg(feature = "writable")]
+use backlog_core::{
+    id::{TeamId, UserId},
+    identifier::Identifier,
+};
+#[cfg(feature = "writable")]
+use serde::Serialize;
+
+/// Response type for updating a team.
+#[cfg(feature = "writable")]
+pub type UpdateTeamResponse = TeamResponse;
+
+/// Parameters for updating a team.
+///
+/// `members` replaces the whole member list, so it must contain every user
+/// that should remain in the team. Fields left as `None` are not changed.
+/// The API cannot empty a team; an empty list leaves the members unchanged.
+///
+/// # Required Permissions
+/// - Administrator
+/// - Project Administrator (only teams they created)
+#[cfg(feature = "writable")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpdateTeamParams {
+    pub team_id: TeamId,
+    /// New name of the team.
+    pub name: Option<String>,
+    /// Full list of team members after the update.
+    pub members: Option<Vec<UserId>>,
+}
+
+#[cfg(feature = "writable")]
+impl From<&UpdateTeamParams> for Vec<(String, String)> {
+    fn from(params: &UpdateTeamParams) -> Self {
+        let mut seq = Vec::new();
+        if let Some(name) = &params.name {
+            seq.push(("name".to_string(), name.clone()));
+        }
+        if let Some(members) = &params.members {
+            seq.extend(
+                members
+                    .iter()
+                    .map(|id| ("members[]".to_string(), id.value().to_string())),
+            );
+        }
+        seq
+    }
+}
+
+#[cfg(feature = "writable")]
+impl IntoRequest for UpdateTeamParams {
+    fn method(&self) -> HttpMethod {
+        HttpMethod::Patch
+    }
+
+    fn path(&self) -> String {
+        format!("/api/v2/teams/{}", self.team_id)
+    }
+
+    fn to_form(&self) -> impl Serialize {
+        let params: Vec<(String, String)> = self.into();
+        params
+    }
+}
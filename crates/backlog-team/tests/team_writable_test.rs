@@ -0,0 +1,170 @@
+#![cfg(feature = "writable")]
+
+use backlog_core::{
+    id::{TeamId, UserId},
+    identifier::Identifier,
+};
+use backlog_team::api::{AddTeamParams, DeleteTeamParams, UpdateTeamParams};
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use wiremock::{
+    matchers::{body_string, method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+mod common;
+use common::setup_team_api;
+
+fn user_json(id: u32, name: &str) -> serde_json::Value {
+    json!({
+        "id": id,
+        "userId": name,
+        "name": name,
+        "roleType": 2,
+        "lang": "ja",
+        "mailAddress": format!("{name}@example.com")
+    })
+}
+
+fn team_json(id: u32, name: &str, members: &[(u32, &str)]) -> serde_json::Value {
+    json!({
+        "id": id,
+        "name": name,
+        "members": members.iter().map(|(id, name)| user_json(*id, name)).collect::<Vec<_>>(),
+        "createdUser": user_json(1, "admin"),
+        "created": "2024-01-01T00:00:00Z",
+        "updatedUser": user_json(1, "admin"),
+        "updated": "2024-01-02T00:00:00Z"
+    })
+}
+
+#[tokio::test]
+async fn test_add_team_success() {
+    let mock_server = MockServer::start().await;
+    let api = setup_team_api(&mock_server).await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/v2/teams"))
+        .and(body_string("name=Design&members%5B%5D=2&members%5B%5D=3"))
+        .respond_with(ResponseTemplate::new(201).set_body_json(team_json(
+            10,
+            "Design",
+            &[(2, "alice"), (3, "bob")],
+        )))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let params = AddTeamParams {
+        name: "Design".to_string(),
+        members: vec![UserId::new(2), UserId::new(3)],
+    };
+    let team = api.add_team(params).await.unwrap();
+
+    assert_eq!(team.id.value(), 10);
+    assert_eq!(team.name, "Design");
+    assert_eq!(team.members.len(), 2);
+}
+
+#[tokio::test]
+async fn test_update_team_replaces_members() {
+    let mock_server = MockServer::start().await;
+    let api = setup_team_api(&mock_server).await;
+
+    Mock::given(method("PATCH"))
+        .and(path("/api/v2/teams/10"))
+        .and(body_string("members%5B%5D=3&members%5B%5D=4"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(team_json(
+            10,
+            "Design",
+            &[(3, "bob"), (4, "carol")],
+        )))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let params = UpdateTeamParams {
+        team_id: TeamId::new(10),
+        name: None,
+        members: Some(vec![UserId::new(3), UserId::new(4)]),
+    };
+    let team = api.update_team(params).await.unwrap();
+
+    let member_ids: Vec<u32> = team.members.iter().map(|m| m.id.value()).collect();
+    assert_eq!(member_ids, vec![3, 4]);
+}
+
+#[tokio::test]
+async fn test_update_team_name_only() {
+    let mock_server = MockServer::start().await;
+    let api = setup_team_api(&mock_server).await;
+
+    Mock::given(method("PATCH"))
+        .and(path("/api/v2/teams/10"))
+        .and(body_string("name=Product+Design"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(team_json(
+            10,
+            "Product Design",
+            &[(2, "alice")],
+        )))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let params = UpdateTeamParams {
+        team_id: TeamId::new(10),
+        name: Some("Product Design".to_string()),
+        members: None,
+    };
+    let team = api.update_team(params).await.unwrap();
+
+    assert_eq!(team.name, "Product Design");
+}
+
+#[tokio::test]
+async fn test_delete_team_success() {
+    let mock_server = MockServer::start().await;
+    let api = setup_team_api(&mock_server).await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v2/teams/10"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(team_json(10, "Design", &[])))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let team = api
+        .delete_team(DeleteTeamParams {
+            team_id: TeamId::new(10),
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(team.id.value(), 10);
+}
+
+#[tokio::test]
+async fn test_delete_team_forbidden() {
+    let mock_server = MockServer::start().await;
+    let api = setup_team_api(&mock_server).await;
+
+    Mock::given(method("DELETE"))
+        .and(path("/api/v2/teams/10"))
+        .respond_with(ResponseTemplate::new(403).set_body_json(json!({
+            "errors": [{
+                "message": "You do not have permission to perform this operation.",
+                "code": 11,
+                "moreInfo": ""
+            }]
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let result = api
+        .delete_team(DeleteTeamParams {
+            team_id: TeamId::new(10),
+        })
+        .await;
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,89 @@
+#[cfg(feature = "writable")]
+use backlog_api_core::{HttpMethod, IntoRequest};
+#[cfg(feature = "writable")]
+use backlog_core::Role;
+#[cfg(feature = "writable")]
+use serde::Serialize;
+
+/// Response type for adding a user
+pub type AddUserResponse = backlog_core::User;
+
+/// Parameters for adding a user.
+///
+/// Corresponds to `POST /api/v2/users`.
+#[cfg(feature = "writable")]
+#[derive(Clone)]
+pub struct AddUserParams {
+    /// The login ID of the new user.
+    pub user_id: String,
+    pub password: String,
+    pub name: String,
+    pub mail_address: String,
+    pub role_type: Role,
+}
+
+#[cfg(feature = "writable")]
+impl AddUserParams {
+    /// Creates a new instance with the required parameters.
+    pub fn new(
+        user_id: impl Into<String>,
+        password: impl Into<String>,
+        name: impl Into<String>,
+        mail_address: impl Into<String>,
+        role_type: Role,
+    ) -> Self {
+        Self {
+            user_id: user_id.into(),
+            password: password.into(),
+            name: name.into(),
+            mail_address: mail_address.into(),
+            role_type,
+        }
+    }
+}
+
+// Written by hand so that logging the parameters never reveals the password
+#[cfg(feature = "writable")]
+impl std::fmt::Debug for AddUserParams {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AddUserParams")
+            .field("user_id", &self.user_id)
+            .field("password", &"********")
+            .field("name", &self.name)
+            .field("mail_address", &self.mail_address)
+            .field("role_type", &self.role_type)
+            .finish()
+    }
+}
+
+#[cfg(feature = "writable")]
+impl From<&AddUserParams> for Vec<(String, String)> {
+    fn from(params: &AddUserParams) -> Self {
+        vec![
+            ("userId".to_string(), params.user_id.clone()),
+            ("password".to_string(), params.password.clone()),
+            ("name".to_string(), params.name.clone()),
+            ("mailAddress".to_string(), params.mail_address.clone()),
+            (
+                "roleType".to_string(),
+                (params.role_type as i32).to_string(),
+            ),
+        ]
+    }
+}
+
+#[cfg(feature = "writable")]
+impl IntoRequest for AddUserParams {
+    fn method(&self) -> HttpMethod {
+        HttpMethod::Post
+    }
+
+    fn path(&self) -> String {
+        "/api/v2/users".to_string()
+    }
+
+    fn to_form(&self) -> impl Serialize {
+        let params: Vec<(String, String)> = self.into();
+        params
+    }
+}
@@ -0,0 +1,37 @@
+#[cfg(feature = "writable")]
+use backlog_api_core::{HttpMethod, IntoRequest};
+#[cfg(feature = "writable")]
+use backlog_core::identifier::UserId;
+
+/// Response type for deleting a user
+pub type DeleteUserResponse = backlog_core::User;
+
+/// Parameters for deleting a user.
+///
+/// Corresponds to `DELETE /api/v2/users/:userId`.
+#[cfg(feature = "writable")]
+#[derive(Debug, Clone)]
+pub struct DeleteUserParams {
+    pub user_id: UserId,
+}
+
+#[cfg(feature = "writable")]
+impl DeleteUserParams {
+    /// Creates a new instance with the required parameters.
+    pub fn new(user_id: impl Into<UserId>) -> Self {
+        Self {
+            user_id: user_id.into(),
+        }
+    }
+}
+
+#[cfg(feature = "writable")]
+impl IntoRequest for DeleteUserParams {
+    fn method(&self) -> HttpMethod {
+        HttpMethod::Delete
+    }
+
+    fn path(&self) -> String {
+        format!("/api/v2/users/{}", self.user_id)
+    }
+}
@@ -1,3 +1,7 @@
+#[cfg(feature = "writable")]
+mod add_user;
+#[cfg(feature = "writable")]
+mod delete_user;
 mod get_notification_count;
 mod get_notifications;
 mod get_own_user;
@@ -13,10 +17,16 @@ mod get_watching_list;
 mod mark_notification_as_read;
 #[cfg(feature = "writable")]
 mod reset_unread_notification_count;
+#[cfg(feature = "writable")]
+mod update_user;
 mod user_api;
 
 pub use user_api::UserApi;
 
+#[cfg(feature = "writable")]
+pub use add_user::{AddUserParams, AddUserResponse};
+#[cfg(feature = "writable")]
+pub use delete_user::{DeleteUserParams, DeleteUserResponse};
 pub use get_notification_count::{GetNotificationCountParams, GetNotificationCountResponse};
 pub use get_notifications::{GetNotificationsParams, GetNotificationsResponse, NotificationOrder};
 pub use get_own_user::{GetOwnUserParams, GetOwnUserResponse};
@@ -35,3 +45,5 @@ pub use get_watching_list::{
 pub use mark_notification_as_read::MarkNotificationAsReadParams;
 #[cfg(feature = "writable")]
 pub use reset_unread_notification_count::ResetUnreadNotificationCountParams;
+#[cfg(feature = "writable")]
+pub use update_user::{UpdateUserParams, UpdateUserResponse};
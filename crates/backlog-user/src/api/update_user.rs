@@ -0,0 +1,110 @@
+#[cfg(feature = "writable")]
+use backlog_api_core::{HttpMethod, IntoRequest};
+#[cfg(feature = "writable")]
+use backlog_core::{Role, identifier::UserId};
+#[cfg(feature = "writable")]
+use serde::Serialize;
+
+/// Response type for updating a user
+pub type UpdateUserResponse = backlog_core::User;
+
+/// Parameters for updating a user.
+///
+/// Only the fields that are set are sent.
+///
+/// Corresponds to `PATCH /api/v2/users/:userId`.
+#[cfg(feature = "writable")]
+#[derive(Clone)]
+pub struct UpdateUserParams {
+    pub user_id: UserId,
+    pub password: Option<String>,
+    pub name: Option<String>,
+    pub mail_address: Option<String>,
+    pub role_type: Option<Role>,
+}
+
+#[cfg(feature = "writable")]
+impl UpdateUserParams {
+    /// Creates a new instance for the given user with no changes.
+    pub fn new(user_id: impl Into<UserId>) -> Self {
+        Self {
+            user_id: user_id.into(),
+            password: None,
+            name: None,
+            mail_address: None,
+            role_type: None,
+        }
+    }
+
+    pub fn password(mut self, password: impl Into<String>) -> Self {
+        self.password = Some(password.into());
+        self
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn mail_address(mut self, mail_address: impl Into<String>) -> Self {
+        self.mail_address = Some(mail_address.into());
+        self
+    }
+
+    pub fn role_type(mut self, role_type: Role) -> Self {
+        self.role_type = Some(role_type);
+        self
+    }
+}
+
+// Written by hand so that logging the parameters never reveals the password
+#[cfg(feature = "writable")]
+impl std::fmt::Debug for UpdateUserParams {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UpdateUserParams")
+            .field("user_id", &self.user_id)
+            .field("password", &self.password.as_ref().map(|_| "********"))
+            .field("name", &self.name)
+            .field("mail_address", &self.mail_address)
+            .field("role_type", &self.role_type)
+            .finish()
+    }
+}
+
+#[cfg(feature = "writable")]
+impl From<&UpdateUserParams> for Vec<(String, String)> {
+    fn from(params: &UpdateUserParams) -> Self {
+        let mut seq = Vec::new();
+
+        if let Some(password) = &params.password {
+            seq.push(("password".to_string(), password.clone()));
+        }
+        if let Some(name) = &params.name {
+            seq.push(("name".to_string(), name.clone()));
+        }
+        if let Some(mail_address) = &params.mail_address {
+            seq.push(("mailAddress".to_string(), mail_address.clone()));
+        }
+        if let Some(role_type) = params.role_type {
+            seq.push(("roleType".to_string(), (role_type as i32).to_string()));
+        }
+
+        seq
+    }
+}
+
+#[cfg(feature = "writable")]
+impl IntoRequest for UpdateUserParams {
+    fn method(&self) -> HttpMethod {
+        HttpMethod::Patch
+    }
+
+    fn path(&self) -> String {
+        format!("/api/v2/users/{}", self.user_id)
+    }
+
+    fn to_form(&self) -> impl Serialize {
+        let params: Vec<(String, String)> = self.into();
+        params
+    }
+}
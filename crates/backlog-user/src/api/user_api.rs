@@ -137,4 +137,34 @@ impl UserApi {
         let params = super::ResetUnreadNotificationCountParams::new();
         self.0.execute(params).await
     }
+
+    /// Add a user to the space.
+    ///
+    /// Corresponds to `POST /api/v2/users`.
+    #[cfg(feature = "writable")]
+    pub async fn add_user(&self, params: super::AddUserParams) -> Result<super::AddUserResponse> {
+        self.0.execute(params).await
+    }
+
+    /// Update a user's name, password, mail address or role.
+    ///
+    /// Corresponds to `PATCH /api/v2/users/:userId`.
+    #[cfg(feature = "writable")]
+    pub async fn update_user(
+        &self,
+        params: super::UpdateUserParams,
+    ) -> Result<super::UpdateUserResponse> {
+        self.0.execute(params).await
+    }
+
+    /// Delete a user from the space.
+    ///
+    /// Corresponds to `DELETE /api/v2/users/:userId`.
+    #[cfg(feature = "writable")]
+    pub async fn delete_user(
+        &self,
+        params: super::DeleteUserParams,
+    ) -> Result<super::DeleteUserResponse> {
+        self.0.execute(params).await
+    }
 }
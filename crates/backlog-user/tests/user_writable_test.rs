@@ -0,0 +1,179 @@
+#[cfg(feature = "writable")]
+mod writable_tests {
+    use backlog_core::Role;
+    use backlog_core::identifier::UserId;
+    use backlog_user::api::{AddUserParams, DeleteUserParams, UpdateUserParams, UserApi};
+    use client::test_utils::setup_client;
+    use wiremock::matchers::{body_string, body_string_contains, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn setup_user_api(mock_server: &MockServer) -> UserApi {
+        let client = setup_client(mock_server).await;
+        UserApi::new(client)
+    }
+
+    fn user_json(id: u32, user_id: &str, name: &str, role_type: i32) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "userId": user_id,
+            "name": name,
+            "roleType": role_type,
+            "lang": null,
+            "mailAddress": format!("{user_id}@example.com"),
+            "lastLoginTime": null
+        })
+    }
+
+    #[tokio::test]
+    async fn test_add_user_success() {
+        let mock_server = MockServer::start().await;
+        let api = setup_user_api(&mock_server).await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v2/users"))
+            .and(body_string(
+                "userId=alice&password=secret123&name=Alice&mailAddress=alice%40example.com&roleType=2",
+            ))
+            .respond_with(
+                ResponseTemplate::new(201).set_body_json(user_json(10, "alice", "Alice", 2)),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let params = AddUserParams::new(
+            "alice",
+            "secret123",
+            "Alice",
+            "alice@example.com",
+            Role::User,
+        );
+        let user = api.add_user(params).await.expect("add_user should succeed");
+        assert_eq!(user.id, UserId::new(10));
+        assert_eq!(user.user_id.as_deref(), Some("alice"));
+        assert_eq!(user.role_type, Role::User);
+    }
+
+    #[tokio::test]
+    async fn test_add_user_duplicate_error() {
+        let mock_server = MockServer::start().await;
+        let api = setup_user_api(&mock_server).await;
+
+        Mock::given(method("POST"))
+            .and(path("/api/v2/users"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+                "errors": [{
+                    "message": "The user ID is already in use.",
+                    "code": 7,
+                    "moreInfo": ""
+                }]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let params = AddUserParams::new("alice", "secret123", "Alice", "a@example.com", Role::User);
+        assert!(api.add_user(params).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_update_user_sends_only_set_fields() {
+        let mock_server = MockServer::start().await;
+        let api = setup_user_api(&mock_server).await;
+
+        Mock::given(method("PATCH"))
+            .and(path("/api/v2/users/10"))
+            .and(body_string("name=Alice+Smith&roleType=3"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(user_json(
+                10,
+                "alice",
+                "Alice Smith",
+                3,
+            )))
+            .mount(&mock_server)
+            .await;
+
+        let params = UpdateUserParams::new(UserId::new(10))
+            .name("Alice Smith")
+            .role_type(Role::Reporter);
+        let user = api
+            .update_user(params)
+            .await
+            .expect("update_user should succeed");
+        assert_eq!(user.name, "Alice Smith");
+        assert_eq!(user.role_type, Role::Reporter);
+    }
+
+    #[tokio::test]
+    async fn test_update_user_password_and_mail_address() {
+        let mock_server = MockServer::start().await;
+        let api = setup_user_api(&mock_server).await;
+
+        Mock::given(method("PATCH"))
+            .and(path("/api/v2/users/10"))
+            .and(body_string_contains("password=new-pass"))
+            .and(body_string_contains("mailAddress=alice2%40example.com"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(user_json(10, "alice", "Alice", 2)),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let params = UpdateUserParams::new(UserId::new(10))
+            .password("new-pass")
+            .mail_address("alice2@example.com");
+        assert!(api.update_user(params).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_delete_user_success() {
+        let mock_server = MockServer::start().await;
+        let api = setup_user_api(&mock_server).await;
+
+        Mock::given(method("DELETE"))
+            .and(path("/api/v2/users/10"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(user_json(10, "alice", "Alice", 2)),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let user = api
+            .delete_user(DeleteUserParams::new(UserId::new(10)))
+            .await
+            .expect("delete_user should succeed");
+        assert_eq!(user.id, UserId::new(10));
+    }
+
+    #[tokio::test]
+    async fn test_delete_user_not_found() {
+        let mock_server = MockServer::start().await;
+        let api = setup_user_api(&mock_server).await;
+
+        Mock::given(method("DELETE"))
+            .and(path("/api/v2/users/999"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                "errors": [{
+                    "message": "No user.",
+                    "code": 6,
+                    "moreInfo": ""
+                }]
+            })))
+            .mount(&mock_server)
+            .await;
+
+        let result = api
+            .delete_user(DeleteUserParams::new(UserId::new(999)))
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_debug_output_redacts_password() {
+        let add = AddUserParams::new("alice", "secret123", "Alice", "a@example.com", Role::User);
+        let update = UpdateUserParams::new(UserId::new(10)).password("secret123");
+
+        for debug in [format!("{add:?}"), format!("{update:?}")] {
+            assert!(!debug.contains("secret123"), "{debug}");
+            assert!(debug.contains("********"), "{debug}");
+        }
+    }
+}
@@ -14,9 +14,14 @@ use crate::api::{
     GetWikiSharedFileListResponse, GetWikiStarsParams, GetWikiStarsResponse, GetWikiTagListParams,
     GetWikiTagListResponse,
 };
+use crate::models::{HistoryOrder, WikiHistory};
 use backlog_api_core::Result;
+use backlog_core::identifier::WikiId;
 use client::Client;
 
+/// Page size used when walking the history of a wiki page.
+const HISTORY_PAGE_SIZE: u32 = 100;
+
 pub struct WikiApi(Client);
 
 impl WikiApi {
@@ -64,6 +69,40 @@ impl WikiApi {
         self.0.execute(params).await
     }
 
+    /// Find a single version of a wiki page by paging through its history.
+    ///
+    /// Returns `None` if the page history has no entry for `version`.
+    pub async fn get_wiki_version(
+        &self,
+        wiki_id: impl Into<WikiId>,
+        version: u32,
+    ) -> Result<Option<WikiHistory>> {
+        let wiki_id = wiki_id.into();
+        let mut min_id = None;
+
+        loop {
+            let mut params = GetWikiHistoryParams::new(wiki_id)
+                .order(HistoryOrder::Asc)
+                .count(HISTORY_PAGE_SIZE);
+            if let Some(min_id) = min_id {
+                params = params.min_id(min_id);
+            }
+
+            let page = self.get_wiki_history(params).await?;
+            if let Some(entry) = page.iter().find(|entry| entry.version == version) {
+                return Ok(Some(entry.clone()));
+            }
+
+            let Some(last) = page.iter().map(|entry| entry.version).max() else {
+                return Ok(None);
+            };
+            if page.len() < HISTORY_PAGE_SIZE as usize || last > version || min_id == Some(last) {
+                return Ok(None);
+            }
+            min_id = Some(last);
+        }
+    }
+
     /// Get wiki attachment list
     /// Corresponds to `GET /api/v2/wikis/:wikiId/attachments`.
     pub async fn get_wiki_attachment_list(
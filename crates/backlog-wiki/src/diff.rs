@@ -0,0 +1,195 @@
+//! Line-based diffs between versions of a wiki page.
+
+use crate::models::{WikiDetail, WikiHistory};
+use backlog_core::identifier::WikiId;
+use serde::Serialize;
+use similar::{ChangeTag, TextDiff};
+
+/// Number of unchanged lines shown around each hunk.
+pub const DEFAULT_CONTEXT_LINES: usize = 3;
+
+/// A unified diff between two versions of a wiki page.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WikiVersionDiff {
+    /// The wiki page ID.
+    pub wiki_id: WikiId,
+    /// The older version.
+    pub from_version: u32,
+    /// The newer version, or `None` when compared against the current page.
+    pub to_version: Option<u32>,
+    /// The page name at the older version.
+    pub from_name: String,
+    /// The page name at the newer version.
+    pub to_name: String,
+    /// Number of lines added between the two versions.
+    pub added_lines: usize,
+    /// Number of lines removed between the two versions.
+    pub removed_lines: usize,
+    /// Unified diff of the page content. Empty when the content is identical.
+    pub diff: String,
+}
+
+impl WikiVersionDiff {
+    /// Compare two history entries of the same page.
+    pub fn between(from: &WikiHistory, to: &WikiHistory) -> Self {
+        Self::build(
+            from,
+            Some(to.version),
+            &to.name,
+            &format!("v{}", to.version),
+            &to.content,
+        )
+    }
+
+    /// Compare a history entry with the current content of the page.
+    pub fn against_current(from: &WikiHistory, current: &WikiDetail) -> Self {
+        Self::build(from, None, &current.name, "current", &current.content)
+    }
+
+    /// Returns `true` if neither the name nor the content changed.
+    pub fn is_unchanged(&self) -> bool {
+        self.diff.is_empty() && self.from_name == self.to_name
+    }
+
+    fn build(
+        from: &WikiHistory,
+        to_version: Option<u32>,
+        to_name: &str,
+        to_label: &str,
+        to_content: &str,
+    ) -> Self {
+        let old = normalize_newlines(&from.content);
+        let new = normalize_newlines(to_content);
+        let (added_lines, removed_lines) = count_changes(&old, &new);
+
+        Self {
+            wiki_id: from.page_id,
+            from_version: from.version,
+            to_version,
+            from_name: from.name.clone(),
+            to_name: to_name.to_string(),
+            added_lines,
+            removed_lines,
+            diff: unified_diff(
+                &old,
+                &new,
+                &format!("{} (v{})", from.name, from.version),
+                &format!("{to_name} ({to_label})"),
+                DEFAULT_CONTEXT_LINES,
+            ),
+        }
+    }
+}
+
+/// Produce a unified line diff of `old` and `new`.
+///
+/// Returns an empty string when both texts are identical.
+pub fn unified_diff(
+    old: &str,
+    new: &str,
+    old_label: &str,
+    new_label: &str,
+    context: usize,
+) -> String {
+    if old == new {
+        return String::new();
+    }
+    TextDiff::from_lines(old, new)
+        .unified_diff()
+        .context_radius(context)
+        .header(old_label, new_label)
+        .to_string()
+}
+
+fn count_changes(old: &str, new: &str) -> (usize, usize) {
+    TextDiff::from_lines(old, new)
+        .iter_all_changes()
+        .fold((0, 0), |(added, removed), change| match change.tag() {
+            ChangeTag::Insert => (added + 1, removed),
+            ChangeTag::Delete => (added, removed + 1),
+            ChangeTag::Equal => (added, removed),
+        })
+}
+
+// Backlog stores content with CRLF line endings when edited in the browser.
+fn normalize_newlines(text: &str) -> String {
+    text.replace("\r\n", "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use backlog_core::{Language, Role, User, identifier::UserId};
+    use chrono::{TimeZone, Utc};
+
+    fn history(version: u32, name: &str, content: &str) -> WikiHistory {
+        WikiHistory {
+            page_id: WikiId::new(1),
+            version,
+            name: name.to_string(),
+            content: content.to_string(),
+            created_user: User {
+                id: UserId::new(1),
+                user_id: Some("john".to_string()),
+                name: "john".to_string(),
+                role_type: Role::User,
+                lang: Some(Language::Japanese),
+                mail_address: "john@example.com".to_string(),
+                last_login_time: None,
+            },
+            created: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_unified_diff_identical() {
+        assert_eq!(unified_diff("a\nb\n", "a\nb\n", "old", "new", 3), "");
+    }
+
+    #[test]
+    fn test_unified_diff_changed_line() {
+        let diff = unified_diff("a\nb\nc\n", "a\nB\nc\n", "old", "new", 3);
+        assert!(diff.starts_with("--- old\n+++ new\n"));
+        assert!(diff.contains("@@ -1,3 +1,3 @@"));
+        assert!(diff.contains("-b\n"));
+        assert!(diff.contains("+B\n"));
+        assert!(diff.contains(" a\n"));
+    }
+
+    #[test]
+    fn test_version_diff_between() {
+        let from = history(1, "Home", "line1\nline2\n");
+        let to = history(3, "Home", "line1\nline2 edited\nline3\n");
+
+        let diff = WikiVersionDiff::between(&from, &to);
+        assert_eq!(diff.from_version, 1);
+        assert_eq!(diff.to_version, Some(3));
+        assert_eq!(diff.added_lines, 2);
+        assert_eq!(diff.removed_lines, 1);
+        assert!(diff.diff.contains("--- Home (v1)"));
+        assert!(diff.diff.contains("+++ Home (v3)"));
+        assert!(!diff.is_unchanged());
+    }
+
+    #[test]
+    fn test_version_diff_ignores_crlf() {
+        let from = history(1, "Home", "a\r\nb\r\n");
+        let to = history(2, "Home", "a\nb\n");
+
+        let diff = WikiVersionDiff::between(&from, &to);
+        assert!(diff.is_unchanged());
+        assert_eq!(diff.added_lines, 0);
+        assert_eq!(diff.removed_lines, 0);
+    }
+
+    #[test]
+    fn test_version_diff_rename_only() {
+        let from = history(1, "Old", "same\n");
+        let to = history(2, "New", "same\n");
+
+        let diff = WikiVersionDiff::between(&from, &to);
+        assert!(diff.diff.is_empty());
+        assert!(!diff.is_unchanged());
+    }
+}
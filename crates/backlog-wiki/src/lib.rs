@@ -4,5 +4,9 @@ pub use api::*;
 // Re-export domain models
 pub use models::*;
 
+// Re-export version diff helpers
+pub use diff::{WikiVersionDiff, unified_diff};
+
 pub mod api;
+pub mod diff;
 pub mod models;
@@ -9,7 +9,7 @@ use backlog_wiki::api::{
     GetWikiStarsParams, GetWikiTagListParams,
 };
 use wiremock::MockServer;
-use wiremock::matchers::{method, path, query_param};
+use wiremock::matchers::{method, path, query_param, query_param_is_missing};
 
 #[tokio::test]
 async fn test_get_wiki_list_empty_params_success() {
@@ -869,3 +869,75 @@ async fn test_get_wiki_stars_access_forbidden() {
         .await;
     assert!(result.is_err());
 }
+
+#[tokio::test]
+async fn test_get_wiki_version_found() {
+    let mock_server = MockServer::start().await;
+    let wiki_api = setup_wiki_api(&mock_server).await;
+
+    let history = vec![
+        create_mock_wiki_history(123, 1, "Initial Page", "john"),
+        create_mock_wiki_history(123, 2, "Updated Page", "alice"),
+    ];
+
+    Mock::given(method("GET"))
+        .and(path("/api/v2/wikis/123/history"))
+        .and(query_param("order", "asc"))
+        .and(query_param("count", "100"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&history))
+        .mount(&mock_server)
+        .await;
+
+    let version = wiki_api.get_wiki_version(123u32, 2).await.unwrap().unwrap();
+    assert_eq!(version.version, 2);
+    assert_eq!(version.name, "Updated Page");
+}
+
+#[tokio::test]
+async fn test_get_wiki_version_pages_through_history() {
+    let mock_server = MockServer::start().await;
+    let wiki_api = setup_wiki_api(&mock_server).await;
+
+    let first_page: Vec<WikiHistory> = (1..=100)
+        .map(|v| create_mock_wiki_history(123, v, "Page", "john"))
+        .collect();
+    let second_page = vec![create_mock_wiki_history(123, 101, "Page", "alice")];
+
+    Mock::given(method("GET"))
+        .and(path("/api/v2/wikis/123/history"))
+        .and(query_param("minId", "100"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&second_page))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/wikis/123/history"))
+        .and(query_param_is_missing("minId"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&first_page))
+        .mount(&mock_server)
+        .await;
+
+    let version = wiki_api
+        .get_wiki_version(123u32, 101)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(version.version, 101);
+    assert_eq!(version.created_user.name, "alice");
+}
+
+#[tokio::test]
+async fn test_get_wiki_version_missing() {
+    let mock_server = MockServer::start().await;
+    let wiki_api = setup_wiki_api(&mock_server).await;
+
+    let history = vec![create_mock_wiki_history(123, 1, "Initial Page", "john")];
+
+    Mock::given(method("GET"))
+        .and(path("/api/v2/wikis/123/history"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&history))
+        .mount(&mock_server)
+        .await;
+
+    let version = wiki_api.get_wiki_version(123u32, 5).await.unwrap();
+    assert!(version.is_none());
+}
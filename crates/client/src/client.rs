@@ -1,9 +1,19 @@
+use crate::download::{
+    DownloadProgress, StreamedDownload, content_disposition_filename, parse_content_range,
+    partial_path, validator_path,
+};
 use backlog_api_core::{
     BacklogApiErrorResponse, Error as ApiError, IntoDownloadRequest, IntoRequest,
     IntoUploadRequest, Result, bytes,
 };
-use reqwest::header::{CONTENT_DISPOSITION, CONTENT_TYPE};
+use reqwest::StatusCode;
+use reqwest::header::{
+    CONTENT_DISPOSITION, CONTENT_RANGE, CONTENT_TYPE, ETAG, HeaderMap, HeaderValue, IF_RANGE,
+    LAST_MODIFIED, RANGE,
+};
+use std::path::Path;
 use tokio::fs;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 use url::Url;
 
 /// A trait for converting HTTP responses into different output types
@@ -91,30 +101,10 @@ impl IntoResponse for FileResponse {
         let bytes_content = response.bytes().await.map_err(ApiError::from)?;
 
         // Extract filename from Content-Disposition
-        let filename = headers
-            .get(CONTENT_DISPOSITION)
-            .and_then(|value| value.to_str().ok())
-            .and_then(|value| {
-                // Simple parser for `filename="name.ext"` or `filename*=UTF-8''name.ext`
-                if let Some(start) = value.find("filename=\"") {
-                    let remainder = &value[start + 10..];
-                    remainder.find('"').map(|end| remainder[..end].to_string())
-                } else if let Some(start) = value.find("filename*=UTF-8''") {
-                    let remainder = &value[start + 17..];
-                    // This doesn't handle URL decoding, but it's a start
-                    Some(remainder.to_string())
-                } else {
-                    None
-                }
-            })
-            .unwrap_or_else(|| "downloaded_file".to_string()); // Default filename
+        let filename = response_filename(&headers);
 
         // Extract Content-Type
-        let content_type = headers
-            .get(CONTENT_TYPE)
-            .and_then(|value| value.to_str().ok())
-            .unwrap_or("application/octet-stream") // Default content type
-            .to_string();
+        let content_type = response_content_type(&headers);
 
         Ok(DownloadedFile {
             filename,
@@ -124,6 +114,22 @@ impl IntoResponse for FileResponse {
     }
 }
 
+fn response_filename(headers: &HeaderMap) -> String {
+    headers
+        .get(CONTENT_DISPOSITION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(content_disposition_filename)
+        .unwrap_or_else(|| "downloaded_file".to_string()) // Default filename
+}
+
+fn response_content_type(headers: &HeaderMap) -> String {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("application/octet-stream") // Default content type
+        .to_string()
+}
+
 #[derive(Debug, Clone)]
 pub struct Client {
     base_url: Url,
@@ -154,6 +160,11 @@ impl Client {
         self
     }
 
+    /// The space URL requests are sent to
+    pub fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
     /// Executes a request using the IntoRequest trait
     pub async fn execute<T, P>(&self, params: P) -> Result<T>
     where
@@ -174,6 +185,141 @@ impl Client {
         self.execute_unified(request, FileResponse).await
     }
 
+    /// Downloads a file into `writer` chunk by chunk
+    ///
+    /// `on_progress` is called after every chunk. The writer is flushed but
+    /// not shut down.
+    pub async fn download_to_writer<P, W, F>(
+        &self,
+        params: P,
+        writer: &mut W,
+        mut on_progress: F,
+    ) -> Result<StreamedDownload>
+    where
+        P: IntoDownloadRequest,
+        W: AsyncWrite + Unpin,
+        F: FnMut(DownloadProgress),
+    {
+        let request = params.into_request(&self.client, &self.base_url)?;
+        let response = self.send(request).await?;
+        let response = Self::check_status(response).await?;
+        let total = response.content_length();
+        stream_body(response, writer, 0, total, "<writer>", &mut on_progress).await
+    }
+
+    /// Downloads a file to `path`, resuming an earlier partial download
+    ///
+    /// Data is written to `<path>.part` and renamed to `path` once complete.
+    /// The `ETag` (or `Last-Modified`) of the response is kept in
+    /// `<path>.part.validator` while the download is in progress. If both are
+    /// left over from an interrupted download, only the remaining bytes are
+    /// requested with `Range` and `If-Range`; when the file changed on the
+    /// server, or the server ignores ranges, the whole file is sent and
+    /// replaces the partial one. A `.part` file without a validator is never
+    /// resumed.
+    pub async fn download_to_path<P, F>(
+        &self,
+        params: P,
+        path: impl AsRef<Path>,
+        mut on_progress: F,
+    ) -> Result<StreamedDownload>
+    where
+        P: IntoDownloadRequest + Clone,
+        F: FnMut(DownloadProgress),
+    {
+        let path = path.as_ref();
+        let part_path = partial_path(path);
+        let validator_path = validator_path(path);
+        let validator = fs::read_to_string(&validator_path)
+            .await
+            .ok()
+            .and_then(|validator| HeaderValue::from_str(validator.trim()).ok())
+            .filter(|validator| !validator.is_empty());
+        let mut offset = match validator {
+            Some(_) => fs::metadata(&part_path)
+                .await
+                .map(|metadata| metadata.len())
+                .unwrap_or(0),
+            None => 0,
+        };
+
+        let response = loop {
+            let mut request = params.clone().into_request(&self.client, &self.base_url)?;
+            if offset > 0
+                && let Some(validator) = &validator
+            {
+                let range = format!("bytes={offset}-")
+                    .parse()
+                    .map_err(|e| ApiError::RequestBuild(format!("Invalid range header: {e}")))?;
+                request.headers_mut().insert(RANGE, range);
+                request.headers_mut().insert(IF_RANGE, validator.clone());
+            }
+            let response = self.send(request).await?;
+            // The partial file is already complete or larger than the file
+            // on the server, so start over.
+            if response.status() == StatusCode::RANGE_NOT_SATISFIABLE && offset > 0 {
+                offset = 0;
+                continue;
+            }
+            break Self::check_status(response).await?;
+        };
+
+        let (resumed_from, total, mut file) = if response.status() == StatusCode::PARTIAL_CONTENT {
+            let (start, total) = response
+                .headers()
+                .get(CONTENT_RANGE)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_content_range)
+                .ok_or_else(|| ApiError::UnexpectedStatus {
+                    status: StatusCode::PARTIAL_CONTENT.as_u16(),
+                    body: "missing or invalid Content-Range header".to_string(),
+                })?;
+            if start != offset {
+                return Err(ApiError::UnexpectedStatus {
+                    status: StatusCode::PARTIAL_CONTENT.as_u16(),
+                    body: format!("requested bytes from {offset}, received from {start}"),
+                });
+            }
+            let file = fs::OpenOptions::new()
+                .append(true)
+                .open(&part_path)
+                .await
+                .map_err(|e| write_error(&part_path, e))?;
+            (offset, total, file)
+        } else {
+            let total = response.content_length();
+            let file = fs::File::create(&part_path)
+                .await
+                .map_err(|e| write_error(&part_path, e))?;
+            match response_validator(response.headers()) {
+                Some(validator) => fs::write(&validator_path, validator)
+                    .await
+                    .map_err(|e| write_error(&validator_path, e))?,
+                None => remove_if_exists(&validator_path).await?,
+            }
+            (0, total, file)
+        };
+
+        let display_path = part_path.display().to_string();
+        let mut download = stream_body(
+            response,
+            &mut file,
+            resumed_from,
+            total,
+            &display_path,
+            &mut on_progress,
+        )
+        .await?;
+        drop(file);
+
+        fs::rename(&part_path, path)
+            .await
+            .map_err(|e| write_error(path, e))?;
+        remove_if_exists(&validator_path).await?;
+        download.resumed_from = resumed_from;
+        Ok(download)
+    }
+
     /// Executes a request that returns 204 No Content
     pub async fn execute_no_content<P>(&self, params: P) -> Result<()>
     where
@@ -198,20 +344,35 @@ impl Client {
         let field_name = params.file_field_name().to_string();
         let additional_fields = params.additional_fields();
 
-        // ファイル読み込み
-        let file_content = fs::read(&file_path).await.map_err(|e| ApiError::FileRead {
-            path: file_path.to_string_lossy().to_string(),
-            message: e.to_string(),
-        })?;
-
         let filename = file_path
             .file_name()
             .and_then(|name| name.to_str())
             .unwrap_or("attachment")
             .to_string();
 
-        // Multipart form構築
-        let file_part = reqwest::multipart::Part::bytes(file_content).file_name(filename);
+        // In-memory content is sent as is; files are streamed from disk
+        let file_part = match params.file_content() {
+            Some(content) => {
+                let content_type = params
+                    .content_type()
+                    .unwrap_or_else(|| crate::detect_content_type(&filename, content))
+                    .to_string();
+                reqwest::multipart::Part::bytes(content.to_vec())
+                    .file_name(filename)
+                    .mime_str(&content_type)
+            }
+            None => {
+                let (file, length, head) = open_for_upload(&file_path).await?;
+                let content_type = params
+                    .content_type()
+                    .unwrap_or_else(|| crate::detect_content_type(&filename, &head))
+                    .to_string();
+                reqwest::multipart::Part::stream_with_length(file, length)
+                    .file_name(filename)
+                    .mime_str(&content_type)
+            }
+        }
+        .map_err(|e| ApiError::RequestBuild(format!("Invalid content type: {e}")))?;
 
         let mut form = reqwest::multipart::Form::new().part(field_name.clone(), file_part);
 
@@ -234,12 +395,19 @@ impl Client {
     /// Unified method for executing requests with customizable response handling
     pub async fn execute_unified<R>(
         &self,
-        mut request: reqwest::Request,
+        request: reqwest::Request,
         response_handler: R,
     ) -> Result<R::Output>
     where
         R: IntoResponse,
     {
+        let response = self.send(request).await?;
+        let response = Self::check_status(response).await?;
+        response_handler.from_response(response).await
+    }
+
+    /// Add authentication to the request and send it
+    async fn send(&self, mut request: reqwest::Request) -> Result<reqwest::Response> {
         // Add authentication headers to the request
         if let Some(token) = &self.auth_token {
             let headers = request.headers_mut();
@@ -256,10 +424,18 @@ impl Client {
             url.query_pairs_mut().append_pair("apiKey", key);
         }
 
-        let response = self.client.execute(request).await?;
+        Ok(self.client.execute(request).await?)
+    }
 
+    /// Turn a non-success response into an API error
+    async fn check_status(response: reqwest::Response) -> Result<reqwest::Response> {
         if !response.status().is_success() {
             let status = response.status().as_u16();
+            let rate_limit_reset = response
+                .headers()
+                .get("X-RateLimit-Reset")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse().ok());
             let error_body_text = response
                 .text()
                 .await
@@ -278,17 +454,111 @@ impl Client {
                         status,
                         errors: parsed_errors.errors,
                         errors_summary: summary,
+                        rate_limit_reset,
                     });
                 }
                 Err(_) => {
                     return Err(ApiError::UnparseableErrorResponse {
                         status,
                         body: error_body_text,
+                        rate_limit_reset,
                     });
                 }
             }
         }
 
-        response_handler.from_response(response).await
+        Ok(response)
+    }
+}
+
+/// Write the response body to `writer`, reporting progress after each chunk
+async fn stream_body<W, F>(
+    mut response: reqwest::Response,
+    writer: &mut W,
+    offset: u64,
+    total: Option<u64>,
+    display_path: &str,
+    on_progress: &mut F,
+) -> Result<StreamedDownload>
+where
+    W: AsyncWrite + Unpin,
+    F: FnMut(DownloadProgress),
+{
+    let headers = response.headers().clone();
+    let mut downloaded = offset;
+    on_progress(DownloadProgress { downloaded, total });
+
+    while let Some(chunk) = response.chunk().await? {
+        writer
+            .write_all(&chunk)
+            .await
+            .map_err(|e| write_error(display_path, e))?;
+        downloaded += chunk.len() as u64;
+        on_progress(DownloadProgress { downloaded, total });
     }
+    // The size is known now, so report completion
+    if total.is_none() {
+        on_progress(DownloadProgress {
+            downloaded,
+            total: Some(downloaded),
+        });
+    }
+    writer
+        .flush()
+        .await
+        .map_err(|e| write_error(display_path, e))?;
+
+    Ok(StreamedDownload {
+        filename: response_filename(&headers),
+        content_type: response_content_type(&headers),
+        size: downloaded,
+        resumed_from: offset,
+    })
+}
+
+/// The validator to resume a download with: a strong `ETag`, or else
+/// `Last-Modified`, since `If-Range` does not accept weak entity tags
+fn response_validator(headers: &HeaderMap) -> Option<&[u8]> {
+    headers
+        .get(ETAG)
+        .filter(|etag| !etag.as_bytes().starts_with(b"W/"))
+        .or_else(|| headers.get(LAST_MODIFIED))
+        .map(HeaderValue::as_bytes)
+}
+
+async fn remove_if_exists(path: &Path) -> Result<()> {
+    match fs::remove_file(path).await {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(write_error(path, e)),
+        _ => Ok(()),
+    }
+}
+
+fn write_error(path: impl AsRef<Path>, error: std::io::Error) -> ApiError {
+    ApiError::FileWrite {
+        path: path.as_ref().display().to_string(),
+        message: error.to_string(),
+    }
+}
+
+/// Open a file for a streaming upload, returning its length and first bytes
+/// for content-type detection
+async fn open_for_upload(path: &Path) -> Result<(fs::File, u64, Vec<u8>)> {
+    use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+    let read_error = |e: std::io::Error| ApiError::FileRead {
+        path: path.to_string_lossy().to_string(),
+        message: e.to_string(),
+    };
+    let mut file = fs::File::open(path).await.map_err(read_error)?;
+    let length = file.metadata().await.map_err(read_error)?.len();
+
+    let mut head = Vec::with_capacity(16);
+    (&mut file)
+        .take(16)
+        .read_to_end(&mut head)
+        .await
+        .map_err(read_error)?;
+    file.rewind().await.map_err(read_error)?;
+
+    Ok((file, length, head))
 }
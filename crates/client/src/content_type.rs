@@ -0,0 +1,107 @@
+//! MIME type detection for uploaded files
+
+/// Detect the MIME type of a file from its leading bytes, falling back to its extension
+///
+/// Returns `application/octet-stream` when neither is recognised.
+pub fn detect_content_type(file_name: &str, content: &[u8]) -> &'static str {
+    sniff(content).unwrap_or_else(|| from_extension(file_name))
+}
+
+fn sniff(content: &[u8]) -> Option<&'static str> {
+    const SIGNATURES: &[(&[u8], &str)] = &[
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"GIF87a", "image/gif"),
+        (b"GIF89a", "image/gif"),
+        (b"BM", "image/bmp"),
+        (b"%PDF-", "application/pdf"),
+        (b"\x1f\x8b", "application/gzip"),
+        (b"7z\xbc\xaf\x27\x1c", "application/x-7z-compressed"),
+    ];
+
+    if content.len() >= 12 && &content[..4] == b"RIFF" && &content[8..12] == b"WEBP" {
+        return Some("image/webp");
+    }
+    SIGNATURES
+        .iter()
+        .find(|(magic, _)| content.starts_with(magic))
+        .map(|(_, mime)| *mime)
+}
+
+fn from_extension(file_name: &str) -> &'static str {
+    let extension = file_name
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "bmp" => "image/bmp",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "gz" => "application/gzip",
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "txt" | "log" => "text/plain",
+        "md" => "text/markdown",
+        "csv" => "text/csv",
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "js" => "text/javascript",
+        "yaml" | "yml" => "application/yaml",
+        "doc" => "application/msword",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "xls" => "application/vnd.ms-excel",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "ppt" => "application/vnd.ms-powerpoint",
+        "pptx" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        "mp4" => "video/mp4",
+        "mov" => "video/quicktime",
+        "mp3" => "audio/mpeg",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_from_content() {
+        assert_eq!(
+            detect_content_type("screenshot", b"\x89PNG\r\n\x1a\n\0\0"),
+            "image/png"
+        );
+        // Content wins over a misleading extension
+        assert_eq!(
+            detect_content_type("photo.txt", b"\xff\xd8\xff\xe0"),
+            "image/jpeg"
+        );
+        assert_eq!(
+            detect_content_type("anim", b"RIFF\0\0\0\0WEBPVP8 "),
+            "image/webp"
+        );
+        assert_eq!(detect_content_type("x", b"%PDF-1.7"), "application/pdf");
+    }
+
+    #[test]
+    fn test_detect_from_extension() {
+        assert_eq!(detect_content_type("notes.MD", b"# Title"), "text/markdown");
+        assert_eq!(
+            detect_content_type("report.xlsx", b"PK\x03\x04"),
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+        );
+        assert_eq!(
+            detect_content_type("data", b"\x00\x01"),
+            "application/octet-stream"
+        );
+        assert_eq!(
+            detect_content_type(".hidden", b""),
+            "application/octet-stream"
+        );
+    }
+}
@@ -0,0 +1,161 @@
+//! Streaming download support
+//!
+//! [`Client::download_to_writer`](crate::Client::download_to_writer) and
+//! [`Client::download_to_path`](crate::Client::download_to_path) write the
+//! response body chunk by chunk instead of holding it in a [`DownloadedFile`](crate::DownloadedFile).
+
+use std::path::{Path, PathBuf};
+
+/// Progress of a streaming download, reported after every chunk
+///
+/// When the server did not report the size, a last report with `total` set
+/// to the downloaded size marks the end of the download.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DownloadProgress {
+    /// Bytes written so far, including any part resumed from disk
+    pub downloaded: u64,
+    /// Full size of the file, when the server reports it
+    pub total: Option<u64>,
+}
+
+/// Metadata of a file written by a streaming download
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamedDownload {
+    pub filename: String,
+    pub content_type: String,
+    /// Size of the complete file in bytes
+    pub size: u64,
+    /// Number of bytes that were already on disk when the download resumed
+    pub resumed_from: u64,
+}
+
+/// Extract the file name from a `Content-Disposition` header value
+///
+/// `filename*=UTF-8''...` (RFC 5987) is percent-decoded and preferred over
+/// `filename="..."`, as RFC 6266 recommends.
+pub fn content_disposition_filename(value: &str) -> Option<String> {
+    let mut plain = None;
+    for param in value.split(';').map(str::trim) {
+        let Some((key, raw)) = param.split_once('=') else {
+            continue;
+        };
+        match key.trim().to_ascii_lowercase().as_str() {
+            "filename*" => {
+                let raw = raw.trim().trim_matches('"');
+                // charset'language'value
+                let mut parts = raw.splitn(3, '\'');
+                let charset = parts.next().unwrap_or_default();
+                let encoded = match (parts.next(), parts.next()) {
+                    (Some(_language), Some(encoded)) => encoded,
+                    _ => raw,
+                };
+                let decoded: Vec<u8> = percent_encoding::percent_decode_str(encoded).collect();
+                let name = if charset.eq_ignore_ascii_case("utf-8") {
+                    String::from_utf8_lossy(&decoded).into_owned()
+                } else {
+                    // ISO-8859-1: every byte is the code point of the same value
+                    decoded.iter().map(|&b| b as char).collect()
+                };
+                if !name.is_empty() {
+                    return Some(name);
+                }
+            }
+            "filename" => {
+                let name = raw.trim().trim_matches('"');
+                if !name.is_empty() {
+                    plain = Some(name.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    plain
+}
+
+/// Delete what an interrupted download to `path` left behind
+///
+/// Use this before downloading a file that changed since the earlier
+/// attempt, so the new download never resumes from data of the old one.
+pub async fn discard_partial_download(path: impl AsRef<Path>) -> std::io::Result<()> {
+    let path = path.as_ref();
+    for leftover in [partial_path(path), validator_path(path)] {
+        match tokio::fs::remove_file(&leftover).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Where the data of an unfinished download to `path` is written
+pub(crate) fn partial_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".part");
+    PathBuf::from(name)
+}
+
+/// Where the validator of an unfinished download to `path` is kept
+pub(crate) fn validator_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".part.validator");
+    PathBuf::from(name)
+}
+
+/// Parse the start offset and full size from `Content-Range: bytes start-end/total`
+pub(crate) fn parse_content_range(value: &str) -> Option<(u64, Option<u64>)> {
+    let range = value.trim().strip_prefix("bytes ")?;
+    let (span, total) = range.split_once('/')?;
+    let (start, _end) = span.split_once('-')?;
+    let start = start.trim().parse().ok()?;
+    let total = total.trim().parse().ok();
+    Some((start, total))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_disposition_filename() {
+        assert_eq!(
+            content_disposition_filename("attachment; filename=\"report.pdf\""),
+            Some("report.pdf".to_string())
+        );
+        assert_eq!(
+            content_disposition_filename(
+                "attachment; filename*=UTF-8''%E8%A8%AD%E8%A8%88%20v2.png"
+            ),
+            Some("設計 v2.png".to_string())
+        );
+        // The extended form wins regardless of order
+        assert_eq!(
+            content_disposition_filename(
+                "attachment; filename*=UTF-8''na%C3%AFve.txt; filename=\"naive.txt\""
+            ),
+            Some("naïve.txt".to_string())
+        );
+        assert_eq!(
+            content_disposition_filename("attachment; filename*=ISO-8859-1'en'caf%E9.txt"),
+            Some("café.txt".to_string())
+        );
+        assert_eq!(content_disposition_filename("inline"), None);
+    }
+
+    #[test]
+    fn test_content_disposition_filename_keeps_invalid_escapes() {
+        assert_eq!(
+            content_disposition_filename("attachment; filename*=UTF-8''100%25_50%_%zz.txt"),
+            Some("100%_50%_%zz.txt".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_content_range() {
+        assert_eq!(
+            parse_content_range("bytes 100-199/200"),
+            Some((100, Some(200)))
+        );
+        assert_eq!(parse_content_range("bytes 0-9/*"), Some((0, None)));
+        assert_eq!(parse_content_range("items 0-9/10"), None);
+    }
+}
@@ -1,5 +1,11 @@
 pub mod client;
+pub mod content_type;
+pub mod download;
 pub use client::{Client, DownloadedFile, FileResponse, IntoResponse, NoContentResponse};
+pub use content_type::detect_content_type;
+pub use download::{
+    DownloadProgress, StreamedDownload, content_disposition_filename, discard_partial_download,
+};
 
 #[cfg(feature = "test-utils")]
 pub mod test_utils;
@@ -0,0 +1,282 @@
+use backlog_api_core::{IntoDownloadRequest, IntoUploadRequest};
+use client::{Client, DownloadProgress};
+use std::path::PathBuf;
+use tempfile::TempDir;
+use wiremock::matchers::{body_string_contains, header, header_exists, method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[derive(Debug, Clone)]
+struct TestDownloadParams;
+
+impl IntoDownloadRequest for TestDownloadParams {
+    fn path(&self) -> String {
+        "/api/v2/issues/TEST-1/attachments/1".to_string()
+    }
+}
+
+const CONTENT: &[u8] = b"0123456789abcdefghij";
+
+fn file_response(status: u16, body: &[u8]) -> ResponseTemplate {
+    ResponseTemplate::new(status)
+        .insert_header(
+            "Content-Disposition",
+            "attachment; filename*=UTF-8''%E4%BB%95%E6%A7%98.txt",
+        )
+        .insert_header("Content-Type", "text/plain")
+        .set_body_bytes(body.to_vec())
+}
+
+#[tokio::test]
+async fn test_download_to_writer_reports_progress() {
+    let server = MockServer::start().await;
+    let client = Client::new(&server.uri()).unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/api/v2/issues/TEST-1/attachments/1"))
+        .respond_with(file_response(200, CONTENT))
+        .mount(&server)
+        .await;
+
+    let mut buffer = Vec::new();
+    let mut progress = Vec::new();
+    let download = client
+        .download_to_writer(TestDownloadParams, &mut buffer, |p| progress.push(p))
+        .await
+        .unwrap();
+
+    assert_eq!(buffer, CONTENT);
+    assert_eq!(download.filename, "仕様.txt");
+    assert_eq!(download.content_type, "text/plain");
+    assert_eq!(download.size, CONTENT.len() as u64);
+    assert_eq!(download.resumed_from, 0);
+    assert_eq!(
+        progress.last(),
+        Some(&DownloadProgress {
+            downloaded: CONTENT.len() as u64,
+            total: Some(CONTENT.len() as u64),
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_download_to_path_resumes_partial_file() {
+    let server = MockServer::start().await;
+    let client = Client::new(&server.uri()).unwrap();
+    let dir = TempDir::new().unwrap();
+    let target = dir.path().join("spec.txt");
+    std::fs::write(dir.path().join("spec.txt.part"), &CONTENT[..8]).unwrap();
+    std::fs::write(dir.path().join("spec.txt.part.validator"), "\"v1\"").unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/api/v2/issues/TEST-1/attachments/1"))
+        .and(header("Range", "bytes=8-"))
+        .and(header("If-Range", "\"v1\""))
+        .respond_with(
+            file_response(206, &CONTENT[8..]).insert_header("Content-Range", "bytes 8-19/20"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let mut progress = Vec::new();
+    let download = client
+        .download_to_path(TestDownloadParams, &target, |p| progress.push(p))
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read(&target).unwrap(), CONTENT);
+    assert!(!dir.path().join("spec.txt.part").exists());
+    assert!(!dir.path().join("spec.txt.part.validator").exists());
+    assert_eq!(download.resumed_from, 8);
+    assert_eq!(download.size, 20);
+    assert_eq!(
+        progress.first(),
+        Some(&DownloadProgress {
+            downloaded: 8,
+            total: Some(20),
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_download_to_path_restarts_when_range_is_ignored() {
+    let server = MockServer::start().await;
+    let client = Client::new(&server.uri()).unwrap();
+    let dir = TempDir::new().unwrap();
+    let target = dir.path().join("spec.txt");
+    std::fs::write(dir.path().join("spec.txt.part"), b"stale").unwrap();
+    std::fs::write(dir.path().join("spec.txt.part.validator"), "\"v1\"").unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/api/v2/issues/TEST-1/attachments/1"))
+        .respond_with(file_response(200, CONTENT))
+        .mount(&server)
+        .await;
+
+    let download = client
+        .download_to_path(TestDownloadParams, &target, |_| {})
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read(&target).unwrap(), CONTENT);
+    assert_eq!(download.resumed_from, 0);
+}
+
+#[tokio::test]
+async fn test_download_to_path_does_not_resume_without_validator() {
+    let server = MockServer::start().await;
+    let client = Client::new(&server.uri()).unwrap();
+    let dir = TempDir::new().unwrap();
+    let target = dir.path().join("spec.txt");
+    std::fs::write(dir.path().join("spec.txt.part"), b"stale").unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/api/v2/issues/TEST-1/attachments/1"))
+        .and(header_exists("Range"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/issues/TEST-1/attachments/1"))
+        .respond_with(file_response(200, CONTENT))
+        .mount(&server)
+        .await;
+
+    let download = client
+        .download_to_path(TestDownloadParams, &target, |_| {})
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read(&target).unwrap(), CONTENT);
+    assert_eq!(download.resumed_from, 0);
+}
+
+#[tokio::test]
+async fn test_download_to_path_keeps_validator_while_downloading() {
+    let server = MockServer::start().await;
+    let client = Client::new(&server.uri()).unwrap();
+    let dir = TempDir::new().unwrap();
+    let target = dir.path().join("spec.txt");
+    let validator_path = dir.path().join("spec.txt.part.validator");
+
+    Mock::given(method("GET"))
+        .and(path("/api/v2/issues/TEST-1/attachments/1"))
+        .respond_with(
+            file_response(200, CONTENT)
+                .insert_header("ETag", "W/\"weak\"")
+                .insert_header("Last-Modified", "Tue, 14 May 2024 09:00:00 GMT"),
+        )
+        .mount(&server)
+        .await;
+
+    let mut validators = Vec::new();
+    client
+        .download_to_path(TestDownloadParams, &target, |_| {
+            validators.push(std::fs::read_to_string(&validator_path).ok());
+        })
+        .await
+        .unwrap();
+
+    // Weak entity tags cannot be used with If-Range
+    assert_eq!(
+        validators.first(),
+        Some(&Some("Tue, 14 May 2024 09:00:00 GMT".to_string()))
+    );
+    assert!(!validator_path.exists());
+}
+
+#[tokio::test]
+async fn test_download_to_path_restarts_on_unsatisfiable_range() {
+    let server = MockServer::start().await;
+    let client = Client::new(&server.uri()).unwrap();
+    let dir = TempDir::new().unwrap();
+    let target = dir.path().join("spec.txt");
+    std::fs::write(dir.path().join("spec.txt.part"), [0u8; 32]).unwrap();
+    std::fs::write(dir.path().join("spec.txt.part.validator"), "\"v1\"").unwrap();
+
+    Mock::given(method("GET"))
+        .and(path("/api/v2/issues/TEST-1/attachments/1"))
+        .and(header("Range", "bytes=32-"))
+        .respond_with(ResponseTemplate::new(416))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v2/issues/TEST-1/attachments/1"))
+        .respond_with(file_response(200, CONTENT))
+        .mount(&server)
+        .await;
+
+    client
+        .download_to_path(TestDownloadParams, &target, |_| {})
+        .await
+        .unwrap();
+
+    assert_eq!(std::fs::read(&target).unwrap(), CONTENT);
+}
+
+#[tokio::test]
+async fn test_download_to_path_keeps_partial_file_on_error() {
+    let server = MockServer::start().await;
+    let client = Client::new(&server.uri()).unwrap();
+    let dir = TempDir::new().unwrap();
+    let target = dir.path().join("spec.txt");
+
+    Mock::given(method("GET"))
+        .and(path("/api/v2/issues/TEST-1/attachments/1"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+            "errors": [{"message": "No attachment.", "code": 6, "moreInfo": ""}]
+        })))
+        .mount(&server)
+        .await;
+
+    let result = client
+        .download_to_path(TestDownloadParams, &target, |_| {})
+        .await;
+
+    assert!(result.is_err());
+    assert!(!target.exists());
+}
+
+#[derive(Debug, Clone)]
+struct TestUploadParams {
+    file_path: PathBuf,
+}
+
+impl IntoUploadRequest for TestUploadParams {
+    fn path(&self) -> String {
+        "/api/v2/space/attachment".to_string()
+    }
+
+    fn file_path(&self) -> &PathBuf {
+        &self.file_path
+    }
+}
+
+#[tokio::test]
+async fn test_upload_file_streams_from_disk() {
+    let server = MockServer::start().await;
+    let client = Client::new(&server.uri()).unwrap();
+    let dir = TempDir::new().unwrap();
+    let file_path = dir.path().join("notes.md");
+    std::fs::write(&file_path, "# streamed upload").unwrap();
+
+    Mock::given(method("POST"))
+        .and(path("/api/v2/space/attachment"))
+        .and(body_string_contains("filename=\"notes.md\""))
+        .and(body_string_contains("Content-Type: text/markdown"))
+        .and(body_string_contains("# streamed upload"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "id": 1, "name": "notes.md", "size": 17
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let response: serde_json::Value = client
+        .upload_file(TestUploadParams { file_path })
+        .await
+        .unwrap();
+    assert_eq!(response["id"], 1);
+}
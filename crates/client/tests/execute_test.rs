@@ -363,6 +363,72 @@ async fn test_execute_404_not_found() {
     ));
 }
 
+#[tokio::test]
+async fn test_execute_429_keeps_rate_limit_reset() {
+    let server = MockServer::start().await;
+    let client = Client::new(&server.uri()).expect("Client::new should succeed");
+
+    let error_response = serde_json::json!({
+        "errors": [{
+            "message": "Rate limit exceeded",
+            "code": 9,
+            "moreInfo": ""
+        }]
+    });
+
+    Mock::given(method("GET"))
+        .and(path("/api/v2/rate_limited"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("X-RateLimit-Reset", "1700000060")
+                .set_body_json(&error_response),
+        )
+        .mount(&server)
+        .await;
+
+    let params = TestRequest::get("/api/v2/rate_limited");
+    let result: Result<TestResponse, _> = client.execute(params).await;
+
+    let err = result.expect_err("execute should fail with 429 Too Many Requests");
+    assert!(matches!(
+        err,
+        backlog_api_core::Error::HttpStatus {
+            status: 429,
+            rate_limit_reset: Some(1_700_000_060),
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn test_execute_429_without_json_body_keeps_rate_limit_reset() {
+    let server = MockServer::start().await;
+    let client = Client::new(&server.uri()).expect("Client::new should succeed");
+
+    Mock::given(method("GET"))
+        .and(path("/api/v2/rate_limited"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("X-RateLimit-Reset", "1700000060")
+                .set_body_string("Too Many Requests"),
+        )
+        .mount(&server)
+        .await;
+
+    let params = TestRequest::get("/api/v2/rate_limited");
+    let result: Result<TestResponse, _> = client.execute(params).await;
+
+    let err = result.expect_err("execute should fail with 429 Too Many Requests");
+    assert!(matches!(
+        err,
+        backlog_api_core::Error::UnparseableErrorResponse {
+            status: 429,
+            rate_limit_reset: Some(1_700_000_060),
+            ..
+        }
+    ));
+}
+
 #[tokio::test]
 async fn test_execute_500_server_error() {
     let server = MockServer::start().await;